@@ -0,0 +1,267 @@
+//! The TypeScript constructs Tyrus knows about and whether codegen translates
+//! them.
+//!
+//! This registry is the one place that decides what is supported: codegen
+//! emits `todo!()` for every [`Feature`] that is not, and [`unsupported`]
+//! finds the same constructs in a module without generating it, so
+//! `tyrus check` rejects exactly what `tyrus build` cannot translate.
+
+#[cfg(test)]
+mod tests;
+
+use std::fmt;
+
+use tyrus_ast::visit::{walk_expr_mut, walk_stmt_mut, VisitMut};
+use tyrus_ast::{
+    AssignOp, AssignTarget, BinaryOp, ClassDecl, Decl, Expr, ExprKind, Item, Lit, Module, Pat,
+    Prop, PropKey, Span, Stmt, StmtKind, UnaryOp,
+};
+
+/// A construct whose support codegen has to decide on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Feature {
+    ForLoops,
+    ForOfLoops,
+    ForInLoops,
+    DoWhileLoops,
+    TryCatch,
+    Switch,
+    /// A function or class declared inside a body.
+    NestedDeclarations,
+    RestAndDefaultPatterns,
+    DestructuringAssignments,
+    RegexAndBigIntLiterals,
+    /// `{ [key]: value }` in an object literal.
+    ComputedKeys,
+    /// Spreads, methods, getters and setters in an object literal.
+    ObjectLiteralMembers,
+    /// Async methods of an interface a class mutates `this` in the methods
+    /// of: its trait objects are locked, and the lock can't be held across
+    /// an `.await`.
+    AsyncMethodsOfMutatedInterfaces,
+    Unary(UnaryOp),
+    Binary(BinaryOp),
+    /// `=` and the compound assignments.
+    Assign(AssignOp),
+    /// A construct lowering has no IR for, under the name lowering gives it
+    /// (`StmtKind::Unsupported`, `ExprKind::Unsupported`).
+    Syntax(String),
+}
+
+impl Feature {
+    /// Whether codegen translates the construct to Rust.
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Feature::ForLoops
+            | Feature::ForOfLoops
+            | Feature::ForInLoops
+            | Feature::DoWhileLoops
+            | Feature::TryCatch
+            | Feature::Switch => true,
+            Feature::NestedDeclarations
+            | Feature::RestAndDefaultPatterns
+            | Feature::DestructuringAssignments
+            | Feature::RegexAndBigIntLiterals
+            | Feature::ComputedKeys
+            | Feature::ObjectLiteralMembers
+            | Feature::AsyncMethodsOfMutatedInterfaces
+            | Feature::Syntax(_) => false,
+            Feature::Unary(op) => matches!(
+                op,
+                UnaryOp::Not | UnaryOp::Minus | UnaryOp::Plus | UnaryOp::TypeOf
+            ),
+            Feature::Binary(op) => operator_symbol(*op).is_none(),
+            Feature::Assign(op) => *op != AssignOp::Other,
+        }
+    }
+}
+
+/// The symbol of a binary operator codegen has no Rust translation for.
+fn operator_symbol(op: BinaryOp) -> Option<&'static str> {
+    Some(match op {
+        BinaryOp::Exp => "**",
+        BinaryOp::BitAnd => "&",
+        BinaryOp::BitOr => "|",
+        BinaryOp::BitXor => "^",
+        BinaryOp::LShift => "<<",
+        BinaryOp::RShift => ">>",
+        BinaryOp::ZeroFillRShift => ">>>",
+        BinaryOp::In => "in",
+        BinaryOp::InstanceOf => "instanceof",
+        _ => return None,
+    })
+}
+
+/// How diagnostics name the construct, e.g. "the `void` operator".
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Feature::ForLoops => "for loops",
+            Feature::ForOfLoops => "for-of loops",
+            Feature::ForInLoops => "for-in loops",
+            Feature::DoWhileLoops => "do-while loops",
+            Feature::TryCatch => "try-catch blocks",
+            Feature::Switch => "switch statements",
+            Feature::NestedDeclarations => "nested declarations",
+            Feature::RestAndDefaultPatterns => "rest and default-value patterns",
+            Feature::DestructuringAssignments => "destructuring assignments",
+            Feature::RegexAndBigIntLiterals => "regex and BigInt literals",
+            Feature::ComputedKeys => "computed property keys",
+            Feature::ObjectLiteralMembers => "spreads, methods and accessors in object literals",
+            Feature::AsyncMethodsOfMutatedInterfaces => {
+                "async methods of interfaces whose implementations mutate `this`"
+            }
+            Feature::Unary(op) => {
+                let symbol = match op {
+                    UnaryOp::Not => "!",
+                    UnaryOp::Minus => "-",
+                    UnaryOp::Plus => "+",
+                    UnaryOp::TypeOf => "typeof",
+                    UnaryOp::Tilde => "~",
+                    UnaryOp::Void => "void",
+                    UnaryOp::Delete => "delete",
+                };
+                return write!(f, "the `{symbol}` operator");
+            }
+            Feature::Binary(op) => match operator_symbol(*op) {
+                Some(symbol) => return write!(f, "the `{symbol}` operator"),
+                None => "arithmetic, comparison and logical operators",
+            },
+            Feature::Assign(AssignOp::Other) => {
+                "logical, bitwise and exponent compound assignments"
+            }
+            Feature::Assign(_) => "assignments",
+            Feature::Syntax(what) => what,
+        };
+        f.write_str(name)
+    }
+}
+
+/// Every construct in `module` codegen would emit as `todo!()`, with where it
+/// is.
+///
+/// Mirrors the traversal of codegen: the children of a construct are only
+/// searched when codegen converts them in spite of it. Interfaces are only
+/// checked against the classes of `module`, whose ownership should have
+/// been inferred.
+pub fn unsupported(module: &mut Module) -> Vec<(Feature, Span)> {
+    unsupported_with(module, &[])
+}
+
+/// Like [`unsupported`], for a module whose interfaces the `imported`
+/// classes of the other modules of its project can implement too.
+pub fn unsupported_with(module: &mut Module, imported: &[&ClassDecl]) -> Vec<(Feature, Span)> {
+    let mut finder = Finder::default();
+    let classes: Vec<&ClassDecl> = imported
+        .iter()
+        .copied()
+        .chain(module.items.iter().filter_map(|item| match item {
+            Item::Decl {
+                decl: Decl::Class(class),
+                ..
+            } => Some(class),
+            _ => None,
+        }))
+        .collect();
+    for item in &module.items {
+        if let Item::Decl {
+            decl: Decl::Interface(iface),
+            ..
+        } = item
+        {
+            if iface.has_async_methods() && iface.is_mutated_by(classes.iter().copied()) {
+                finder.check(Feature::AsyncMethodsOfMutatedInterfaces, iface.span);
+            }
+        }
+    }
+    finder.visit_module_mut(module);
+    finder.found
+}
+
+#[derive(Default)]
+struct Finder {
+    found: Vec<(Feature, Span)>,
+}
+
+impl Finder {
+    /// Records `feature` at `span` unless codegen supports it.
+    fn check(&mut self, feature: Feature, span: Span) -> bool {
+        let supported = feature.is_supported();
+        if !supported {
+            self.found.push((feature, span));
+        }
+        supported
+    }
+}
+
+impl VisitMut for Finder {
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        match &stmt.kind {
+            StmtKind::Decl(_) => {
+                self.check(Feature::NestedDeclarations, stmt.span);
+                return;
+            }
+            StmtKind::Unsupported { what } => {
+                self.check(Feature::Syntax(what.clone()), stmt.span);
+                return;
+            }
+            StmtKind::Var(var) => {
+                for decl in &var.decls {
+                    if matches!(decl.pat, Pat::Unsupported) {
+                        self.check(Feature::RestAndDefaultPatterns, decl.span);
+                    }
+                }
+            }
+            _ => {}
+        }
+        walk_stmt_mut(self, stmt);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        let span = expr.span;
+        match &mut expr.kind {
+            ExprKind::Lit(Lit::Unsupported) => {
+                self.check(Feature::RegexAndBigIntLiterals, span);
+            }
+            ExprKind::Unsupported { what } => {
+                self.check(Feature::Syntax(what.clone()), span);
+            }
+            ExprKind::Object(props) => {
+                let feature = props.iter().find_map(|prop| match prop {
+                    Prop::KeyValue {
+                        key: PropKey::Computed(_),
+                        ..
+                    } => Some(Feature::ComputedKeys),
+                    Prop::Unsupported => Some(Feature::ObjectLiteralMembers),
+                    _ => None,
+                });
+                match feature {
+                    Some(feature) => {
+                        self.check(feature, span);
+                    }
+                    None => walk_expr_mut(self, expr),
+                }
+            }
+            ExprKind::Unary { op, .. } => {
+                self.check(Feature::Unary(*op), span);
+                walk_expr_mut(self, expr);
+            }
+            ExprKind::Bin { op, .. } => {
+                self.check(Feature::Binary(*op), span);
+                walk_expr_mut(self, expr);
+            }
+            ExprKind::Assign { op, target, right } => {
+                if !self.check(Feature::Assign(*op), span) {
+                    return;
+                }
+                if matches!(target, AssignTarget::Unsupported) {
+                    self.check(Feature::DestructuringAssignments, span);
+                    self.visit_expr_mut(right);
+                    return;
+                }
+                walk_expr_mut(self, expr);
+            }
+            _ => walk_expr_mut(self, expr),
+        }
+    }
+}
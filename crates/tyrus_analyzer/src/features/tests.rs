@@ -0,0 +1,209 @@
+use swc_common::BytePos;
+use tyrus_ast::{BinaryOp, UnaryOp};
+
+use super::{unsupported, Feature};
+
+/// The unsupported features of `src` with the source text they cover.
+fn find(src: &str) -> Vec<(String, String)> {
+    let (program, _) = tyrus_parser::parse_str(src).expect("test source should parse");
+    let mut module = tyrus_ast::lower_program(&program);
+    crate::ownership::infer_ownership(&mut module);
+    unsupported(&mut module)
+        .into_iter()
+        .map(|(feature, span)| {
+            let offset = |pos: BytePos| pos.0 as usize - 1;
+            let text = &src[offset(span.lo)..offset(span.hi)];
+            (feature.to_string(), text.to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn test_supported_statements_are_not_reported() {
+    let found = find(
+        r#"
+        function f(xs: number[], key: string): number {
+            let total = 0;
+            for (let i = 0; i < xs.length; i++) {
+                total += xs[i];
+            }
+            for (const x of xs) {
+                total -= x;
+            }
+            for (const k in xs) {
+                total += 1;
+            }
+            do {
+                total--;
+            } while (total > 10);
+            switch (key) {
+                case "a":
+                    total = 1;
+                    break;
+                default:
+                    total = 2;
+            }
+            try {
+                total = total * 2;
+            } catch (e) {
+                total = 0;
+            }
+            return total;
+        }
+        "#,
+    );
+    assert_eq!(found, vec![]);
+    for feature in [
+        Feature::ForLoops,
+        Feature::ForOfLoops,
+        Feature::ForInLoops,
+        Feature::DoWhileLoops,
+        Feature::TryCatch,
+        Feature::Switch,
+    ] {
+        assert!(feature.is_supported(), "{feature}");
+    }
+}
+
+#[test]
+fn test_unsupported_constructs_are_reported() {
+    let found = find(
+        r#"
+        function f(a: number, b: number, text: string): number {
+            const mask = a & b;
+            void text;
+            a **= 2;
+            label: while (a > 0) {
+                a--;
+            }
+            function inner(): number {
+                return a | b;
+            }
+            return ~mask;
+        }
+        "#,
+    );
+    let found: Vec<_> = found
+        .iter()
+        .map(|(feature, text)| (feature.as_str(), text.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("the `&` operator", "a & b"),
+            ("the `void` operator", "void text"),
+            (
+                "logical, bitwise and exponent compound assignments",
+                "a **= 2"
+            ),
+            (
+                "labeled statements",
+                "label: while (a > 0) {\n                a--;\n            }"
+            ),
+            // Nested declarations are not converted, so neither is `a | b`
+            (
+                "nested declarations",
+                "function inner(): number {\n                return a | b;\n            }"
+            ),
+            ("the `~` operator", "~mask"),
+        ]
+    );
+}
+
+#[test]
+fn test_async_methods_of_mutated_interfaces_are_reported() {
+    let found = find(
+        r#"
+        interface Queue {
+            push(item: string): void;
+            flush(): Promise<number>;
+        }
+        interface Reader {
+            read(): Promise<string>;
+        }
+        class MemoryQueue implements Queue {
+            items: string[] = [];
+            push(item: string): void {
+                this.items.push(item);
+            }
+            async flush(): Promise<number> {
+                return 0;
+            }
+        }
+        class FileReader implements Reader {
+            async read(): Promise<string> {
+                return "";
+            }
+        }
+        "#,
+    );
+    let found: Vec<_> = found.iter().map(|(feature, _)| feature.as_str()).collect();
+    assert_eq!(
+        found,
+        vec!["async methods of interfaces whose implementations mutate `this`"]
+    );
+}
+
+#[test]
+fn test_computed_keys_are_reported() {
+    let found = find(
+        r#"
+        function f(key: string, a: number, b: number): object {
+            const plain = { name: key, "quoted": 1, 2: a & b };
+            return { [key]: a | b };
+        }
+        "#,
+    );
+    let found: Vec<_> = found
+        .iter()
+        .map(|(feature, text)| (feature.as_str(), text.as_str()))
+        .collect();
+    // The computed literal is not converted, so neither is `a | b`
+    assert_eq!(
+        found,
+        vec![
+            ("the `&` operator", "a & b"),
+            ("computed property keys", "{ [key]: a | b }"),
+        ]
+    );
+}
+
+#[test]
+fn test_object_literal_members_are_reported() {
+    let found = find(
+        r#"
+        function f(base: object, a: number): object {
+            const copy = { ...base };
+            const shape = { area() { return a; } };
+            const sized = { get size() { return a; } };
+            return { a };
+        }
+        "#,
+    );
+    let found: Vec<_> = found
+        .iter()
+        .map(|(feature, text)| (feature.as_str(), text.as_str()))
+        .collect();
+    let feature = "spreads, methods and accessors in object literals";
+    assert_eq!(
+        found,
+        vec![
+            (feature, "{ ...base }"),
+            (feature, "{ area() { return a; } }"),
+            (feature, "{ get size() { return a; } }"),
+        ]
+    );
+}
+
+#[test]
+fn test_operator_support() {
+    assert!(Feature::Binary(BinaryOp::NullishCoalescing).is_supported());
+    assert!(Feature::Binary(BinaryOp::Mod).is_supported());
+    assert!(!Feature::Binary(BinaryOp::InstanceOf).is_supported());
+    assert!(Feature::Unary(UnaryOp::TypeOf).is_supported());
+    assert!(!Feature::Unary(UnaryOp::Delete).is_supported());
+    assert_eq!(
+        Feature::Binary(BinaryOp::ZeroFillRShift).to_string(),
+        "the `>>>` operator"
+    );
+}
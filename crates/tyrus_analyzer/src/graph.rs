@@ -1,12 +1,17 @@
 use petgraph::algo::toposort;
 use petgraph::graph::{DiGraph, NodeIndex};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{Visit, VisitWith};
 
 pub struct DependencyGraph {
     graph: DiGraph<String, ()>,
     node_map: HashMap<String, NodeIndex>,
+    /// Classes implementing each interface or extending each abstract class.
+    implementations: HashMap<String, Vec<String>>,
+    /// Classes extending each class, abstract or not.
+    subclasses: HashMap<String, Vec<String>>,
+    abstract_classes: HashSet<String>,
 }
 
 impl DependencyGraph {
@@ -14,6 +19,9 @@ impl DependencyGraph {
         Self {
             graph: DiGraph::new(),
             node_map: HashMap::new(),
+            implementations: HashMap::new(),
+            subclasses: HashMap::new(),
+            abstract_classes: HashSet::new(),
         }
     }
 }
@@ -66,13 +74,24 @@ impl DependencyGraph {
             Err(_) => Err("Cycle detected in dependency graph".to_string()),
         }
     }
+    /// The class that provides a dependency on `name`, an interface or an
+    /// abstract class, when a single class implements or extends it.
+    pub fn provider(&self, name: &str) -> Option<&str> {
+        match self.implementations.get(name)?.as_slice() {
+            [class] => Some(class),
+            _ => None,
+        }
+    }
+
     pub fn get_dependencies(&self, node_name: &str) -> Option<Vec<String>> {
         if let Some(&idx) = self.node_map.get(node_name) {
-            let deps: Vec<String> = self
+            let mut deps: Vec<String> = self
                 .graph
                 .neighbors(idx)
                 .map(|neighbor_idx| self.graph[neighbor_idx].clone())
                 .collect();
+            // Neighbors come newest edge first; constructors take them in order
+            deps.reverse();
             Some(deps)
         } else {
             None
@@ -89,6 +108,25 @@ impl<'a> Visit for DependencyVisitor<'a> {
     fn visit_class_decl(&mut self, n: &ClassDecl) {
         let class_name = n.ident.sym.to_string();
         self.current_class = Some(class_name.clone());
+        for iface in &n.class.implements {
+            if let Some(iface) = iface.expr.as_ident() {
+                self.graph
+                    .implementations
+                    .entry(iface.sym.to_string())
+                    .or_default()
+                    .push(class_name.clone());
+            }
+        }
+        if let Some(base) = n.class.super_class.as_deref().and_then(Expr::as_ident) {
+            self.graph
+                .subclasses
+                .entry(base.sym.to_string())
+                .or_default()
+                .push(class_name.clone());
+        }
+        if n.class.is_abstract {
+            self.graph.abstract_classes.insert(class_name.clone());
+        }
         self.graph.add_node(class_name);
         n.visit_children_with(self);
         self.current_class = None;
@@ -139,5 +177,26 @@ pub fn build_graph(programs: &[Program]) -> DependencyGraph {
         };
         program.visit_with(&mut visitor);
     }
+    // Only abstract classes, which are never created themselves, are provided
+    let subclasses = std::mem::take(&mut graph.subclasses);
+    for (base, classes) in subclasses {
+        if graph.abstract_classes.contains(&base) {
+            graph
+                .implementations
+                .entry(base)
+                .or_default()
+                .extend(classes);
+        }
+    }
+    // Whatever depends on an interface is created after its provider
+    let mut provided: Vec<_> = graph
+        .implementations
+        .keys()
+        .filter_map(|iface| Some((iface.clone(), graph.provider(iface)?.to_string())))
+        .collect();
+    provided.sort();
+    for (iface, class) in provided {
+        graph.add_dependency(&iface, &class);
+    }
     graph
 }
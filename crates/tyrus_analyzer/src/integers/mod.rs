@@ -0,0 +1,964 @@
+//! Integer inference over the Tyrus IR.
+//!
+//! TypeScript has a single `number` type, which Tyrus maps to `f64`.
+//! [`infer_integers`] finds the local variables that only ever hold integers
+//! and sets their [`Binding::num`] to `i64`, or to `usize` when they are also
+//! never negative and index an array or string, as the counter of
+//! `for (let i = 0; i < xs.length; i++)` does. Integers come from integer
+//! literals, `.length` and `.size` and `indexOf`, and from `+`, `-`, `*`,
+//! `Math.floor` and friends over other integers, and `%` of one by a non-zero
+//! constant. `parseInt` results stay `f64`: they are `NaN` when the text is
+//! not a number, which an integer would turn into `0`. So do `Math.floor(x)`
+//! of an `f64`, which can be infinite, and `a % b`, which is `NaN` where an
+//! integer `b` of `0` would panic. So do variables reassigned a product or a
+//! sum of two variables, like `big = big * 2` or `total += n`, which can
+//! outgrow an `i64` where an `f64` only loses precision.
+//!
+//! Products are integers only while they fit an `i64`. Tyrus bounds every
+//! integer by the bits its value can take: a literal has its own, a counter
+//! stepped by `++` or a constant, a length or an index 32, and a value it
+//! can't bound all 64. So `i * 1000` is an `i64`, while
+//! `i * 1000000000 * 1000000000` stays `f64`.
+//!
+//! It then records on every `number` expression the Rust type its code
+//! evaluates to ([`Expr::num`]) and, when the place it is used in expects
+//! another one, the type to convert it to ([`Expr::num_cast`]). Function
+//! arguments, return values and array elements stay `f64`, so an integer
+//! variable is converted where it flows into one of them.
+//!
+//! A `// tyrus-float` comment on a declaration keeps its variables `f64`.
+
+#[cfg(test)]
+mod tests;
+
+use tyrus_ast::visit::{walk_expr_mut, walk_function_mut, walk_stmt_mut, VisitMut};
+use tyrus_ast::{
+    ArrowBody, AssignOp, AssignTarget, BinaryOp, Binding, CallExpr, Callee, ClassMember, CtorParam,
+    Decl, Expr, ExprKind, ForHead, ForInit, Function, Lit, MemberExpr, MemberProp, Module, NumRepr,
+    ObjectPatProp, Pat, Prop, PropKey, Stmt, StmtKind, Type, UnaryOp, UpdateOp, VarDecl,
+};
+
+use crate::scope::Scopes;
+
+/// Largest integer an `f64` holds exactly.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Bits of the magnitudes an `i64` holds.
+const I64_BITS: u32 = 63;
+
+/// Bits assumed for counters, lengths and indices, which count things held
+/// in memory.
+const COUNTER_BITS: u32 = 32;
+
+/// Bits of a value nothing is known about.
+const UNBOUNDED_BITS: u32 = 64;
+
+/// Picks integer representations for the `number` variables of `module`
+/// and annotates its expressions with the conversions they need.
+pub fn infer_integers(module: &mut Module) {
+    // Every candidate starts out as a non-negative integer; each pass demotes
+    // the variables assigned something that is not, until nothing changes.
+    let mut vars = Vec::new();
+    loop {
+        let known = vars.len();
+        let mut pass = Integers::new(vars, false);
+        pass.visit_module_mut(module);
+        vars = pass.scopes.vars;
+        if !pass.changed && vars.len() == known {
+            break;
+        }
+    }
+    Integers::new(vars, true).visit_module_mut(module);
+}
+
+/// The values a variable holds.
+#[derive(Debug, Clone, Copy)]
+struct Var {
+    /// A local `number` variable that is not opted out with `// tyrus-float`.
+    candidate: bool,
+    integral: bool,
+    non_negative: bool,
+    /// Whether it indexes an array or string, or is compared with a length.
+    index: bool,
+    /// Bits of the largest magnitude it holds, provided it is integral.
+    bits: u32,
+}
+
+impl Var {
+    fn new(candidate: bool) -> Self {
+        Self {
+            candidate,
+            integral: candidate,
+            non_negative: candidate,
+            index: false,
+            bits: 0,
+        }
+    }
+
+    fn repr(&self) -> NumRepr {
+        match (
+            self.candidate && self.integral,
+            self.non_negative && self.index,
+        ) {
+            (false, _) => NumRepr::F64,
+            (true, false) => NumRepr::I64,
+            (true, true) => NumRepr::Usize,
+        }
+    }
+}
+
+/// The Rust type the code for an expression naturally evaluates to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Natural {
+    /// An integer literal, which takes the type its context expects.
+    Literal,
+    Repr(NumRepr),
+}
+
+impl Natural {
+    fn resolve(self) -> NumRepr {
+        match self {
+            Natural::Literal => NumRepr::F64,
+            Natural::Repr(repr) => repr,
+        }
+    }
+
+    fn is_integer(self) -> bool {
+        self != Natural::Repr(NumRepr::F64)
+    }
+
+    /// Type of `+`, `*` and `%` over operands of types `self` and `other`.
+    fn join(self, other: Natural) -> Natural {
+        match (self, other) {
+            (Natural::Literal, other) | (other, Natural::Literal) => other,
+            (Natural::Repr(a), Natural::Repr(b)) if a == b => Natural::Repr(a),
+            (Natural::Repr(NumRepr::F64), _) | (_, Natural::Repr(NumRepr::F64)) => {
+                Natural::Repr(NumRepr::F64)
+            }
+            _ => Natural::Repr(NumRepr::I64),
+        }
+    }
+
+    /// Type of `-`, which can go below zero.
+    fn signed(self) -> Natural {
+        match self {
+            Natural::Repr(NumRepr::Usize) => Natural::Repr(NumRepr::I64),
+            other => other,
+        }
+    }
+}
+
+struct Integers {
+    scopes: Scopes<Var>,
+    /// Whether this pass learned anything new.
+    changed: bool,
+    /// Whether this is the final pass, which annotates the module.
+    apply: bool,
+}
+
+impl Integers {
+    fn new(vars: Vec<Var>, apply: bool) -> Self {
+        Self {
+            scopes: Scopes::new(vars),
+            changed: false,
+            apply,
+        }
+    }
+
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.scopes.push();
+        f(self);
+        self.scopes.pop();
+    }
+
+    /// Allocates the next variable, before it is in scope.
+    fn reserve(&mut self, candidate: bool) -> usize {
+        self.scopes.reserve(|| Var::new(candidate))
+    }
+
+    fn declare(&mut self, name: &str) {
+        let id = self.reserve(false);
+        self.scopes.bind(name, id);
+    }
+
+    fn declare_binding(&mut self, binding: &mut Binding, id: usize) {
+        self.scopes.bind(&binding.name, id);
+        if self.apply {
+            binding.num = self.scopes.vars[id].repr();
+        }
+    }
+
+    /// Declares the names bound by `pat`, none of which is a candidate.
+    fn pat(&mut self, pat: &mut Pat) {
+        match pat {
+            Pat::Ident(binding) => {
+                let id = self.reserve(false);
+                self.declare_binding(binding, id);
+            }
+            Pat::Object(props) => {
+                for prop in props {
+                    match prop {
+                        ObjectPatProp::KeyValue { value, .. } => self.pat(value),
+                        ObjectPatProp::Assign { key, default } => {
+                            if let Some(default) = default {
+                                self.root(default, None);
+                            }
+                            self.declare(key);
+                        }
+                        ObjectPatProp::Rest => {}
+                    }
+                }
+            }
+            Pat::Array(elems) => {
+                for elem in elems.iter_mut().flatten() {
+                    self.pat(elem);
+                }
+            }
+            Pat::Unsupported => {}
+        }
+    }
+
+    fn var_decl(&mut self, var: &mut VarDecl) {
+        for decl in &mut var.decls {
+            let Pat::Ident(binding) = &mut decl.pat else {
+                if let Some(init) = &mut decl.init {
+                    self.root(init, None);
+                }
+                self.pat(&mut decl.pat);
+                continue;
+            };
+            let number = match (&binding.ty, &decl.init) {
+                (Some(ty), _) => ty.is_number(),
+                (None, Some(init)) => init.ty.is_number(),
+                (None, None) => false,
+            };
+            // The initializer can't see the variable it initializes
+            let id = self.reserve(number && !var.keep_float);
+            if let Some(init) = &mut decl.init {
+                self.root(init, Some(self.scopes.vars[id].repr()));
+                self.store(id, init);
+            }
+            self.declare_binding(binding, id);
+        }
+    }
+
+    /// The candidate variable `expr` reads, if it is one.
+    fn candidate(&self, expr: &Expr) -> Option<usize> {
+        let id = self.scopes.lookup(expr.as_ident()?)?;
+        self.scopes.vars[id].candidate.then_some(id)
+    }
+
+    fn update(&mut self, id: usize, f: impl FnOnce(&mut Var)) {
+        let var = &mut self.scopes.vars[id];
+        let before = (var.integral, var.non_negative, var.index, var.bits);
+        f(var);
+        self.changed |= before != (var.integral, var.non_negative, var.index, var.bits);
+    }
+
+    /// Records that variable `id` is set to `value`.
+    fn store(&mut self, id: usize, value: &Expr) {
+        let (integral, non_negative) = (self.integral(value), self.non_negative(value));
+        let bits = self.bits(value);
+        self.update(id, |var| {
+            var.integral &= integral;
+            var.non_negative &= non_negative;
+            var.bits = var.bits.max(bits);
+        });
+    }
+
+    /// Records that variable `id` is stepped, by `++` or a constant.
+    fn step(&mut self, id: usize) {
+        self.update(id, |var| var.bits = var.bits.max(COUNTER_BITS));
+    }
+
+    /// Records `name op= value`.
+    fn assign(&mut self, op: AssignOp, name: &str, value: &Expr) {
+        let Some(id) = self.scopes.lookup(name) else {
+            return;
+        };
+        match op {
+            AssignOp::Assign if self.bounded(value) => self.store(id, value),
+            AssignOp::AddAssign if is_step(value) => {
+                self.store(id, value);
+                self.step(id);
+            }
+            AssignOp::ModAssign if is_divisor(value) => self.store(id, value),
+            AssignOp::SubAssign if is_step(value) => {
+                let integral = self.integral(value);
+                self.update(id, |var| {
+                    var.integral &= integral;
+                    var.non_negative = false;
+                });
+                self.step(id);
+            }
+            // `big = big * 2` or `total += n` in a loop can outgrow an `i64`,
+            // where an `f64` only loses precision
+            _ => self.update(id, |var| var.integral = false),
+        }
+    }
+
+    /// Whether storing `expr` into a variable again and again can't make it
+    /// grow without bound: a copy, or another value stepped by a constant.
+    fn bounded(&self, expr: &Expr) -> bool {
+        match &expr.kind {
+            ExprKind::Paren(inner)
+            | ExprKind::Unary {
+                op: UnaryOp::Minus | UnaryOp::Plus,
+                arg: inner,
+            } => self.bounded(inner),
+            ExprKind::Bin {
+                op: BinaryOp::Add | BinaryOp::Sub,
+                left,
+                right,
+            } => (is_step(left) || is_step(right)) && self.bounded(left) && self.bounded(right),
+            ExprKind::Bin {
+                op: BinaryOp::Mod,
+                left,
+                ..
+            } => self.bounded(left),
+            ExprKind::Bin {
+                op: BinaryOp::Mul, ..
+            } => false,
+            ExprKind::Cond { cons, alt, .. } => self.bounded(cons) && self.bounded(alt),
+            ExprKind::Seq(exprs) => exprs.last().is_none_or(|last| self.bounded(last)),
+            _ => true,
+        }
+    }
+
+    fn mark_index(&mut self, expr: &Expr) {
+        if let Some(id) = self.candidate(expr.unparen()) {
+            self.update(id, |var| var.index = true);
+        }
+    }
+
+    /// Whether `expr` is a number in the generated code.
+    fn numeric(&self, expr: &Expr) -> bool {
+        expr.ty.is_number() || self.natural(expr).is_integer()
+    }
+
+    fn natural(&self, expr: &Expr) -> Natural {
+        match &expr.kind {
+            ExprKind::Lit(Lit::Num(value)) if is_integer(*value) => Natural::Literal,
+            ExprKind::Ident(_) => match self.candidate(expr) {
+                Some(id) => Natural::Repr(self.scopes.vars[id].repr()),
+                None => Natural::Repr(NumRepr::F64),
+            },
+            ExprKind::Member(member) if is_length(member) => Natural::Repr(NumRepr::Usize),
+            ExprKind::Paren(inner) => self.natural(inner),
+            ExprKind::Unary {
+                op: UnaryOp::Minus,
+                arg,
+            } => self.natural(arg).signed(),
+            ExprKind::Unary {
+                op: UnaryOp::Plus,
+                arg,
+            } => self.natural(arg),
+            ExprKind::Bin {
+                op: BinaryOp::Mod,
+                right,
+                ..
+            } if !is_divisor(right) => Natural::Repr(NumRepr::F64),
+            ExprKind::Bin { op, left, right } => {
+                let (left, right) = (self.natural(left), self.natural(right));
+                match op {
+                    BinaryOp::Mul if !self.fits(expr) => Natural::Repr(NumRepr::F64),
+                    BinaryOp::Add | BinaryOp::Mul | BinaryOp::Mod => left.join(right),
+                    BinaryOp::Sub => left.join(right).signed(),
+                    _ => Natural::Repr(NumRepr::F64),
+                }
+            }
+            ExprKind::Cond { cons, alt, .. } => self.natural(cons).join(self.natural(alt)),
+            ExprKind::Seq(exprs) => exprs
+                .last()
+                .map_or(Natural::Repr(NumRepr::F64), |last| self.natural(last)),
+            _ => Natural::Repr(NumRepr::F64),
+        }
+    }
+
+    /// Whether `expr` always evaluates to an integer.
+    fn integral(&self, expr: &Expr) -> bool {
+        match &expr.kind {
+            ExprKind::Lit(Lit::Num(value)) => is_integer(*value),
+            ExprKind::Ident(_) => self
+                .candidate(expr)
+                .is_some_and(|id| self.scopes.vars[id].integral),
+            ExprKind::Member(member) => is_length(member),
+            ExprKind::Call(call) => self.integral_call(call),
+            ExprKind::Paren(inner) => self.integral(inner),
+            ExprKind::Unary {
+                op: UnaryOp::Minus | UnaryOp::Plus,
+                arg,
+            } => self.integral(arg),
+            ExprKind::Update { arg, .. } => self.integral(arg),
+            ExprKind::Bin {
+                op: BinaryOp::Mul,
+                left,
+                right,
+            } => self.integral(left) && self.integral(right) && self.fits(expr),
+            ExprKind::Bin {
+                op: BinaryOp::Add | BinaryOp::Sub,
+                left,
+                right,
+            } => self.integral(left) && self.integral(right),
+            ExprKind::Bin {
+                op: BinaryOp::Mod,
+                left,
+                right,
+            } => self.integral(left) && is_divisor(right),
+            ExprKind::Cond { cons, alt, .. } => self.integral(cons) && self.integral(alt),
+            ExprKind::Seq(exprs) => exprs.last().is_some_and(|last| self.integral(last)),
+            _ => false,
+        }
+    }
+
+    /// Whether `expr`, provided it is integral, can't overflow an `i64`.
+    fn fits(&self, expr: &Expr) -> bool {
+        self.bits(expr) <= I64_BITS
+    }
+
+    /// Bits of the largest magnitude `expr` evaluates to, provided it is
+    /// integral.
+    fn bits(&self, expr: &Expr) -> u32 {
+        match &expr.kind {
+            ExprKind::Lit(Lit::Num(value)) => u64::BITS - (value.abs() as u64).leading_zeros(),
+            ExprKind::Ident(_) => self
+                .candidate(expr)
+                .map_or(UNBOUNDED_BITS, |id| self.scopes.vars[id].bits),
+            ExprKind::Member(member) if is_length(member) => COUNTER_BITS,
+            ExprKind::Call(call) => self.call_bits(call),
+            ExprKind::Paren(inner)
+            | ExprKind::Unary { arg: inner, .. }
+            | ExprKind::Update { arg: inner, .. } => self.bits(inner),
+            // Stepping by a constant counts like `++`, so `i = i + 1` can't
+            // grow `i` pass after pass
+            ExprKind::Bin {
+                op: BinaryOp::Add | BinaryOp::Sub,
+                left,
+                right,
+            } if is_step(left) || is_step(right) => {
+                self.bits(left).max(self.bits(right)).max(COUNTER_BITS)
+            }
+            ExprKind::Bin {
+                op: BinaryOp::Add | BinaryOp::Sub,
+                left,
+                right,
+            } => (self.bits(left).max(self.bits(right)) + 1).min(UNBOUNDED_BITS),
+            ExprKind::Bin {
+                op: BinaryOp::Mul,
+                left,
+                right,
+            } => (self.bits(left) + self.bits(right)).min(UNBOUNDED_BITS),
+            ExprKind::Bin {
+                op: BinaryOp::Mod,
+                left,
+                right,
+            } => self.bits(left).min(self.bits(right)),
+            ExprKind::Cond { cons, alt, .. } => self.bits(cons).max(self.bits(alt)),
+            ExprKind::Seq(exprs) => exprs.last().map_or(UNBOUNDED_BITS, |last| self.bits(last)),
+            _ => UNBOUNDED_BITS,
+        }
+    }
+
+    fn call_bits(&self, call: &CallExpr) -> u32 {
+        let args = || call.args.iter().map(|arg| self.bits(&arg.expr));
+        match call.callee.as_static_call() {
+            Some(("Math", "max" | "min" | "abs" | "floor" | "ceil" | "round" | "trunc")) => {
+                args().max().unwrap_or(UNBOUNDED_BITS)
+            }
+            Some(("Math", _)) => UNBOUNDED_BITS,
+            // `indexOf` and friends
+            _ if self.integral_call(call) => COUNTER_BITS,
+            _ => UNBOUNDED_BITS,
+        }
+    }
+
+    fn integral_call(&self, call: &CallExpr) -> bool {
+        let integral_args = || {
+            call.args
+                .iter()
+                .all(|arg| !arg.spread && self.integral(&arg.expr))
+        };
+        if let Some(("Math", "max" | "min" | "abs" | "floor" | "ceil" | "round" | "trunc")) =
+            call.callee.as_static_call()
+        {
+            return integral_args();
+        }
+        call.callee.as_member().is_some_and(|member| {
+            matches!(member.obj.ty, Type::Array(_) | Type::String)
+                && matches!(
+                    member.prop.as_ident(),
+                    Some("indexOf" | "lastIndexOf" | "findIndex")
+                )
+        })
+    }
+
+    /// Whether `expr` is never negative, provided it is integral.
+    fn non_negative(&self, expr: &Expr) -> bool {
+        match &expr.kind {
+            ExprKind::Lit(Lit::Num(value)) => *value >= 0.0,
+            ExprKind::Ident(_) => self
+                .candidate(expr)
+                .is_some_and(|id| self.scopes.vars[id].non_negative),
+            ExprKind::Member(member) => is_length(member),
+            ExprKind::Paren(inner) => self.non_negative(inner),
+            ExprKind::Bin {
+                op: BinaryOp::Add | BinaryOp::Mul | BinaryOp::Mod,
+                left,
+                right,
+            } => self.non_negative(left) && self.non_negative(right),
+            ExprKind::Cond { cons, alt, .. } => self.non_negative(cons) && self.non_negative(alt),
+            _ => false,
+        }
+    }
+
+    /// Records the type `expr` evaluates to, and what it is converted to for
+    /// a context expecting `want` (`None` when any number will do).
+    fn settle(&self, expr: &mut Expr, want: Option<NumRepr>) {
+        if !self.apply || !self.numeric(expr) {
+            return;
+        }
+        let natural = self.natural(expr);
+        let target = want.unwrap_or(natural.resolve());
+        // Parentheses and branches pass the conversion on to their operands
+        let flexible = natural == Natural::Literal
+            || matches!(
+                expr.kind,
+                ExprKind::Paren(_) | ExprKind::Cond { .. } | ExprKind::Seq(_)
+            );
+        // `xs[xs.length - 1]` can subtract in `usize`: a negative index fails either way
+        let unsigned_sub = match &expr.kind {
+            ExprKind::Bin {
+                op: BinaryOp::Sub,
+                left,
+                right,
+            } => [left, right].iter().all(|operand| {
+                matches!(
+                    self.natural(operand),
+                    Natural::Literal | Natural::Repr(NumRepr::Usize)
+                )
+            }),
+            _ => false,
+        };
+        if flexible || (unsigned_sub && target == NumRepr::Usize) {
+            expr.num = target;
+            expr.num_cast = None;
+        } else {
+            expr.num = natural.resolve();
+            expr.num_cast = (expr.num != target).then_some(target);
+        }
+    }
+
+    /// Settles a statement-level expression, then visits it.
+    fn root(&mut self, expr: &mut Expr, want: Option<NumRepr>) {
+        self.settle(expr, want);
+        self.visit_expr_mut(expr);
+    }
+
+    /// Settles the operands of `expr` for the type `expr` itself settled on.
+    fn settle_operands(&self, expr: &mut Expr) {
+        let own = self.numeric(expr).then_some(expr.num);
+        match &mut expr.kind {
+            ExprKind::Bin { op, left, right } => {
+                let want = match op {
+                    BinaryOp::Div | BinaryOp::Exp => Some(NumRepr::F64),
+                    BinaryOp::Lt
+                    | BinaryOp::LtEq
+                    | BinaryOp::Gt
+                    | BinaryOp::GtEq
+                    | BinaryOp::EqEq
+                    | BinaryOp::NotEq
+                    | BinaryOp::EqEqEq
+                    | BinaryOp::NotEqEq => (self.numeric(left) && self.numeric(right)).then(|| {
+                        match (self.natural(left), self.natural(right)) {
+                            // Two literals compare as they always did
+                            (Natural::Literal, Natural::Literal) => NumRepr::F64,
+                            (left, right) => left.join(right).resolve(),
+                        }
+                    }),
+                    BinaryOp::Add
+                    | BinaryOp::Sub
+                    | BinaryOp::Mul
+                    | BinaryOp::Mod
+                    | BinaryOp::BitAnd
+                    | BinaryOp::BitOr
+                    | BinaryOp::BitXor
+                    | BinaryOp::LShift
+                    | BinaryOp::RShift
+                    | BinaryOp::ZeroFillRShift => own,
+                    _ => None,
+                };
+                self.settle(left, want);
+                self.settle(right, want);
+            }
+            ExprKind::Unary {
+                op: UnaryOp::Minus | UnaryOp::Plus,
+                arg,
+            } => self.settle(arg, own),
+            ExprKind::Unary { arg, .. } | ExprKind::Await(arg) => self.settle(arg, None),
+            ExprKind::Paren(inner) => self.settle(inner, own),
+            ExprKind::Cond { test, cons, alt } => {
+                self.settle(test, None);
+                self.settle(cons, own);
+                self.settle(alt, own);
+            }
+            ExprKind::Seq(exprs) => {
+                if let Some((last, init)) = exprs.split_last_mut() {
+                    for expr in init {
+                        self.settle(expr, None);
+                    }
+                    self.settle(last, own);
+                }
+            }
+            ExprKind::Member(member) | ExprKind::OptMember(member) => self.settle_member(member),
+            ExprKind::Call(call) | ExprKind::New(call) | ExprKind::OptCall(call) => {
+                if let Callee::Expr(callee) = &mut call.callee {
+                    self.settle(callee, None);
+                }
+                // `console.log` prints integers as JavaScript does; everything
+                // else takes `f64` parameters
+                let want = match call.callee.as_static_call() {
+                    Some(("console", _)) => None,
+                    _ => Some(NumRepr::F64),
+                };
+                for arg in &mut call.args {
+                    self.settle(&mut arg.expr, want);
+                }
+            }
+            ExprKind::Tpl { exprs, .. } => {
+                for expr in exprs {
+                    self.settle(expr, None);
+                }
+            }
+            ExprKind::Array(elems) => {
+                for elem in elems.iter_mut().flatten() {
+                    self.settle(&mut elem.expr, Some(NumRepr::F64));
+                }
+            }
+            ExprKind::Object(props) => {
+                for prop in props {
+                    if let Prop::KeyValue { key, value } = prop {
+                        if let PropKey::Computed(key) = key {
+                            self.settle(key, None);
+                        }
+                        self.settle(value, None);
+                    }
+                }
+            }
+            ExprKind::Assign { op, target, right } => {
+                let want = match target {
+                    AssignTarget::Ident(name) => match self.scopes.lookup(name) {
+                        Some(id) => self.scopes.vars[id].repr(),
+                        None => NumRepr::F64,
+                    },
+                    AssignTarget::Member(member) => {
+                        self.settle_member(member);
+                        NumRepr::F64
+                    }
+                    AssignTarget::Unsupported => NumRepr::F64,
+                };
+                let want = match op {
+                    AssignOp::DivAssign => NumRepr::F64,
+                    _ => want,
+                };
+                self.settle(right, Some(want));
+            }
+            ExprKind::Update { arg, .. } => {
+                let want = self.natural(arg).resolve();
+                self.settle(arg, Some(want));
+            }
+            ExprKind::Ident(_)
+            | ExprKind::This
+            | ExprKind::Super
+            | ExprKind::Lit(_)
+            | ExprKind::Arrow(_)
+            | ExprKind::Unsupported { .. } => {}
+        }
+    }
+
+    fn settle_member(&self, member: &mut MemberExpr) {
+        // Methods of numbers, like `toFixed`, are defined on `f64`
+        self.settle(&mut member.obj, Some(NumRepr::F64));
+        if let MemberProp::Computed(prop) = &mut member.prop {
+            let want = if indexable(&member.obj.ty) {
+                NumRepr::Usize
+            } else {
+                NumRepr::F64
+            };
+            self.settle(prop, Some(want));
+        }
+    }
+
+    fn for_head(&mut self, head: &mut ForHead) {
+        match head {
+            ForHead::Var(var) => {
+                for decl in &mut var.decls {
+                    self.pat(&mut decl.pat);
+                }
+            }
+            // Elements and keys are never known to be integers
+            ForHead::Pat(Pat::Ident(binding)) => {
+                if let Some(id) = self.scopes.lookup(&binding.name) {
+                    self.update(id, |var| var.integral = false);
+                }
+            }
+            ForHead::Pat(_) => {}
+        }
+    }
+
+    fn block(&mut self, stmts: &mut [Stmt]) {
+        self.scoped(|this| {
+            for stmt in stmts {
+                this.visit_stmt_mut(stmt);
+            }
+        });
+    }
+}
+
+impl VisitMut for Integers {
+    fn visit_decl_mut(&mut self, decl: &mut Decl) {
+        match decl {
+            Decl::Class(class) => {
+                for member in &mut class.members {
+                    match member {
+                        ClassMember::Prop(prop) => {
+                            if let Some(value) = &mut prop.value {
+                                self.root(value, Some(NumRepr::F64));
+                            }
+                        }
+                        ClassMember::Method(method) => {
+                            self.visit_function_mut(&mut method.function)
+                        }
+                        ClassMember::Constructor(ctor) => self.scoped(|this| {
+                            for param in &mut ctor.params {
+                                match param {
+                                    CtorParam::Param(param) => this.pat(&mut param.pat),
+                                    CtorParam::Prop { name, .. } => this.declare(name),
+                                }
+                            }
+                            for stmt in ctor.body.iter_mut().flatten() {
+                                this.visit_stmt_mut(stmt);
+                            }
+                        }),
+                    }
+                }
+            }
+            // Module-level variables become statics of their declared type
+            Decl::Var(_) => {}
+            _ => tyrus_ast::visit::walk_decl_mut(self, decl),
+        }
+    }
+
+    fn visit_function_mut(&mut self, function: &mut Function) {
+        self.scoped(|this| walk_function_mut(this, function));
+    }
+
+    fn visit_pat_mut(&mut self, pat: &mut Pat) {
+        // Parameters; other patterns are declared where they are bound
+        self.pat(pat);
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        match &mut stmt.kind {
+            StmtKind::Expr(expr) | StmtKind::Throw(expr) => self.root(expr, None),
+            StmtKind::Return(Some(arg)) => self.root(arg, Some(NumRepr::F64)),
+            StmtKind::Var(var) => self.var_decl(var),
+            StmtKind::Block(stmts) => self.block(stmts),
+            StmtKind::If { test, cons, alt } => {
+                self.root(test, None);
+                self.visit_stmt_mut(cons);
+                if let Some(alt) = alt {
+                    self.visit_stmt_mut(alt);
+                }
+            }
+            StmtKind::While { test, body } | StmtKind::DoWhile { body, test } => {
+                self.root(test, None);
+                self.visit_stmt_mut(body);
+            }
+            StmtKind::For {
+                init,
+                test,
+                update,
+                body,
+            } => self.scoped(|this| {
+                match init {
+                    Some(ForInit::Var(var)) => this.var_decl(var),
+                    Some(ForInit::Expr(expr)) => this.root(expr, None),
+                    None => {}
+                }
+                if let Some(test) = test {
+                    this.root(test, None);
+                }
+                if let Some(update) = update {
+                    this.root(update, None);
+                }
+                this.visit_stmt_mut(body);
+            }),
+            StmtKind::ForOf { left, right, body } | StmtKind::ForIn { left, right, body } => {
+                self.root(right, None);
+                self.scoped(|this| {
+                    this.for_head(left);
+                    this.visit_stmt_mut(body);
+                });
+            }
+            StmtKind::Switch {
+                discriminant,
+                cases,
+            } => {
+                let want = self.natural(discriminant).resolve();
+                self.root(discriminant, Some(want));
+                for case in cases {
+                    if let Some(test) = &mut case.test {
+                        self.root(test, Some(want));
+                    }
+                    self.block(&mut case.cons);
+                }
+            }
+            StmtKind::Match { scrutinee, arms } => {
+                self.root(scrutinee, None);
+                for arm in arms {
+                    self.scoped(|this| {
+                        this.scopes.declare_arm(&arm.pats, || Var::new(false));
+                        for stmt in &mut arm.body {
+                            this.visit_stmt_mut(stmt);
+                        }
+                    });
+                }
+            }
+            StmtKind::Try {
+                block,
+                handler,
+                finalizer,
+            } => {
+                self.block(block);
+                if let Some(handler) = handler {
+                    self.scoped(|this| {
+                        if let Some(param) = &mut handler.param {
+                            this.pat(param);
+                        }
+                        for stmt in &mut handler.body {
+                            this.visit_stmt_mut(stmt);
+                        }
+                    });
+                }
+                if let Some(finalizer) = finalizer {
+                    self.block(finalizer);
+                }
+            }
+            _ => walk_stmt_mut(self, stmt),
+        }
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        self.settle_operands(expr);
+        if let ExprKind::Assign {
+            op,
+            target: AssignTarget::Ident(name),
+            ..
+        } = &expr.kind
+        {
+            let (op, name) = (*op, name.clone());
+            // The value is computed before it is stored
+            walk_expr_mut(self, expr);
+            if let ExprKind::Assign { right, .. } = &expr.kind {
+                self.assign(op, &name, right);
+            }
+            return;
+        }
+        match &mut expr.kind {
+            ExprKind::Update { op, arg, .. } => {
+                if let Some(id) = self.candidate(arg) {
+                    self.step(id);
+                    if *op == UpdateOp::Dec {
+                        self.update(id, |var| var.non_negative = false);
+                    }
+                }
+            }
+            ExprKind::Member(member) | ExprKind::OptMember(member) => {
+                if let MemberProp::Computed(prop) = &member.prop {
+                    if indexable(&member.obj.ty) {
+                        self.mark_index(prop);
+                    }
+                }
+            }
+            ExprKind::Bin {
+                op: BinaryOp::Lt | BinaryOp::LtEq | BinaryOp::Gt | BinaryOp::GtEq,
+                left,
+                right,
+            } => {
+                // `i < xs.length` bounds an index
+                for (var, bound) in [(&**left, &**right), (&**right, &**left)] {
+                    if matches!(&bound.unparen().kind, ExprKind::Member(member) if is_length(member))
+                    {
+                        self.mark_index(var);
+                    }
+                }
+            }
+            ExprKind::Arrow(arrow) => {
+                self.scoped(|this| {
+                    for param in &mut arrow.params {
+                        this.pat(param);
+                    }
+                    match &mut arrow.body {
+                        ArrowBody::Block(stmts) => {
+                            for stmt in stmts {
+                                this.visit_stmt_mut(stmt);
+                            }
+                        }
+                        ArrowBody::Expr(body) => this.root(body, Some(NumRepr::F64)),
+                    }
+                });
+                return;
+            }
+            _ => {}
+        }
+        walk_expr_mut(self, expr);
+    }
+}
+
+fn is_integer(value: f64) -> bool {
+    value.fract() == 0.0 && value.abs() <= MAX_SAFE_INTEGER
+}
+
+/// An integer literal or a length, which a variable can be stepped by
+/// without outgrowing an `i64`.
+fn is_step(expr: &Expr) -> bool {
+    match &expr.unparen().kind {
+        ExprKind::Lit(Lit::Num(_)) => true,
+        ExprKind::Member(member) => is_length(member),
+        ExprKind::Unary {
+            op: UnaryOp::Minus | UnaryOp::Plus,
+            arg,
+        } => is_step(arg),
+        _ => false,
+    }
+}
+
+/// A non-zero integer literal, which `%` can divide an integer by without
+/// panicking where JavaScript gives `NaN`.
+fn is_divisor(expr: &Expr) -> bool {
+    match &expr.unparen().kind {
+        ExprKind::Lit(Lit::Num(value)) => *value != 0.0 && is_integer(*value),
+        ExprKind::Unary {
+            op: UnaryOp::Minus | UnaryOp::Plus,
+            arg,
+        } => is_divisor(arg),
+        _ => false,
+    }
+}
+
+/// `arr.length`, `str.length`, `map.size` or `set.size`: a `usize` in Rust.
+fn is_length(member: &MemberExpr) -> bool {
+    match (&member.obj.ty, member.prop.as_ident()) {
+        (Type::Array(_) | Type::String, Some("length")) => true,
+        (Type::Named { name, .. }, Some("size")) => name == "Map" || name == "Set",
+        _ => false,
+    }
+}
+
+/// Whether values of type `ty` are indexed by position.
+fn indexable(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Array(_) | Type::Tuple(_) | Type::String | Type::Unknown | Type::Any
+    )
+}
@@ -0,0 +1,248 @@
+use tyrus_ast::visit::{walk_expr_mut, walk_pat_mut, VisitMut};
+use tyrus_ast::{Expr, ExprKind, Module, NumRepr, Pat};
+
+use super::infer_integers;
+use crate::typeck::check_module;
+
+fn infer(src: &str) -> Module {
+    let (program, comments) = tyrus_parser::parse_str(src).expect("test source should parse");
+    let mut module = tyrus_ast::lower_program_with_comments(&program, &comments);
+    check_module(&mut module);
+    infer_integers(&mut module);
+    module
+}
+
+/// Representation of every binding, in declaration order.
+fn reprs(module: &mut Module) -> Vec<(String, NumRepr)> {
+    struct Collect(Vec<(String, NumRepr)>);
+    impl VisitMut for Collect {
+        fn visit_pat_mut(&mut self, pat: &mut Pat) {
+            if let Pat::Ident(binding) = pat {
+                self.0.push((binding.name.clone(), binding.num));
+            }
+            walk_pat_mut(self, pat);
+        }
+    }
+    let mut collect = Collect(Vec::new());
+    collect.visit_module_mut(module);
+    collect.0
+}
+
+/// Conversions of every read of variable `name`, in source order.
+fn casts(module: &mut Module, name: &str) -> Vec<Option<NumRepr>> {
+    struct Collect<'a> {
+        name: &'a str,
+        found: Vec<Option<NumRepr>>,
+    }
+    impl VisitMut for Collect<'_> {
+        fn visit_expr_mut(&mut self, expr: &mut Expr) {
+            if matches!(&expr.kind, ExprKind::Ident(name) if name == self.name) {
+                self.found.push(expr.num_cast);
+            }
+            walk_expr_mut(self, expr);
+        }
+    }
+    let mut collect = Collect {
+        name,
+        found: Vec::new(),
+    };
+    collect.visit_module_mut(module);
+    collect.found
+}
+
+fn named(pairs: &[(&str, NumRepr)]) -> Vec<(String, NumRepr)> {
+    pairs
+        .iter()
+        .map(|(name, repr)| (name.to_string(), *repr))
+        .collect()
+}
+
+#[test]
+fn test_counters_and_indices() {
+    let mut module = infer(
+        r#"
+        function f(xs: number[], limit: number): number {
+            let sum = 0;
+            let count = 0;
+            for (let i = 0; i < xs.length; i++) {
+                sum += xs[i];
+                count++;
+            }
+            let down = xs.length;
+            while (down > 0) {
+                down--;
+            }
+            return sum + count + limit;
+        }
+        "#,
+    );
+    assert_eq!(
+        reprs(&mut module),
+        named(&[
+            ("xs", NumRepr::F64),
+            ("limit", NumRepr::F64),
+            ("sum", NumRepr::F64),
+            ("count", NumRepr::I64),
+            ("i", NumRepr::Usize),
+            ("down", NumRepr::I64),
+        ])
+    );
+    // Converted where it is mixed with the `f64` sum
+    assert_eq!(casts(&mut module, "count"), vec![None, Some(NumRepr::F64)]);
+    assert_eq!(casts(&mut module, "i"), vec![None, None, None]);
+}
+
+#[test]
+fn test_integral_sources() {
+    let mut module = infer(
+        r#"
+        function f(text: string, x: number, names: string[]): void {
+            const floor = Math.floor(x);
+            const parsed = parseInt(text);
+            const found = names.indexOf(text);
+            const rounded = Math.round(found);
+            const product = found * 2 - rounded;
+            const half = rounded / 2;
+            let later = 0;
+            later = x;
+        }
+        "#,
+    );
+    let found: Vec<_> = reprs(&mut module).into_iter().skip(3).collect();
+    assert_eq!(
+        found,
+        named(&[
+            // Infinite or NaN when `x` is
+            ("floor", NumRepr::F64),
+            // NaN when `text` is not a number
+            ("parsed", NumRepr::F64),
+            ("found", NumRepr::I64),
+            ("rounded", NumRepr::I64),
+            ("product", NumRepr::I64),
+            ("half", NumRepr::F64),
+            ("later", NumRepr::F64),
+        ])
+    );
+    // Operands of `/` are converted to `f64`
+    assert_eq!(
+        casts(&mut module, "rounded"),
+        vec![None, Some(NumRepr::F64)]
+    );
+}
+
+#[test]
+fn test_remainders_by_variables_stay_float() {
+    let mut module = infer(
+        r#"
+        function f(xs: number[]): void {
+            const count = xs.length;
+            for (let i = 0; i < count; i++) {
+                const parity = i % 2;
+                const slot = i % count;
+                let kept = i;
+                kept %= -3;
+                let wrapped = i;
+                wrapped %= count;
+            }
+        }
+        "#,
+    );
+    assert_eq!(
+        reprs(&mut module).into_iter().skip(1).collect::<Vec<_>>(),
+        named(&[
+            ("count", NumRepr::I64),
+            ("i", NumRepr::I64),
+            ("parity", NumRepr::I64),
+            // NaN in JavaScript when `count` is 0, where an integer panics
+            ("slot", NumRepr::F64),
+            ("kept", NumRepr::I64),
+            ("wrapped", NumRepr::F64),
+        ])
+    );
+    // Both operands of a remainder that stays `f64` are converted to it
+    assert_eq!(
+        casts(&mut module, "count"),
+        vec![None, Some(NumRepr::F64), Some(NumRepr::F64)]
+    );
+}
+
+#[test]
+fn test_float_directive() {
+    let mut module = infer(
+        r#"
+        function f(): void {
+            // tyrus-float
+            let kept = 0;
+            let counted = 0;
+            kept++;
+            counted++;
+        }
+        "#,
+    );
+    assert_eq!(
+        reprs(&mut module),
+        named(&[("kept", NumRepr::F64), ("counted", NumRepr::I64)])
+    );
+}
+
+#[test]
+fn test_growing_variables_stay_float() {
+    let mut module = infer(
+        r#"
+        function f(xs: number[]): void {
+            let big = 1;
+            let total = 0;
+            let step = 0;
+            let wrapped = 0;
+            for (let i = 0; i < 70; i++) {
+                big = big * 2;
+                total += i;
+                step = step + 2;
+                wrapped = (wrapped * 31 + i) % 7;
+            }
+            let lengths = 0;
+            lengths += xs.length;
+        }
+        "#,
+    );
+    assert_eq!(
+        reprs(&mut module).into_iter().skip(1).collect::<Vec<_>>(),
+        named(&[
+            ("big", NumRepr::F64),
+            ("total", NumRepr::F64),
+            ("step", NumRepr::I64),
+            ("wrapped", NumRepr::F64),
+            ("i", NumRepr::I64),
+            ("lengths", NumRepr::I64),
+        ])
+    );
+}
+
+#[test]
+fn test_products_that_can_overflow_stay_float() {
+    let mut module = infer(
+        r#"
+        function f(x: number): void {
+            for (let i = 0; i < 10; i++) {
+                const scaled = i * 1000000000;
+                const huge = i * 1000000000 * 1000000000;
+                const twice = Math.floor(x) * 2;
+                const rescaled = scaled * 1000000000;
+            }
+        }
+        "#,
+    );
+    assert_eq!(
+        reprs(&mut module).into_iter().skip(1).collect::<Vec<_>>(),
+        named(&[
+            ("i", NumRepr::I64),
+            ("scaled", NumRepr::I64),
+            ("huge", NumRepr::F64),
+            // `Math.floor(x)` is as large as `x` is
+            ("twice", NumRepr::F64),
+            ("rescaled", NumRepr::F64),
+        ])
+    );
+    // The factors of a product that stays `f64` are converted to it
+    assert_eq!(casts(&mut module, "scaled"), vec![Some(NumRepr::F64)]);
+}
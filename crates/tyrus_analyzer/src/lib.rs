@@ -1,17 +1,76 @@
+pub mod features;
 pub mod graph;
+pub mod integers;
 pub mod lints;
+pub mod mutability;
+pub mod ownership;
+mod scope;
+pub mod typeck;
 
-use crate::lints::LintVisitor;
+use crate::lints::{Fix, LintLevels, LintVisitor};
+use crate::typeck::TypeEnv;
+use swc_common::comments::SingleThreadedComments;
 use swc_ecma_ast::Program;
 use swc_ecma_visit::VisitWith;
+use tyrus_ast::ClassDecl;
 use tyrus_diagnostics::TyrusError;
 
 pub struct Analyzer;
 
 impl Analyzer {
-    pub fn analyze(program: &Program, source_code: String, file_name: String) -> Vec<TyrusError> {
-        let mut visitor = LintVisitor::new(source_code, file_name);
+    /// Everything `tyrus check` reports: violations of the Oxidizable rules
+    /// at their `levels`, and the constructs codegen cannot translate, given
+    /// the `exports` and `classes` of the other modules of the project.
+    pub fn analyze(
+        program: &Program,
+        comments: &SingleThreadedComments,
+        source_code: String,
+        file_name: String,
+        levels: &LintLevels,
+        exports: &TypeEnv,
+        classes: &[&ClassDecl],
+    ) -> Vec<TyrusError> {
+        Self::analyze_with_fixes(
+            program,
+            comments,
+            source_code,
+            file_name,
+            levels,
+            exports,
+            classes,
+        )
+        .0
+    }
+
+    /// [`analyze`](Self::analyze), with the fixes of the lints it reports.
+    pub fn analyze_with_fixes(
+        program: &Program,
+        comments: &SingleThreadedComments,
+        source_code: String,
+        file_name: String,
+        levels: &LintLevels,
+        exports: &TypeEnv,
+        classes: &[&ClassDecl],
+    ) -> (Vec<TyrusError>, Vec<Fix>) {
+        let mut visitor = LintVisitor::new(source_code, file_name, comments, levels.clone());
+        program.visit_with(&mut visitor);
+        visitor.check_typed(program);
+        visitor.check_features(program, exports, classes);
+        (visitor.errors, visitor.fixes)
+    }
+
+    /// Violations of the Oxidizable rules alone, for builds, which learn
+    /// the untranslatable constructs from codegen.
+    pub fn lint(
+        program: &Program,
+        comments: &SingleThreadedComments,
+        source_code: String,
+        file_name: String,
+        levels: &LintLevels,
+    ) -> Vec<TyrusError> {
+        let mut visitor = LintVisitor::new(source_code, file_name, comments, levels.clone());
         program.visit_with(&mut visitor);
+        visitor.check_typed(program);
         visitor.errors
     }
 }
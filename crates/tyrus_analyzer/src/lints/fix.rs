@@ -0,0 +1,35 @@
+//! Mechanical rewrites of the source that resolve a lint, for `tyrus check --fix`.
+
+use std::ops::Range;
+
+use super::Lint;
+
+/// Replaces `range` of the source with `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// The lint this resolves.
+    pub lint: Lint,
+    /// Byte offsets into the source file.
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+/// `source` with `fixes` applied. Fixes overlapping an earlier one are
+/// skipped; a second run of `--fix` picks them up.
+pub fn apply(source: &str, fixes: &[Fix]) -> String {
+    let mut fixes: Vec<&Fix> = fixes.iter().collect();
+    fixes.sort_by_key(|fix| (fix.range.start, fix.range.end));
+
+    let mut fixed = String::with_capacity(source.len());
+    let mut end = 0;
+    for fix in fixes {
+        if fix.range.start < end {
+            continue;
+        }
+        fixed.push_str(&source[end..fix.range.start]);
+        fixed.push_str(&fix.replacement);
+        end = fix.range.end;
+    }
+    fixed.push_str(&source[end..]);
+    fixed
+}
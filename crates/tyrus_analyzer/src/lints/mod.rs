@@ -0,0 +1,735 @@
+//! The rules of the Oxidizable Standard, checked on the SWC AST, and on the
+//! type-checked IR for those about the types of expressions.
+//!
+//! Each [`Lint`] has a [`LintLevel`]: `deny` reports an error, `warn` a
+//! warning and `allow` nothing. A `// tyrus-ignore-next-line no-any` comment
+//! silences the lints it names on the line below it, or all of them when it
+//! names none.
+//!
+//! Lints with a mechanical resolution also record a [`Fix`], which
+//! `tyrus check --fix` applies to the source.
+
+pub mod fix;
+#[cfg(test)]
+mod tests;
+mod typed;
+
+pub use fix::Fix;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use miette::{NamedSource, SourceSpan};
+use swc_common::comments::SingleThreadedComments;
+use swc_common::Spanned;
+use tyrus_diagnostics::TyrusError;
+
+use crate::features;
+use crate::typeck::TypeEnv;
+
+use swc_ecma_ast::{
+    ArrowExpr, AssignExpr, AssignTarget, BinExpr, BinaryOp, BindingIdent, BlockStmt, CallExpr,
+    Callee, Class, Expr, FnDecl, FnExpr, ForHead, ForInStmt, ForOfStmt, ForStmt, Function,
+    GetterProp, Ident, Lit, MemberExpr, MemberProp, MethodProp, NewExpr, Pat, Program, SetterProp,
+    SimpleAssignTarget, SwitchStmt, ThisExpr, TsInterfaceDecl, TsKeywordType, TsKeywordTypeKind,
+    TsTypeElement, UnaryExpr, UnaryOp, UpdateExpr, VarDecl, VarDeclKind, VarDeclarator, WithStmt,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+/// A rule of the Oxidizable Standard, under the name configs and
+/// suppression comments use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Lint {
+    /// `no-var`
+    NoVar,
+    /// `no-any`
+    NoAny,
+    /// `no-eval`
+    NoEval,
+    /// `no-loose-equality`, allowed unless enabled.
+    NoLooseEquality,
+    /// `no-arguments`
+    NoArguments,
+    /// `no-with`
+    NoWith,
+    /// `no-delete`
+    NoDelete,
+    /// `no-prototype-mutation`
+    NoPrototypeMutation,
+    /// `no-dynamic-access`
+    NoDynamicAccess,
+    /// `no-function-constructor`
+    NoFunctionConstructor,
+    /// `no-function-this`
+    NoFunctionThis,
+    /// `no-mixed-equality`
+    NoMixedEquality,
+}
+
+impl Lint {
+    pub const ALL: &'static [Lint] = &[
+        Lint::NoVar,
+        Lint::NoAny,
+        Lint::NoEval,
+        Lint::NoLooseEquality,
+        Lint::NoArguments,
+        Lint::NoWith,
+        Lint::NoDelete,
+        Lint::NoPrototypeMutation,
+        Lint::NoDynamicAccess,
+        Lint::NoFunctionConstructor,
+        Lint::NoFunctionThis,
+        Lint::NoMixedEquality,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Lint::NoVar => "no-var",
+            Lint::NoAny => "no-any",
+            Lint::NoEval => "no-eval",
+            Lint::NoLooseEquality => "no-loose-equality",
+            Lint::NoArguments => "no-arguments",
+            Lint::NoWith => "no-with",
+            Lint::NoDelete => "no-delete",
+            Lint::NoPrototypeMutation => "no-prototype-mutation",
+            Lint::NoDynamicAccess => "no-dynamic-access",
+            Lint::NoFunctionConstructor => "no-function-constructor",
+            Lint::NoFunctionThis => "no-function-this",
+            Lint::NoMixedEquality => "no-mixed-equality",
+        }
+    }
+
+    /// The level of the lint when neither a config nor a flag sets it.
+    pub fn default_level(self) -> LintLevel {
+        match self {
+            // `==` between operands of one type means the same in Rust
+            Lint::NoLooseEquality => LintLevel::Allow,
+            _ => LintLevel::Deny,
+        }
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Lint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Lint::ALL
+            .iter()
+            .copied()
+            .find(|lint| lint.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Lint::ALL.iter().map(|lint| lint.name()).collect();
+                format!("unknown lint `{s}`, expected one of {}", names.join(", "))
+            })
+    }
+}
+
+/// How a lint is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl FromStr for LintLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(LintLevel::Allow),
+            "warn" => Ok(LintLevel::Warn),
+            "deny" => Ok(LintLevel::Deny),
+            _ => Err(format!(
+                "unknown lint level `{s}`, expected allow, warn or deny"
+            )),
+        }
+    }
+}
+
+/// The level of every lint; those never set are at their default level.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LintLevels {
+    levels: HashMap<Lint, LintLevel>,
+}
+
+impl LintLevels {
+    pub fn set(&mut self, lint: Lint, level: LintLevel) {
+        self.levels.insert(lint, level);
+    }
+
+    pub fn level(&self, lint: Lint) -> LintLevel {
+        self.levels
+            .get(&lint)
+            .copied()
+            .unwrap_or_else(|| lint.default_level())
+    }
+}
+
+/// The comment silencing lints on the line below it.
+const IGNORE_NEXT_LINE: &str = "tyrus-ignore-next-line";
+
+/// Lines silenced by an ignore comment, with the lints it names (`None` for
+/// all of them).
+fn suppressions(
+    comments: &SingleThreadedComments,
+    lines: &LineIndex,
+) -> HashMap<usize, Option<Vec<Lint>>> {
+    let (leading, trailing) = comments.borrow_all();
+    leading
+        .values()
+        .chain(trailing.values())
+        .flatten()
+        .filter_map(|comment| {
+            let names = comment.text.trim().strip_prefix(IGNORE_NEXT_LINE)?;
+            if !names.is_empty() && !names.starts_with(char::is_whitespace) {
+                return None;
+            }
+            let names: Vec<_> = names
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|name| !name.is_empty())
+                .collect();
+            // Unknown names silence nothing rather than everything
+            let lints =
+                (!names.is_empty()).then(|| names.iter().filter_map(|n| n.parse().ok()).collect());
+            Some((lines.line(comment.span.lo) + 1, lints))
+        })
+        .collect()
+}
+
+/// Start offsets of the lines of a source file.
+struct LineIndex(Vec<usize>);
+
+impl LineIndex {
+    fn new(source: &str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        LineIndex(starts)
+    }
+
+    /// The 0-based line of `pos`, in a file parsed on its own, starting at 1.
+    fn line(&self, pos: swc_common::BytePos) -> usize {
+        let offset = (pos.0 as usize).saturating_sub(1);
+        self.0.partition_point(|&start| start <= offset) - 1
+    }
+}
+
+/// Names bound by `var` and names assigned to anywhere in a file, which
+/// decide how a `var` is fixed.
+#[derive(Default)]
+struct Bindings {
+    assigned: HashSet<String>,
+    var_declared: HashMap<String, usize>,
+    /// The block, loop head or file each `var` name is declared in, which
+    /// would scope it as a `let`.
+    var_scopes: HashMap<String, swc_common::Span>,
+    /// Where each name appears, declarations included, with the start of
+    /// the function or arrow it appears in.
+    references: HashMap<String, Vec<(swc_common::BytePos, Option<swc_common::BytePos>)>>,
+    scopes: Vec<swc_common::Span>,
+    /// The starts of the functions and arrows being visited, innermost last.
+    functions: Vec<swc_common::BytePos>,
+}
+
+impl Bindings {
+    /// Visits `node`, which scopes the `let`s declared in it.
+    fn scoped(&mut self, span: swc_common::Span, node: &impl VisitWith<Self>) {
+        self.scopes.push(span);
+        node.visit_children_with(self);
+        self.scopes.pop();
+    }
+
+    /// Visits the function or arrow `node`.
+    fn function(&mut self, span: swc_common::Span, node: &impl VisitWith<Self>) {
+        self.functions.push(span.lo);
+        node.visit_children_with(self);
+        self.functions.pop();
+    }
+}
+
+/// Collects the names a pattern binds.
+struct Names<'a>(&'a mut HashSet<String>);
+
+impl Visit for Names<'_> {
+    fn visit_binding_ident(&mut self, n: &BindingIdent) {
+        self.0.insert(n.id.sym.to_string());
+    }
+}
+
+impl Visit for Bindings {
+    fn visit_program(&mut self, n: &Program) {
+        self.scoped(n.span(), n);
+    }
+
+    fn visit_block_stmt(&mut self, n: &BlockStmt) {
+        self.scoped(n.span, n);
+    }
+
+    fn visit_for_stmt(&mut self, n: &ForStmt) {
+        self.scoped(n.span, n);
+    }
+
+    fn visit_switch_stmt(&mut self, n: &SwitchStmt) {
+        self.scoped(n.span, n);
+    }
+
+    fn visit_function(&mut self, n: &Function) {
+        self.function(n.span, n);
+    }
+
+    fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+        self.function(n.span, n);
+    }
+
+    fn visit_ident(&mut self, n: &Ident) {
+        let function = self.functions.last().copied();
+        self.references
+            .entry(n.sym.to_string())
+            .or_default()
+            .push((n.span.lo, function));
+    }
+
+    fn visit_assign_expr(&mut self, n: &AssignExpr) {
+        match &n.left {
+            AssignTarget::Simple(SimpleAssignTarget::Ident(ident)) => {
+                self.assigned.insert(ident.id.sym.to_string());
+            }
+            AssignTarget::Pat(pat) => pat.visit_with(&mut Names(&mut self.assigned)),
+            _ => {}
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_update_expr(&mut self, n: &UpdateExpr) {
+        if let Expr::Ident(ident) = &*n.arg {
+            self.assigned.insert(ident.sym.to_string());
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_for_in_stmt(&mut self, n: &ForInStmt) {
+        if let ForHead::Pat(pat) = &n.left {
+            pat.visit_with(&mut Names(&mut self.assigned));
+        }
+        self.scoped(n.span, n);
+    }
+
+    fn visit_for_of_stmt(&mut self, n: &ForOfStmt) {
+        if let ForHead::Pat(pat) = &n.left {
+            pat.visit_with(&mut Names(&mut self.assigned));
+        }
+        self.scoped(n.span, n);
+    }
+
+    fn visit_var_decl(&mut self, n: &VarDecl) {
+        if n.kind == VarDeclKind::Var {
+            for decl in &n.decls {
+                if let Pat::Ident(ident) = &decl.name {
+                    let name = ident.id.sym.to_string();
+                    if let Some(&scope) = self.scopes.last() {
+                        self.var_scopes.insert(name.clone(), scope);
+                    }
+                    *self.var_declared.entry(name).or_default() += 1;
+                }
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+pub struct LintVisitor {
+    pub errors: Vec<TyrusError>,
+    /// Fixes of the reported lints that have one.
+    pub fixes: Vec<Fix>,
+    pub source_code: String,
+    pub file_name: String,
+    levels: LintLevels,
+    lines: LineIndex,
+    suppressions: HashMap<usize, Option<Vec<Lint>>>,
+    bindings: Bindings,
+    /// Types inferred for `any` annotations, by their start.
+    inferred: HashMap<swc_common::BytePos, &'static str>,
+    /// Whether each function `this` may be used in is a plain `function`,
+    /// innermost last. Arrow functions keep the `this` around them.
+    this_scopes: Vec<bool>,
+    /// Interfaces with an index signature.
+    dictionaries: HashSet<String>,
+}
+
+impl LintVisitor {
+    pub fn new(
+        source_code: String,
+        file_name: String,
+        comments: &SingleThreadedComments,
+        levels: LintLevels,
+    ) -> Self {
+        let lines = LineIndex::new(&source_code);
+        let suppressions = suppressions(comments, &lines);
+        Self {
+            errors: Vec::new(),
+            fixes: Vec::new(),
+            source_code,
+            file_name,
+            levels,
+            lines,
+            suppressions,
+            bindings: Bindings::default(),
+            inferred: HashMap::new(),
+            this_scopes: Vec::new(),
+            dictionaries: HashSet::new(),
+        }
+    }
+
+    fn create_span(&self, span: swc_common::Span) -> SourceSpan {
+        let start = span.lo.0 as usize - 1;
+        let end = span.hi.0 as usize - 1;
+        let len = end - start;
+        SourceSpan::new(start.into(), len)
+    }
+
+    /// Reports `lint` at `span` as built by `error`, at the level of the lint
+    /// unless a comment silences it. Returns whether it was reported.
+    fn report(
+        &mut self,
+        lint: Lint,
+        span: swc_common::Span,
+        error: impl FnOnce(NamedSource<String>, SourceSpan) -> TyrusError,
+    ) -> bool {
+        let suppressed = self
+            .suppressions
+            .get(&self.lines.line(span.lo))
+            .is_some_and(|lints| lints.as_ref().is_none_or(|lints| lints.contains(&lint)));
+        if suppressed {
+            return false;
+        }
+        let src = NamedSource::new(self.file_name.clone(), self.source_code.clone());
+        let error = error(src, self.create_span(span));
+        match self.levels.level(lint) {
+            LintLevel::Allow => return false,
+            LintLevel::Warn => self.errors.push(TyrusError::LintWarning(Box::new(error))),
+            LintLevel::Deny => self.errors.push(error),
+        }
+        true
+    }
+
+    /// Records the fix of `lint` replacing the `expected` text at `start`.
+    /// Nothing is recorded when the source reads otherwise there.
+    fn fix(&mut self, lint: Lint, start: usize, expected: &str, replacement: &str) {
+        let range = start..start + expected.len();
+        if self.source_code.get(range.clone()) == Some(expected) {
+            self.fixes.push(Fix {
+                lint,
+                range,
+                replacement: replacement.to_string(),
+            });
+        }
+    }
+
+    fn offset(pos: swc_common::BytePos) -> usize {
+        pos.0 as usize - 1
+    }
+
+    /// `let`, or `const` when none of the names `decl` binds is ever assigned
+    /// to. `None` when a name is declared by several `var`s, which `let` and
+    /// `const` reject, is used outside its block or before its declaration,
+    /// which only hoisting allows, or is captured by a closure, which sees
+    /// one `var` but a `let` per loop iteration.
+    fn var_replacement(&self, decl: &VarDecl) -> Option<&'static str> {
+        let mut constant = true;
+        for declarator in &decl.decls {
+            match &declarator.name {
+                Pat::Ident(ident) => {
+                    let name = ident.id.sym.to_string();
+                    if self.bindings.var_declared.get(&name).copied() > Some(1) {
+                        return None;
+                    }
+                    let scope = self.bindings.var_scopes.get(&name)?;
+                    let references = &self.bindings.references[&name];
+                    let (_, function) = references
+                        .iter()
+                        .find(|&&(pos, _)| pos == ident.id.span.lo)?;
+                    let scoped = references.iter().all(|(pos, used_in)| {
+                        ident.id.span.lo <= *pos && *pos < scope.hi && used_in == function
+                    });
+                    if !scoped {
+                        return None;
+                    }
+                    constant &=
+                        declarator.init.is_some() && !self.bindings.assigned.contains(&name);
+                }
+                _ => constant = false,
+            }
+        }
+        Some(if constant { "const" } else { "let" })
+    }
+
+    /// Reports the lints about the types of expressions, on `program`
+    /// lowered and type-checked. Call it after visiting `program`.
+    pub fn check_typed(&mut self, program: &Program) {
+        let mut module = tyrus_ast::lower_program(program);
+        for finding in typed::find(&mut module, &self.dictionaries) {
+            match finding {
+                typed::Finding::DynamicAccess { ty, span } => {
+                    self.report(Lint::NoDynamicAccess, span, |src, span| {
+                        TyrusError::DynamicPropertyAccess { ty, src, span }
+                    });
+                }
+                typed::Finding::MixedEquality { left, right, span } => {
+                    // `===` between different types is always false, so
+                    // `--fix` must leave these to the developer
+                    let range = Self::offset(span.lo)..Self::offset(span.hi);
+                    self.fixes.retain(|fix| {
+                        fix.lint != Lint::NoLooseEquality || !range.contains(&fix.range.start)
+                    });
+                    self.report(Lint::NoMixedEquality, span, |src, span| {
+                        TyrusError::MixedEquality {
+                            left,
+                            right,
+                            src,
+                            span,
+                        }
+                    });
+                }
+            }
+        }
+    }
+
+    /// Visits a function body in which `this` is that of a plain `function`
+    /// or not.
+    fn with_this_scope(&mut self, plain: bool, visit: impl FnOnce(&mut Self)) {
+        self.this_scopes.push(plain);
+        visit(self);
+        self.this_scopes.pop();
+    }
+
+    /// Reports every construct of `program` that codegen cannot translate,
+    /// as decided by the [feature registry](crate::features). It is
+    /// type-checked first, against the `exports` of the other modules of its
+    /// project, so the type guards codegen turns into `match` arms are not
+    /// reported, and its classes' mutation of `this` inferred along with
+    /// that of the project's `classes`.
+    pub fn check_features(
+        &mut self,
+        program: &Program,
+        exports: &TypeEnv,
+        classes: &[&tyrus_ast::ClassDecl],
+    ) {
+        let mut module = tyrus_ast::lower_program(program);
+        crate::typeck::check_module_with(&mut module, exports);
+        crate::ownership::infer_ownership_with(&mut module, classes);
+        for (feature, span) in features::unsupported_with(&mut module, classes) {
+            self.errors.push(TyrusError::UnsupportedFeature {
+                feature: feature.to_string(),
+                src: NamedSource::new(self.file_name.clone(), self.source_code.clone()),
+                span: self.create_span(span),
+            });
+        }
+    }
+}
+
+impl Visit for LintVisitor {
+    fn visit_program(&mut self, n: &Program) {
+        n.visit_with(&mut self.bindings);
+        n.visit_children_with(self);
+    }
+
+    fn visit_var_decl(&mut self, n: &VarDecl) {
+        if n.kind == VarDeclKind::Var {
+            let reported = self.report(Lint::NoVar, n.span, |src, span| TyrusError::UseOfVar {
+                src,
+                span,
+            });
+            if let Some(replacement) = self.var_replacement(n).filter(|_| reported) {
+                self.fix(Lint::NoVar, Self::offset(n.span.lo), "var", replacement);
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        // `const x: any = "a"` is fixed to `const x: string = "a"`, unless
+        // `x` is assigned later, maybe a value of another type
+        if let (Pat::Ident(ident), Some(init)) = (&n.name, &n.init) {
+            let inferred = match &**init {
+                Expr::Lit(Lit::Str(_)) | Expr::Tpl(_) => Some("string"),
+                Expr::Lit(Lit::Num(_)) => Some("number"),
+                Expr::Lit(Lit::Bool(_)) => Some("boolean"),
+                _ => None,
+            }
+            .filter(|_| !self.bindings.assigned.contains(&*ident.id.sym));
+            if let (Some(ann), Some(inferred)) = (&ident.type_ann, inferred) {
+                self.inferred.insert(ann.type_ann.span_lo(), inferred);
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_ts_keyword_type(&mut self, n: &TsKeywordType) {
+        if n.kind == TsKeywordTypeKind::TsAnyKeyword {
+            let reported = self.report(Lint::NoAny, n.span, |src, span| TyrusError::UseOfAny {
+                src,
+                span,
+            });
+            // Only a literal initializer tells what `any` stands for: `unknown`
+            // would reject the uses `any` allows
+            let inferred = self.inferred.get(&n.span.lo).copied();
+            if let Some(replacement) = inferred.filter(|_| reported) {
+                self.fix(Lint::NoAny, Self::offset(n.span.lo), "any", replacement);
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_bin_expr(&mut self, n: &BinExpr) {
+        if let (BinaryOp::EqEq | BinaryOp::NotEq, op) = (n.op, n.op.as_str()) {
+            let reported = self.report(Lint::NoLooseEquality, n.span, |src, span| {
+                TyrusError::UseOfLooseEquality { src, span }
+            });
+            // The operator is the first `==`/`!=` after the left operand
+            let (from, to) = (
+                Self::offset(n.left.span_hi()),
+                Self::offset(n.right.span_lo()),
+            );
+            let start = self
+                .source_code
+                .get(from..to)
+                .and_then(|between| between.find(op))
+                .map(|i| from + i);
+            // `x == null` also holds for `undefined`, which `===` does not
+            let nullish = |expr: &Expr| match expr {
+                Expr::Lit(Lit::Null(_)) => true,
+                Expr::Ident(ident) => ident.sym == "undefined",
+                _ => false,
+            };
+            let strict = !nullish(&n.left) && !nullish(&n.right);
+            if let Some(start) = start.filter(|_| reported && strict) {
+                self.fix(Lint::NoLooseEquality, start, op, &format!("{op}="));
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_expr(&mut self, n: &Expr) {
+        if let Expr::Ident(ident) = n {
+            if ident.sym == "arguments" {
+                self.report(Lint::NoArguments, ident.span, |src, span| {
+                    TyrusError::UseOfArguments { src, span }
+                });
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_with_stmt(&mut self, n: &WithStmt) {
+        self.report(Lint::NoWith, n.span, |src, span| TyrusError::UseOfWith {
+            src,
+            span,
+        });
+        n.visit_children_with(self);
+    }
+
+    fn visit_unary_expr(&mut self, n: &UnaryExpr) {
+        if n.op == UnaryOp::Delete {
+            self.report(Lint::NoDelete, n.span, |src, span| {
+                TyrusError::UseOfDelete { src, span }
+            });
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_assign_expr(&mut self, n: &AssignExpr) {
+        // `X.prototype = …`, `X.prototype.y = …` and `x.__proto__ = …`
+        let is_prototype = |member: &MemberExpr| matches!(&member.prop, MemberProp::Ident(prop) if prop.sym == "prototype");
+        if let AssignTarget::Simple(SimpleAssignTarget::Member(member)) = &n.left {
+            let mutated = is_prototype(member)
+                || matches!(&member.prop, MemberProp::Ident(prop) if prop.sym == "__proto__")
+                || matches!(&*member.obj, Expr::Member(obj) if is_prototype(obj));
+            if mutated {
+                self.report(Lint::NoPrototypeMutation, n.span, |src, span| {
+                    TyrusError::PrototypeMutation { src, span }
+                });
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_new_expr(&mut self, n: &NewExpr) {
+        if matches!(&*n.callee, Expr::Ident(ident) if ident.sym == "Function") {
+            self.report(Lint::NoFunctionConstructor, n.span, |src, span| {
+                TyrusError::FunctionConstructor { src, span }
+            });
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_fn_decl(&mut self, n: &FnDecl) {
+        self.with_this_scope(true, |v| n.visit_children_with(v));
+    }
+
+    fn visit_fn_expr(&mut self, n: &FnExpr) {
+        self.with_this_scope(true, |v| n.visit_children_with(v));
+    }
+
+    fn visit_class(&mut self, n: &Class) {
+        self.with_this_scope(false, |v| n.visit_children_with(v));
+    }
+
+    fn visit_method_prop(&mut self, n: &MethodProp) {
+        self.with_this_scope(false, |v| n.visit_children_with(v));
+    }
+
+    fn visit_getter_prop(&mut self, n: &GetterProp) {
+        self.with_this_scope(false, |v| n.visit_children_with(v));
+    }
+
+    fn visit_setter_prop(&mut self, n: &SetterProp) {
+        self.with_this_scope(false, |v| n.visit_children_with(v));
+    }
+
+    fn visit_this_expr(&mut self, n: &ThisExpr) {
+        if self.this_scopes.last() == Some(&true) {
+            self.report(Lint::NoFunctionThis, n.span, |src, span| {
+                TyrusError::ThisInFunction { src, span }
+            });
+        }
+    }
+
+    fn visit_ts_interface_decl(&mut self, n: &TsInterfaceDecl) {
+        let indexed = n
+            .body
+            .body
+            .iter()
+            .any(|member| matches!(member, TsTypeElement::TsIndexSignature(_)));
+        if indexed {
+            self.dictionaries.insert(n.id.sym.to_string());
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if let Callee::Expr(expr) = &n.callee {
+            if let Expr::Ident(ident) = &**expr {
+                if ident.sym == "Function" {
+                    self.report(Lint::NoFunctionConstructor, n.span, |src, span| {
+                        TyrusError::FunctionConstructor { src, span }
+                    });
+                }
+                if ident.sym == "eval" {
+                    self.report(Lint::NoEval, n.span, |src, span| TyrusError::UseOfEval {
+                        src,
+                        span,
+                    });
+                }
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
@@ -0,0 +1,370 @@
+use swc_ecma_visit::VisitWith;
+use tyrus_diagnostics::TyrusError;
+
+use super::{fix, Lint, LintLevel, LintLevels, LintVisitor};
+
+fn visit(src: &str, levels: LintLevels) -> LintVisitor {
+    let (program, comments) = tyrus_parser::parse_str(src).expect("test source should parse");
+    let mut visitor = LintVisitor::new(src.to_string(), "test.ts".to_string(), &comments, levels);
+    program.visit_with(&mut visitor);
+    visitor.check_typed(&program);
+    visitor
+}
+
+/// The lints reported on `src` at `levels`, as `(lint, line, warning)`.
+fn lint(src: &str, levels: LintLevels) -> Vec<(&'static str, usize, bool)> {
+    visit(src, levels)
+        .errors
+        .iter()
+        .map(|error| {
+            let (error, warning) = match error {
+                TyrusError::LintWarning(error) => (&**error, true),
+                error => (error, false),
+            };
+            let (name, span) = match error {
+                TyrusError::UseOfVar { span, .. } => ("no-var", span),
+                TyrusError::UseOfAny { span, .. } => ("no-any", span),
+                TyrusError::UseOfEval { span, .. } => ("no-eval", span),
+                TyrusError::UseOfLooseEquality { span, .. } => ("no-loose-equality", span),
+                TyrusError::UseOfArguments { span, .. } => ("no-arguments", span),
+                TyrusError::UseOfWith { span, .. } => ("no-with", span),
+                TyrusError::UseOfDelete { span, .. } => ("no-delete", span),
+                TyrusError::PrototypeMutation { span, .. } => ("no-prototype-mutation", span),
+                TyrusError::DynamicPropertyAccess { span, .. } => ("no-dynamic-access", span),
+                TyrusError::FunctionConstructor { span, .. } => ("no-function-constructor", span),
+                TyrusError::ThisInFunction { span, .. } => ("no-function-this", span),
+                TyrusError::MixedEquality { span, .. } => ("no-mixed-equality", span),
+                other => panic!("not a lint: {other:?}"),
+            };
+            let line = src[..span.offset()].matches('\n').count() + 1;
+            (name, line, warning)
+        })
+        .collect()
+}
+
+#[test]
+fn test_levels() {
+    let src = "var a = 1;\nconst b: any = 2;\neval(\"b\");\n";
+    assert_eq!(
+        lint(src, LintLevels::default()),
+        vec![
+            ("no-var", 1, false),
+            ("no-any", 2, false),
+            ("no-eval", 3, false)
+        ]
+    );
+
+    let mut levels = LintLevels::default();
+    levels.set(Lint::NoVar, LintLevel::Allow);
+    levels.set(Lint::NoAny, LintLevel::Warn);
+    assert_eq!(
+        lint(src, levels),
+        vec![("no-any", 2, true), ("no-eval", 3, false)]
+    );
+}
+
+#[test]
+fn test_ignore_next_line() {
+    let src = r#"// tyrus-ignore-next-line no-any
+const a: any = 1;
+const b: any = 2;
+// tyrus-ignore-next-line no-var, no-eval
+var c = eval("a");
+// tyrus-ignore-next-line
+var d: any = 3;
+// tyrus-ignore-next-line no-var
+const e: any = 4;
+// tyrus-ignore-next-line no-such-lint
+var f = 5;
+"#;
+    assert_eq!(
+        lint(src, LintLevels::default()),
+        vec![
+            ("no-any", 3, false),
+            ("no-any", 9, false),
+            ("no-var", 11, false)
+        ]
+    );
+}
+
+#[test]
+fn test_parse_names() {
+    assert_eq!("no-any".parse(), Ok(Lint::NoAny));
+    assert_eq!("warn".parse(), Ok(LintLevel::Warn));
+    assert_eq!("no-mixed-equality".parse(), Ok(Lint::NoMixedEquality));
+    let error = "no_any".parse::<Lint>().unwrap_err();
+    assert!(error.starts_with("unknown lint `no_any`, expected one of no-var, no-any, no-eval,"));
+    assert!(error.ends_with("no-mixed-equality"));
+    assert!("error".parse::<LintLevel>().is_err());
+}
+
+/// `src` with the fixes of the lints reported at `levels` applied.
+fn fixed(src: &str, levels: LintLevels) -> String {
+    fix::apply(src, &visit(src, levels).fixes)
+}
+
+#[test]
+fn test_fix_var() {
+    let src = r#"var total = 0;
+var limit = 10;
+for (var i = 0; i < limit; i++) {
+  total += i;
+}
+var { a, b } = { a: 1, b: 2 };
+var pending;
+var twice = 1;
+var twice = 2;
+"#;
+    assert_eq!(
+        fixed(src, LintLevels::default()),
+        r#"let total = 0;
+const limit = 10;
+for (let i = 0; i < limit; i++) {
+  total += i;
+}
+let { a, b } = { a: 1, b: 2 };
+let pending;
+var twice = 1;
+var twice = 2;
+"#
+    );
+}
+
+#[test]
+fn test_fix_var_leaves_hoisted_uses() {
+    let src = r#"function pick(flag: boolean): number {
+  if (flag) {
+    var result = 1;
+  } else {
+    result = 2;
+  }
+  return result;
+}
+function early(): number {
+  later = 1;
+  var later = 2;
+  return later;
+}
+function scoped(flag: boolean): number {
+  if (flag) {
+    var inner = 1;
+    return inner;
+  }
+  return 0;
+}
+"#;
+    // Still reported, but `let` would not reach the other branch, the
+    // return or the assignment before the declaration
+    assert_eq!(
+        lint(src, LintLevels::default()),
+        vec![
+            ("no-var", 3, false),
+            ("no-var", 11, false),
+            ("no-var", 16, false)
+        ]
+    );
+    assert_eq!(
+        fixed(src, LintLevels::default()),
+        src.replace("var inner", "const inner")
+    );
+}
+
+#[test]
+fn test_fix_var_leaves_captured_variables() {
+    let src = r#"function handlers(): (() => number)[] {
+  const result: (() => number)[] = [];
+  for (var i = 0; i < 3; i++) {
+    result.push(() => i);
+  }
+  return result;
+}
+function later(): () => number {
+  var count = 1;
+  return function () {
+    return count;
+  };
+}
+function plain(): number {
+  var total = 2;
+  return total;
+}
+"#;
+    // Each closure would see its own `let`, where they share the `var`
+    assert_eq!(
+        fixed(src, LintLevels::default()),
+        src.replace("var total", "const total")
+    );
+}
+
+#[test]
+fn test_fix_any() {
+    let src = "const name: any = \"a\";\nconst count: any = 1;\n";
+    assert_eq!(
+        fixed(src, LintLevels::default()),
+        "const name: string = \"a\";\nconst count: number = 1;\n"
+    );
+
+    // `unknown` would reject `x.length`, which `any` allows
+    let src = "function f(x: any): any[] {\n  return [x.length];\n}\n";
+    assert_eq!(fixed(src, LintLevels::default()), src);
+
+    // `v` later holds a string, so `number` would not type-check
+    let src = "let v: any = 1;\nv = \"text\";\n";
+    assert_eq!(fixed(src, LintLevels::default()), src);
+}
+
+#[test]
+fn test_fix_loose_equality() {
+    let src = "const same = a == b;\nconst other = (a) != /* != */ b;\n";
+    // Allowed by default, so neither reported nor fixed
+    assert_eq!(lint(src, LintLevels::default()), vec![]);
+    assert_eq!(fixed(src, LintLevels::default()), src);
+
+    let mut levels = LintLevels::default();
+    levels.set(Lint::NoLooseEquality, LintLevel::Warn);
+    assert_eq!(
+        fixed(src, levels.clone()),
+        "const same = a === b;\nconst other = (a) !== /* != */ b;\n"
+    );
+
+    // `== null` also holds for `undefined`, so it is reported but kept
+    let src = "const missing = a == null;\nconst unset = undefined != b;\n";
+    assert_eq!(
+        lint(src, levels.clone()),
+        vec![
+            ("no-loose-equality", 1, true),
+            ("no-loose-equality", 2, true)
+        ]
+    );
+    assert_eq!(fixed(src, levels), src);
+}
+
+#[test]
+fn test_fix_only_reported_lints() {
+    let src = "// tyrus-ignore-next-line\nvar a = 1;\nvar b: any = 2;\n";
+    let mut levels = LintLevels::default();
+    levels.set(Lint::NoAny, LintLevel::Allow);
+    assert_eq!(
+        fixed(src, levels),
+        "// tyrus-ignore-next-line\nvar a = 1;\nconst b: any = 2;\n"
+    );
+}
+
+#[test]
+fn test_runtime_semantics() {
+    let src = r#"function count() {
+  return arguments.length;
+}
+function drop(user: { name?: string }) {
+  delete user.name;
+}
+Array.prototype.last = () => 1;
+Point.prototype = {};
+const add = new Function("a", "b", "return a + b");
+const one = Function("return 1");
+"#;
+    assert_eq!(
+        lint(src, LintLevels::default()),
+        vec![
+            ("no-arguments", 2, false),
+            ("no-delete", 5, false),
+            ("no-prototype-mutation", 7, false),
+            ("no-prototype-mutation", 8, false),
+            ("no-function-constructor", 9, false),
+            ("no-function-constructor", 10, false),
+        ]
+    );
+}
+
+#[test]
+fn test_with() {
+    let src = "function f(o: { a: number }) {\n  with (o) {\n    a;\n  }\n}\n";
+    assert_eq!(
+        lint(src, LintLevels::default()),
+        vec![("no-with", 2, false)]
+    );
+}
+
+#[test]
+fn test_function_this() {
+    let src = r#"const handler = function () {
+  return this.value;
+};
+function outer() {
+  const inner = () => this.value;
+}
+class Counter {
+  count = 0;
+  increment() {
+    const bump = () => this.count++;
+    function reset() {
+      this.count = 0;
+    }
+  }
+}
+const point = {
+  x: 1,
+  getX() {
+    return this.x;
+  },
+};
+"#;
+    assert_eq!(
+        lint(src, LintLevels::default()),
+        vec![
+            ("no-function-this", 2, false),
+            ("no-function-this", 5, false),
+            ("no-function-this", 12, false),
+        ]
+    );
+}
+
+#[test]
+fn test_dynamic_access() {
+    let src = r#"interface User {
+  name: string;
+}
+interface Scores {
+  [name: string]: number;
+}
+function read(user: User, scores: Scores, totals: Record<string, number>, xs: number[], key: string, i: number) {
+  const a = user[key];
+  const b = user["name"];
+  const c = scores[key];
+  const d = totals[key];
+  const e = xs[i];
+}
+"#;
+    assert_eq!(
+        lint(src, LintLevels::default()),
+        vec![("no-dynamic-access", 8, false)]
+    );
+}
+
+#[test]
+fn test_mixed_equality() {
+    let src = r#"function compare(n: number, s: string, b: boolean, m: number, o?: string) {
+  const x = n == s;
+  const y = b != 1;
+  const z = n == m;
+  const w = o == null;
+  const v = n === m;
+}
+"#;
+    assert_eq!(
+        lint(src, LintLevels::default()),
+        vec![
+            ("no-mixed-equality", 2, false),
+            ("no-mixed-equality", 3, false)
+        ]
+    );
+
+    // `===` would always be false here, so only same-typed `==` is fixed
+    let mut levels = LintLevels::default();
+    levels.set(Lint::NoLooseEquality, LintLevel::Warn);
+    let fixed = fixed(src, levels);
+    assert!(fixed.contains("n == s;"));
+    assert!(fixed.contains("b != 1;"));
+    assert!(fixed.contains("n === m;"));
+    assert!(fixed.contains("o == null;"));
+}
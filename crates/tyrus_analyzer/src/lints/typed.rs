@@ -0,0 +1,104 @@
+//! The lints that need the types of expressions, found on the lowered,
+//! type-checked IR.
+
+use std::collections::HashSet;
+
+use swc_common::Span;
+use tyrus_ast::visit::{walk_expr_mut, VisitMut};
+use tyrus_ast::{BinaryOp, Expr, ExprKind, LitType, MemberExpr, MemberProp, Module, Type};
+
+use crate::typeck::{self, TypeEnv};
+
+/// A typed lint violation.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Finding {
+    /// `obj[key]` with a non-literal key on a class, interface or object type.
+    DynamicAccess { ty: String, span: Span },
+    /// `==` or `!=` between a string, a number and a boolean.
+    MixedEquality {
+        left: &'static str,
+        right: &'static str,
+        span: Span,
+    },
+}
+
+/// The typed lint violations of `module`. `dictionaries` are interfaces with
+/// an index signature, which the IR does not keep, so keyed access on them
+/// is fine.
+pub(super) fn find(module: &mut Module, dictionaries: &HashSet<String>) -> Vec<Finding> {
+    let env = typeck::check_module(module);
+    let mut finder = Finder {
+        env,
+        dictionaries,
+        findings: Vec::new(),
+    };
+    finder.visit_module_mut(module);
+    finder.findings
+}
+
+struct Finder<'a> {
+    env: TypeEnv,
+    dictionaries: &'a HashSet<String>,
+    findings: Vec<Finding>,
+}
+
+impl Finder<'_> {
+    /// The name of `ty` when it is a struct in the generated code.
+    fn struct_name(&self, ty: &Type) -> Option<String> {
+        match self.env.resolve(ty) {
+            Type::Named { name, .. }
+                if self.env.shapes.contains_key(&name) && !self.dictionaries.contains(&name) =>
+            {
+                Some(name)
+            }
+            Type::Object(_) => Some("object".to_string()),
+            _ => None,
+        }
+    }
+
+    /// `string`, `number` or `boolean` for primitive types, `None` for the
+    /// rest, which includes `null` and `undefined` (`x == null` is fine).
+    fn primitive(&self, ty: &Type) -> Option<&'static str> {
+        match self.env.resolve(ty) {
+            Type::String | Type::Literal(LitType::Str(_)) => Some("string"),
+            Type::Number | Type::Literal(LitType::Num(_)) => Some("number"),
+            Type::Bool | Type::Literal(LitType::Bool(_)) => Some("boolean"),
+            _ => None,
+        }
+    }
+}
+
+impl VisitMut for Finder<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match &expr.kind {
+            ExprKind::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Computed(key),
+            }) if !matches!(key.kind, ExprKind::Lit(_)) => {
+                if let Some(ty) = self.struct_name(&obj.ty) {
+                    self.findings.push(Finding::DynamicAccess {
+                        ty,
+                        span: expr.span,
+                    });
+                }
+            }
+            ExprKind::Bin {
+                op: BinaryOp::EqEq | BinaryOp::NotEq,
+                left,
+                right,
+            } => {
+                if let (Some(l), Some(r)) = (self.primitive(&left.ty), self.primitive(&right.ty)) {
+                    if l != r {
+                        self.findings.push(Finding::MixedEquality {
+                            left: l,
+                            right: r,
+                            span: expr.span,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+        walk_expr_mut(self, expr);
+    }
+}
@@ -0,0 +1,411 @@
+//! Mutability inference over the Tyrus IR.
+//!
+//! [`infer_mutability`] sets [`Binding::mutable`] on every variable and
+//! parameter that Rust needs to declare `mut`: bindings that are reassigned,
+//! updated in place (`i++`, `x += 1`) or borrowed mutably, for instance by a
+//! `push` on an array. It reads the [`Usage`] left by ownership inference, so
+//! it runs after [`infer_ownership`](crate::ownership::infer_ownership).
+//!
+//! A binding declared without an initializer is assigned once in Rust
+//! (`let x; x = 1;`) and only needs `mut` when a path assigns it twice,
+//! including from inside a loop. A closure that mutates what it captures is
+//! `FnMut`, so the variable holding it is `mut` as well.
+
+#[cfg(test)]
+mod tests;
+
+use tyrus_ast::visit::{walk_expr_mut, walk_function_mut, walk_stmt_mut, VisitMut};
+use tyrus_ast::{
+    ArrowBody, AssignOp, AssignTarget, Binding, ClassMember, CtorParam, Decl, Expr, ExprKind,
+    ForHead, ForInit, Function, MatchPat, Module, ObjectPatProp, Pat, Stmt, StmtKind, Usage,
+    VarDecl,
+};
+
+use crate::scope::Scopes;
+
+/// Marks the bindings of `module` that have to be declared `mut`.
+pub fn infer_mutability(module: &mut Module) {
+    // Both passes walk the module in the same order, so the n-th binding
+    // declared by the second pass is the n-th one analyzed by the first.
+    let mut collect = Mutability::new(None);
+    collect.visit_module_mut(module);
+    let mutable = collect.scopes.vars.iter().map(Var::is_mutable).collect();
+    Mutability::new(Some(mutable)).visit_module_mut(module);
+}
+
+/// How a variable is assigned and mutated.
+struct Var {
+    /// Whether it holds a value from the start (initializer, parameter,
+    /// loop variable).
+    initialized: bool,
+    /// Loop nesting at the declaration.
+    loop_depth: usize,
+    /// Most assignments along any single path.
+    assigns: u32,
+    /// Whether it is reassigned after holding a value, or mutated in place.
+    mutated: bool,
+}
+
+impl Var {
+    fn new(initialized: bool, loop_depth: usize) -> Self {
+        Self {
+            initialized,
+            loop_depth,
+            assigns: 0,
+            mutated: false,
+        }
+    }
+
+    fn is_mutable(&self) -> bool {
+        self.mutated || self.assigns > 1
+    }
+}
+
+struct Mutability {
+    scopes: Scopes<Var>,
+    loop_depth: usize,
+    /// Number of scopes outside the closure being analyzed.
+    closure_base: usize,
+    /// Whether the closure being analyzed mutates a variable it captures.
+    mutates_captures: bool,
+    /// Flags found by the first pass, applied to the bindings by the second.
+    apply: Option<Vec<bool>>,
+}
+
+impl Mutability {
+    fn new(apply: Option<Vec<bool>>) -> Self {
+        Self {
+            scopes: Scopes::new(Vec::new()),
+            loop_depth: 0,
+            closure_base: 0,
+            mutates_captures: false,
+            apply,
+        }
+    }
+
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.scopes.push();
+        f(self);
+        self.scopes.pop();
+    }
+
+    fn looped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.loop_depth += 1;
+        f(self);
+        self.loop_depth -= 1;
+    }
+
+    fn declare(&mut self, name: &str, initialized: bool) -> usize {
+        let loop_depth = self.loop_depth;
+        self.scopes
+            .declare(name, || Var::new(initialized, loop_depth))
+    }
+
+    fn declare_binding(&mut self, binding: &mut Binding, initialized: bool) {
+        let id = self.declare(&binding.name, initialized);
+        if let Some(apply) = &self.apply {
+            binding.mutable = apply[id];
+        }
+    }
+
+    /// Declares the names bound by `pat`.
+    fn pat(&mut self, pat: &mut Pat, initialized: bool) {
+        match pat {
+            Pat::Ident(binding) => self.declare_binding(binding, initialized),
+            Pat::Object(props) => {
+                for prop in props {
+                    match prop {
+                        ObjectPatProp::KeyValue { value, .. } => self.pat(value, initialized),
+                        ObjectPatProp::Assign { key, default } => {
+                            if let Some(default) = default {
+                                self.visit_expr_mut(default);
+                            }
+                            self.declare(key, initialized);
+                        }
+                        ObjectPatProp::Rest => {}
+                    }
+                }
+            }
+            Pat::Array(elems) => {
+                for elem in elems.iter_mut().flatten() {
+                    self.pat(elem, initialized);
+                }
+            }
+            Pat::Unsupported => {}
+        }
+    }
+
+    fn var_decl(&mut self, var: &mut VarDecl) {
+        for decl in &mut var.decls {
+            let mut closure_mutates = false;
+            if let Some(init) = &mut decl.init {
+                let outer = std::mem::replace(&mut self.mutates_captures, false);
+                self.visit_expr_mut(init);
+                closure_mutates = matches!(init.kind, ExprKind::Arrow(_)) && self.mutates_captures;
+                self.mutates_captures |= outer;
+            }
+            self.pat(&mut decl.pat, decl.init.is_some());
+            if closure_mutates {
+                if let Pat::Ident(binding) = &decl.pat {
+                    self.mutate(&binding.name);
+                }
+            }
+        }
+    }
+
+    /// Records a mutation of variable `name` other than a plain assignment.
+    fn mutate(&mut self, name: &str) {
+        let Some((depth, id)) = self.scopes.resolve(name) else {
+            return;
+        };
+        if depth < self.closure_base {
+            self.mutates_captures = true;
+        }
+        self.scopes.vars[id].mutated = true;
+    }
+
+    /// Records `name = value`.
+    fn assign(&mut self, name: &str) {
+        let Some((depth, id)) = self.scopes.resolve(name) else {
+            return;
+        };
+        let captured = depth < self.closure_base;
+        if captured {
+            self.mutates_captures = true;
+        }
+        let loop_depth = self.loop_depth;
+        let var = &mut self.scopes.vars[id];
+        var.assigns += 1;
+        // Assigning in a loop or a closure may happen any number of times
+        var.mutated |= var.initialized || captured || loop_depth > var.loop_depth;
+    }
+
+    /// Walks mutually exclusive `branches`, each binding what its patterns
+    /// match, counting for each variable the assignments of the branch that
+    /// assigns it most.
+    fn branches(&mut self, branches: Vec<(&[MatchPat], &mut [Stmt])>) {
+        let before: Vec<u32> = self.scopes.vars.iter().map(|var| var.assigns).collect();
+        let mut most = before.clone();
+        for (pats, stmts) in branches {
+            for (var, &assigns) in self.scopes.vars.iter_mut().zip(&before) {
+                var.assigns = assigns;
+            }
+            self.scoped(|this| {
+                let loop_depth = this.loop_depth;
+                this.scopes.declare_arm(pats, || Var::new(true, loop_depth));
+                for stmt in stmts {
+                    this.visit_stmt_mut(stmt);
+                }
+            });
+            for (most, var) in most.iter_mut().zip(&self.scopes.vars) {
+                *most = (*most).max(var.assigns);
+            }
+        }
+        for (var, most) in self.scopes.vars.iter_mut().zip(most) {
+            var.assigns = most;
+        }
+    }
+
+    fn for_head(&mut self, head: &mut ForHead) {
+        match head {
+            ForHead::Var(var) => {
+                for decl in &mut var.decls {
+                    self.pat(&mut decl.pat, true);
+                }
+            }
+            ForHead::Pat(Pat::Ident(binding)) => self.assign(&binding.name),
+            ForHead::Pat(_) => {}
+        }
+    }
+}
+
+impl VisitMut for Mutability {
+    fn visit_decl_mut(&mut self, decl: &mut Decl) {
+        match decl {
+            Decl::Class(class) => {
+                for member in &mut class.members {
+                    match member {
+                        ClassMember::Prop(prop) => {
+                            if let Some(value) = &mut prop.value {
+                                self.visit_expr_mut(value);
+                            }
+                        }
+                        ClassMember::Method(method) => {
+                            self.visit_function_mut(&mut method.function)
+                        }
+                        ClassMember::Constructor(ctor) => self.scoped(|this| {
+                            for param in &mut ctor.params {
+                                match param {
+                                    CtorParam::Param(param) => this.pat(&mut param.pat, true),
+                                    CtorParam::Prop { name, .. } => {
+                                        this.declare(name, true);
+                                    }
+                                }
+                            }
+                            for stmt in ctor.body.iter_mut().flatten() {
+                                this.visit_stmt_mut(stmt);
+                            }
+                        }),
+                    }
+                }
+            }
+            // Module-level variables are not emitted as `let` bindings
+            Decl::Var(_) => {}
+            _ => tyrus_ast::visit::walk_decl_mut(self, decl),
+        }
+    }
+
+    fn visit_function_mut(&mut self, function: &mut Function) {
+        let outer_loops = std::mem::take(&mut self.loop_depth);
+        self.scoped(|this| walk_function_mut(this, function));
+        self.loop_depth = outer_loops;
+    }
+
+    fn visit_pat_mut(&mut self, pat: &mut Pat) {
+        // Parameters; other patterns are declared where they are bound
+        self.pat(pat, true);
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        match &mut stmt.kind {
+            StmtKind::Var(var) => self.var_decl(var),
+            StmtKind::Block(stmts) => self.scoped(|this| {
+                for stmt in stmts {
+                    this.visit_stmt_mut(stmt);
+                }
+            }),
+            StmtKind::If { test, cons, alt } => {
+                self.visit_expr_mut(test);
+                let mut branches = vec![(&[][..], std::slice::from_mut(&mut **cons))];
+                if let Some(alt) = alt {
+                    branches.push((&[], std::slice::from_mut(&mut **alt)));
+                }
+                self.branches(branches);
+            }
+            StmtKind::Match { scrutinee, arms } => {
+                self.visit_expr_mut(scrutinee);
+                let branches = arms
+                    .iter_mut()
+                    .map(|arm| (arm.pats.as_slice(), arm.body.as_mut_slice()))
+                    .collect();
+                self.branches(branches);
+            }
+            StmtKind::While { test, body } | StmtKind::DoWhile { body, test } => {
+                self.looped(|this| {
+                    this.visit_expr_mut(test);
+                    this.visit_stmt_mut(body);
+                });
+            }
+            StmtKind::For {
+                init,
+                test,
+                update,
+                body,
+            } => self.scoped(|this| {
+                match init {
+                    Some(ForInit::Var(var)) => this.var_decl(var),
+                    Some(ForInit::Expr(expr)) => this.visit_expr_mut(expr),
+                    None => {}
+                }
+                this.looped(|this| {
+                    if let Some(test) = test {
+                        this.visit_expr_mut(test);
+                    }
+                    if let Some(update) = update {
+                        this.visit_expr_mut(update);
+                    }
+                    this.visit_stmt_mut(body);
+                });
+            }),
+            StmtKind::ForOf { left, right, body } | StmtKind::ForIn { left, right, body } => {
+                self.visit_expr_mut(right);
+                self.looped(|this| {
+                    this.scoped(|this| {
+                        this.for_head(left);
+                        this.visit_stmt_mut(body);
+                    })
+                });
+            }
+            StmtKind::Try {
+                block,
+                handler,
+                finalizer,
+            } => {
+                self.scoped(|this| {
+                    for stmt in block {
+                        this.visit_stmt_mut(stmt);
+                    }
+                });
+                if let Some(handler) = handler {
+                    self.scoped(|this| {
+                        if let Some(param) = &mut handler.param {
+                            this.pat(param, true);
+                        }
+                        for stmt in &mut handler.body {
+                            this.visit_stmt_mut(stmt);
+                        }
+                    });
+                }
+                self.scoped(|this| {
+                    for stmt in finalizer.iter_mut().flatten() {
+                        this.visit_stmt_mut(stmt);
+                    }
+                });
+            }
+            _ => walk_stmt_mut(self, stmt),
+        }
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let ExprKind::Assign {
+            op,
+            target: AssignTarget::Ident(name),
+            ..
+        } = &expr.kind
+        {
+            let (op, name) = (*op, name.clone());
+            // The value is computed before it is stored
+            walk_expr_mut(self, expr);
+            if op == AssignOp::Assign {
+                self.assign(&name);
+            } else {
+                self.mutate(&name);
+            }
+            return;
+        }
+        match &mut expr.kind {
+            ExprKind::Ident(name) if expr.usage == Usage::BorrowMut => {
+                let name = name.clone();
+                self.mutate(&name);
+            }
+            ExprKind::Update { arg, .. } => {
+                if let ExprKind::Ident(name) = &arg.kind {
+                    let name = name.clone();
+                    self.mutate(&name);
+                }
+            }
+            ExprKind::Arrow(arrow) => {
+                let outer_base = std::mem::replace(&mut self.closure_base, self.scopes.depth());
+                let outer_loops = std::mem::take(&mut self.loop_depth);
+                self.scoped(|this| {
+                    for param in &mut arrow.params {
+                        this.pat(param, true);
+                    }
+                    match &mut arrow.body {
+                        ArrowBody::Block(stmts) => {
+                            for stmt in stmts {
+                                this.visit_stmt_mut(stmt);
+                            }
+                        }
+                        ArrowBody::Expr(body) => this.visit_expr_mut(body),
+                    }
+                });
+                self.closure_base = outer_base;
+                self.loop_depth = outer_loops;
+                return;
+            }
+            _ => {}
+        }
+        walk_expr_mut(self, expr);
+    }
+}
@@ -0,0 +1,109 @@
+use tyrus_ast::visit::{walk_pat_mut, VisitMut};
+use tyrus_ast::{Module, Pat};
+
+use super::infer_mutability;
+use crate::ownership::infer_ownership;
+use crate::typeck::check_module;
+
+fn infer(src: &str) -> Module {
+    let (program, _) = tyrus_parser::parse_str(src).expect("test source should parse");
+    let mut module = tyrus_ast::lower_program(&program);
+    check_module(&mut module);
+    infer_ownership(&mut module);
+    infer_mutability(&mut module);
+    module
+}
+
+/// Names of the mutable bindings, in declaration order.
+fn mutable(src: &str) -> Vec<String> {
+    struct Collect(Vec<String>);
+    impl VisitMut for Collect {
+        fn visit_pat_mut(&mut self, pat: &mut Pat) {
+            if let Pat::Ident(binding) = pat {
+                if binding.mutable {
+                    self.0.push(binding.name.clone());
+                }
+            }
+            walk_pat_mut(self, pat);
+        }
+    }
+    let mut collect = Collect(Vec::new());
+    collect.visit_module_mut(&mut infer(src));
+    collect.0
+}
+
+#[test]
+fn test_reassigned_and_mutated_bindings() {
+    let found = mutable(
+        r#"
+        function f(limit: number, flag: boolean): number[] {
+            const items: number[] = [];
+            const kept: number[] = [];
+            let total = 0;
+            let unchanged = 1;
+            let count = 0;
+            items.push(unchanged);
+            total += kept.length;
+            count++;
+            if (flag) {
+                limit = 0;
+            }
+            return items;
+        }
+        "#,
+    );
+    assert_eq!(found, vec!["limit", "items", "total", "count"]);
+}
+
+#[test]
+fn test_deferred_initialization() {
+    let found = mutable(
+        r#"
+        function once(flag: boolean): string {
+            let text: string;
+            if (flag) {
+                text = "on";
+            } else {
+                text = "off";
+            }
+            return text;
+        }
+        function twice(): string {
+            let text: string;
+            text = "a";
+            text = "b";
+            return text;
+        }
+        function looped(values: string[]): string {
+            let last: string;
+            for (const value of values) {
+                last = value;
+            }
+            return "";
+        }
+        "#,
+    );
+    assert_eq!(found, vec!["text", "last"]);
+}
+
+#[test]
+fn test_shadowing_and_closures() {
+    let found = mutable(
+        r#"
+        function f(): number {
+            let count = 0;
+            const bump = () => {
+                count += 1;
+            };
+            const read = () => count;
+            {
+                let count = 5;
+                console.log(count);
+            }
+            bump();
+            return read();
+        }
+        "#,
+    );
+    assert_eq!(found, vec!["count", "bump"]);
+}
@@ -0,0 +1,969 @@
+//! Ownership inference over the Tyrus IR.
+//!
+//! [`infer_ownership`] runs a backward liveness analysis over every function,
+//! method, constructor and closure body of a type-checked [`Module`] and
+//! records in [`Expr::usage`] how each local variable and `this` field is used
+//! where it appears: moved when nothing reads it afterwards, cloned when it is
+//! consumed but read again later, or borrowed when the operation only needs a
+//! reference. Methods that mutate `this` are flagged in
+//! [`ClassMethod::mutates_this`](tyrus_ast::ClassMethod::mutates_this).
+//!
+//! Globals (functions, classes, module-level constants) are left
+//! `Usage::Unknown`, so codegen keeps its defaults for them. The right operand
+//! of a string concatenation, whatever it is, is always borrowed.
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::{HashMap, HashSet};
+
+use tyrus_ast::{
+    ArrowBody, ArrowExpr, AssignOp, AssignTarget, BinaryOp, CallExpr, Callee, ClassDecl,
+    ClassMember, CtorParam, Decl, Expr, ExprKind, ForHead, ForInit, Function, Item, MatchPat,
+    MemberExpr, MemberProp, Module, ObjectPatProp, Pat, Prop, PropKey, Stmt, StmtKind, Type, Usage,
+    VarDecl,
+};
+
+/// Methods of built-in collections that mutate their receiver.
+const MUTATING_METHODS: &[&str] = &[
+    "push", "pop", "shift", "unshift", "splice", "sort", "reverse", "set", "add", "delete", "clear",
+];
+
+/// Methods of built-in collections that store their arguments; the others
+/// only read them.
+const STORING_METHODS: &[&str] = &["push", "unshift", "set", "add"];
+
+/// Array methods that consume the elements of their receiver.
+const ITERATING_METHODS: &[&str] = &["map", "forEach"];
+
+/// Annotates every expression of `module` with its [`Usage`].
+pub fn infer_ownership(module: &mut Module) {
+    infer_ownership_with(module, &[]);
+}
+
+/// Like [`infer_ownership`], for a module that can also use the `imported`
+/// classes of the other modules of its project, already analyzed: calls to
+/// their methods that mutate `this` borrow the receiver mutably.
+pub fn infer_ownership_with(module: &mut Module, imported: &[&ClassDecl]) {
+    let classes: Vec<&ClassDecl> = imported
+        .iter()
+        .copied()
+        .chain(module.items.iter().filter_map(|item| match item {
+            Item::Decl {
+                decl: Decl::Class(class),
+                ..
+            } => Some(class),
+            _ => None,
+        }))
+        .collect();
+    let supers: HashMap<String, String> = classes
+        .iter()
+        .filter_map(|class| Some((class.name.clone(), class.super_class_name()?.to_string())))
+        .collect();
+    let mut imported_mutating: HashMap<String, HashSet<String>> = HashMap::new();
+    for class in imported {
+        for method in class.methods().filter(|m| m.mutates_this) {
+            imported_mutating
+                .entry(class.name.clone())
+                .or_default()
+                .insert(method.name.clone());
+        }
+    }
+    // Calling a mutating method on `this` mutates it as well, so repeat until
+    // the set of mutating methods settles. It only grows, so this terminates.
+    let mut mutating = imported_mutating.clone();
+    loop {
+        let mut pass = Ownership::new(&supers, &mutating);
+        pass.module(module);
+        let mut found = imported_mutating.clone();
+        found.extend(pass.found);
+        overridden(&mut found, &supers);
+        if found == mutating {
+            break;
+        }
+        mutating = found;
+    }
+}
+
+/// How an operation uses the value of one of its operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ctx {
+    /// Takes ownership (assignments, arguments, returned values, ...).
+    Consume,
+    /// Reads through a shared reference.
+    Borrow,
+    /// Mutates in place.
+    BorrowMut,
+    /// Iterates the receiver's elements, which can be moved out of a local
+    /// that is not used again.
+    Iterate,
+}
+
+/// Where a variable is bound, relative to the body being analyzed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    Local,
+    /// Bound in a function enclosing the closure being analyzed.
+    Captured,
+    Global,
+}
+
+/// Live variables at the targets of `break` and `continue`.
+struct Jump {
+    brk: HashSet<String>,
+    /// `None` for `switch`, which `continue` skips over.
+    cont: Option<HashSet<String>>,
+}
+
+struct Ownership<'a> {
+    /// Superclass of each class in the module and those it imports.
+    supers: &'a HashMap<String, String>,
+    /// Methods known to mutate `this`, by class.
+    /// Those of a class count for its ancestors too.
+    mutating: &'a HashMap<String, HashSet<String>>,
+    /// Methods found to mutate `this` in this pass.
+    found: HashMap<String, HashSet<String>>,
+    /// Variables read after the current point.
+    live: HashSet<String>,
+    /// Variables bound by the function being analyzed and, for closures, by
+    /// the functions enclosing it; innermost last.
+    locals: Vec<HashSet<String>>,
+    /// Variables that stay live throughout the current point, such as those
+    /// read by the `catch` block of an enclosing `try`.
+    pinned: HashSet<String>,
+    /// Enclosing variables read by the closure being analyzed.
+    captures: HashSet<String>,
+    jumps: Vec<Jump>,
+    /// Whether the method being analyzed mutates `this`.
+    mutates_this: bool,
+}
+
+impl<'a> Ownership<'a> {
+    fn new(
+        supers: &'a HashMap<String, String>,
+        mutating: &'a HashMap<String, HashSet<String>>,
+    ) -> Self {
+        Self {
+            supers,
+            mutating,
+            found: HashMap::new(),
+            live: HashSet::new(),
+            locals: Vec::new(),
+            pinned: HashSet::new(),
+            captures: HashSet::new(),
+            jumps: Vec::new(),
+            mutates_this: false,
+        }
+    }
+
+    fn module(&mut self, module: &mut Module) {
+        let mut script = Vec::new();
+        for item in &mut module.items {
+            match item {
+                Item::Import(_) => {}
+                Item::Decl { decl, .. } => self.decl(decl),
+                Item::Stmt(stmt) => script.push(stmt),
+            }
+        }
+        // Top-level statements run in order, as the body of `main`
+        let mut locals = HashSet::new();
+        declared_in(script.iter().map(|stmt| &**stmt), &mut locals);
+        self.locals = vec![locals];
+        self.live.clear();
+        for stmt in script.into_iter().rev() {
+            self.stmt(stmt);
+        }
+    }
+
+    fn decl(&mut self, decl: &mut Decl) {
+        match decl {
+            Decl::Fn(f) => self.function(&mut f.function),
+            Decl::Class(class) => self.class(class),
+            Decl::Interface(_) | Decl::TypeAlias(_) | Decl::Enum(_) | Decl::Var(_) => {}
+        }
+    }
+
+    fn class(&mut self, class: &mut ClassDecl) {
+        for member in &mut class.members {
+            match member {
+                ClassMember::Prop(_) => {}
+                ClassMember::Method(method) => {
+                    let outer = std::mem::replace(&mut self.mutates_this, false);
+                    self.function(&mut method.function);
+                    method.mutates_this =
+                        std::mem::replace(&mut self.mutates_this, outer) && !method.is_static;
+                    // Calls through an interface lock its trait object
+                    // rather than borrow it mutably, so only the class counts
+                    if method.mutates_this {
+                        self.found
+                            .entry(class.name.clone())
+                            .or_default()
+                            .insert(method.name.clone());
+                    }
+                }
+                ClassMember::Constructor(ctor) => {
+                    // Parameter properties are moved into their fields after
+                    // the body has run
+                    let props: HashSet<String> = ctor
+                        .params
+                        .iter()
+                        .filter_map(|param| match param {
+                            CtorParam::Prop { name, .. } => Some(name.clone()),
+                            CtorParam::Param(_) => None,
+                        })
+                        .collect();
+                    let mut locals = props.clone();
+                    for param in &ctor.params {
+                        if let CtorParam::Param(param) = param {
+                            bound_names(&param.pat, &mut locals);
+                        }
+                    }
+                    if let Some(body) = &mut ctor.body {
+                        let outer = std::mem::replace(&mut self.mutates_this, false);
+                        self.body(locals, body, props);
+                        self.mutates_this = outer;
+                    }
+                }
+            }
+        }
+    }
+
+    fn function(&mut self, function: &mut Function) {
+        let mut locals = HashSet::new();
+        for param in &function.params {
+            bound_names(&param.pat, &mut locals);
+        }
+        if let Some(body) = &mut function.body {
+            self.body(locals, body, HashSet::new());
+        }
+    }
+
+    /// Analyzes a function body binding `locals`, with `live_out` read after it.
+    fn body(&mut self, mut locals: HashSet<String>, body: &mut [Stmt], live_out: HashSet<String>) {
+        declared_in(body.iter(), &mut locals);
+        let outer_locals = std::mem::replace(&mut self.locals, vec![locals]);
+        let outer_live = std::mem::replace(&mut self.live, live_out);
+        let outer_pinned = std::mem::take(&mut self.pinned);
+        let outer_captures = std::mem::take(&mut self.captures);
+        let outer_jumps = std::mem::take(&mut self.jumps);
+        self.block(body);
+        self.locals = outer_locals;
+        self.live = outer_live;
+        self.pinned = outer_pinned;
+        self.captures = outer_captures;
+        self.jumps = outer_jumps;
+    }
+
+    fn block(&mut self, stmts: &mut [Stmt]) {
+        let out = self.live.clone();
+        let mut declared = HashSet::new();
+        for stmt in stmts.iter() {
+            if let StmtKind::Var(var) = &stmt.kind {
+                var_names(var, &mut declared);
+            }
+        }
+        for stmt in stmts.iter_mut().rev() {
+            self.stmt(stmt);
+        }
+        // A declaration in the block shadows an outer variable of the same
+        // name, which is still live around the block
+        self.live
+            .extend(out.into_iter().filter(|name| declared.contains(name)));
+    }
+
+    fn stmt(&mut self, stmt: &mut Stmt) {
+        match &mut stmt.kind {
+            StmtKind::Expr(expr) => self.expr(expr, Ctx::Borrow),
+            StmtKind::Return(arg) => {
+                self.live.clear();
+                if let Some(arg) = arg {
+                    self.expr(arg, Ctx::Consume);
+                }
+            }
+            StmtKind::Throw(arg) => {
+                self.live.clear();
+                self.expr(arg, Ctx::Consume);
+            }
+            StmtKind::Var(var) => self.var_decl(var),
+            StmtKind::Block(stmts) => self.block(stmts),
+            StmtKind::If { test, cons, alt } => {
+                let out = self.live.clone();
+                self.stmt(cons);
+                let cons_in = std::mem::replace(&mut self.live, out);
+                if let Some(alt) = alt {
+                    self.stmt(alt);
+                }
+                self.live.extend(cons_in);
+                self.expr(test, Ctx::Borrow);
+            }
+            StmtKind::While { test, body } => {
+                self.loop_body(Some(test), body, None, &HashSet::new());
+            }
+            StmtKind::DoWhile { body, test } => {
+                self.loop_body(None, body, Some(test), &HashSet::new());
+            }
+            StmtKind::For {
+                init,
+                test,
+                update,
+                body,
+            } => {
+                self.loop_body(test.as_mut(), body, update.as_mut(), &HashSet::new());
+                match init {
+                    Some(ForInit::Var(var)) => self.var_decl(var),
+                    Some(ForInit::Expr(expr)) => self.expr(expr, Ctx::Borrow),
+                    None => {}
+                }
+            }
+            StmtKind::ForOf { left, right, body } => {
+                let mut binding = HashSet::new();
+                head_names(left, &mut binding);
+                self.loop_body(None, body, None, &binding);
+                self.expr(right, Ctx::Consume);
+            }
+            StmtKind::ForIn { left, right, body } => {
+                let mut binding = HashSet::new();
+                head_names(left, &mut binding);
+                self.loop_body(None, body, None, &binding);
+                self.expr(right, Ctx::Borrow);
+            }
+            StmtKind::Try {
+                block,
+                handler,
+                finalizer,
+            } => {
+                if let Some(finalizer) = finalizer {
+                    self.block(finalizer);
+                }
+                let mut handler_in = HashSet::new();
+                if let Some(handler) = handler {
+                    let out = self.live.clone();
+                    self.block(&mut handler.body);
+                    if let Some(param) = &handler.param {
+                        let mut names = HashSet::new();
+                        bound_names(param, &mut names);
+                        self.live.retain(|name| !names.contains(name));
+                    }
+                    handler_in = std::mem::replace(&mut self.live, out);
+                }
+                // Any statement of the block may throw into the handler
+                let outer = self.pinned.clone();
+                self.pinned.extend(handler_in.iter().cloned());
+                self.block(block);
+                self.pinned = outer;
+                self.live.extend(handler_in);
+            }
+            StmtKind::Switch {
+                discriminant,
+                cases,
+            } => {
+                let after = self.live.clone();
+                let mut entry = after.clone();
+                self.jumps.push(Jump {
+                    brk: after,
+                    cont: None,
+                });
+                // Each case falls through into the next one
+                for case in cases.iter_mut().rev() {
+                    self.block(&mut case.cons);
+                    entry.extend(self.live.iter().cloned());
+                }
+                self.jumps.pop();
+                self.live = entry;
+                for case in cases.iter_mut().rev() {
+                    if let Some(test) = &mut case.test {
+                        self.expr(test, Ctx::Borrow);
+                    }
+                }
+                self.expr(discriminant, Ctx::Borrow);
+            }
+            StmtKind::Match { scrutinee, arms } => {
+                let after = std::mem::take(&mut self.live);
+                let mut entry = HashSet::new();
+                for arm in arms.iter_mut() {
+                    self.live = after.clone();
+                    self.block(&mut arm.body);
+                    let mut bindings = HashSet::new();
+                    for pat in &arm.pats {
+                        match_names(pat, &mut bindings);
+                    }
+                    entry.extend(self.live.drain().filter(|name| !bindings.contains(name)));
+                }
+                self.live = entry;
+                self.expr(scrutinee, Ctx::Consume);
+            }
+            StmtKind::Break => {
+                if let Some(jump) = self.jumps.last() {
+                    self.live = jump.brk.clone();
+                }
+            }
+            StmtKind::Continue => {
+                if let Some(cont) = self.jumps.iter().rev().find_map(|jump| jump.cont.as_ref()) {
+                    self.live = cont.clone();
+                }
+            }
+            StmtKind::Decl(decl) => match &mut **decl {
+                Decl::Var(var) => self.var_decl(var),
+                decl => self.decl(decl),
+            },
+            StmtKind::Empty | StmtKind::Unsupported { .. } => {}
+        }
+    }
+
+    /// A loop running `pre` (the test of `while` and `for`), `body`, then
+    /// `post` (the update of `for`, the test of `do..while`) on each
+    /// iteration, and binding `binding` at the start of each one.
+    fn loop_body(
+        &mut self,
+        mut pre: Option<&mut Expr>,
+        body: &mut Stmt,
+        mut post: Option<&mut Expr>,
+        binding: &HashSet<String>,
+    ) {
+        let after = self.live.clone();
+        // Live at the start of an iteration, grown to a fixpoint
+        let mut head = after.clone();
+        loop {
+            self.live = head.union(&after).cloned().collect();
+            if let Some(post) = post.as_deref_mut() {
+                self.expr(post, Ctx::Borrow);
+            }
+            self.jumps.push(Jump {
+                brk: after.clone(),
+                cont: Some(self.live.clone()),
+            });
+            self.stmt(body);
+            self.jumps.pop();
+            self.live.retain(|name| !binding.contains(name));
+            if let Some(pre) = pre.as_deref_mut() {
+                self.live.extend(after.iter().cloned());
+                self.expr(pre, Ctx::Borrow);
+            }
+            if self.live.is_subset(&head) {
+                break;
+            }
+            head.extend(self.live.drain());
+        }
+        self.live = head;
+    }
+
+    fn var_decl(&mut self, var: &mut VarDecl) {
+        for decl in var.decls.iter_mut().rev() {
+            self.pat(&mut decl.pat);
+            if let Some(init) = &mut decl.init {
+                self.expr(init, Ctx::Consume);
+            }
+        }
+    }
+
+    /// Binds the names of `pat`, whose defaults run after the initializer.
+    fn pat(&mut self, pat: &mut Pat) {
+        match pat {
+            Pat::Ident(binding) => {
+                self.live.remove(&binding.name);
+            }
+            Pat::Object(props) => {
+                for prop in props.iter_mut().rev() {
+                    match prop {
+                        ObjectPatProp::KeyValue { value, .. } => self.pat(value),
+                        ObjectPatProp::Assign { key, default } => {
+                            self.live.remove(key.as_str());
+                            if let Some(default) = default {
+                                self.expr(default, Ctx::Consume);
+                            }
+                        }
+                        ObjectPatProp::Rest => {}
+                    }
+                }
+            }
+            Pat::Array(elems) => {
+                for elem in elems.iter_mut().rev().flatten() {
+                    self.pat(elem);
+                }
+            }
+            Pat::Unsupported => {}
+        }
+    }
+
+    fn scope(&self, name: &str) -> Scope {
+        let mut locals = self.locals.iter().rev();
+        if locals.next().is_some_and(|locals| locals.contains(name)) {
+            Scope::Local
+        } else if locals.any(|locals| locals.contains(name)) {
+            Scope::Captured
+        } else {
+            Scope::Global
+        }
+    }
+
+    /// Records a use of variable `name` and returns how it is used.
+    fn ident(&mut self, name: &str, is_copy: bool, ctx: Ctx) -> Usage {
+        let scope = self.scope(name);
+        if scope == Scope::Global {
+            return Usage::Unknown;
+        }
+        // A closure may run any number of times, so it never moves what it captures
+        let used_later =
+            scope == Scope::Captured || self.live.contains(name) || self.pinned.contains(name);
+        if scope == Scope::Captured {
+            self.captures.insert(name.to_string());
+        }
+        self.live.insert(name.to_string());
+        match ctx {
+            Ctx::Consume if is_copy || !used_later => Usage::Move,
+            Ctx::Consume => Usage::Clone,
+            Ctx::Iterate if !used_later => Usage::Move,
+            Ctx::Iterate | Ctx::Borrow => Usage::Borrow,
+            Ctx::BorrowMut => Usage::BorrowMut,
+        }
+    }
+
+    fn expr(&mut self, expr: &mut Expr, ctx: Ctx) {
+        let is_copy = expr.ty.is_copy();
+        let usage = match &mut expr.kind {
+            ExprKind::Ident(name) => {
+                let name = name.clone();
+                self.ident(&name, is_copy, ctx)
+            }
+            ExprKind::This => {
+                if ctx == Ctx::BorrowMut {
+                    self.mutates_this = true;
+                    Usage::BorrowMut
+                } else {
+                    Usage::Borrow
+                }
+            }
+            ExprKind::Member(member) => self.member(member, is_copy, ctx),
+            ExprKind::Call(call) => {
+                self.call(call);
+                Usage::Unknown
+            }
+            ExprKind::New(call) | ExprKind::OptCall(call) => {
+                for arg in call.args.iter_mut().rev() {
+                    self.expr(&mut arg.expr, Ctx::Consume);
+                }
+                if let Callee::Expr(callee) = &mut call.callee {
+                    self.expr(callee, Ctx::Borrow);
+                }
+                Usage::Unknown
+            }
+            ExprKind::OptMember(member) => {
+                if let MemberProp::Computed(index) = &mut member.prop {
+                    self.expr(index, Ctx::Borrow);
+                }
+                self.expr(&mut member.obj, Ctx::Borrow);
+                Usage::Unknown
+            }
+            ExprKind::Bin { op, left, right } => {
+                let concat = *op == BinaryOp::Add && is_concat(&expr.ty, right);
+                let (left_ctx, right_ctx) = match op {
+                    // `String + &str` consumes the left operand only
+                    BinaryOp::Add if concat => (Ctx::Consume, Ctx::Borrow),
+                    BinaryOp::Add => (Ctx::Consume, Ctx::Consume),
+                    BinaryOp::NullishCoalescing => (Ctx::Consume, Ctx::Consume),
+                    _ => (Ctx::Borrow, Ctx::Borrow),
+                };
+                self.expr(right, right_ctx);
+                self.expr(left, left_ctx);
+                if concat {
+                    // Whatever it is, the right operand is appended through a
+                    // reference (numbers once formatted)
+                    right.usage = Usage::Borrow;
+                }
+                Usage::Unknown
+            }
+            ExprKind::Assign { op, target, right } => {
+                match target {
+                    AssignTarget::Ident(name) if *op == AssignOp::Assign => {
+                        self.live.remove(name.as_str());
+                    }
+                    AssignTarget::Ident(name) => {
+                        let name = name.clone();
+                        self.ident(&name, false, Ctx::BorrowMut);
+                    }
+                    AssignTarget::Member(member) => {
+                        if let MemberProp::Computed(index) = &mut member.prop {
+                            self.expr(index, Ctx::Borrow);
+                        }
+                        self.expr(&mut member.obj, Ctx::BorrowMut);
+                    }
+                    AssignTarget::Unsupported => {}
+                }
+                self.expr(right, Ctx::Consume);
+                Usage::Unknown
+            }
+            ExprKind::Update { arg, .. } => {
+                self.expr(arg, Ctx::BorrowMut);
+                Usage::Unknown
+            }
+            ExprKind::Unary { arg, .. } => {
+                self.expr(arg, Ctx::Borrow);
+                Usage::Unknown
+            }
+            ExprKind::Cond { test, cons, alt } => {
+                let out = self.live.clone();
+                self.expr(cons, ctx);
+                let cons_in = std::mem::replace(&mut self.live, out);
+                self.expr(alt, ctx);
+                self.live.extend(cons_in);
+                self.expr(test, Ctx::Borrow);
+                Usage::Unknown
+            }
+            ExprKind::Tpl { exprs, .. } => {
+                for expr in exprs.iter_mut().rev() {
+                    self.expr(expr, Ctx::Borrow);
+                }
+                Usage::Unknown
+            }
+            ExprKind::Array(elems) => {
+                for elem in elems.iter_mut().rev().flatten() {
+                    self.expr(&mut elem.expr, Ctx::Consume);
+                }
+                Usage::Unknown
+            }
+            // Object literals become `json!`, which serializes its values by reference
+            ExprKind::Object(props) => {
+                for prop in props.iter_mut().rev() {
+                    match prop {
+                        Prop::KeyValue { key, value } => {
+                            self.expr(value, Ctx::Borrow);
+                            if let PropKey::Computed(key) = key {
+                                self.expr(key, Ctx::Borrow);
+                            }
+                        }
+                        Prop::Shorthand(name) => {
+                            let name = name.clone();
+                            self.ident(&name, false, Ctx::Borrow);
+                        }
+                        Prop::Unsupported => {}
+                    }
+                }
+                Usage::Unknown
+            }
+            ExprKind::Seq(exprs) => {
+                if let Some((last, init)) = exprs.split_last_mut() {
+                    self.expr(last, ctx);
+                    for expr in init.iter_mut().rev() {
+                        self.expr(expr, Ctx::Borrow);
+                    }
+                }
+                Usage::Unknown
+            }
+            ExprKind::Paren(inner) => {
+                self.expr(inner, ctx);
+                Usage::Unknown
+            }
+            ExprKind::Await(arg) => {
+                self.expr(arg, Ctx::Consume);
+                Usage::Unknown
+            }
+            ExprKind::Arrow(arrow) => {
+                self.arrow(arrow);
+                Usage::Unknown
+            }
+            ExprKind::Super | ExprKind::Lit(_) | ExprKind::Unsupported { .. } => Usage::Unknown,
+        };
+        expr.usage = usage;
+    }
+
+    /// `obj.prop` or `obj[index]`. Consuming a field moves it out of a local
+    /// that is not used again, and clones it otherwise; fields of `this` and
+    /// array elements are always cloned.
+    fn member(&mut self, member: &mut MemberExpr, is_copy: bool, ctx: Ctx) -> Usage {
+        let usage = match ctx {
+            Ctx::Consume if is_copy => Usage::Move,
+            Ctx::Consume => match member_root(member) {
+                (ExprKind::Ident(name), false)
+                    if self.scope(name) == Scope::Local
+                        && !self.live.contains(name)
+                        && !self.pinned.contains(name) =>
+                {
+                    Usage::Move
+                }
+                (ExprKind::Ident(name), _) if self.scope(name) == Scope::Global => Usage::Unknown,
+                (ExprKind::Ident(_) | ExprKind::This, _) => Usage::Clone,
+                _ => Usage::Unknown,
+            },
+            Ctx::Borrow | Ctx::Iterate => Usage::Borrow,
+            Ctx::BorrowMut => Usage::BorrowMut,
+        };
+        if let MemberProp::Computed(index) = &mut member.prop {
+            self.expr(index, Ctx::Borrow);
+        }
+        let obj_ctx = if ctx == Ctx::BorrowMut {
+            Ctx::BorrowMut
+        } else {
+            Ctx::Borrow
+        };
+        self.expr(&mut member.obj, obj_ctx);
+        usage
+    }
+
+    fn call(&mut self, call: &mut CallExpr) {
+        let (receiver_ctx, arg_ctx) = self.call_ctx(call);
+        for arg in call.args.iter_mut().rev() {
+            self.expr(&mut arg.expr, arg_ctx);
+        }
+        let Callee::Expr(callee) = &mut call.callee else {
+            return;
+        };
+        match &mut callee.kind {
+            ExprKind::Member(member) => {
+                if let MemberProp::Computed(index) = &mut member.prop {
+                    self.expr(index, Ctx::Borrow);
+                }
+                self.expr(&mut member.obj, receiver_ctx);
+            }
+            _ => self.expr(callee, Ctx::Borrow),
+        }
+    }
+
+    /// How a call uses its receiver and its arguments.
+    fn call_ctx(&self, call: &CallExpr) -> (Ctx, Ctx) {
+        if let Some(("console" | "JSON", _)) = call.callee.as_static_call() {
+            return (Ctx::Borrow, Ctx::Borrow);
+        }
+        let Some((member, method)) = call
+            .callee
+            .as_member()
+            .and_then(|member| Some((member, member.prop.as_ident()?)))
+        else {
+            return (Ctx::Borrow, Ctx::Consume);
+        };
+        let receiver = match &member.obj.ty {
+            Type::Optional(inner) => inner,
+            ty => ty,
+        };
+        let builtin = match receiver {
+            Type::Array(_) | Type::String => true,
+            Type::Named { name, .. } => name == "Map" || name == "Set",
+            _ => false,
+        };
+        let mutating = MUTATING_METHODS.contains(&method);
+        match receiver {
+            _ if builtin => {
+                let receiver_ctx = if mutating {
+                    Ctx::BorrowMut
+                } else if ITERATING_METHODS.contains(&method) && matches!(receiver, Type::Array(_))
+                {
+                    Ctx::Iterate
+                } else {
+                    Ctx::Borrow
+                };
+                let arg_ctx = if STORING_METHODS.contains(&method) {
+                    Ctx::Consume
+                } else {
+                    Ctx::Borrow
+                };
+                (receiver_ctx, arg_ctx)
+            }
+            Type::Named { name, .. } if self.class_mutates(name, method) => {
+                (Ctx::BorrowMut, Ctx::Consume)
+            }
+            // Shims for receivers of unknown type are picked by method name
+            Type::Unknown | Type::Any if mutating => (Ctx::BorrowMut, Ctx::Consume),
+            _ => (Ctx::Borrow, Ctx::Consume),
+        }
+    }
+
+    /// Whether `method` of `class`, or the ancestor declaring it, mutates `this`.
+    fn class_mutates(&self, class: &str, method: &str) -> bool {
+        let mut current = Some(class);
+        for _ in 0..=self.supers.len() {
+            let Some(class) = current else {
+                return false;
+            };
+            if self
+                .mutating
+                .get(class)
+                .is_some_and(|methods| methods.contains(method))
+            {
+                return true;
+            }
+            current = self.supers.get(class).map(String::as_str);
+        }
+        false
+    }
+
+    fn arrow(&mut self, arrow: &mut ArrowExpr) {
+        let mut locals = HashSet::new();
+        for param in &arrow.params {
+            bound_names(param, &mut locals);
+        }
+        if let ArrowBody::Block(stmts) = &arrow.body {
+            declared_in(stmts, &mut locals);
+        }
+        self.locals.push(locals);
+        let outer_live = std::mem::take(&mut self.live);
+        let outer_pinned = std::mem::take(&mut self.pinned);
+        let outer_captures = std::mem::take(&mut self.captures);
+        let outer_jumps = std::mem::take(&mut self.jumps);
+        match &mut arrow.body {
+            ArrowBody::Block(stmts) => self.block(stmts),
+            ArrowBody::Expr(body) => self.expr(body, Ctx::Consume),
+        }
+        self.locals.pop();
+        self.live = outer_live;
+        self.pinned = outer_pinned;
+        self.jumps = outer_jumps;
+        // The closure borrows what it captures from where it is created
+        let captures = std::mem::replace(&mut self.captures, outer_captures);
+        for name in captures {
+            self.ident(&name, false, Ctx::Borrow);
+        }
+    }
+}
+
+/// Adds the mutating methods of each class to its ancestors as well: calls
+/// through a superclass may reach a subclass's override.
+fn overridden(mutating: &mut HashMap<String, HashSet<String>>, supers: &HashMap<String, String>) {
+    for (class, methods) in mutating.clone() {
+        let mut current = supers.get(&class);
+        for _ in 0..supers.len() {
+            let Some(parent) = current else {
+                break;
+            };
+            mutating
+                .entry(parent.clone())
+                .or_default()
+                .extend(methods.iter().cloned());
+            current = supers.get(parent);
+        }
+    }
+}
+
+/// Whether `left + right`, of type `ty`, appends `right` to a string. An
+/// untyped variable on the right is assumed to hold a string.
+fn is_concat(ty: &Type, right: &Expr) -> bool {
+    match ty {
+        Type::String => true,
+        Type::Unknown => right.ty == Type::Unknown && right.as_ident().is_some(),
+        _ => false,
+    }
+}
+
+/// The expression at the root of a member chain, and whether the chain
+/// indexes into an array on the way.
+fn member_root(member: &MemberExpr) -> (&ExprKind, bool) {
+    let mut computed = matches!(member.prop, MemberProp::Computed(_));
+    let mut obj = &member.obj;
+    loop {
+        match &obj.kind {
+            ExprKind::Member(inner) => {
+                computed |= matches!(inner.prop, MemberProp::Computed(_));
+                obj = &inner.obj;
+            }
+            ExprKind::Paren(inner) => obj = inner,
+            kind => return (kind, computed),
+        }
+    }
+}
+
+/// Adds the variables declared anywhere in `stmts`, outside nested functions
+/// and closures, to `out`.
+fn declared_in<'s>(stmts: impl IntoIterator<Item = &'s Stmt>, out: &mut HashSet<String>) {
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Var(var) => var_names(var, out),
+            StmtKind::Block(stmts) => declared_in(stmts, out),
+            StmtKind::If { cons, alt, .. } => {
+                declared_in([&**cons], out);
+                declared_in(alt.as_deref(), out);
+            }
+            StmtKind::While { body, .. } | StmtKind::DoWhile { body, .. } => {
+                declared_in([&**body], out);
+            }
+            StmtKind::For { init, body, .. } => {
+                if let Some(ForInit::Var(var)) = init {
+                    var_names(var, out);
+                }
+                declared_in([&**body], out);
+            }
+            StmtKind::ForOf { left, body, .. } | StmtKind::ForIn { left, body, .. } => {
+                head_names(left, out);
+                declared_in([&**body], out);
+            }
+            StmtKind::Try {
+                block,
+                handler,
+                finalizer,
+            } => {
+                declared_in(block, out);
+                if let Some(handler) = handler {
+                    if let Some(param) = &handler.param {
+                        bound_names(param, out);
+                    }
+                    declared_in(&handler.body, out);
+                }
+                declared_in(finalizer.iter().flatten(), out);
+            }
+            StmtKind::Switch { cases, .. } => {
+                for case in cases {
+                    declared_in(&case.cons, out);
+                }
+            }
+            StmtKind::Match { arms, .. } => {
+                for arm in arms {
+                    for pat in &arm.pats {
+                        match_names(pat, out);
+                    }
+                    declared_in(&arm.body, out);
+                }
+            }
+            StmtKind::Decl(decl) => {
+                if let Decl::Var(var) = &**decl {
+                    var_names(var, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn var_names(var: &VarDecl, out: &mut HashSet<String>) {
+    for decl in &var.decls {
+        bound_names(&decl.pat, out);
+    }
+}
+
+fn head_names(head: &ForHead, out: &mut HashSet<String>) {
+    match head {
+        ForHead::Var(var) => var_names(var, out),
+        ForHead::Pat(pat) => bound_names(pat, out),
+    }
+}
+
+fn bound_names(pat: &Pat, out: &mut HashSet<String>) {
+    match pat {
+        Pat::Ident(binding) => {
+            out.insert(binding.name.clone());
+        }
+        Pat::Object(props) => {
+            for prop in props {
+                match prop {
+                    ObjectPatProp::KeyValue { value, .. } => bound_names(value, out),
+                    ObjectPatProp::Assign { key, .. } => {
+                        out.insert(key.clone());
+                    }
+                    ObjectPatProp::Rest => {}
+                }
+            }
+        }
+        Pat::Array(elems) => {
+            for elem in elems.iter().flatten() {
+                bound_names(elem, out);
+            }
+        }
+        Pat::Unsupported => {}
+    }
+}
+
+pub(crate) fn match_names(pat: &MatchPat, out: &mut HashSet<String>) {
+    match pat {
+        MatchPat::Variant { fields, .. } => out.extend(fields.iter().cloned()),
+        MatchPat::Newtype { binding, .. } => {
+            out.insert(binding.clone());
+        }
+        MatchPat::Wildcard => {}
+    }
+}
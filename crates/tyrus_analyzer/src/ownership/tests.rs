@@ -0,0 +1,298 @@
+use tyrus_ast::visit::{walk_expr_mut, VisitMut};
+use tyrus_ast::{ClassMember, Decl, Expr, ExprKind, Item, Module, Usage};
+
+use super::{infer_ownership, infer_ownership_with};
+use crate::typeck::check_module;
+
+fn infer(src: &str) -> Module {
+    let (program, _) = tyrus_parser::parse_str(src).expect("test source should parse");
+    let mut module = tyrus_ast::lower_program(&program);
+    check_module(&mut module);
+    infer_ownership(&mut module);
+    module
+}
+
+/// Usages of every read of variable `name` (or of field `name` of `this`),
+/// in source order.
+fn usages(module: &mut Module, name: &str) -> Vec<Usage> {
+    struct Collect<'a> {
+        name: &'a str,
+        found: Vec<Usage>,
+    }
+    impl VisitMut for Collect<'_> {
+        fn visit_expr_mut(&mut self, expr: &mut Expr) {
+            match &expr.kind {
+                ExprKind::Ident(name) if name == self.name => self.found.push(expr.usage),
+                ExprKind::Member(member)
+                    if member.obj.is_this() && member.prop.as_ident() == Some(self.name) =>
+                {
+                    self.found.push(expr.usage)
+                }
+                _ => {}
+            }
+            walk_expr_mut(self, expr);
+        }
+    }
+    let mut collect = Collect {
+        name,
+        found: Vec::new(),
+    };
+    collect.visit_module_mut(module);
+    collect.found
+}
+
+#[test]
+fn test_last_use_moves_and_earlier_uses_clone() {
+    let mut module = infer(
+        r#"
+        function f(names: string[]): string[] {
+            const copy = names;
+            const other = names;
+            return other;
+        }
+        "#,
+    );
+    assert_eq!(
+        usages(&mut module, "names"),
+        vec![Usage::Clone, Usage::Move]
+    );
+    assert_eq!(usages(&mut module, "other"), vec![Usage::Move]);
+}
+
+#[test]
+fn test_reads_borrow_and_copies_move() {
+    let mut module = infer(
+        r#"
+        function f(name: string, n: number): string {
+            console.log(name);
+            const total = n + n;
+            if (name === "x") {
+                return `${name}!`;
+            }
+            return name;
+        }
+        "#,
+    );
+    assert_eq!(
+        usages(&mut module, "name"),
+        vec![Usage::Borrow, Usage::Borrow, Usage::Borrow, Usage::Move]
+    );
+    assert_eq!(usages(&mut module, "n"), vec![Usage::Move, Usage::Move]);
+}
+
+#[test]
+fn test_branches_and_loops() {
+    let mut module = infer(
+        r#"
+        function keep(s: string): void {}
+        function branches(s: string, flag: boolean): void {
+            if (flag) {
+                keep(s);
+            } else {
+                keep(s);
+            }
+        }
+        function loops(t: string): void {
+            for (const i of [1, 2]) {
+                keep(t);
+            }
+        }
+        "#,
+    );
+    assert_eq!(usages(&mut module, "s"), vec![Usage::Move, Usage::Move]);
+    // The next iteration reads `t` again
+    assert_eq!(usages(&mut module, "t"), vec![Usage::Clone]);
+}
+
+#[test]
+fn test_closures_clone_captures_and_iteration_moves() {
+    let mut module = infer(
+        r#"
+        function f(items: string[], prefix: string): string[] {
+            const all = items.map((item) => prefix + item);
+            const more = items.map((item) => item);
+            return more;
+        }
+        "#,
+    );
+    assert_eq!(
+        usages(&mut module, "items"),
+        vec![Usage::Borrow, Usage::Move]
+    );
+    assert_eq!(usages(&mut module, "prefix"), vec![Usage::Clone]);
+}
+
+#[test]
+fn test_methods_mutating_this() {
+    let mut module = infer(
+        r#"
+        class Cart {
+            items: string[] = [];
+            add(item: string): void {
+                this.items.push(item);
+            }
+            addTwice(item: string): void {
+                this.add(item);
+                this.add(item);
+            }
+            first(): string {
+                return this.items[0];
+            }
+            count(): number {
+                return this.items.length;
+            }
+        }
+        "#,
+    );
+    let Item::Decl {
+        decl: Decl::Class(class),
+        ..
+    } = &module.items[0]
+    else {
+        panic!("expected a class");
+    };
+    let mutating: Vec<_> = class
+        .members
+        .iter()
+        .filter_map(|member| match member {
+            ClassMember::Method(method) if method.mutates_this => Some(method.name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(mutating, vec!["add", "addTwice"]);
+    assert_eq!(
+        usages(&mut module, "items"),
+        vec![Usage::BorrowMut, Usage::Borrow, Usage::Borrow]
+    );
+    assert_eq!(
+        usages(&mut module, "item"),
+        vec![Usage::Move, Usage::Clone, Usage::Move]
+    );
+}
+
+#[test]
+fn test_imported_methods_mutating_this() {
+    let imported = infer(
+        r#"
+        export class Store {
+            items: string[] = [];
+            add(item: string) { this.items.push(item); }
+        }
+        "#,
+    );
+    let Item::Decl {
+        decl: Decl::Class(store),
+        ..
+    } = &imported.items[0]
+    else {
+        panic!("expected a class");
+    };
+    let store = store.clone();
+    let (program, _) = tyrus_parser::parse_str(
+        r#"
+        class Cache extends Store {
+            put(item: string) { this.add(item); }
+        }
+        function fill(store: Store) { store.add("x"); }
+        "#,
+    )
+    .expect("test source should parse");
+    let mut module = tyrus_ast::lower_program(&program);
+    check_module(&mut module);
+    infer_ownership_with(&mut module, &[&store]);
+    let Item::Decl {
+        decl: Decl::Class(cache),
+        ..
+    } = &module.items[0]
+    else {
+        panic!("expected a class");
+    };
+    assert!(cache.methods().all(|method| method.mutates_this));
+    assert_eq!(usages(&mut module, "store"), vec![Usage::BorrowMut]);
+}
+
+#[test]
+fn test_interface_methods_mutating_this() {
+    let mut module = infer(
+        r#"
+        interface Sink {
+            write(item: string): void;
+        }
+        class Buffer implements Sink {
+            items: string[] = [];
+            write(item: string) { this.items.push(item); }
+        }
+        class Logger {
+            constructor(private sink: Sink) {}
+            log(item: string) { this.sink.write(item); }
+        }
+        function flush(sink: Sink) { sink.write("x"); }
+        "#,
+    );
+    let Item::Decl {
+        decl: Decl::Class(logger),
+        ..
+    } = &module.items[2]
+    else {
+        panic!("expected a class");
+    };
+    // The implementation mutates, but trait objects of `Sink` are locked
+    let Item::Decl {
+        decl: Decl::Class(buffer),
+        ..
+    } = &module.items[1]
+    else {
+        panic!("expected a class");
+    };
+    assert!(buffer.methods().all(|method| method.mutates_this));
+    assert!(logger.methods().all(|method| !method.mutates_this));
+    assert_eq!(usages(&mut module, "sink"), vec![Usage::Borrow; 2]);
+}
+
+#[test]
+fn test_overrides_mutating_this() {
+    let mut module = infer(
+        r#"
+        class Base {
+            count: number = 0;
+            record() {}
+            log() { this.record(); }
+        }
+        class Counter extends Base {
+            record() { this.count += 1; }
+        }
+        function run(base: Base) { base.log(); }
+        "#,
+    );
+    let Item::Decl {
+        decl: Decl::Class(base),
+        ..
+    } = &module.items[0]
+    else {
+        panic!("expected a class");
+    };
+    let log = base.methods().find(|method| method.name == "log").unwrap();
+    assert!(log.mutates_this);
+    assert_eq!(usages(&mut module, "base"), vec![Usage::BorrowMut]);
+}
+
+#[test]
+fn test_string_concatenation_borrows_the_right_operand() {
+    let mut module = infer(
+        r#"
+        function label(width: number, name: string): string {
+            const next = width + width;
+            return name + width + name + next;
+        }
+        "#,
+    );
+    assert_eq!(
+        usages(&mut module, "width"),
+        vec![Usage::Move, Usage::Move, Usage::Borrow]
+    );
+    assert_eq!(
+        usages(&mut module, "name"),
+        vec![Usage::Clone, Usage::Borrow]
+    );
+    assert_eq!(usages(&mut module, "next"), vec![Usage::Borrow]);
+}
@@ -0,0 +1,97 @@
+//! Name resolution for the passes that track local variables.
+//!
+//! [`Scopes`] numbers variables in the order they are declared and resolves
+//! each name to the innermost variable it refers to. A pass that walks a
+//! module several times in the same order gets the same numbers on every
+//! walk, so what one walk learns about a variable is found by the next.
+
+use std::collections::{HashMap, HashSet};
+
+use tyrus_ast::MatchPat;
+
+use crate::ownership::match_names;
+
+pub(crate) struct Scopes<V> {
+    /// What the pass knows about each variable, by number.
+    pub vars: Vec<V>,
+    /// Numbers of the variables in scope by name, innermost scope last.
+    names: Vec<HashMap<String, usize>>,
+    /// Variables declared so far by this walk.
+    declared: usize,
+}
+
+impl<V> Scopes<V> {
+    /// Scopes for a walk that starts from the `vars` of an earlier one.
+    pub fn new(vars: Vec<V>) -> Self {
+        Self {
+            vars,
+            names: vec![HashMap::new()],
+            declared: 0,
+        }
+    }
+
+    pub fn push(&mut self) {
+        self.names.push(HashMap::new());
+    }
+
+    pub fn pop(&mut self) {
+        self.names.pop();
+    }
+
+    /// Number of scopes open, counting the module's.
+    pub fn depth(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Numbers the next variable before it is in scope, adding `var` for it
+    /// when no earlier walk did.
+    pub fn reserve(&mut self, var: impl FnOnce() -> V) -> usize {
+        let id = self.declared;
+        self.declared += 1;
+        if id == self.vars.len() {
+            self.vars.push(var());
+        }
+        id
+    }
+
+    /// Puts variable `id` in the innermost scope as `name`.
+    pub fn bind(&mut self, name: &str, id: usize) {
+        if let Some(scope) = self.names.last_mut() {
+            scope.insert(name.to_string(), id);
+        }
+    }
+
+    pub fn declare(&mut self, name: &str, var: impl FnOnce() -> V) -> usize {
+        let id = self.reserve(var);
+        self.bind(name, id);
+        id
+    }
+
+    /// Declares the bindings of a `match` arm, which shadow variables of the
+    /// same name.
+    pub fn declare_arm(&mut self, pats: &[MatchPat], var: impl Fn() -> V) {
+        let mut names = HashSet::new();
+        for pat in pats {
+            match_names(pat, &mut names);
+        }
+        // In the same order on every walk
+        let mut names: Vec<_> = names.into_iter().collect();
+        names.sort();
+        for name in &names {
+            self.declare(name, &var);
+        }
+    }
+
+    /// The variable `name` refers to, and the depth of the scope declaring it.
+    pub fn resolve(&self, name: &str) -> Option<(usize, usize)> {
+        self.names
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(depth, scope)| Some((depth, *scope.get(name)?)))
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<usize> {
+        self.resolve(name).map(|(_, id)| id)
+    }
+}
@@ -0,0 +1,241 @@
+//! Signatures of the JavaScript built-ins Tyrus knows how to translate.
+//!
+//! Generic callbacks use `$`-prefixed type parameters so they can never clash
+//! with type parameters declared in user code.
+
+use tyrus_ast::Type;
+
+use super::env::FnSig;
+
+fn sig(params: Vec<Type>, ret: Type) -> FnSig {
+    FnSig {
+        type_params: Vec::new(),
+        params,
+        ret,
+    }
+}
+
+fn generic_sig(type_param: &str, params: Vec<Type>, ret: Type) -> FnSig {
+    FnSig {
+        type_params: vec![type_param.to_string()],
+        params,
+        ret,
+    }
+}
+
+fn callback(params: Vec<Type>, ret: Type) -> Type {
+    Type::Function {
+        params,
+        ret: Box::new(ret),
+    }
+}
+
+fn array(elem: Type) -> Type {
+    Type::Array(Box::new(elem))
+}
+
+fn optional(inner: Type) -> Type {
+    Type::Optional(Box::new(inner))
+}
+
+/// `Map<K, V>` and `Set<T>` are modelled as named types with the usual arguments.
+pub fn is_collection(name: &str) -> bool {
+    matches!(name, "Map" | "Set")
+}
+
+/// Non-method members of built-in types, e.g. `arr.length`.
+pub fn member_type(recv: &Type, name: &str) -> Option<Type> {
+    match (recv, name) {
+        (Type::Array(_) | Type::String, "length") => Some(Type::Number),
+        (Type::Named { name: ty, .. }, "size") if is_collection(ty) => Some(Type::Number),
+        _ => None,
+    }
+}
+
+/// Methods of built-in types, with the receiver's element types already filled in.
+pub fn method_sig(recv: &Type, name: &str) -> Option<FnSig> {
+    match recv {
+        Type::Array(elem) => array_method(elem, name),
+        Type::String => string_method(name),
+        Type::Number => match name {
+            "toFixed" | "toString" | "toPrecision" => Some(sig(vec![Type::Number], Type::String)),
+            _ => None,
+        },
+        Type::Bool => (name == "toString").then(|| sig(Vec::new(), Type::String)),
+        Type::Date => match name {
+            "getTime" | "getFullYear" | "getMonth" | "getDate" | "getHours" | "getMinutes"
+            | "getSeconds" | "valueOf" => Some(sig(Vec::new(), Type::Number)),
+            "toISOString" | "toString" | "toJSON" => Some(sig(Vec::new(), Type::String)),
+            _ => None,
+        },
+        Type::Named { name: ty, args } if ty == "Map" => {
+            let key = args.first().cloned().unwrap_or(Type::Unknown);
+            let value = args.get(1).cloned().unwrap_or(Type::Unknown);
+            match name {
+                "get" => Some(sig(vec![key], optional(value))),
+                "set" => Some(sig(vec![key, value], recv.clone())),
+                "has" | "delete" => Some(sig(vec![key], Type::Bool)),
+                "clear" => Some(sig(Vec::new(), Type::Void)),
+                "keys" => Some(sig(Vec::new(), array(key))),
+                "values" => Some(sig(Vec::new(), array(value))),
+                "entries" => Some(sig(Vec::new(), array(Type::Tuple(vec![key, value])))),
+                "forEach" => Some(sig(
+                    vec![callback(vec![value, key], Type::Void)],
+                    Type::Void,
+                )),
+                _ => None,
+            }
+        }
+        Type::Named { name: ty, args } if ty == "Set" => {
+            let elem = args.first().cloned().unwrap_or(Type::Unknown);
+            match name {
+                "add" => Some(sig(vec![elem], recv.clone())),
+                "has" | "delete" => Some(sig(vec![elem], Type::Bool)),
+                "clear" => Some(sig(Vec::new(), Type::Void)),
+                "values" | "keys" => Some(sig(Vec::new(), array(elem))),
+                "forEach" => Some(sig(vec![callback(vec![elem], Type::Void)], Type::Void)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn array_method(elem: &Type, name: &str) -> Option<FnSig> {
+    let elem = elem.clone();
+    let arr = array(elem.clone());
+    let predicate = || callback(vec![elem.clone(), Type::Number], Type::Bool);
+    let result = Type::Param("$U".to_string());
+    Some(match name {
+        "map" => generic_sig(
+            "$U",
+            vec![callback(vec![elem, Type::Number], result.clone())],
+            array(result),
+        ),
+        "flatMap" => generic_sig(
+            "$U",
+            vec![callback(vec![elem, Type::Number], array(result.clone()))],
+            array(result),
+        ),
+        "reduce" => generic_sig(
+            "$U",
+            vec![
+                callback(vec![result.clone(), elem, Type::Number], result.clone()),
+                result.clone(),
+            ],
+            result,
+        ),
+        "filter" => sig(vec![predicate()], arr),
+        "find" => sig(vec![predicate()], optional(elem)),
+        "findIndex" => sig(vec![predicate()], Type::Number),
+        "some" | "every" => sig(vec![predicate()], Type::Bool),
+        "forEach" => sig(
+            vec![callback(vec![elem, Type::Number], Type::Void)],
+            Type::Void,
+        ),
+        "includes" => sig(vec![elem], Type::Bool),
+        "indexOf" | "lastIndexOf" => sig(vec![elem], Type::Number),
+        "push" | "unshift" => sig(vec![elem], Type::Number),
+        "pop" | "shift" => sig(Vec::new(), optional(elem)),
+        "join" => sig(vec![Type::String], Type::String),
+        "slice" => sig(vec![Type::Number, Type::Number], arr),
+        "concat" => sig(vec![arr.clone()], arr),
+        "reverse" => sig(Vec::new(), arr),
+        "sort" => sig(vec![callback(vec![elem.clone(), elem], Type::Number)], arr),
+        _ => return None,
+    })
+}
+
+fn string_method(name: &str) -> Option<FnSig> {
+    Some(match name {
+        "includes" | "startsWith" | "endsWith" => sig(vec![Type::String], Type::Bool),
+        "indexOf" | "lastIndexOf" => sig(vec![Type::String], Type::Number),
+        "charCodeAt" => sig(vec![Type::Number], Type::Number),
+        "split" => sig(vec![Type::String], array(Type::String)),
+        "replace" | "replaceAll" => sig(vec![Type::String, Type::String], Type::String),
+        "toUpperCase" | "toLowerCase" | "trim" | "trimStart" | "trimEnd" | "toString" => {
+            sig(Vec::new(), Type::String)
+        }
+        "slice" | "substring" | "substr" => sig(vec![Type::Number, Type::Number], Type::String),
+        "charAt" | "repeat" => sig(vec![Type::Number], Type::String),
+        "padStart" | "padEnd" => sig(vec![Type::Number, Type::String], Type::String),
+        "concat" => sig(vec![Type::String], Type::String),
+        _ => return None,
+    })
+}
+
+/// Calls on global namespaces such as `Math.floor(x)` or `JSON.stringify(v)`.
+pub fn static_sig(object: &str, name: &str) -> Option<FnSig> {
+    let value = Type::Param("$T".to_string());
+    match (object, name) {
+        ("Math", "random") => Some(sig(Vec::new(), Type::Number)),
+        ("Math", _) => Some(sig(vec![Type::Number, Type::Number], Type::Number)),
+        ("JSON", "stringify") => Some(sig(vec![Type::Unknown], Type::String)),
+        ("JSON", "parse") => Some(sig(vec![Type::String], Type::Any)),
+        ("console", _) => Some(sig(Vec::new(), Type::Void)),
+        ("Object", "keys") => Some(sig(vec![Type::Unknown], array(Type::String))),
+        ("Object", "values") => Some(sig(vec![Type::Unknown], array(Type::Unknown))),
+        ("Object", "entries") => Some(sig(
+            vec![Type::Unknown],
+            array(Type::Tuple(vec![Type::String, Type::Unknown])),
+        )),
+        ("Array", "isArray") => Some(sig(vec![Type::Unknown], Type::Bool)),
+        ("Number", "isInteger" | "isFinite" | "isNaN") => Some(sig(vec![Type::Number], Type::Bool)),
+        ("Number", "parseInt" | "parseFloat") => Some(sig(vec![Type::String], Type::Number)),
+        ("Date", "now") => Some(sig(Vec::new(), Type::Number)),
+        ("Promise", "resolve") => Some(generic_sig(
+            "$T",
+            vec![value.clone()],
+            Type::Promise(Box::new(value)),
+        )),
+        _ => None,
+    }
+}
+
+/// Constants on global namespaces, e.g. `Math.PI`.
+pub fn static_member(object: &str, name: &str) -> Option<Type> {
+    match (object, name) {
+        ("Math", _) => Some(Type::Number),
+        ("Number", "MAX_SAFE_INTEGER" | "MIN_SAFE_INTEGER" | "EPSILON") => Some(Type::Number),
+        _ => None,
+    }
+}
+
+/// Global functions such as `parseInt`.
+pub fn global_fn_sig(name: &str) -> Option<FnSig> {
+    match name {
+        "parseInt" | "parseFloat" | "Number" => Some(sig(vec![Type::Unknown], Type::Number)),
+        "String" => Some(sig(vec![Type::Unknown], Type::String)),
+        "Boolean" => Some(sig(vec![Type::Unknown], Type::Bool)),
+        "isNaN" | "isFinite" => Some(sig(vec![Type::Number], Type::Bool)),
+        _ => None,
+    }
+}
+
+/// Whether `name` is a global namespace with static members modelled here.
+pub fn is_global_namespace(name: &str) -> bool {
+    matches!(
+        name,
+        "Math" | "JSON" | "console" | "Object" | "Array" | "Number" | "Date" | "Promise"
+    )
+}
+
+/// Type produced by `new Name<args>()` for built-in constructors.
+pub fn constructed_type(name: &str, type_args: &[Type]) -> Option<Type> {
+    let arg = |i: usize| type_args.get(i).cloned().unwrap_or(Type::Unknown);
+    match name {
+        "Map" => Some(Type::Named {
+            name: "Map".to_string(),
+            args: vec![arg(0), arg(1)],
+        }),
+        "Set" => Some(Type::Named {
+            name: "Set".to_string(),
+            args: vec![arg(0)],
+        }),
+        "Array" => Some(array(arg(0))),
+        "Date" => Some(Type::Date),
+        "Promise" => Some(Type::Promise(Box::new(arg(0)))),
+        _ if name.ends_with("Error") => Some(Type::named("Error")),
+        _ => None,
+    }
+}
@@ -0,0 +1,442 @@
+use std::collections::{HashMap, HashSet};
+
+use tyrus_ast::{
+    ClassDecl, ClassMember, CtorParam, Decl, Function, InterfaceDecl, InterfaceMember, Item,
+    MethodKind, Module, Param, Pat, Stmt, StmtKind, Type, TypeParam,
+};
+
+/// Signature of a function or method. Generic signatures keep their own type
+/// parameters as `Type::Param` and are instantiated per call site.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FnSig {
+    pub type_params: Vec<String>,
+    pub params: Vec<Type>,
+    pub ret: Type,
+}
+
+/// Fields and methods of a class or interface.
+#[derive(Debug, Clone, Default)]
+pub struct Shape {
+    pub type_params: Vec<String>,
+    pub fields: HashMap<String, Type>,
+    pub methods: HashMap<String, FnSig>,
+    pub static_fields: HashMap<String, Type>,
+    pub static_methods: HashMap<String, FnSig>,
+    /// `extends` of an interface, or the superclass of a class.
+    pub extends: Vec<Type>,
+    /// Constructor parameter types; `None` for interfaces.
+    pub ctor: Option<Vec<Type>>,
+}
+
+/// Everything the checker knows about the top-level declarations of a module.
+#[derive(Debug, Clone, Default)]
+pub struct TypeEnv {
+    pub functions: HashMap<String, FnSig>,
+    pub shapes: HashMap<String, Shape>,
+    pub aliases: HashMap<String, (Vec<String>, Type)>,
+    pub enums: HashSet<String>,
+}
+
+/// Guards alias expansion and inheritance walks against cycles.
+const MAX_DEPTH: usize = 16;
+
+impl TypeEnv {
+    pub fn collect(module: &Module) -> Self {
+        let mut env = Self::default();
+        for item in &module.items {
+            match item {
+                Item::Decl { decl, .. } => env.declare(decl),
+                Item::Stmt(stmt) => env.declare_nested(stmt),
+                Item::Import(_) => {}
+            }
+        }
+        env
+    }
+
+    /// The exported top-level declarations of `module`, which the other
+    /// modules of a project can import.
+    pub fn collect_exports(module: &Module) -> Self {
+        let mut env = Self::default();
+        for item in &module.items {
+            if let Item::Decl {
+                decl,
+                exported: true,
+            } = item
+            {
+                env.declare(decl);
+            }
+        }
+        env
+    }
+
+    /// Adds the declarations of `other`, replacing those of the same name.
+    pub fn extend(&mut self, other: TypeEnv) {
+        self.functions.extend(other.functions);
+        self.shapes.extend(other.shapes);
+        self.aliases.extend(other.aliases);
+        self.enums.extend(other.enums);
+    }
+
+    fn declare_nested(&mut self, stmt: &Stmt) {
+        if let StmtKind::Decl(decl) = &stmt.kind {
+            self.declare(decl);
+        }
+    }
+
+    pub(crate) fn declare(&mut self, decl: &Decl) {
+        match decl {
+            Decl::Fn(f) => {
+                self.functions.insert(f.name.clone(), fn_sig(&f.function));
+            }
+            Decl::Class(class) => {
+                self.shapes.insert(class.name.clone(), class_shape(class));
+            }
+            Decl::Interface(iface) => {
+                self.shapes
+                    .insert(iface.name.clone(), interface_shape(iface));
+            }
+            Decl::TypeAlias(alias) => {
+                self.aliases.insert(
+                    alias.name.clone(),
+                    (param_names(&alias.type_params), alias.ty.clone()),
+                );
+            }
+            Decl::Enum(e) => {
+                self.enums.insert(e.name.clone());
+            }
+            Decl::Var(_) => {}
+        }
+    }
+
+    pub fn is_class(&self, name: &str) -> bool {
+        self.shapes.get(name).is_some_and(|s| s.ctor.is_some())
+    }
+
+    /// Expands type aliases until the type is no longer an alias reference.
+    pub fn resolve(&self, ty: &Type) -> Type {
+        let mut ty = ty.clone();
+        for _ in 0..MAX_DEPTH {
+            let Type::Named { name, args } = &ty else {
+                break;
+            };
+            let Some((params, target)) = self.aliases.get(name) else {
+                break;
+            };
+            ty = substitute(target, &bind_params(params, args));
+        }
+        ty
+    }
+
+    /// Type of field `name` on a class or interface instance, including inherited fields.
+    pub fn field_type(&self, ty: &Type, name: &str) -> Option<Type> {
+        self.lookup_member(ty, 0, &|shape| shape.fields.get(name).cloned())
+    }
+
+    /// Signature of method `name` on a class or interface instance, including inherited methods.
+    pub fn method_sig(&self, ty: &Type, name: &str) -> Option<FnSig> {
+        self.lookup_member(ty, 0, &|shape| shape.methods.get(name).cloned())
+    }
+
+    /// The superclass of a class instance type, with the class's type arguments applied.
+    pub fn superclass(&self, ty: &Type) -> Option<Type> {
+        let Type::Named { name, args } = self.resolve(ty) else {
+            return None;
+        };
+        let shape = self.shapes.get(&name)?;
+        let parent = shape.extends.first()?;
+        Some(substitute(parent, &bind_params(&shape.type_params, &args)))
+    }
+
+    /// Number of fields a JSON object must hold to deserialize into the class
+    /// or interface `name`: its non-optional fields, including inherited ones.
+    pub fn required_fields(&self, name: &str) -> usize {
+        self.count_required_fields(name, 0)
+    }
+
+    fn count_required_fields(&self, name: &str, depth: usize) -> usize {
+        if depth > MAX_DEPTH {
+            return 0;
+        }
+        let Some(shape) = self.shapes.get(name) else {
+            return 0;
+        };
+        let own = shape.fields.values().filter(|ty| !ty.is_optional()).count();
+        let inherited: usize = shape
+            .extends
+            .iter()
+            .filter_map(Type::as_named)
+            .map(|parent| self.count_required_fields(parent, depth + 1))
+            .sum();
+        own + inherited
+    }
+
+    fn lookup_member<T: Substitute>(
+        &self,
+        ty: &Type,
+        depth: usize,
+        find: &dyn Fn(&Shape) -> Option<T>,
+    ) -> Option<T> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let Type::Named { name, args } = self.resolve(ty) else {
+            return None;
+        };
+        let shape = self.shapes.get(&name)?;
+        let subst = bind_params(&shape.type_params, &args);
+        if let Some(found) = find(shape) {
+            return Some(found.substitute(&subst));
+        }
+        shape
+            .extends
+            .iter()
+            .find_map(|parent| self.lookup_member(&substitute(parent, &subst), depth + 1, find))
+    }
+}
+
+/// Types that can have type parameters replaced.
+trait Substitute {
+    fn substitute(self, subst: &HashMap<String, Type>) -> Self;
+}
+
+impl Substitute for Type {
+    fn substitute(self, subst: &HashMap<String, Type>) -> Self {
+        substitute(&self, subst)
+    }
+}
+
+impl Substitute for FnSig {
+    fn substitute(self, subst: &HashMap<String, Type>) -> Self {
+        // A method's own type parameters shadow the class ones.
+        let subst: HashMap<_, _> = subst
+            .iter()
+            .filter(|(name, _)| !self.type_params.contains(name))
+            .map(|(name, ty)| (name.clone(), ty.clone()))
+            .collect();
+        FnSig {
+            params: self.params.iter().map(|p| substitute(p, &subst)).collect(),
+            ret: substitute(&self.ret, &subst),
+            type_params: self.type_params,
+        }
+    }
+}
+
+pub(crate) fn param_names(params: &[TypeParam]) -> Vec<String> {
+    params.iter().map(|p| p.name.clone()).collect()
+}
+
+/// Pairs type parameter names with the given arguments; missing arguments become `Unknown`.
+pub(crate) fn bind_params(params: &[String], args: &[Type]) -> HashMap<String, Type> {
+    params
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.clone(), args.get(i).cloned().unwrap_or(Type::Unknown)))
+        .collect()
+}
+
+/// Replaces every `Type::Param` bound in `subst`.
+pub fn substitute(ty: &Type, subst: &HashMap<String, Type>) -> Type {
+    if subst.is_empty() {
+        return ty.clone();
+    }
+    let sub = |t: &Type| Box::new(substitute(t, subst));
+    match ty {
+        Type::Param(name) => subst.get(name).cloned().unwrap_or_else(|| ty.clone()),
+        Type::Array(inner) => Type::Array(sub(inner)),
+        Type::Promise(inner) => Type::Promise(sub(inner)),
+        Type::Optional(inner) => Type::Optional(sub(inner)),
+        Type::Record(key, value) => Type::Record(sub(key), sub(value)),
+        Type::Union(members) => Type::Union(members.iter().map(|m| substitute(m, subst)).collect()),
+        Type::Tuple(elems) => Type::Tuple(elems.iter().map(|e| substitute(e, subst)).collect()),
+        Type::Object(props) => Type::Object(
+            props
+                .iter()
+                .map(|p| tyrus_ast::PropSig {
+                    name: p.name.clone(),
+                    ty: substitute(&p.ty, subst),
+                    optional: p.optional,
+                })
+                .collect(),
+        ),
+        Type::Function { params, ret } => Type::Function {
+            params: params.iter().map(|p| substitute(p, subst)).collect(),
+            ret: sub(ret),
+        },
+        Type::Named { name, args } => Type::Named {
+            name: name.clone(),
+            args: args.iter().map(|a| substitute(a, subst)).collect(),
+        },
+        _ => ty.clone(),
+    }
+}
+
+/// Binds the type parameters listed in `params` by matching `pattern` against `actual`.
+/// Bindings that are still `Unknown` may be refined by later arguments.
+pub fn unify(pattern: &Type, actual: &Type, params: &[String], subst: &mut HashMap<String, Type>) {
+    match (pattern, actual) {
+        (_, Type::Unknown) => {}
+        (Type::Param(name), _) if params.contains(name) => {
+            let bound = subst.entry(name.clone()).or_insert(Type::Unknown);
+            if *bound == Type::Unknown {
+                *bound = actual.clone();
+            }
+        }
+        (Type::Array(p), Type::Array(a))
+        | (Type::Promise(p), Type::Promise(a))
+        | (Type::Optional(p), Type::Optional(a)) => unify(p, a, params, subst),
+        (Type::Optional(p), a) => unify(p, a, params, subst),
+        (Type::Record(pk, pv), Type::Record(ak, av)) => {
+            unify(pk, ak, params, subst);
+            unify(pv, av, params, subst);
+        }
+        (Type::Tuple(ps), Type::Tuple(as_)) => {
+            for (p, a) in ps.iter().zip(as_) {
+                unify(p, a, params, subst);
+            }
+        }
+        (
+            Type::Function {
+                params: pp,
+                ret: pr,
+            },
+            Type::Function {
+                params: ap,
+                ret: ar,
+            },
+        ) => {
+            for (p, a) in pp.iter().zip(ap) {
+                unify(p, a, params, subst);
+            }
+            unify(pr, ar, params, subst);
+        }
+        (Type::Named { name: pn, args: pa }, Type::Named { name: an, args: aa }) if pn == an => {
+            for (p, a) in pa.iter().zip(aa) {
+                unify(p, a, params, subst);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn pat_type(pat: &Pat) -> Type {
+    match pat {
+        Pat::Ident(binding) => binding.ty.clone().unwrap_or(Type::Unknown),
+        _ => Type::Unknown,
+    }
+}
+
+fn param_types(params: &[Param]) -> Vec<Type> {
+    params.iter().map(|p| pat_type(&p.pat)).collect()
+}
+
+pub(crate) fn fn_sig(function: &Function) -> FnSig {
+    FnSig {
+        type_params: param_names(&function.type_params),
+        params: param_types(&function.params),
+        ret: declared_return(function),
+    }
+}
+
+/// The annotated return type, or `Unknown` (`Promise<Unknown>` for async functions).
+pub(crate) fn declared_return(function: &Function) -> Type {
+    match &function.return_type {
+        Some(ty) => ty.clone(),
+        None if function.is_async => Type::Promise(Box::new(Type::Unknown)),
+        None => Type::Unknown,
+    }
+}
+
+fn class_shape(class: &ClassDecl) -> Shape {
+    let mut shape = Shape {
+        type_params: param_names(&class.type_params),
+        extends: class.super_class.iter().cloned().collect(),
+        ctor: Some(Vec::new()),
+        ..Default::default()
+    };
+    for member in &class.members {
+        match member {
+            ClassMember::Prop(prop) => {
+                let mut ty = prop.ty.clone().unwrap_or(Type::Unknown);
+                if prop.optional && !ty.is_optional() {
+                    ty = Type::Optional(Box::new(ty));
+                }
+                if prop.is_static {
+                    shape.static_fields.insert(prop.name.clone(), ty);
+                } else {
+                    shape.fields.insert(prop.name.clone(), ty);
+                }
+            }
+            ClassMember::Method(method) => {
+                let sig = fn_sig(&method.function);
+                match method.kind {
+                    MethodKind::Getter => {
+                        shape.fields.insert(method.name.clone(), sig.ret);
+                    }
+                    MethodKind::Setter => {
+                        shape.fields.entry(method.name.clone()).or_insert_with(|| {
+                            sig.params.first().cloned().unwrap_or(Type::Unknown)
+                        });
+                    }
+                    MethodKind::Method if method.is_static => {
+                        shape.static_methods.insert(method.name.clone(), sig);
+                    }
+                    MethodKind::Method => {
+                        shape.methods.insert(method.name.clone(), sig);
+                    }
+                }
+            }
+            ClassMember::Constructor(ctor) => {
+                let mut params = Vec::new();
+                for param in &ctor.params {
+                    match param {
+                        CtorParam::Prop { name, ty, .. } => {
+                            let ty = ty.clone().unwrap_or(Type::Unknown);
+                            shape.fields.insert(name.clone(), ty.clone());
+                            params.push(ty);
+                        }
+                        CtorParam::Param(param) => params.push(pat_type(&param.pat)),
+                    }
+                }
+                shape.ctor = Some(params);
+            }
+        }
+    }
+    shape
+}
+
+fn interface_shape(iface: &InterfaceDecl) -> Shape {
+    let mut shape = Shape {
+        type_params: param_names(&iface.type_params),
+        extends: iface.extends.clone(),
+        ..Default::default()
+    };
+    for member in &iface.members {
+        match member {
+            InterfaceMember::Property {
+                name, ty, optional, ..
+            } => {
+                let mut ty = ty.clone().unwrap_or(Type::Unknown);
+                if *optional && !ty.is_optional() {
+                    ty = Type::Optional(Box::new(ty));
+                }
+                shape.fields.insert(name.clone(), ty);
+            }
+            InterfaceMember::Method {
+                name,
+                params,
+                return_type,
+                ..
+            } => {
+                shape.methods.insert(
+                    name.clone(),
+                    FnSig {
+                        type_params: Vec::new(),
+                        params: param_types(params),
+                        ret: return_type.clone().unwrap_or(Type::Unknown),
+                    },
+                );
+            }
+        }
+    }
+    shape
+}
@@ -0,0 +1,1194 @@
+//! Local type inference over the Tyrus IR.
+//!
+//! [`check_module`] walks a lowered [`Module`] and records a type on every
+//! expression it can work out: literals, locals, calls to known functions and
+//! methods (built-in or user-defined), member access on interface and class
+//! fields, and generic instantiation from call arguments. Anything it cannot
+//! type stays `Type::Unknown`, so consumers must keep a fallback.
+
+pub mod builtins;
+mod env;
+mod narrow;
+pub mod unions;
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+
+use tyrus_ast::{
+    Arg, ArrowBody, ArrowExpr, AssignOp, AssignTarget, BinaryOp, CallExpr, Callee, ClassDecl,
+    ClassMember, CtorParam, Decl, Expr, ExprKind, ForHead, ForInit, Function, Item, Lit,
+    MemberExpr, MemberProp, Module, ObjectPatProp, Pat, Prop, PropKey, PropSig, Stmt, StmtKind,
+    Type, UnaryOp, VarDecl,
+};
+
+pub use env::{substitute, unify, FnSig, Shape, TypeEnv};
+
+/// Infers types for every expression in `module`, storing them in [`Expr::ty`],
+/// then lowers narrowing on discriminated unions into `match` statements.
+///
+/// Returns the declaration environment so later stages can query the
+/// signatures of the module's functions, classes and interfaces.
+pub fn check_module(module: &mut Module) -> TypeEnv {
+    check_module_with(module, &TypeEnv::default())
+}
+
+/// Like [`check_module`], for a module that can also use the declarations of
+/// `imported`: those the other modules of its project export. The module's
+/// own declarations win over imported ones of the same name.
+pub fn check_module_with(module: &mut Module, imported: &TypeEnv) -> TypeEnv {
+    let mut env = imported.clone();
+    env.extend(TypeEnv::collect(module));
+    let mut checker = Checker::new(env);
+    // The first pass infers return types of unannotated functions and methods,
+    // so call sites that appear before the declaration see them as well.
+    for _ in 0..2 {
+        checker.scopes = vec![HashMap::new()];
+        for item in &mut module.items {
+            checker.check_item(item);
+        }
+    }
+    narrow::narrow_module(module, &checker.env);
+    checker.env
+}
+
+struct Checker {
+    env: TypeEnv,
+    scopes: Vec<HashMap<String, Type>>,
+    this_ty: Option<Type>,
+    /// Declared return type of the enclosing function (unwrapped for async functions).
+    expected_return: Option<Type>,
+    /// Types of the `return` arguments seen in the enclosing function.
+    returns: Vec<Type>,
+}
+
+impl Checker {
+    fn new(env: TypeEnv) -> Self {
+        Self {
+            env,
+            scopes: vec![HashMap::new()],
+            this_ty: None,
+            expected_return: None,
+            returns: Vec::new(),
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Type> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    fn declare(&mut self, name: &str, ty: Type) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), ty);
+        }
+    }
+
+    fn with_scope<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.scopes.push(HashMap::new());
+        let result = f(self);
+        self.scopes.pop();
+        result
+    }
+
+    /// Whether `name` refers to a global rather than a local binding.
+    fn is_global(&self, name: &str) -> bool {
+        self.lookup(name).is_none()
+    }
+
+    fn check_item(&mut self, item: &mut Item) {
+        match item {
+            Item::Import(_) => {}
+            Item::Decl { decl, .. } => self.check_decl(decl),
+            Item::Stmt(stmt) => self.check_stmt(stmt),
+        }
+    }
+
+    fn check_decl(&mut self, decl: &mut Decl) {
+        match decl {
+            Decl::Fn(f) => {
+                let ret = self.check_function(&mut f.function, None);
+                if f.function.return_type.is_none() {
+                    if let Some(sig) = self.env.functions.get_mut(&f.name) {
+                        sig.ret = ret;
+                    }
+                }
+            }
+            Decl::Class(class) => self.check_class(class),
+            Decl::Enum(e) => {
+                for member in &mut e.members {
+                    if let Some(init) = &mut member.init {
+                        self.check_expr(init, None);
+                    }
+                }
+            }
+            Decl::Var(var) => self.check_var_decl(var),
+            Decl::Interface(_) | Decl::TypeAlias(_) => {}
+        }
+    }
+
+    /// Checks a function body and returns its (declared or inferred) return type.
+    fn check_function(&mut self, function: &mut Function, this_ty: Option<Type>) -> Type {
+        let declared = env::declared_return(function);
+        let outer_this = std::mem::replace(&mut self.this_ty, this_ty);
+        let outer_expected = std::mem::replace(
+            &mut self.expected_return,
+            function
+                .return_type
+                .as_ref()
+                .map(|ty| ty.unwrap_promise().clone()),
+        );
+        let outer_returns = std::mem::take(&mut self.returns);
+
+        self.with_scope(|this| {
+            for param in &function.params {
+                this.bind_pat(&param.pat, Type::Unknown);
+            }
+            if let Some(body) = &mut function.body {
+                this.check_block(body);
+            }
+        });
+
+        let returns = std::mem::replace(&mut self.returns, outer_returns);
+        self.expected_return = outer_expected;
+        self.this_ty = outer_this;
+
+        if function.return_type.is_some() || function.body.is_none() {
+            return declared;
+        }
+        let inferred = join_all(returns).unwrap_or(Type::Void);
+        if function.is_async {
+            Type::Promise(Box::new(inferred))
+        } else {
+            inferred
+        }
+    }
+
+    fn check_class(&mut self, class: &mut ClassDecl) {
+        let this_ty = Type::Named {
+            name: class.name.clone(),
+            args: class
+                .type_params
+                .iter()
+                .map(|p| Type::Param(p.name.clone()))
+                .collect(),
+        };
+        for member in &mut class.members {
+            match member {
+                ClassMember::Prop(prop) => {
+                    if let Some(value) = &mut prop.value {
+                        let outer = self.this_ty.replace(this_ty.clone());
+                        let ty = self.check_expr(value, prop.ty.as_ref());
+                        self.this_ty = outer;
+                        if prop.ty.is_none() {
+                            self.update_shape(&class.name, |shape| {
+                                let fields = if prop.is_static {
+                                    &mut shape.static_fields
+                                } else {
+                                    &mut shape.fields
+                                };
+                                fields.insert(prop.name.clone(), ty);
+                            });
+                        }
+                    }
+                }
+                ClassMember::Method(method) => {
+                    let this = (!method.is_static).then(|| this_ty.clone());
+                    let ret = self.check_function(&mut method.function, this);
+                    if method.function.return_type.is_none() {
+                        self.update_shape(&class.name, |shape| {
+                            let sig = if method.is_static {
+                                shape.static_methods.get_mut(&method.name)
+                            } else {
+                                shape.methods.get_mut(&method.name)
+                            };
+                            match (sig, method.kind) {
+                                (Some(sig), _) => sig.ret = ret,
+                                (None, tyrus_ast::MethodKind::Getter) => {
+                                    shape.fields.insert(method.name.clone(), ret);
+                                }
+                                (None, _) => {}
+                            }
+                        });
+                    }
+                }
+                ClassMember::Constructor(ctor) => {
+                    let outer = self.this_ty.replace(this_ty.clone());
+                    self.with_scope(|this| {
+                        for param in &ctor.params {
+                            match param {
+                                CtorParam::Prop { name, ty, .. } => {
+                                    this.declare(name, ty.clone().unwrap_or(Type::Unknown));
+                                }
+                                CtorParam::Param(param) => this.bind_pat(&param.pat, Type::Unknown),
+                            }
+                        }
+                        if let Some(body) = &mut ctor.body {
+                            this.check_block(body);
+                        }
+                    });
+                    self.this_ty = outer;
+                }
+            }
+        }
+    }
+
+    fn update_shape(&mut self, name: &str, f: impl FnOnce(&mut Shape)) {
+        if let Some(shape) = self.env.shapes.get_mut(name) {
+            f(shape);
+        }
+    }
+
+    fn check_block(&mut self, stmts: &mut [Stmt]) {
+        self.with_scope(|this| {
+            // Function declarations are hoisted to the top of their block.
+            for stmt in stmts.iter() {
+                if let StmtKind::Decl(decl) = &stmt.kind {
+                    if let Decl::Fn(f) = decl.as_ref() {
+                        let sig = env::fn_sig(&f.function);
+                        this.declare(&f.name, fn_type(&sig));
+                    }
+                    this.env.declare(decl);
+                }
+            }
+            for stmt in stmts.iter_mut() {
+                this.check_stmt(stmt);
+                // `if (typeof x === "number") return x;` leaves `x` the other
+                // member for the rest of the block, when a single one is left
+                if let Some((name, after)) = narrow::early_exit_guard(stmt, &this.env) {
+                    this.declare(&name, after);
+                }
+            }
+        });
+    }
+
+    fn check_stmt(&mut self, stmt: &mut Stmt) {
+        match &mut stmt.kind {
+            StmtKind::Expr(expr) | StmtKind::Throw(expr) => {
+                self.check_expr(expr, None);
+            }
+            StmtKind::Return(arg) => {
+                if let Some(arg) = arg {
+                    let expected = self.expected_return.clone();
+                    let ty = self.check_expr(arg, expected.as_ref());
+                    self.returns.push(ty);
+                }
+            }
+            StmtKind::Var(var) => self.check_var_decl(var),
+            StmtKind::Block(stmts) => self.check_block(stmts),
+            StmtKind::If { test, cons, alt } => {
+                self.check_expr(test, None);
+                let guard = self.type_guard(test);
+                self.with_scope(|this| {
+                    if let Some((name, narrowed, _)) = &guard {
+                        this.declare(name, narrowed.clone());
+                    }
+                    this.check_stmt(cons);
+                });
+                if let Some(alt) = alt {
+                    self.with_scope(|this| {
+                        if let Some((name, _, rest)) = &guard {
+                            this.declare(name, rest.clone());
+                        }
+                        this.check_stmt(alt);
+                    });
+                }
+            }
+            StmtKind::While { test, body } | StmtKind::DoWhile { body, test } => {
+                self.check_expr(test, None);
+                self.check_nested(body);
+            }
+            StmtKind::For {
+                init,
+                test,
+                update,
+                body,
+            } => self.with_scope(|this| {
+                match init {
+                    Some(ForInit::Var(var)) => this.check_var_decl(var),
+                    Some(ForInit::Expr(expr)) => {
+                        this.check_expr(expr, None);
+                    }
+                    None => {}
+                }
+                if let Some(test) = test {
+                    this.check_expr(test, None);
+                }
+                if let Some(update) = update {
+                    this.check_expr(update, None);
+                }
+                this.check_nested(body);
+            }),
+            StmtKind::ForOf { left, right, body } => {
+                let iterable = self.check_expr(right, None);
+                let item = self.element_type(&iterable);
+                self.with_scope(|this| {
+                    this.bind_for_head(left, item);
+                    this.check_nested(body);
+                });
+            }
+            StmtKind::ForIn { left, right, body } => {
+                self.check_expr(right, None);
+                self.with_scope(|this| {
+                    this.bind_for_head(left, Type::String);
+                    this.check_nested(body);
+                });
+            }
+            StmtKind::Try {
+                block,
+                handler,
+                finalizer,
+            } => {
+                self.check_block(block);
+                if let Some(catch) = handler {
+                    self.with_scope(|this| {
+                        if let Some(param) = &catch.param {
+                            this.bind_pat(param, Type::Unknown);
+                        }
+                        this.check_block(&mut catch.body);
+                    });
+                }
+                if let Some(finalizer) = finalizer {
+                    self.check_block(finalizer);
+                }
+            }
+            StmtKind::Switch {
+                discriminant,
+                cases,
+            } => {
+                let discriminant_ty = self.check_expr(discriminant, None);
+                self.with_scope(|this| {
+                    for case in cases {
+                        if let Some(test) = &mut case.test {
+                            this.check_expr(test, Some(&discriminant_ty));
+                        }
+                        for stmt in &mut case.cons {
+                            this.check_stmt(stmt);
+                        }
+                    }
+                });
+            }
+            StmtKind::Match { scrutinee, arms } => {
+                self.check_expr(scrutinee, None);
+                for arm in arms {
+                    self.with_scope(|this| this.check_block(&mut arm.body));
+                }
+            }
+            StmtKind::Decl(decl) => self.check_decl(decl),
+            StmtKind::Break
+            | StmtKind::Continue
+            | StmtKind::Empty
+            | StmtKind::Unsupported { .. } => {}
+        }
+    }
+
+    /// `typeof x === "string"` or `x instanceof C` on a union-typed local.
+    /// Returns the local with its type inside the guarded branch and in the other one.
+    fn type_guard(&self, test: &Expr) -> Option<(String, Type, Type)> {
+        let guard = TypeGuard::parse(test)?;
+        let Type::Union(members) = self.env.resolve(self.lookup(guard.name)?) else {
+            return None;
+        };
+        let (guarded, rest): (Vec<Type>, Vec<Type>) = members
+            .into_iter()
+            .partition(|member| guard.kind.admits(member));
+        if guarded.is_empty() || rest.is_empty() {
+            return None;
+        }
+        let narrowed = join_all(guarded.iter().map(widen).collect())?;
+        let rest = if rest.len() == 1 {
+            rest.into_iter().next()?
+        } else {
+            Type::Union(rest)
+        };
+        let name = guard.name.to_string();
+        if guard.negated {
+            Some((name, rest, narrowed))
+        } else {
+            Some((name, narrowed, rest))
+        }
+    }
+
+    /// Checks the body of an `if`/loop, which gets its own scope even without braces.
+    fn check_nested(&mut self, stmt: &mut Stmt) {
+        self.with_scope(|this| this.check_stmt(stmt));
+    }
+
+    fn check_var_decl(&mut self, var: &mut VarDecl) {
+        for decl in &mut var.decls {
+            let annotated = decl.pat.as_ident().and_then(|b| b.ty.clone());
+            let ty = match &mut decl.init {
+                Some(init) => self.check_expr(init, annotated.as_ref()),
+                None => Type::Unknown,
+            };
+            self.bind_pat(&decl.pat, annotated.unwrap_or(ty));
+        }
+    }
+
+    fn bind_for_head(&mut self, head: &ForHead, item: Type) {
+        match head {
+            ForHead::Var(var) => {
+                if let Some(decl) = var.decls.first() {
+                    self.bind_pat(&decl.pat, item);
+                }
+            }
+            ForHead::Pat(_) => {}
+        }
+    }
+
+    /// Declares the names bound by `pat`, given the type of the value it destructures.
+    fn bind_pat(&mut self, pat: &Pat, ty: Type) {
+        match pat {
+            Pat::Ident(binding) => {
+                let ty = binding.ty.clone().unwrap_or(ty);
+                self.declare(&binding.name, ty);
+            }
+            Pat::Object(props) => {
+                for prop in props {
+                    match prop {
+                        ObjectPatProp::KeyValue { key, value } => {
+                            let field = prop_key_name(key)
+                                .map(|name| self.member_type(&ty, name))
+                                .unwrap_or(Type::Unknown);
+                            self.bind_pat(value, field);
+                        }
+                        ObjectPatProp::Assign { key, default } => {
+                            let mut field = self.member_type(&ty, key);
+                            if default.is_some() {
+                                field = strip_optional(field);
+                            }
+                            self.declare(key, field);
+                        }
+                        ObjectPatProp::Rest => {}
+                    }
+                }
+            }
+            Pat::Array(elems) => {
+                for (i, elem) in elems.iter().enumerate() {
+                    if let Some(elem) = elem {
+                        let elem_ty = match self.env.resolve(&ty) {
+                            Type::Tuple(types) => types.get(i).cloned().unwrap_or(Type::Unknown),
+                            Type::Array(inner) => *inner,
+                            _ => Type::Unknown,
+                        };
+                        self.bind_pat(elem, elem_ty);
+                    }
+                }
+            }
+            Pat::Unsupported => {}
+        }
+    }
+
+    /// The type of the items produced by iterating over a value of type `ty`.
+    fn element_type(&self, ty: &Type) -> Type {
+        match self.env.resolve(ty) {
+            Type::Array(inner) => *inner,
+            Type::String => Type::String,
+            Type::Named { name, args } if name == "Map" => Type::Tuple(args),
+            Type::Named { name, mut args } if name == "Set" => args.pop().unwrap_or(Type::Unknown),
+            Type::Any => Type::Any,
+            _ => Type::Unknown,
+        }
+    }
+
+    /// Infers the type of `expr`, records it on the node and returns it.
+    ///
+    /// `expected` is the type the context requires, used to type callback
+    /// parameters and empty literals, and recorded in [`Expr::coerce`] when
+    /// it is wider than the type inferred.
+    fn check_expr(&mut self, expr: &mut Expr, expected: Option<&Type>) -> Type {
+        let ty = self.infer_expr(expr, expected);
+        // Parentheses and branches pass `expected` on to their operands
+        let passes_on = matches!(expr.kind, ExprKind::Paren(_) | ExprKind::Cond { .. });
+        expr.coerce = expected
+            .filter(|expected| !passes_on && self.widens(expected, &ty))
+            .map(|expected| Box::new(expected.clone()));
+        expr.ty = ty.clone();
+        ty
+    }
+
+    /// Whether a value of type `from` is converted to be used as a `to`: a
+    /// class instance where an interface or a base class is expected, or a
+    /// member of a union where the union is.
+    fn widens(&self, to: &Type, from: &Type) -> bool {
+        match (self.env.resolve(to), self.env.resolve(from)) {
+            (_, Type::Unknown | Type::Any | Type::Null | Type::Param(_) | Type::Union(_)) => false,
+            (Type::Union(_), _) => true,
+            (Type::Named { name: to, .. }, Type::Named { name: from, .. }) => to != from,
+            _ => false,
+        }
+    }
+
+    fn infer_expr(&mut self, expr: &mut Expr, expected: Option<&Type>) -> Type {
+        match &mut expr.kind {
+            ExprKind::Ident(name) => self.ident_type(name),
+            ExprKind::This => self.this_ty.clone().unwrap_or(Type::Unknown),
+            ExprKind::Super => self
+                .this_ty
+                .as_ref()
+                .and_then(|this| self.env.superclass(this))
+                .unwrap_or(Type::Unknown),
+            ExprKind::Lit(lit) => match lit {
+                Lit::Str(_) => Type::String,
+                Lit::Num(_) => Type::Number,
+                Lit::Bool(_) => Type::Bool,
+                Lit::Null => Type::Null,
+                Lit::Unsupported => Type::Unknown,
+            },
+            ExprKind::Tpl { exprs, .. } => {
+                for expr in exprs {
+                    self.check_expr(expr, None);
+                }
+                Type::String
+            }
+            ExprKind::Array(elems) => {
+                let expected_elem = expected.and_then(|ty| match self.env.resolve(ty) {
+                    Type::Array(inner) => Some(*inner),
+                    _ => None,
+                });
+                let mut elem_types = Vec::new();
+                for elem in elems.iter_mut().flatten() {
+                    let ty = self.check_expr(&mut elem.expr, expected_elem.as_ref());
+                    elem_types.push(if elem.spread {
+                        self.element_type(&ty)
+                    } else {
+                        ty
+                    });
+                }
+                let elem = join_all(elem_types)
+                    .filter(|ty| *ty != Type::Unknown)
+                    .or(expected_elem)
+                    .unwrap_or(Type::Unknown);
+                Type::Array(Box::new(elem))
+            }
+            ExprKind::Object(props) => {
+                let mut sigs = Vec::new();
+                for prop in props {
+                    match prop {
+                        Prop::KeyValue { key, value } => {
+                            if let PropKey::Computed(key) = key {
+                                self.check_expr(key, None);
+                            }
+                            let field = prop_key_name(key)
+                                .zip(expected)
+                                .map(|(name, ty)| self.member_type(ty, name))
+                                .filter(|ty| *ty != Type::Unknown);
+                            let ty = self.check_expr(value, field.as_ref());
+                            if let Some(name) = prop_key_name(key) {
+                                sigs.push(PropSig {
+                                    name: name.to_string(),
+                                    ty,
+                                    optional: false,
+                                });
+                            }
+                        }
+                        Prop::Shorthand(name) => sigs.push(PropSig {
+                            name: name.clone(),
+                            ty: self.ident_type(name),
+                            optional: false,
+                        }),
+                        Prop::Unsupported => {}
+                    }
+                }
+                Type::Object(sigs)
+            }
+            ExprKind::Unary { op, arg } => {
+                self.check_expr(arg, None);
+                match op {
+                    UnaryOp::Not | UnaryOp::Delete => Type::Bool,
+                    UnaryOp::Minus | UnaryOp::Plus | UnaryOp::Tilde => Type::Number,
+                    UnaryOp::TypeOf => Type::String,
+                    UnaryOp::Void => Type::Null,
+                }
+            }
+            ExprKind::Update { arg, .. } => {
+                self.check_expr(arg, None);
+                Type::Number
+            }
+            ExprKind::Bin { op, left, right } => {
+                let left = self.check_expr(left, None);
+                let right = self.check_expr(right, None);
+                self.binary_type(*op, left, right)
+            }
+            ExprKind::Assign { op, target, right } => {
+                let target_ty = match target {
+                    AssignTarget::Ident(name) => self.lookup(name).cloned(),
+                    AssignTarget::Member(member) => Some(self.check_member(member)),
+                    AssignTarget::Unsupported => None,
+                };
+                let right_ty = self.check_expr(right, target_ty.as_ref());
+                match op {
+                    AssignOp::Assign => right_ty,
+                    _ => target_ty.unwrap_or(right_ty),
+                }
+            }
+            ExprKind::Member(member) => self.check_member(member),
+            ExprKind::OptMember(member) => {
+                let ty = self.check_member(member);
+                make_optional(ty)
+            }
+            ExprKind::Cond { test, cons, alt } => {
+                self.check_expr(test, None);
+                let cons = self.check_expr(cons, expected);
+                let alt = self.check_expr(alt, expected);
+                join(cons, alt)
+            }
+            ExprKind::Call(call) => self.check_call(call),
+            ExprKind::OptCall(call) => {
+                let ty = self.check_call(call);
+                make_optional(ty)
+            }
+            ExprKind::New(call) => self.check_new(call),
+            ExprKind::Seq(exprs) => {
+                let mut last = Type::Unknown;
+                for expr in exprs {
+                    last = self.check_expr(expr, None);
+                }
+                last
+            }
+            ExprKind::Paren(inner) => self.check_expr(inner, expected),
+            ExprKind::Arrow(arrow) => self.check_arrow(arrow, expected),
+            ExprKind::Await(arg) => {
+                let ty = self.check_expr(arg, None);
+                ty.unwrap_promise().clone()
+            }
+            ExprKind::Unsupported { .. } => Type::Unknown,
+        }
+    }
+
+    /// Checks the object of a member access. When its type is an alias of a
+    /// built-in type (`type Names = string[]`), the expanded type is recorded
+    /// instead, so codegen can dispatch on it without an environment.
+    fn check_receiver(&mut self, expr: &mut Expr) -> Type {
+        let ty = self.check_expr(expr, None);
+        let resolved = self.env.resolve(&ty);
+        if resolved != ty && is_builtin(&resolved) {
+            expr.ty = resolved;
+        }
+        ty
+    }
+
+    fn ident_type(&self, name: &str) -> Type {
+        if let Some(ty) = self.lookup(name) {
+            return ty.clone();
+        }
+        if let Some(sig) = self.env.functions.get(name) {
+            return fn_type(sig);
+        }
+        match name {
+            "undefined" => Type::Null,
+            "NaN" | "Infinity" => Type::Number,
+            _ => Type::Unknown,
+        }
+    }
+
+    fn binary_type(&self, op: BinaryOp, left: Type, right: Type) -> Type {
+        let (l, r) = (
+            widen(&self.env.resolve(&left)),
+            widen(&self.env.resolve(&right)),
+        );
+        match op {
+            BinaryOp::Add => match (&l, &r) {
+                (Type::String, _) | (_, Type::String) => Type::String,
+                (Type::Number, Type::Number) => Type::Number,
+                (Type::Any, _) | (_, Type::Any) => Type::Any,
+                _ => Type::Unknown,
+            },
+            BinaryOp::Sub
+            | BinaryOp::Mul
+            | BinaryOp::Div
+            | BinaryOp::Mod
+            | BinaryOp::Exp
+            | BinaryOp::BitAnd
+            | BinaryOp::BitOr
+            | BinaryOp::BitXor
+            | BinaryOp::LShift
+            | BinaryOp::RShift
+            | BinaryOp::ZeroFillRShift => Type::Number,
+            BinaryOp::EqEq
+            | BinaryOp::NotEq
+            | BinaryOp::EqEqEq
+            | BinaryOp::NotEqEq
+            | BinaryOp::Lt
+            | BinaryOp::LtEq
+            | BinaryOp::Gt
+            | BinaryOp::GtEq
+            | BinaryOp::In
+            | BinaryOp::InstanceOf => Type::Bool,
+            BinaryOp::LogicalAnd => right,
+            BinaryOp::LogicalOr => match left {
+                Type::Optional(inner) => join(*inner, right),
+                left => join(left, right),
+            },
+            BinaryOp::NullishCoalescing => match (left, right) {
+                (Type::Optional(inner), right) if !right.is_optional() => join(*inner, right),
+                (left, right) => join(left, right),
+            },
+        }
+    }
+
+    fn check_member(&mut self, member: &mut MemberExpr) -> Type {
+        if let (Some(object), Some(name)) = (member.obj.as_ident(), member.prop.as_ident()) {
+            if self.is_global(object) {
+                if let Some(ty) = self.static_member_type(object, name) {
+                    let object_ty = if self.env.enums.contains(object) {
+                        Type::named(object)
+                    } else {
+                        Type::Unknown
+                    };
+                    member.obj.ty = object_ty;
+                    return ty;
+                }
+            }
+        }
+
+        let obj_ty = self.check_receiver(&mut member.obj);
+        match &mut member.prop {
+            MemberProp::Ident(name) | MemberProp::Private(name) => self.member_type(&obj_ty, name),
+            MemberProp::Computed(index) => {
+                let index_ty = self.check_expr(index, None);
+                let key = match &index.kind {
+                    ExprKind::Lit(Lit::Str(key)) => Some(key.clone()),
+                    _ => None,
+                };
+                match self.env.resolve(&obj_ty) {
+                    Type::Array(inner) => *inner,
+                    Type::Tuple(types) => match index.kind {
+                        ExprKind::Lit(Lit::Num(i)) => {
+                            types.get(i as usize).cloned().unwrap_or(Type::Unknown)
+                        }
+                        _ => join_all(types).unwrap_or(Type::Unknown),
+                    },
+                    Type::Record(_, value) => *value,
+                    Type::String if widen(&index_ty) == Type::Number => Type::String,
+                    Type::Any => Type::Any,
+                    _ => key
+                        .map(|key| self.member_type(&obj_ty, &key))
+                        .unwrap_or(Type::Unknown),
+                }
+            }
+        }
+    }
+
+    /// `Enum.Member`, `Class.staticField` and constants such as `Math.PI`.
+    fn static_member_type(&self, object: &str, name: &str) -> Option<Type> {
+        if self.env.enums.contains(object) {
+            return Some(Type::named(object));
+        }
+        if let Some(shape) = self.env.shapes.get(object) {
+            return shape
+                .static_fields
+                .get(name)
+                .cloned()
+                .or_else(|| shape.static_methods.get(name).map(fn_type));
+        }
+        builtins::static_member(object, name)
+    }
+
+    /// The type of property `name` on a value of type `ty`.
+    fn member_type(&self, ty: &Type, name: &str) -> Type {
+        let resolved = self.env.resolve(ty);
+        match &resolved {
+            Type::Optional(inner) => return self.member_type(inner, name),
+            Type::Any => return Type::Any,
+            Type::Union(members) => {
+                let found = members
+                    .iter()
+                    .map(|member| self.member_type(member, name))
+                    .filter(|ty| *ty != Type::Unknown)
+                    .collect();
+                if let Some(ty) = join_all(found) {
+                    return ty;
+                }
+            }
+            Type::Object(props) => {
+                if let Some(prop) = props.iter().find(|p| p.name == name) {
+                    return if prop.optional {
+                        make_optional(prop.ty.clone())
+                    } else {
+                        prop.ty.clone()
+                    };
+                }
+            }
+            _ => {}
+        }
+        if let Some(field) = self.env.field_type(&resolved, name) {
+            return field;
+        }
+        if let Some(sig) = self.env.method_sig(&resolved, name) {
+            return fn_type(&sig);
+        }
+        if let Some(ty) = builtins::member_type(&resolved, name) {
+            return ty;
+        }
+        if let Some(sig) = builtins::method_sig(&resolved, name) {
+            return fn_type(&sig);
+        }
+        Type::Unknown
+    }
+
+    /// Signature of method `name` on a receiver of type `recv`. User-defined
+    /// shapes win over built-ins, so a class's own `map` is never mistaken
+    /// for `Array.prototype.map`.
+    fn method_sig(&self, recv: &Type, name: &str) -> Option<FnSig> {
+        let resolved = match self.env.resolve(recv) {
+            Type::Optional(inner) => self.env.resolve(&inner),
+            ty => ty,
+        };
+        self.env
+            .method_sig(&resolved, name)
+            .or_else(|| builtins::method_sig(&resolved, name))
+    }
+
+    fn check_call(&mut self, call: &mut CallExpr) -> Type {
+        let callee = match &mut call.callee {
+            Callee::Expr(callee) => callee,
+            Callee::Super => {
+                self.check_args(&mut call.args, &[]);
+                return Type::Void;
+            }
+            Callee::Import => {
+                self.check_args(&mut call.args, &[]);
+                return Type::Promise(Box::new(Type::Unknown));
+            }
+        };
+
+        if let Some(sig) = self.callee_sig(callee) {
+            callee.ty = fn_type(&sig);
+            return self.apply_sig(&sig, &mut call.args, &call.type_args);
+        }
+
+        let callee_ty = self.check_expr(callee, None);
+        match self.env.resolve(&callee_ty) {
+            Type::Function { params, ret } => {
+                let sig = FnSig {
+                    type_params: Vec::new(),
+                    params,
+                    ret: *ret,
+                };
+                self.apply_sig(&sig, &mut call.args, &call.type_args)
+            }
+            Type::Any => {
+                self.check_args(&mut call.args, &[]);
+                Type::Any
+            }
+            _ => {
+                self.check_args(&mut call.args, &[]);
+                Type::Unknown
+            }
+        }
+    }
+
+    /// Resolves the signature of a directly named callee: a global function, a
+    /// static or built-in namespace function, or a method on a typed receiver.
+    fn callee_sig(&mut self, callee: &mut Expr) -> Option<FnSig> {
+        match &mut callee.kind {
+            ExprKind::Ident(name) if self.is_global(name) => self
+                .env
+                .functions
+                .get(name.as_str())
+                .cloned()
+                .or_else(|| builtins::global_fn_sig(name)),
+            ExprKind::Member(member) => {
+                let method = member.prop.as_ident()?.to_string();
+                if let Some(object) = member.obj.as_ident() {
+                    if self.is_global(object) {
+                        if let Some(shape) = self.env.shapes.get(object) {
+                            if let Some(sig) = shape.static_methods.get(&method) {
+                                return Some(sig.clone());
+                            }
+                        } else if builtins::is_global_namespace(object) {
+                            return builtins::static_sig(object, &method);
+                        }
+                    }
+                }
+                let recv = self.check_receiver(&mut member.obj);
+                self.method_sig(&recv, &method)
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks call arguments against `sig`, infers its type parameters from the
+    /// explicit type arguments and the argument types, and returns the
+    /// instantiated return type.
+    fn apply_sig(&mut self, sig: &FnSig, args: &mut [Arg], type_args: &[Type]) -> Type {
+        let mut subst: HashMap<String, Type> = sig
+            .type_params
+            .iter()
+            .zip(type_args)
+            .map(|(name, ty)| (name.clone(), ty.clone()))
+            .collect();
+
+        for (i, arg) in args.iter_mut().enumerate() {
+            let param = if arg.spread { None } else { sig.params.get(i) };
+            let expected = param.map(|p| self.instantiate(p, &sig.type_params, &subst));
+            let actual = self.check_expr(&mut arg.expr, expected.as_ref());
+            if let Some(param) = param {
+                unify(param, &actual, &sig.type_params, &mut subst);
+            }
+        }
+
+        self.instantiate(&sig.ret, &sig.type_params, &subst)
+    }
+
+    /// Substitutes inferred type parameters; those still unbound become `Unknown`.
+    fn instantiate(&self, ty: &Type, params: &[String], subst: &HashMap<String, Type>) -> Type {
+        let mut full = subst.clone();
+        for param in params {
+            full.entry(param.clone()).or_insert(Type::Unknown);
+        }
+        substitute(ty, &full)
+    }
+
+    fn check_args(&mut self, args: &mut [Arg], params: &[Type]) {
+        for (i, arg) in args.iter_mut().enumerate() {
+            self.check_expr(&mut arg.expr, params.get(i));
+        }
+    }
+
+    fn check_new(&mut self, call: &mut CallExpr) -> Type {
+        let name = match &call.callee {
+            Callee::Expr(callee) => callee.as_ident().map(str::to_string),
+            _ => None,
+        };
+        let Some(name) = name.filter(|name| self.is_global(name)) else {
+            self.check_args(&mut call.args, &[]);
+            return Type::Unknown;
+        };
+
+        if let Some(shape) = self.env.shapes.get(&name) {
+            if let Some(params) = &shape.ctor {
+                let sig = FnSig {
+                    type_params: shape.type_params.clone(),
+                    params: params.clone(),
+                    ret: Type::Named {
+                        name: name.clone(),
+                        args: shape
+                            .type_params
+                            .iter()
+                            .map(|p| Type::Param(p.clone()))
+                            .collect(),
+                    },
+                };
+                return self.apply_sig(&sig, &mut call.args, &call.type_args);
+            }
+        }
+
+        self.check_args(&mut call.args, &[]);
+        builtins::constructed_type(&name, &call.type_args).unwrap_or(Type::Named {
+            name,
+            args: call.type_args.clone(),
+        })
+    }
+
+    fn check_arrow(&mut self, arrow: &mut ArrowExpr, expected: Option<&Type>) -> Type {
+        let (expected_params, expected_ret) = match expected.map(|ty| self.env.resolve(ty)) {
+            Some(Type::Function { params, ret }) => (params, Some(*ret)),
+            _ => (Vec::new(), None),
+        };
+
+        let params: Vec<Type> = arrow
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, pat)| match pat {
+                Pat::Ident(binding) => binding
+                    .ty
+                    .clone()
+                    .or_else(|| expected_params.get(i).cloned())
+                    .unwrap_or(Type::Unknown),
+                _ => expected_params.get(i).cloned().unwrap_or(Type::Unknown),
+            })
+            .collect();
+
+        let outer_expected = std::mem::replace(
+            &mut self.expected_return,
+            expected_ret.filter(|ty| !contains_unknown(ty)),
+        );
+        let outer_returns = std::mem::take(&mut self.returns);
+
+        let ret = self.with_scope(|this| {
+            for (pat, ty) in arrow.params.iter().zip(&params) {
+                this.bind_pat(pat, ty.clone());
+            }
+            match &mut arrow.body {
+                ArrowBody::Expr(body) => {
+                    let expected = this.expected_return.clone();
+                    this.check_expr(body, expected.as_ref())
+                }
+                ArrowBody::Block(stmts) => {
+                    this.check_block(stmts);
+                    join_all(std::mem::take(&mut this.returns)).unwrap_or(Type::Void)
+                }
+            }
+        });
+
+        self.returns = outer_returns;
+        self.expected_return = outer_expected;
+
+        let ret = if arrow.is_async {
+            Type::Promise(Box::new(ret))
+        } else {
+            ret
+        };
+        Type::Function {
+            params,
+            ret: Box::new(ret),
+        }
+    }
+}
+
+fn fn_type(sig: &FnSig) -> Type {
+    Type::Function {
+        params: sig.params.clone(),
+        ret: Box::new(sig.ret.clone()),
+    }
+}
+
+fn is_builtin(ty: &Type) -> bool {
+    match ty {
+        Type::String | Type::Number | Type::Bool | Type::Date | Type::Array(_) => true,
+        Type::Named { name, .. } => builtins::is_collection(name),
+        _ => false,
+    }
+}
+
+fn prop_key_name(key: &PropKey) -> Option<&str> {
+    match key {
+        PropKey::Ident(name) | PropKey::Str(name) => Some(name),
+        _ => None,
+    }
+}
+
+/// Literal types behave like their base type in arithmetic and comparisons.
+/// A `typeof x === "tag"` or `x instanceof C` test on the local `name`.
+struct TypeGuard<'a> {
+    name: &'a str,
+    kind: GuardKind,
+    /// `!==` / `!=`: the guarded type applies to the else branch.
+    negated: bool,
+}
+
+enum GuardKind {
+    TypeOf(String),
+    InstanceOf(String),
+}
+
+impl<'a> TypeGuard<'a> {
+    fn parse(test: &'a Expr) -> Option<Self> {
+        let ExprKind::Bin { op, left, right } = &test.unparen().kind else {
+            return None;
+        };
+        match op {
+            BinaryOp::InstanceOf => Some(Self {
+                name: left.as_ident()?,
+                kind: GuardKind::InstanceOf(right.as_ident()?.to_string()),
+                negated: false,
+            }),
+            BinaryOp::EqEqEq | BinaryOp::EqEq | BinaryOp::NotEqEq | BinaryOp::NotEq => {
+                let (name, tag) = match (typeof_operand(left), str_lit(right)) {
+                    (Some(name), Some(tag)) => (name, tag),
+                    _ => (typeof_operand(right)?, str_lit(left)?),
+                };
+                Some(Self {
+                    name,
+                    kind: GuardKind::TypeOf(tag.to_string()),
+                    negated: matches!(op, BinaryOp::NotEqEq | BinaryOp::NotEq),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl GuardKind {
+    /// Whether a union member passes the guard.
+    fn admits(&self, member: &Type) -> bool {
+        match self {
+            GuardKind::InstanceOf(class) => member.as_named() == Some(class.as_str()),
+            GuardKind::TypeOf(tag) => match tag.as_str() {
+                "string" => widen(member) == Type::String,
+                "number" => widen(member) == Type::Number,
+                "boolean" => widen(member) == Type::Bool,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// `x` in `typeof x`.
+fn typeof_operand(expr: &Expr) -> Option<&str> {
+    match &expr.unparen().kind {
+        ExprKind::Unary {
+            op: UnaryOp::TypeOf,
+            arg,
+        } => arg.as_ident(),
+        _ => None,
+    }
+}
+
+fn str_lit(expr: &Expr) -> Option<&str> {
+    match &expr.unparen().kind {
+        ExprKind::Lit(Lit::Str(value)) => Some(value),
+        _ => None,
+    }
+}
+
+fn widen(ty: &Type) -> Type {
+    match ty {
+        Type::Literal(tyrus_ast::LitType::Str(_)) => Type::String,
+        Type::Literal(tyrus_ast::LitType::Num(_)) => Type::Number,
+        Type::Literal(tyrus_ast::LitType::Bool(_)) => Type::Bool,
+        other => other.clone(),
+    }
+}
+
+fn make_optional(ty: Type) -> Type {
+    match ty {
+        Type::Optional(_) | Type::Unknown | Type::Any => ty,
+        ty => Type::Optional(Box::new(ty)),
+    }
+}
+
+fn strip_optional(ty: Type) -> Type {
+    match ty {
+        Type::Optional(inner) => *inner,
+        ty => ty,
+    }
+}
+
+fn contains_unknown(ty: &Type) -> bool {
+    match ty {
+        Type::Unknown => true,
+        Type::Array(inner) | Type::Promise(inner) | Type::Optional(inner) => {
+            contains_unknown(inner)
+        }
+        _ => false,
+    }
+}
+
+/// The smallest type covering both `a` and `b`.
+fn join(a: Type, b: Type) -> Type {
+    if a == b {
+        return a;
+    }
+    match (a, b) {
+        (Type::Unknown, other) | (other, Type::Unknown) => other,
+        (Type::Never, other) | (other, Type::Never) => other,
+        (Type::Null, other) | (other, Type::Null) => make_optional(other),
+        (Type::Optional(a), b) | (b, Type::Optional(a)) => make_optional(join(*a, b)),
+        (a, b) if widen(&a) == widen(&b) => widen(&a),
+        (Type::Union(mut members), other) | (other, Type::Union(mut members)) => {
+            if !members.contains(&other) {
+                members.push(other);
+            }
+            Type::Union(members)
+        }
+        (a, b) => Type::Union(vec![a, b]),
+    }
+}
+
+fn join_all(types: Vec<Type>) -> Option<Type> {
+    types.into_iter().reduce(join)
+}
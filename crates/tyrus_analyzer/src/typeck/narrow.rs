@@ -0,0 +1,711 @@
+//! Lowers narrowing on unions into `match` statements.
+//!
+//! `switch (s.kind)` and `if (s.kind === "...")` chains whose scrutinee is
+//! typed as a tagged-union alias become [`StmtKind::Match`]. Each arm binds the
+//! variant fields it reads, and `s.field` inside the arm is rewritten to the
+//! binding, which is how the narrowed value is reached in Rust.
+//!
+//! `s.kind !== "..."` selects every other tag, and `&&` keeps the tags both
+//! sides select. An `if` on the tag without an `else` whose body returns,
+//! throws or continues narrows the rest of its block: those statements become
+//! the arm of the tags the test excludes.
+//!
+//! `typeof x === "..."` and `x instanceof C` chains on an untagged union bind
+//! the variant's value under the same name, shadowing `x` inside the arm. As
+//! an early exit, a guard narrows the rest of the block when a single variant
+//! is left for it.
+//!
+//! Statements after a `match` every arm of which exits are unreachable, and
+//! dropped. A test on the tags of several values at once, such as
+//! `a.kind === "circle" && b.kind === "circle"`, has no `match` to become,
+//! and is left unsupported.
+
+use tyrus_ast::visit::{walk_decl_mut, walk_expr_mut, walk_function_mut, walk_stmt_mut, VisitMut};
+use tyrus_ast::{
+    ArrowBody, BinaryOp, ClassMember, Constructor, Decl, Expr, ExprKind, Function, Lit, MatchArm,
+    MatchPat, Module, Stmt, StmtKind, SwitchCase, Type,
+};
+
+use super::env::TypeEnv;
+use super::unions::{tagged_union, untagged_union, TaggedUnion, UntaggedUnion};
+use super::{str_lit, GuardKind, TypeGuard};
+
+pub(super) fn narrow_module(module: &mut Module, env: &TypeEnv) {
+    Narrower { env }.visit_module_mut(module);
+}
+
+/// The local a type guard in `stmt`, an `if` whose body exits, narrows for
+/// the rest of its block, with the type of the variant it is bound to there.
+pub(super) fn early_exit_guard(stmt: &Stmt, env: &TypeEnv) -> Option<(String, Type)> {
+    match (Narrower { env }).early_exit(stmt)? {
+        EarlyExit::Guard { narrowed, .. } => Some(narrowed),
+        EarlyExit::Tags(..) => None,
+    }
+}
+
+struct Narrower<'a> {
+    env: &'a TypeEnv,
+}
+
+/// The value being narrowed: `s` in `s.kind`.
+struct Scrutinee {
+    expr: Expr,
+    path: String,
+    union_name: String,
+    union: TaggedUnion,
+}
+
+impl VisitMut for Narrower<'_> {
+    fn visit_function_mut(&mut self, function: &mut Function) {
+        if let Some(body) = &mut function.body {
+            self.narrow_early_exit(body);
+        }
+        walk_function_mut(self, function);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let ExprKind::Arrow(arrow) = &mut expr.kind {
+            if let ArrowBody::Block(body) = &mut arrow.body {
+                self.narrow_early_exit(body);
+            }
+        }
+        walk_expr_mut(self, expr);
+    }
+
+    fn visit_decl_mut(&mut self, decl: &mut Decl) {
+        if let Decl::Class(class) = decl {
+            for member in &mut class.members {
+                if let ClassMember::Constructor(Constructor {
+                    body: Some(body), ..
+                }) = member
+                {
+                    self.narrow_early_exit(body);
+                }
+            }
+        }
+        walk_decl_mut(self, decl);
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        // Every block type checking narrowed the rest of
+        match &mut stmt.kind {
+            StmtKind::Block(stmts) => self.narrow_early_exit(stmts),
+            StmtKind::Try {
+                block,
+                handler,
+                finalizer,
+            } => {
+                self.narrow_early_exit(block);
+                if let Some(handler) = handler {
+                    self.narrow_early_exit(&mut handler.body);
+                }
+                if let Some(finalizer) = finalizer {
+                    self.narrow_early_exit(finalizer);
+                }
+            }
+            _ => {}
+        }
+        let narrowed = match &stmt.kind {
+            StmtKind::If { test, .. } if self.tag_paths(test).len() > 1 => {
+                Some(StmtKind::Unsupported {
+                    what: "tests on the tags of several unions at once".to_string(),
+                })
+            }
+            StmtKind::Switch {
+                discriminant,
+                cases,
+            } => self.narrow_switch(discriminant, cases),
+            StmtKind::If { .. } => self
+                .narrow_if(stmt)
+                .or_else(|| self.narrow_guards(stmt))
+                .map(|mut kind| {
+                    // The arms are the blocks of the `if` chain
+                    if let StmtKind::Match { arms, .. } = &mut kind {
+                        for arm in arms {
+                            self.narrow_early_exit(&mut arm.body);
+                        }
+                    }
+                    kind
+                }),
+            _ => None,
+        };
+        if let Some(kind) = narrowed {
+            stmt.kind = kind;
+        }
+        walk_stmt_mut(self, stmt);
+    }
+}
+
+impl Narrower<'_> {
+    /// Matches `s.kind` where `s` has a tagged-union alias type and `kind` is its tag.
+    fn discriminant(&self, expr: &Expr) -> Option<Scrutinee> {
+        let ExprKind::Member(member) = &expr.unparen().kind else {
+            return None;
+        };
+        let Type::Named { name, .. } = &member.obj.ty else {
+            return None;
+        };
+        if !self.env.aliases.contains_key(name) {
+            return None;
+        }
+        let union = tagged_union(&self.env.resolve(&member.obj.ty))?;
+        if member.prop.as_ident() != Some(union.tag.as_str()) {
+            return None;
+        }
+        Some(Scrutinee {
+            expr: (*member.obj).clone(),
+            path: place_path(&member.obj)?,
+            union_name: name.clone(),
+            union,
+        })
+    }
+
+    fn narrow_switch(&self, discriminant: &Expr, cases: &[SwitchCase]) -> Option<StmtKind> {
+        let scrutinee = self.discriminant(discriminant)?;
+        let mut arms = Vec::new();
+        let mut pending = Vec::new();
+        let mut has_default = false;
+
+        for case in cases {
+            match &case.test {
+                Some(test) => {
+                    let tag = str_lit(test)?;
+                    scrutinee.union.variant(tag)?;
+                    pending.push(tag.to_string());
+                }
+                None => has_default = true,
+            }
+            // Empty cases fall through into the next one.
+            if case.cons.is_empty() {
+                continue;
+            }
+            let body: Vec<Stmt> = case
+                .cons
+                .iter()
+                .filter(|s| !matches!(s.kind, StmtKind::Break))
+                .cloned()
+                .collect();
+            let tags = std::mem::take(&mut pending);
+            arms.push(if case.test.is_none() {
+                MatchArm {
+                    pats: vec![MatchPat::Wildcard],
+                    body,
+                }
+            } else {
+                scrutinee.arm(&tags, body)
+            });
+        }
+        if !pending.is_empty() {
+            arms.push(scrutinee.arm(&pending, Vec::new()));
+        }
+        Some(scrutinee.finish(arms, has_default))
+    }
+
+    fn narrow_if(&self, stmt: &Stmt) -> Option<StmtKind> {
+        let StmtKind::If { test, .. } = &stmt.kind else {
+            return None;
+        };
+        let scrutinee = self.tag_test(test)?.0;
+        let mut arms = Vec::new();
+        let mut rest = Some(stmt);
+
+        while let Some(stmt) = rest.take() {
+            if let StmtKind::If { test, cons, alt } = &stmt.kind {
+                if let Some((candidate, tags)) = self.tag_test(test) {
+                    if candidate.path == scrutinee.path {
+                        arms.push(scrutinee.arm(&tags, block_stmts(cons)));
+                        rest = alt.as_deref();
+                        continue;
+                    }
+                }
+            }
+            // The else branch binds the fields of the tags no test selected.
+            let covered: Vec<String> = arms
+                .iter()
+                .flat_map(|arm| &arm.pats)
+                .filter_map(|pat| match pat {
+                    MatchPat::Variant { tag, .. } => Some(tag.clone()),
+                    _ => None,
+                })
+                .collect();
+            let others = scrutinee.others(&covered);
+            arms.push(if others.is_empty() {
+                MatchArm {
+                    pats: vec![MatchPat::Wildcard],
+                    body: block_stmts(stmt),
+                }
+            } else {
+                scrutinee.arm(&others, block_stmts(stmt))
+            });
+            return Some(scrutinee.finish(arms, true));
+        }
+        Some(scrutinee.finish(arms, false))
+    }
+
+    /// Turns `if (<test>) <exit>; rest...` into a `match` whose other arm
+    /// runs `rest...`, when the test is on a tag or a type guard.
+    fn narrow_early_exit(&self, stmts: &mut Vec<Stmt>) {
+        // What follows a `match` whose arms all exit is unreachable, but for
+        // the declarations it hoists
+        if let Some(index) = stmts.iter().position(|stmt| self.exits_every_arm(stmt)) {
+            let rest = stmts.split_off(index + 1);
+            stmts.extend(
+                rest.into_iter()
+                    .filter(|stmt| matches!(stmt.kind, StmtKind::Decl(_))),
+            );
+        }
+        let Some(index) = stmts
+            .iter()
+            .position(|stmt| self.early_exit(stmt).is_some())
+            .filter(|index| index + 1 < stmts.len())
+        else {
+            return;
+        };
+        let mut rest = stmts.split_off(index + 1);
+        self.narrow_early_exit(&mut rest);
+        let stmt = stmts.pop().expect("the early exit was found");
+        let early_exit = self.early_exit(&stmt).expect("the early exit was found");
+        let StmtKind::If { cons, .. } = &stmt.kind else {
+            unreachable!("early exits are `if` statements");
+        };
+        stmts.push(Stmt {
+            kind: early_exit.lower(block_stmts(cons), rest),
+            comments: stmt.comments,
+            span: stmt.span,
+        });
+    }
+
+    /// `stmt` when it is an `if` without `else` whose body exits, on a test
+    /// that narrows what follows it.
+    fn early_exit(&self, stmt: &Stmt) -> Option<EarlyExit> {
+        let StmtKind::If {
+            test,
+            cons,
+            alt: None,
+        } = &stmt.kind
+        else {
+            return None;
+        };
+        if !exits(&block_stmts(cons)) {
+            return None;
+        }
+        if let Some((scrutinee, tags)) = self.tag_test(test) {
+            return (!scrutinee.others(&tags).is_empty())
+                .then_some(EarlyExit::Tags(scrutinee, tags));
+        }
+        // The rest can only bind `x` when a single variant is left for it,
+        // which is also when type checking narrowed it there
+        let guard = TypeGuard::parse(test)?;
+        let guarded = self.guarded(test)?;
+        let variant = guarded.variant(&guard.kind)?;
+        let (selected, others): (Vec<_>, Vec<_>) = guarded
+            .union
+            .variants
+            .iter()
+            .partition(|v| v.name == variant);
+        let (exit, rest) = match (guard.negated, selected.as_slice(), others.as_slice()) {
+            (false, [selected], [other]) => (selected, other),
+            (true, [selected], [other]) => (other, selected),
+            (true, [selected], _) => {
+                return Some(EarlyExit::Guard {
+                    exit: MatchPat::Wildcard,
+                    rest: guarded.pat(&selected.name),
+                    narrowed: (guarded.name.clone(), selected.ty.clone()),
+                    scrutinee: guarded.expr,
+                });
+            }
+            _ => return None,
+        };
+        Some(EarlyExit::Guard {
+            exit: guarded.pat(&exit.name),
+            rest: guarded.pat(&rest.name),
+            narrowed: (guarded.name.clone(), rest.ty.clone()),
+            scrutinee: guarded.expr,
+        })
+    }
+
+    fn narrow_guards(&self, stmt: &Stmt) -> Option<StmtKind> {
+        let StmtKind::If { test, .. } = &stmt.kind else {
+            return None;
+        };
+        let guard = TypeGuard::parse(test).filter(|guard| !guard.negated)?;
+        let guarded = self.guarded(test)?;
+        // A guard for a type outside the union selects no variant; the
+        // whole `if` would otherwise become the fallback arm of itself
+        guarded.variant(&guard.kind)?;
+        let mut arms = Vec::new();
+        let mut covered: Vec<String> = Vec::new();
+        let mut rest = Some(stmt);
+
+        while let Some(stmt) = rest.take() {
+            if let StmtKind::If { test, cons, alt } = &stmt.kind {
+                // Later tests see `x` already narrowed, so only the guard is
+                // read from them; the variant comes from the declared union.
+                if let Some(variant) = TypeGuard::parse(test)
+                    .filter(|guard| guard.name == guarded.name && !guard.negated)
+                    .and_then(|guard| guarded.variant(&guard.kind))
+                {
+                    arms.push(MatchArm {
+                        pats: vec![guarded.pat(&variant)],
+                        body: block_stmts(cons),
+                    });
+                    covered.push(variant);
+                    rest = alt.as_deref();
+                    continue;
+                }
+            }
+            // The else branch can still bind `x` when a single variant is left.
+            let remaining: Vec<_> = guarded
+                .union
+                .variants
+                .iter()
+                .filter(|v| !covered.contains(&v.name))
+                .collect();
+            let pat = match remaining.as_slice() {
+                [last] => guarded.pat(&last.name),
+                _ => MatchPat::Wildcard,
+            };
+            arms.push(MatchArm {
+                pats: vec![pat],
+                body: block_stmts(stmt),
+            });
+            return Some(StmtKind::Match {
+                scrutinee: guarded.expr,
+                arms,
+            });
+        }
+        if covered.len() < guarded.union.variants.len() {
+            arms.push(MatchArm {
+                pats: vec![MatchPat::Wildcard],
+                body: Vec::new(),
+            });
+        }
+        Some(StmtKind::Match {
+            scrutinee: guarded.expr,
+            arms,
+        })
+    }
+
+    /// The local tested by a type guard, when it has an untagged-union type.
+    fn guarded(&self, test: &Expr) -> Option<Guarded> {
+        let ExprKind::Bin { left, right, .. } = &test.unparen().kind else {
+            return None;
+        };
+        let name = TypeGuard::parse(test)?.name;
+        let operand = [left, right]
+            .into_iter()
+            .find_map(|side| match &side.unparen().kind {
+                ExprKind::Unary { arg, .. } if arg.as_ident() == Some(name) => Some(&**arg),
+                ExprKind::Ident(ident) if ident == name => Some(&**side),
+                _ => None,
+            })?;
+        let union = untagged_union(&self.env.resolve(&operand.ty))?;
+        let union_name = match &operand.ty {
+            Type::Named { name, .. } => name.clone(),
+            _ => union.synthesized_name(),
+        };
+        Some(Guarded {
+            expr: operand.clone(),
+            name: name.to_string(),
+            union_name,
+            union,
+        })
+    }
+
+    /// Whether `stmt` narrows into a `match` every arm of which exits.
+    fn exits_every_arm(&self, stmt: &Stmt) -> bool {
+        let narrowed = match &stmt.kind {
+            StmtKind::Switch {
+                discriminant,
+                cases,
+            } => self.narrow_switch(discriminant, cases),
+            StmtKind::If { .. } => self.narrow_if(stmt).or_else(|| self.narrow_guards(stmt)),
+            _ => None,
+        };
+        matches!(narrowed, Some(StmtKind::Match { arms, .. }) if arms.iter().all(|arm| exits(&arm.body)))
+    }
+
+    /// The paths of the values whose tags `test` compares, through `||` and
+    /// `&&`: `a` and `b` in `a.kind === "circle" && b.kind === "circle"`.
+    fn tag_paths(&self, test: &Expr) -> Vec<String> {
+        let ExprKind::Bin { op, left, right } = &test.unparen().kind else {
+            return Vec::new();
+        };
+        match op {
+            BinaryOp::LogicalOr | BinaryOp::LogicalAnd => {
+                let mut paths = self.tag_paths(left);
+                for path in self.tag_paths(right) {
+                    if !paths.contains(&path) {
+                        paths.push(path);
+                    }
+                }
+                paths
+            }
+            _ => self
+                .tag_test(test)
+                .map(|(scrutinee, _)| vec![scrutinee.path])
+                .unwrap_or_default(),
+        }
+    }
+
+    /// `s.kind === "a"` or `s.kind !== "a"`, optionally chained with `||` and
+    /// `&&`, returning the tags for which it holds.
+    fn tag_test(&self, test: &Expr) -> Option<(Scrutinee, Vec<String>)> {
+        let ExprKind::Bin { op, left, right } = &test.unparen().kind else {
+            return None;
+        };
+        match op {
+            BinaryOp::LogicalOr => {
+                let (scrutinee, mut tags) = self.tag_test(left)?;
+                let (other, more) = self.tag_test(right)?;
+                if other.path != scrutinee.path {
+                    return None;
+                }
+                tags.extend(more);
+                Some((scrutinee, tags))
+            }
+            BinaryOp::LogicalAnd => {
+                let (scrutinee, tags) = self.tag_test(left)?;
+                let (other, more) = self.tag_test(right)?;
+                if other.path != scrutinee.path {
+                    return None;
+                }
+                let tags: Vec<String> = tags.into_iter().filter(|t| more.contains(t)).collect();
+                (!tags.is_empty()).then_some((scrutinee, tags))
+            }
+            BinaryOp::EqEqEq | BinaryOp::EqEq | BinaryOp::NotEqEq | BinaryOp::NotEq => {
+                let (scrutinee, tag) = match (self.discriminant(left), str_lit(right)) {
+                    (Some(scrutinee), Some(tag)) => (scrutinee, tag),
+                    _ => (self.discriminant(right)?, str_lit(left)?),
+                };
+                scrutinee.union.variant(tag)?;
+                let tags = vec![tag.to_string()];
+                if matches!(op, BinaryOp::EqEqEq | BinaryOp::EqEq) {
+                    return Some((scrutinee, tags));
+                }
+                let others = scrutinee.others(&tags);
+                (!others.is_empty()).then_some((scrutinee, others))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Scrutinee {
+    /// The tags of the union not in `tags`, in declaration order.
+    fn others(&self, tags: &[String]) -> Vec<String> {
+        self.union
+            .variants
+            .iter()
+            .map(|v| v.tag_value.clone())
+            .filter(|tag| !tags.contains(tag))
+            .collect()
+    }
+
+    /// An arm for `tags`, binding the fields the body reads from the scrutinee.
+    fn arm(&self, tags: &[String], mut body: Vec<Stmt>) -> MatchArm {
+        let variants: Vec<_> = tags.iter().filter_map(|t| self.union.variant(t)).collect();
+        // Only fields every alternative has can be bound in an or-pattern.
+        let fields = variants
+            .first()
+            .map(|first| {
+                first
+                    .fields
+                    .iter()
+                    .map(|f| f.name.clone())
+                    .filter(|name| {
+                        variants
+                            .iter()
+                            .all(|v| v.fields.iter().any(|f| &f.name == name))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut binder = FieldBinder {
+            path: &self.path,
+            tag: &self.union.tag,
+            tag_value: (tags.len() == 1).then(|| tags[0].as_str()),
+            fields,
+            used: Vec::new(),
+        };
+        for stmt in &mut body {
+            binder.visit_stmt_mut(stmt);
+        }
+        let pats = tags
+            .iter()
+            .map(|tag| MatchPat::Variant {
+                union: self.union_name.clone(),
+                tag: tag.clone(),
+                fields: binder.used.clone(),
+            })
+            .collect();
+        MatchArm { pats, body }
+    }
+
+    /// Builds the `match`, adding an empty catch-all when the arms are not exhaustive.
+    fn finish(self, mut arms: Vec<MatchArm>, has_default: bool) -> StmtKind {
+        let covered = |tag: &str| {
+            arms.iter().any(|arm| {
+                arm.pats
+                    .iter()
+                    .any(|p| matches!(p, MatchPat::Variant { tag: t, .. } if t == tag))
+            })
+        };
+        let exhaustive = self.union.variants.iter().all(|v| covered(&v.tag_value));
+        if !has_default && !exhaustive {
+            arms.push(MatchArm {
+                pats: vec![MatchPat::Wildcard],
+                body: Vec::new(),
+            });
+        }
+        StmtKind::Match {
+            scrutinee: self.expr,
+            arms,
+        }
+    }
+}
+
+/// An `if` whose body exits, narrowing the statements after it.
+enum EarlyExit {
+    /// A test on the tag of `s`, selecting these tags.
+    Tags(Scrutinee, Vec<String>),
+    /// A type guard on a local, bound by `exit` in the body and `rest` after.
+    Guard {
+        scrutinee: Expr,
+        exit: MatchPat,
+        rest: MatchPat,
+        /// The local and its type after the `if`.
+        narrowed: (String, Type),
+    },
+}
+
+impl EarlyExit {
+    /// The `match` running `exit` for what the test selects and `rest` for
+    /// the rest.
+    fn lower(self, exit: Vec<Stmt>, rest: Vec<Stmt>) -> StmtKind {
+        match self {
+            EarlyExit::Tags(scrutinee, tags) => {
+                let arms = vec![
+                    scrutinee.arm(&tags, exit),
+                    scrutinee.arm(&scrutinee.others(&tags), rest),
+                ];
+                scrutinee.finish(arms, true)
+            }
+            EarlyExit::Guard {
+                scrutinee,
+                exit: exit_pat,
+                rest: rest_pat,
+                ..
+            } => {
+                let exit = MatchArm {
+                    pats: vec![exit_pat],
+                    body: exit,
+                };
+                let rest = MatchArm {
+                    pats: vec![rest_pat],
+                    body: rest,
+                };
+                // A catch-all goes last
+                let arms = if exit.pats == [MatchPat::Wildcard] {
+                    vec![rest, exit]
+                } else {
+                    vec![exit, rest]
+                };
+                StmtKind::Match { scrutinee, arms }
+            }
+        }
+    }
+}
+
+/// A local narrowed by `typeof`/`instanceof` guards.
+struct Guarded {
+    expr: Expr,
+    name: String,
+    union_name: String,
+    union: UntaggedUnion,
+}
+
+impl Guarded {
+    fn variant(&self, guard: &GuardKind) -> Option<String> {
+        let variant = match guard {
+            GuardKind::TypeOf(tag) => self.union.typeof_variant(tag),
+            GuardKind::InstanceOf(class) => self.union.instanceof_variant(class),
+        };
+        variant.map(|v| v.name.clone())
+    }
+
+    fn pat(&self, variant: &str) -> MatchPat {
+        MatchPat::Newtype {
+            union: self.union_name.clone(),
+            variant: variant.to_string(),
+            binding: self.name.clone(),
+        }
+    }
+}
+
+/// Rewrites `s.field` to the arm binding `field`, and `s.kind` to the known tag.
+struct FieldBinder<'a> {
+    path: &'a str,
+    tag: &'a str,
+    tag_value: Option<&'a str>,
+    fields: Vec<String>,
+    used: Vec<String>,
+}
+
+impl VisitMut for FieldBinder<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let ExprKind::Member(member) = &expr.kind {
+            if place_path(&member.obj).as_deref() == Some(self.path) {
+                let prop = member.prop.as_ident().unwrap_or_default().to_string();
+                if self.fields.contains(&prop) {
+                    if !self.used.contains(&prop) {
+                        self.used.push(prop.clone());
+                    }
+                    expr.kind = ExprKind::Ident(prop);
+                    return;
+                }
+                if let (true, Some(value)) = (prop == self.tag, self.tag_value) {
+                    expr.kind = ExprKind::Lit(Lit::Str(value.to_string()));
+                    return;
+                }
+            }
+        }
+        walk_expr_mut(self, expr);
+    }
+}
+
+/// `a`, `this.a` or `a.b.c`: expressions that name a place, as a dotted path.
+fn place_path(expr: &Expr) -> Option<String> {
+    match &expr.kind {
+        ExprKind::Ident(name) => Some(name.clone()),
+        ExprKind::This => Some("this".to_string()),
+        ExprKind::Paren(inner) => place_path(inner),
+        ExprKind::Member(member) => Some(format!(
+            "{}.{}",
+            place_path(&member.obj)?,
+            member.prop.as_ident()?
+        )),
+        _ => None,
+    }
+}
+
+/// Whether control never reaches the end of `stmts`.
+fn exits(stmts: &[Stmt]) -> bool {
+    stmts.last().is_some_and(|stmt| match &stmt.kind {
+        StmtKind::Return(_) | StmtKind::Throw(_) | StmtKind::Continue => true,
+        StmtKind::Block(stmts) => exits(stmts),
+        StmtKind::If {
+            cons,
+            alt: Some(alt),
+            ..
+        } => exits(&block_stmts(cons)) && exits(&block_stmts(alt)),
+        _ => false,
+    })
+}
+
+fn block_stmts(stmt: &Stmt) -> Vec<Stmt> {
+    match &stmt.kind {
+        StmtKind::Block(stmts) => stmts.clone(),
+        _ => vec![stmt.clone()],
+    }
+}
@@ -0,0 +1,401 @@
+use tyrus_ast::{Decl, Expr, Item, MatchPat, Module, StmtKind, Type};
+
+use super::check_module;
+
+fn check(src: &str) -> Module {
+    let (program, _) = tyrus_parser::parse_str(src).expect("test source should parse");
+    let mut module = tyrus_ast::lower_program(&program);
+    check_module(&mut module);
+    module
+}
+
+/// Types of the initializers of top-level `const` declarations, in order.
+fn init_types(module: &Module) -> Vec<Type> {
+    module
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Stmt(stmt) => match &stmt.kind {
+                StmtKind::Var(var) => var.decls[0].init.as_ref().map(|e: &Expr| e.ty.clone()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn array(ty: Type) -> Type {
+    Type::Array(Box::new(ty))
+}
+
+#[test]
+fn test_literals_and_operators() {
+    let module = check(
+        r#"
+        const a = "x";
+        const b = 1 + 2;
+        const c = a + b;
+        const d = b > 1 && a === "x";
+        const e = `n=${b}`;
+        const f = [1, 2, 3];
+        "#,
+    );
+    assert_eq!(
+        init_types(&module),
+        vec![
+            Type::String,
+            Type::Number,
+            Type::String,
+            Type::Bool,
+            Type::String,
+            array(Type::Number),
+        ]
+    );
+}
+
+#[test]
+fn test_calls_to_known_functions() {
+    let module = check(
+        r#"
+        const total = sum([1, 2]);
+        const label = describe(total);
+        function sum(xs: number[]): number { return xs.length; }
+        function describe(n: number) { return "n" + n; }
+        "#,
+    );
+    assert_eq!(init_types(&module), vec![Type::Number, Type::String]);
+}
+
+#[test]
+fn test_member_access_on_interface_and_class_fields() {
+    let module = check(
+        r#"
+        interface User { name: string; tags: string[]; manager?: User; }
+        class Repo {
+            constructor(private readonly users: User[]) {}
+            first(): User { return this.users[0]; }
+        }
+        const repo = new Repo([]);
+        const user = repo.first();
+        const name = user.name;
+        const boss = user.manager;
+        const count = user.tags.length;
+        "#,
+    );
+    let user = Type::named("User");
+    assert_eq!(
+        init_types(&module),
+        vec![
+            Type::named("Repo"),
+            user.clone(),
+            Type::String,
+            Type::Optional(Box::new(user)),
+            Type::Number,
+        ]
+    );
+}
+
+#[test]
+fn test_generic_instantiation() {
+    let module = check(
+        r#"
+        function first<T>(items: T[]): T { return items[0]; }
+        class Box<T> { constructor(public value: T) {} get(): T { return this.value; } }
+        const n = first([1, 2]);
+        const s = first<string>([]);
+        const boxed = new Box("hi").get();
+        const lengths = ["a", "bb"].map(s => s.length);
+        const evens = [1, 2, 3].filter(x => x % 2 === 0);
+        const total = [1, 2].reduce((acc, x) => acc + x, 0);
+        "#,
+    );
+    assert_eq!(
+        init_types(&module),
+        vec![
+            Type::Number,
+            Type::String,
+            Type::String,
+            array(Type::Number),
+            array(Type::Number),
+            Type::Number,
+        ]
+    );
+}
+
+#[test]
+fn test_callback_params_are_typed_from_context() {
+    let module = check("const names = [\"a\"].map(name => name.toUpperCase());");
+    let Item::Stmt(stmt) = &module.items[0] else {
+        panic!("expected statement");
+    };
+    let StmtKind::Var(var) = &stmt.kind else {
+        panic!("expected var");
+    };
+    let tyrus_ast::ExprKind::Call(call) = &var.decls[0].init.as_ref().unwrap().kind else {
+        panic!("expected call");
+    };
+    assert_eq!(
+        call.args[0].expr.ty,
+        Type::Function {
+            params: vec![Type::String],
+            ret: Box::new(Type::String),
+        }
+    );
+}
+
+#[test]
+fn test_user_class_methods_win_over_builtins() {
+    let module = check(
+        r#"
+        class Query { map(f: (x: number) => number): Query { return this; } }
+        const q = new Query().map(x => x + 1);
+        "#,
+    );
+    assert_eq!(init_types(&module), vec![Type::named("Query")]);
+}
+
+#[test]
+fn test_inferred_method_return_types_are_recorded() {
+    let module = check(
+        "class Counter { count = 0; next() { return this.count + 1; } }\nconst n = new Counter().next();",
+    );
+    assert_eq!(init_types(&module), vec![Type::Number]);
+}
+
+#[test]
+fn test_narrowing_on_discriminated_union_becomes_match() {
+    let module = check(
+        r#"
+        type Shape = { kind: "circle"; r: number } | { kind: "square"; side: number };
+        function area(s: Shape): number {
+            switch (s.kind) {
+                case "circle": return s.r * s.r;
+                default: return 0;
+            }
+        }
+        "#,
+    );
+    let Item::Decl {
+        decl: Decl::Fn(f), ..
+    } = &module.items[1]
+    else {
+        panic!("expected function");
+    };
+    let body = f.function.body.as_ref().unwrap();
+    let StmtKind::Match { arms, .. } = &body[0].kind else {
+        panic!("expected match, got {:?}", body[0].kind);
+    };
+    assert_eq!(
+        arms[0].pats,
+        vec![MatchPat::Variant {
+            union: "Shape".to_string(),
+            tag: "circle".to_string(),
+            fields: vec!["r".to_string()],
+        }]
+    );
+    assert_eq!(arms[1].pats, vec![MatchPat::Wildcard]);
+}
+
+#[test]
+fn test_type_guards_narrow_untagged_unions() {
+    let module = check(
+        r#"
+        function f(x: string | number): string {
+            if (typeof x === "string") {
+                return x;
+            } else {
+                return "n";
+            }
+        }
+        "#,
+    );
+    let Item::Decl {
+        decl: Decl::Fn(f), ..
+    } = &module.items[0]
+    else {
+        panic!("expected function");
+    };
+    let body = f.function.body.as_ref().unwrap();
+    let StmtKind::Match { arms, .. } = &body[0].kind else {
+        panic!("expected match, got {:?}", body[0].kind);
+    };
+    let binding = |variant: &str| MatchPat::Newtype {
+        union: "StringOrNumber".to_string(),
+        variant: variant.to_string(),
+        binding: "x".to_string(),
+    };
+    assert_eq!(arms[0].pats, vec![binding("String")]);
+    assert_eq!(arms[1].pats, vec![binding("Number")]);
+    let StmtKind::Return(Some(ret)) = &arms[0].body[0].kind else {
+        panic!("expected return");
+    };
+    assert_eq!(ret.ty, Type::String);
+}
+
+#[test]
+fn test_guards_for_types_outside_the_union_are_left_as_ifs() {
+    let module = check(
+        r#"
+        class User { name: string = ""; }
+        class Admin { level: number = 0; }
+        function f(p: string | number): number {
+            if (typeof p === "boolean") {
+                return 1;
+            }
+            return 0;
+        }
+        function g(p: User | Admin): number {
+            if (p instanceof Date) {
+                return 1;
+            } else {
+                return 0;
+            }
+        }
+        "#,
+    );
+    for item in &module.items[2..] {
+        let Item::Decl {
+            decl: Decl::Fn(f), ..
+        } = item
+        else {
+            panic!("expected function");
+        };
+        let body = f.function.body.as_ref().unwrap();
+        assert!(
+            matches!(body[0].kind, StmtKind::If { .. }),
+            "expected if, got {:?}",
+            body[0].kind
+        );
+    }
+}
+
+#[test]
+fn test_negated_tag_test_narrows_the_rest_of_the_block() {
+    let module = check(
+        r#"
+        type Shape = { kind: "circle"; r: number } | { kind: "square"; side: number };
+        function side(s: Shape): number {
+            if (s.kind !== "square") {
+                return 0;
+            }
+            const side = s.side;
+            return side;
+        }
+        "#,
+    );
+    let Item::Decl {
+        decl: Decl::Fn(f), ..
+    } = &module.items[1]
+    else {
+        panic!("expected function");
+    };
+    let body = f.function.body.as_ref().unwrap();
+    assert_eq!(body.len(), 1);
+    let StmtKind::Match { arms, .. } = &body[0].kind else {
+        panic!("expected match, got {:?}", body[0].kind);
+    };
+    let variant = |tag: &str, fields: &[&str]| MatchPat::Variant {
+        union: "Shape".to_string(),
+        tag: tag.to_string(),
+        fields: fields.iter().map(|f| f.to_string()).collect(),
+    };
+    assert_eq!(arms[0].pats, vec![variant("circle", &[])]);
+    assert_eq!(arms[1].pats, vec![variant("square", &["side"])]);
+    assert_eq!(arms[1].body.len(), 2);
+}
+
+#[test]
+fn test_statements_after_an_exhaustive_match_are_dropped() {
+    let module = check(
+        r#"
+        type Shape = { kind: "circle"; r: number } | { kind: "square"; side: number };
+        function size(s: Shape): number {
+            if (s.kind === "circle") {
+                return s.r;
+            } else if (s.kind === "square") {
+                return s.side;
+            }
+            return 0;
+        }
+        "#,
+    );
+    let Item::Decl {
+        decl: Decl::Fn(f), ..
+    } = &module.items[1]
+    else {
+        panic!("expected function");
+    };
+    let body = f.function.body.as_ref().unwrap();
+    assert_eq!(body.len(), 1);
+    assert!(
+        matches!(body[0].kind, StmtKind::Match { .. }),
+        "expected match, got {:?}",
+        body[0].kind
+    );
+}
+
+#[test]
+fn test_tag_tests_on_several_unions_are_unsupported() {
+    let module = check(
+        r#"
+        type Shape = { kind: "circle"; r: number } | { kind: "square"; side: number };
+        function same(a: Shape, b: Shape): boolean {
+            if (a.kind === "circle" && b.kind === "circle") {
+                return a.r === b.r;
+            }
+            return false;
+        }
+        "#,
+    );
+    let Item::Decl {
+        decl: Decl::Fn(f), ..
+    } = &module.items[1]
+    else {
+        panic!("expected function");
+    };
+    let body = f.function.body.as_ref().unwrap();
+    assert!(
+        matches!(body[0].kind, StmtKind::Unsupported { .. }),
+        "expected unsupported, got {:?}",
+        body[0].kind
+    );
+}
+
+#[test]
+fn test_values_record_the_wider_type_they_flow_into() {
+    let module = check(
+        r#"
+        interface Store { get(key: string): string; }
+        class Mem implements Store { get(key: string): string { return key; } }
+        const store: Store = new Mem();
+        const stores: Store[] = [new Mem(), (new Mem())];
+        const mem = new Mem();
+        "#,
+    );
+    let inits: Vec<&Expr> = module
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Stmt(stmt) => match &stmt.kind {
+                StmtKind::Var(var) => var.decls[0].init.as_ref(),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(inits[0].coerce, Some(Box::new(Type::named("Store"))));
+    let tyrus_ast::ExprKind::Array(elems) = &inits[1].kind else {
+        panic!("expected array");
+    };
+    // Arrays convert their elements, parentheses what they wrap
+    assert_eq!(inits[1].coerce, None);
+    assert_eq!(
+        elems[0].as_ref().unwrap().expr.coerce,
+        Some(Box::new(Type::named("Store")))
+    );
+    let tyrus_ast::ExprKind::Paren(inner) = &elems[1].as_ref().unwrap().expr.kind else {
+        panic!("expected parentheses");
+    };
+    assert_eq!(inner.coerce, Some(Box::new(Type::named("Store"))));
+    assert_eq!(inits[2].coerce, None);
+}
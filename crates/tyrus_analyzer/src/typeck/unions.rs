@@ -0,0 +1,196 @@
+//! Unions that Tyrus emits as Rust enums: discriminated (tagged) and untagged ones.
+
+use tyrus_ast::{LitType, PropSig, Type};
+
+/// A union such as `{ kind: "circle"; r: number } | { kind: "square"; side: number }`,
+/// emitted as a Rust enum with one struct variant per member.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaggedUnion {
+    /// The discriminant property (`kind` above).
+    pub tag: String,
+    pub variants: Vec<TaggedVariant>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaggedVariant {
+    /// Value of the discriminant for this member (`"circle"` above).
+    pub tag_value: String,
+    /// Properties of the member, without the discriminant.
+    pub fields: Vec<PropSig>,
+}
+
+impl TaggedUnion {
+    pub fn variant(&self, tag_value: &str) -> Option<&TaggedVariant> {
+        self.variants.iter().find(|v| v.tag_value == tag_value)
+    }
+}
+
+/// Recognizes a discriminated union. `ty` must already have its aliases
+/// expanded; only inline object members are supported.
+///
+/// The tag is the first property of the first member that every member
+/// declares as a distinct, required string literal.
+pub fn tagged_union(ty: &Type) -> Option<TaggedUnion> {
+    let Type::Union(members) = ty else {
+        return None;
+    };
+    let members: Vec<&[PropSig]> = members
+        .iter()
+        .map(|member| match member {
+            Type::Object(props) => Some(props.as_slice()),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+
+    members.first()?.iter().find_map(|candidate| {
+        let values: Vec<String> = members
+            .iter()
+            .map(|props| literal_prop(props, &candidate.name))
+            .collect::<Option<_>>()?;
+        let distinct = values
+            .iter()
+            .enumerate()
+            .all(|(i, value)| !values[..i].contains(value));
+        if !distinct {
+            return None;
+        }
+        let variants = members
+            .iter()
+            .zip(values)
+            .map(|(props, tag_value)| TaggedVariant {
+                tag_value,
+                fields: props
+                    .iter()
+                    .filter(|p| p.name != candidate.name)
+                    .cloned()
+                    .collect(),
+            })
+            .collect();
+        Some(TaggedUnion {
+            tag: candidate.name.clone(),
+            variants,
+        })
+    })
+}
+
+fn literal_prop(props: &[PropSig], name: &str) -> Option<String> {
+    props.iter().find_map(|p| match &p.ty {
+        Type::Literal(LitType::Str(value)) if p.name == name && !p.optional => Some(value.clone()),
+        _ => None,
+    })
+}
+
+/// A union of unrelated types such as `string | number` or `User | Admin`,
+/// emitted as an `#[serde(untagged)]` enum with one newtype variant per member.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UntaggedUnion {
+    pub variants: Vec<UntaggedVariant>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UntaggedVariant {
+    /// Rust variant name, derived from the member type (`String`, `User`, `NumberList`).
+    pub name: String,
+    pub ty: Type,
+}
+
+impl UntaggedUnion {
+    /// Name of the enum generated for a union written inline rather than
+    /// through an alias, e.g. `StringOrNumber`.
+    pub fn synthesized_name(&self) -> String {
+        self.variants
+            .iter()
+            .map(|v| v.name.as_str())
+            .collect::<Vec<_>>()
+            .join("Or")
+    }
+
+    pub fn variant(&self, name: &str) -> Option<&UntaggedVariant> {
+        self.variants.iter().find(|v| v.name == name)
+    }
+
+    /// The variant selected by `typeof x === tag`.
+    pub fn typeof_variant(&self, tag: &str) -> Option<&UntaggedVariant> {
+        let ty = match tag {
+            "string" => Type::String,
+            "number" => Type::Number,
+            "boolean" => Type::Bool,
+            _ => return None,
+        };
+        self.variants.iter().find(|v| v.ty == ty)
+    }
+
+    /// The variant selected by `x instanceof class`.
+    pub fn instanceof_variant(&self, class: &str) -> Option<&UntaggedVariant> {
+        self.variants
+            .iter()
+            .find(|v| v.ty.as_named() == Some(class))
+    }
+
+    /// The variant a value of type `ty` is wrapped in where the union is
+    /// expected.
+    pub fn variant_of(&self, ty: &Type) -> Option<&UntaggedVariant> {
+        let ty = base_type(ty);
+        self.variants.iter().find(|v| v.ty == ty)
+    }
+
+    /// The named types the variants hold, e.g. `User` in `User | string[]`.
+    pub fn named_types(&self) -> impl Iterator<Item = &str> {
+        self.variants.iter().filter_map(|v| {
+            let mut ty = &v.ty;
+            while let Type::Array(inner) = ty {
+                ty = inner;
+            }
+            ty.as_named()
+        })
+    }
+}
+
+/// `ty`, with a literal type collapsed into its base type.
+fn base_type(ty: &Type) -> Type {
+    match ty {
+        Type::Literal(LitType::Str(_)) => Type::String,
+        Type::Literal(LitType::Num(_)) => Type::Number,
+        Type::Literal(LitType::Bool(_)) => Type::Bool,
+        other => other.clone(),
+    }
+}
+
+/// Recognizes a union that is neither a string-literal union nor a
+/// discriminated union. `ty` must already have its aliases expanded.
+///
+/// Literal members collapse into their base type, so `"auto" | number`
+/// becomes `String | Number`. Members Tyrus cannot name (inline objects,
+/// functions, ...) make the whole union unsupported.
+pub fn untagged_union(ty: &Type) -> Option<UntaggedUnion> {
+    let Type::Union(members) = ty else {
+        return None;
+    };
+    let all_str_literals = members
+        .iter()
+        .all(|m| matches!(m, Type::Literal(LitType::Str(_))));
+    if all_str_literals || tagged_union(ty).is_some() {
+        return None;
+    }
+    let mut variants: Vec<UntaggedVariant> = Vec::new();
+    for member in members {
+        let ty = base_type(member);
+        let name = variant_name(&ty)?;
+        if !variants.iter().any(|v| v.name == name) {
+            variants.push(UntaggedVariant { name, ty });
+        }
+    }
+    (variants.len() > 1).then_some(UntaggedUnion { variants })
+}
+
+fn variant_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::String => Some("String".to_string()),
+        Type::Number => Some("Number".to_string()),
+        Type::Bool => Some("Bool".to_string()),
+        Type::Date => Some("Date".to_string()),
+        Type::Array(inner) => Some(format!("{}List", variant_name(inner)?)),
+        Type::Named { name, args } if args.is_empty() => Some(name.clone()),
+        _ => None,
+    }
+}
@@ -0,0 +1,354 @@
+use swc_common::Span;
+
+use crate::expr::{Expr, Pat};
+use crate::stmt::{Stmt, VarDecl};
+use crate::types::Type;
+
+/// A lowered source file.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Module {
+    pub items: Vec<Item>,
+}
+
+// Items are few per module; boxing every declaration is not worth the noise.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    Import(Import),
+    Decl {
+        decl: Decl,
+        exported: bool,
+    },
+    /// Top-level script statements (everything that is not a declaration).
+    Stmt(Stmt),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Decl {
+    Fn(FnDecl),
+    Class(ClassDecl),
+    Interface(InterfaceDecl),
+    TypeAlias(TypeAliasDecl),
+    Enum(EnumDecl),
+    Var(VarDecl),
+}
+
+impl Decl {
+    /// The documentation of the declaration, which a `var` cannot have.
+    pub fn doc_mut(&mut self) -> Option<&mut Option<Doc>> {
+        match self {
+            Decl::Fn(f) => Some(&mut f.doc),
+            Decl::Class(c) => Some(&mut c.doc),
+            Decl::Interface(i) => Some(&mut i.doc),
+            Decl::TypeAlias(a) => Some(&mut a.doc),
+            Decl::Enum(e) => Some(&mut e.doc),
+            Decl::Var(_) => None,
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Decl::Fn(f) => f.span,
+            Decl::Class(c) => c.span,
+            Decl::Interface(i) => i.span,
+            Decl::TypeAlias(a) => a.span,
+            Decl::Enum(e) => e.span,
+            Decl::Var(v) => v.span,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import {
+    pub src: String,
+    pub specifiers: Vec<ImportSpecifier>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportSpecifier {
+    /// `import { imported as local }`; `imported` is `None` when not renamed.
+    Named {
+        local: String,
+        imported: Option<String>,
+    },
+    Default {
+        local: String,
+    },
+    Namespace {
+        local: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeParam {
+    pub name: String,
+    pub constraint: Option<Type>,
+}
+
+/// A decorator such as `@Controller('cats')` or `@Injectable()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decorator {
+    pub name: String,
+    pub args: Vec<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub pat: Pat,
+    pub decorators: Vec<Decorator>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub params: Vec<Param>,
+    pub return_type: Option<Type>,
+    pub type_params: Vec<TypeParam>,
+    /// `None` for overload signatures and abstract methods.
+    pub body: Option<Vec<Stmt>>,
+    pub is_async: bool,
+    pub decorators: Vec<Decorator>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FnDecl {
+    pub name: String,
+    pub function: Function,
+    pub doc: Option<Doc>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Accessibility {
+    Public,
+    Protected,
+    Private,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassDecl {
+    pub name: String,
+    pub type_params: Vec<TypeParam>,
+    pub decorators: Vec<Decorator>,
+    pub super_class: Option<Type>,
+    pub implements: Vec<Type>,
+    pub is_abstract: bool,
+    pub members: Vec<ClassMember>,
+    pub doc: Option<Doc>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClassMember {
+    Prop(ClassProp),
+    Method(ClassMethod),
+    Constructor(Constructor),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassProp {
+    pub name: String,
+    pub ty: Option<Type>,
+    pub value: Option<Expr>,
+    pub optional: bool,
+    pub is_static: bool,
+    pub readonly: bool,
+    pub accessibility: Option<Accessibility>,
+    /// ES private field (`#name`).
+    pub is_private_name: bool,
+    pub doc: Option<Doc>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MethodKind {
+    Method,
+    Getter,
+    Setter,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassMethod {
+    pub name: String,
+    pub kind: MethodKind,
+    pub function: Function,
+    pub is_static: bool,
+    pub is_abstract: bool,
+    pub accessibility: Option<Accessibility>,
+    /// ES private method (`#name()`).
+    pub is_private_name: bool,
+    /// Whether the body mutates `this`; filled in by ownership inference.
+    pub mutates_this: bool,
+    pub doc: Option<Doc>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constructor {
+    pub params: Vec<CtorParam>,
+    pub body: Option<Vec<Stmt>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CtorParam {
+    /// A parameter property: `constructor(private service: Service)`.
+    Prop {
+        name: String,
+        ty: Option<Type>,
+        accessibility: Option<Accessibility>,
+        readonly: bool,
+    },
+    Param(Param),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceDecl {
+    pub name: String,
+    pub type_params: Vec<TypeParam>,
+    pub extends: Vec<Type>,
+    pub members: Vec<InterfaceMember>,
+    pub doc: Option<Doc>,
+    pub span: Span,
+}
+
+impl InterfaceDecl {
+    /// Interfaces with method signatures describe behaviour rather than data.
+    pub fn has_methods(&self) -> bool {
+        self.members
+            .iter()
+            .any(|m| matches!(m, InterfaceMember::Method { .. }))
+    }
+
+    /// Whether one of `classes` implements the interface and mutates `this`
+    /// in one of its methods, which then can't be called through a shared
+    /// reference. Mutation is only known once ownership has been inferred.
+    pub fn is_mutated_by<'a>(&self, classes: impl IntoIterator<Item = &'a ClassDecl>) -> bool {
+        classes
+            .into_iter()
+            .filter(|class| {
+                class
+                    .implements
+                    .iter()
+                    .any(|ty| ty.as_named() == Some(self.name.as_str()))
+            })
+            .flat_map(ClassDecl::methods)
+            .filter(|method| method.mutates_this)
+            .any(|method| {
+                self.members.iter().any(
+                    |m| matches!(m, InterfaceMember::Method { name, .. } if *name == method.name),
+                )
+            })
+    }
+
+    /// Whether the interface declares an `async` method, one returning a
+    /// `Promise`.
+    pub fn has_async_methods(&self) -> bool {
+        self.members.iter().any(|m| {
+            matches!(
+                m,
+                InterfaceMember::Method {
+                    return_type: Some(Type::Promise(_)),
+                    ..
+                }
+            )
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterfaceMember {
+    Property {
+        name: String,
+        ty: Option<Type>,
+        optional: bool,
+        readonly: bool,
+        doc: Option<Doc>,
+    },
+    Method {
+        name: String,
+        params: Vec<Param>,
+        return_type: Option<Type>,
+        optional: bool,
+        doc: Option<Doc>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAliasDecl {
+    pub name: String,
+    pub type_params: Vec<TypeParam>,
+    pub ty: Type,
+    pub doc: Option<Doc>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumDecl {
+    pub name: String,
+    pub members: Vec<EnumMember>,
+    pub doc: Option<Doc>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumMember {
+    pub name: String,
+    pub init: Option<Expr>,
+    pub doc: Option<Doc>,
+}
+
+/// A JSDoc block (`/** ... */`): its description, and the tags Rust has a
+/// counterpart for.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Doc {
+    /// The description and any other tags, line by line.
+    pub lines: Vec<String>,
+    /// `@param name text`, in order.
+    pub params: Vec<(String, String)>,
+    /// `@returns text`.
+    pub returns: Option<String>,
+    /// `@deprecated text`; empty for a bare `@deprecated`.
+    pub deprecated: Option<String>,
+}
+
+impl Decorator {
+    /// The first argument when it is a string literal, e.g. the route in `@Get('/health')`.
+    pub fn str_arg(&self) -> Option<&str> {
+        match &self.args.first()?.kind {
+            crate::expr::ExprKind::Lit(crate::expr::Lit::Str(s)) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl Param {
+    pub fn has_decorator(&self, name: &str) -> bool {
+        self.decorators.iter().any(|d| d.name == name)
+    }
+}
+
+impl ClassDecl {
+    pub fn decorator(&self, name: &str) -> Option<&Decorator> {
+        self.decorators.iter().find(|d| d.name == name)
+    }
+
+    pub fn constructor(&self) -> Option<&Constructor> {
+        self.members.iter().find_map(|m| match m {
+            ClassMember::Constructor(c) => Some(c),
+            _ => None,
+        })
+    }
+
+    pub fn methods(&self) -> impl Iterator<Item = &ClassMethod> {
+        self.members.iter().filter_map(|m| match m {
+            ClassMember::Method(method) => Some(method),
+            _ => None,
+        })
+    }
+
+    /// Name of the class this one `extends`, if any.
+    pub fn super_class_name(&self) -> Option<&str> {
+        self.super_class.as_ref().and_then(Type::as_named)
+    }
+}
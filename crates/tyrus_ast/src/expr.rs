@@ -0,0 +1,373 @@
+use swc_common::Span;
+
+use crate::stmt::Stmt;
+use crate::types::Type;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+    /// Filled in by the type checker; `Type::Unknown` until then.
+    pub ty: Type,
+    /// Filled in by ownership inference; `Usage::Unknown` until then.
+    pub usage: Usage,
+    /// Rust type the code for this `number` expression evaluates to; filled
+    /// in by integer inference.
+    pub num: NumRepr,
+    /// Rust type the value is converted to where it is used, when that
+    /// differs from `num`.
+    pub num_cast: Option<NumRepr>,
+    /// Type of the place the value flows into, when that is wider than `ty`,
+    /// like an interface a class instance is passed as or a union one of its
+    /// members is; filled in by the type checker.
+    pub coerce: Option<Box<Type>>,
+}
+
+/// How an expression's value is used where it appears.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Usage {
+    /// Not analyzed; codegen keeps its conservative defaults.
+    #[default]
+    Unknown,
+    /// Consumed, and not used again afterwards (or a `Copy` value).
+    Move,
+    /// Consumed, but used again later, so it needs its own copy.
+    Clone,
+    /// Only read through a shared reference.
+    Borrow,
+    /// Mutated in place through a `&mut` reference.
+    BorrowMut,
+}
+
+/// The Rust type a TypeScript `number` is represented as.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NumRepr {
+    #[default]
+    F64,
+    /// Only ever holds integers.
+    I64,
+    /// Only ever holds non-negative integers and is used as an index.
+    Usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind {
+    Ident(String),
+    This,
+    /// `super` as the object of `super.method()`; `super(...)` calls use [`Callee::Super`].
+    Super,
+    Lit(Lit),
+    /// Template literal. `quasis` holds the raw text chunks, interleaved with `exprs`.
+    Tpl {
+        quasis: Vec<String>,
+        exprs: Vec<Expr>,
+    },
+    Array(Vec<Option<Arg>>),
+    Object(Vec<Prop>),
+    Unary {
+        op: UnaryOp,
+        arg: Box<Expr>,
+    },
+    Update {
+        op: UpdateOp,
+        prefix: bool,
+        arg: Box<Expr>,
+    },
+    Bin {
+        op: BinaryOp,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Assign {
+        op: AssignOp,
+        target: AssignTarget,
+        right: Box<Expr>,
+    },
+    Member(MemberExpr),
+    Cond {
+        test: Box<Expr>,
+        cons: Box<Expr>,
+        alt: Box<Expr>,
+    },
+    Call(CallExpr),
+    New(CallExpr),
+    Seq(Vec<Expr>),
+    Paren(Box<Expr>),
+    Arrow(ArrowExpr),
+    Await(Box<Expr>),
+    /// `obj?.prop`
+    OptMember(MemberExpr),
+    /// `f?.(args)`
+    OptCall(CallExpr),
+    /// Anything lowering could not map; `what` names the construct for diagnostics.
+    Unsupported {
+        what: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Lit {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    Null,
+    /// Regex and BigInt literals.
+    Unsupported,
+}
+
+/// A call or array argument, possibly spread (`...xs`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arg {
+    pub expr: Expr,
+    pub spread: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Prop {
+    KeyValue {
+        key: PropKey,
+        value: Expr,
+    },
+    Shorthand(String),
+    /// Spreads, methods, getters and setters in object literals.
+    Unsupported,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropKey {
+    Ident(String),
+    Str(String),
+    Num(String),
+    BigInt(String),
+    Computed(Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberExpr {
+    pub obj: Box<Expr>,
+    pub prop: MemberProp,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MemberProp {
+    Ident(String),
+    Computed(Box<Expr>),
+    /// `#name`
+    Private(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallExpr {
+    pub callee: Callee,
+    pub args: Vec<Arg>,
+    pub type_args: Vec<Type>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Callee {
+    Expr(Box<Expr>),
+    Super,
+    Import,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssignTarget {
+    Ident(String),
+    Member(MemberExpr),
+    /// Destructuring assignments and other targets Tyrus does not lower.
+    Unsupported,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrowExpr {
+    pub params: Vec<Pat>,
+    pub body: ArrowBody,
+    pub is_async: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArrowBody {
+    Block(Vec<Stmt>),
+    Expr(Box<Expr>),
+}
+
+/// A binding pattern, as found in declarations, parameters and loop heads.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pat {
+    Ident(Binding),
+    Object(Vec<ObjectPatProp>),
+    Array(Vec<Option<Pat>>),
+    /// Rest patterns, default-value patterns and anything else not lowered.
+    Unsupported,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub name: String,
+    /// The annotated type, if the source had one.
+    pub ty: Option<Type>,
+    /// Whether the binding is reassigned or mutated in place; filled in by
+    /// mutability inference.
+    pub mutable: bool,
+    /// Filled in by integer inference for `number` variables.
+    pub num: NumRepr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectPatProp {
+    /// `{ key: value }`
+    KeyValue {
+        key: PropKey,
+        value: Pat,
+    },
+    /// `{ key }` or `{ key = default }`
+    Assign {
+        key: String,
+        default: Option<Expr>,
+    },
+    Rest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Not,
+    Minus,
+    Plus,
+    TypeOf,
+    Tilde,
+    Void,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateOp {
+    Inc,
+    Dec,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Exp,
+    EqEq,
+    NotEq,
+    EqEqEq,
+    NotEqEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    LogicalAnd,
+    LogicalOr,
+    NullishCoalescing,
+    BitAnd,
+    BitOr,
+    BitXor,
+    LShift,
+    RShift,
+    ZeroFillRShift,
+    In,
+    InstanceOf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignOp {
+    Assign,
+    AddAssign,
+    SubAssign,
+    MulAssign,
+    DivAssign,
+    ModAssign,
+    /// Logical, bitwise and exponent compound assignments.
+    Other,
+}
+
+impl Expr {
+    pub fn new(kind: ExprKind, span: Span) -> Self {
+        Self {
+            kind,
+            span,
+            ty: Type::Unknown,
+            usage: Usage::Unknown,
+            num: NumRepr::F64,
+            num_cast: None,
+            coerce: None,
+        }
+    }
+
+    /// Strips any number of wrapping parentheses.
+    pub fn unparen(&self) -> &Expr {
+        let mut expr = self;
+        while let ExprKind::Paren(inner) = &expr.kind {
+            expr = inner;
+        }
+        expr
+    }
+
+    pub fn as_ident(&self) -> Option<&str> {
+        match &self.kind {
+            ExprKind::Ident(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    pub fn is_this(&self) -> bool {
+        matches!(self.kind, ExprKind::This)
+    }
+
+    /// For `obj.method(...)` calls, returns `(obj, "method")`.
+    pub fn as_method_call(&self) -> Option<(&Expr, &str, &CallExpr)> {
+        match &self.kind {
+            ExprKind::Call(call) => {
+                let member = call.callee.as_member()?;
+                let method = member.prop.as_ident()?;
+                Some((&member.obj, method, call))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Callee {
+    pub fn as_expr(&self) -> Option<&Expr> {
+        match self {
+            Callee::Expr(expr) => Some(expr),
+            _ => None,
+        }
+    }
+
+    pub fn as_member(&self) -> Option<&MemberExpr> {
+        match &self.as_expr()?.kind {
+            ExprKind::Member(member) => Some(member),
+            _ => None,
+        }
+    }
+
+    /// For `Foo.bar(...)` returns `("Foo", "bar")`.
+    pub fn as_static_call(&self) -> Option<(&str, &str)> {
+        let member = self.as_member()?;
+        Some((member.obj.as_ident()?, member.prop.as_ident()?))
+    }
+}
+
+impl MemberProp {
+    pub fn as_ident(&self) -> Option<&str> {
+        match self {
+            MemberProp::Ident(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+impl Pat {
+    pub fn as_ident(&self) -> Option<&Binding> {
+        match self {
+            Pat::Ident(binding) => Some(binding),
+            _ => None,
+        }
+    }
+}
@@ -1,9 +1,19 @@
-pub struct OxInterface {
-    pub name: String,
-    pub methods: Vec<OxFunction>,
-}
+//! Typed intermediate representation between the SWC AST and Rust codegen.
+//!
+//! The parser produces an SWC [`swc_ecma_ast::Program`]; [`lower::lower_program`]
+//! turns it into a [`Module`] whose types are resolved into [`Type`] once, so
+//! later stages (analyses, codegen) never have to inspect TypeScript syntax.
 
-pub struct OxFunction {
-    pub name: String,
-    pub args: Vec<String>,
-}
+pub mod decl;
+pub mod expr;
+pub mod lower;
+pub mod stmt;
+pub mod types;
+pub mod visit;
+
+pub use decl::*;
+pub use expr::*;
+pub use lower::{lower_program, lower_program_with_comments};
+pub use stmt::*;
+pub use swc_common::Span;
+pub use types::*;
@@ -0,0 +1,168 @@
+//! The comments lowering keeps: JSDoc blocks as [`Doc`]s on declarations,
+//! and the other comments above statements.
+
+use std::collections::HashMap;
+
+use swc_common::comments::{Comment, CommentKind, SingleThreadedComments};
+use swc_common::BytePos;
+
+use crate::decl::Doc;
+
+/// The JSDoc block closest above each position.
+pub(super) fn docs(comments: &SingleThreadedComments) -> HashMap<BytePos, Doc> {
+    let (leading, _) = comments.borrow_all();
+    leading
+        .iter()
+        .filter_map(|(pos, comments)| {
+            let doc = comments.iter().rev().find(|c| is_jsdoc(c))?;
+            Some((*pos, parse_doc(&doc.text[1..])))
+        })
+        .collect()
+}
+
+/// The comments above each position, line by line, without `// tyrus-...`
+/// directives.
+pub(super) fn stmt_comments(comments: &SingleThreadedComments) -> HashMap<BytePos, Vec<String>> {
+    let (leading, _) = comments.borrow_all();
+    leading
+        .iter()
+        .filter_map(|(pos, comments)| {
+            let lines: Vec<String> = comments
+                .iter()
+                .filter(|c| !c.text.trim().starts_with("tyrus-"))
+                .flat_map(comment_lines)
+                .collect();
+            (!lines.is_empty()).then_some((*pos, lines))
+        })
+        .collect()
+}
+
+fn is_jsdoc(comment: &Comment) -> bool {
+    comment.kind == CommentKind::Block && comment.text.starts_with('*')
+}
+
+/// The lines of a comment, without the `*` that block comments start their
+/// lines with.
+fn comment_lines(comment: &Comment) -> Vec<String> {
+    match comment.kind {
+        CommentKind::Line => vec![strip_space(comment.text.trim_end()).to_string()],
+        CommentKind::Block => block_lines(comment.text.trim_start_matches('*')),
+    }
+}
+
+fn block_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = text
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            strip_space(line.strip_prefix('*').unwrap_or(line).trim_end()).to_string()
+        })
+        .collect();
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    let blank = lines.iter().take_while(|line| line.is_empty()).count();
+    lines.drain(..blank);
+    lines
+}
+
+fn strip_space(text: &str) -> &str {
+    text.strip_prefix(' ').unwrap_or(text)
+}
+
+/// The tag a continuation line of a JSDoc block belongs to.
+enum Tag {
+    Description,
+    Param,
+    Returns,
+    Deprecated,
+}
+
+/// Parses the text of a `/** ... */` block, after its first `*`.
+pub(crate) fn parse_doc(text: &str) -> Doc {
+    let mut doc = Doc::default();
+    let mut tag = Tag::Description;
+    for line in block_lines(text) {
+        if let Some(rest) = line.strip_prefix('@') {
+            let (name, rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let rest = rest.trim();
+            tag = match name {
+                "param" | "arg" | "argument" => {
+                    let (param, text) = split_param(strip_type(rest));
+                    doc.params.push((param.to_string(), text.to_string()));
+                    Tag::Param
+                }
+                "returns" | "return" => {
+                    doc.returns = Some(strip_type(rest).to_string());
+                    Tag::Returns
+                }
+                "deprecated" => {
+                    doc.deprecated = Some(rest.to_string());
+                    Tag::Deprecated
+                }
+                _ => {
+                    doc.lines.push(line);
+                    Tag::Description
+                }
+            };
+            continue;
+        }
+        let continued = match tag {
+            Tag::Description => {
+                doc.lines.push(line);
+                continue;
+            }
+            _ if line.is_empty() => continue,
+            Tag::Param => doc.params.last_mut().map(|(_, text)| text),
+            Tag::Returns => doc.returns.as_mut(),
+            Tag::Deprecated => doc.deprecated.as_mut(),
+        };
+        if let Some(text) = continued {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(line.trim());
+        }
+    }
+    while doc.lines.last().is_some_and(String::is_empty) {
+        doc.lines.pop();
+    }
+    doc
+}
+
+/// `text` without the `{type}` JSDoc may start a tag with.
+fn strip_type(text: &str) -> &str {
+    if !text.starts_with('{') {
+        return text;
+    }
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return text[i + 1..].trim_start();
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+/// The name and description of `@param name - text`, where the name may be
+/// `[name]` or `[name=default]` for optional parameters.
+fn split_param(text: &str) -> (&str, &str) {
+    let (name, rest) = if let Some(optional) = text.strip_prefix('[') {
+        let end = optional.find(']').unwrap_or(optional.len());
+        let name = &optional[..end];
+        let name = name.split_once('=').map_or(name, |(name, _)| name);
+        (name, optional.get(end + 1..).unwrap_or(""))
+    } else {
+        text.split_once(char::is_whitespace).unwrap_or((text, ""))
+    };
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix("- ").unwrap_or(rest);
+    (name.trim(), rest.trim())
+}
@@ -0,0 +1,324 @@
+use swc_common::{BytePos, Span};
+use swc_ecma_ast as swc;
+
+use super::{str_value, Lowerer};
+use crate::decl::{
+    Accessibility, ClassDecl, ClassMember, ClassMethod, ClassProp, Constructor, CtorParam,
+    Decorator, EnumDecl, EnumMember, FnDecl, Function, InterfaceDecl, InterfaceMember, MethodKind,
+    Param, TypeAliasDecl, TypeParam,
+};
+use crate::expr::{Binding, NumRepr, Pat};
+use crate::types::Type;
+
+impl Lowerer {
+    pub(crate) fn lower_fn(&mut self, ident: &swc::Ident, function: &swc::Function) -> FnDecl {
+        FnDecl {
+            name: ident.sym.to_string(),
+            function: self.lower_function(function),
+            doc: self.doc([function.span.lo]),
+            span: function.span,
+        }
+    }
+
+    pub(crate) fn lower_class(&mut self, ident: &swc::Ident, class: &swc::Class) -> ClassDecl {
+        self.with_type_params(class.type_params.as_deref(), |this| ClassDecl {
+            name: ident.sym.to_string(),
+            type_params: this.lower_type_params(class.type_params.as_deref()),
+            decorators: lower_decorators(this, &class.decorators),
+            super_class: class.super_class.as_ref().map(|super_class| {
+                this.lower_heritage(super_class, class.super_type_params.as_deref())
+            }),
+            implements: class
+                .implements
+                .iter()
+                .map(|i| this.lower_heritage(&i.expr, i.type_args.as_deref()))
+                .collect(),
+            is_abstract: class.is_abstract,
+            members: this.with_private_names(class, |this| {
+                class
+                    .body
+                    .iter()
+                    .filter_map(|member| this.lower_class_member(member))
+                    .collect()
+            }),
+            doc: this.doc(decorated(class.span, &class.decorators)),
+            span: class.span,
+        })
+    }
+
+    pub(crate) fn lower_interface(&mut self, iface: &swc::TsInterfaceDecl) -> InterfaceDecl {
+        self.with_type_params(iface.type_params.as_deref(), |this| InterfaceDecl {
+            name: iface.id.sym.to_string(),
+            type_params: this.lower_type_params(iface.type_params.as_deref()),
+            extends: iface
+                .extends
+                .iter()
+                .map(|e| this.lower_heritage(&e.expr, e.type_args.as_deref()))
+                .collect(),
+            members: iface
+                .body
+                .body
+                .iter()
+                .filter_map(|member| this.lower_interface_member(member))
+                .collect(),
+            doc: this.doc([iface.span.lo]),
+            span: iface.span,
+        })
+    }
+
+    pub(crate) fn lower_type_alias(&mut self, alias: &swc::TsTypeAliasDecl) -> TypeAliasDecl {
+        self.with_type_params(alias.type_params.as_deref(), |this| TypeAliasDecl {
+            name: alias.id.sym.to_string(),
+            type_params: this.lower_type_params(alias.type_params.as_deref()),
+            ty: this.lower_type(&alias.type_ann),
+            doc: this.doc([alias.span.lo]),
+            span: alias.span,
+        })
+    }
+
+    pub(crate) fn lower_enum(&mut self, enum_decl: &swc::TsEnumDecl) -> EnumDecl {
+        EnumDecl {
+            name: enum_decl.id.sym.to_string(),
+            members: enum_decl
+                .members
+                .iter()
+                .map(|member| EnumMember {
+                    name: match &member.id {
+                        swc::TsEnumMemberId::Ident(ident) => ident.sym.to_string(),
+                        swc::TsEnumMemberId::Str(s) => str_value(s),
+                    },
+                    init: member.init.as_ref().map(|init| self.lower_expr(init)),
+                    doc: self.doc([member.span.lo]),
+                })
+                .collect(),
+            doc: self.doc([enum_decl.span.lo]),
+            span: enum_decl.span,
+        }
+    }
+
+    fn lower_function(&mut self, function: &swc::Function) -> Function {
+        self.with_type_params(function.type_params.as_deref(), |this| Function {
+            params: function
+                .params
+                .iter()
+                .map(|p| this.lower_param(p))
+                .collect(),
+            return_type: this.lower_type_ann(function.return_type.as_deref()),
+            type_params: this.lower_type_params(function.type_params.as_deref()),
+            body: function.body.as_ref().map(|body| this.lower_block(body)),
+            is_async: function.is_async,
+            decorators: lower_decorators(this, &function.decorators),
+        })
+    }
+
+    fn lower_param(&mut self, param: &swc::Param) -> Param {
+        Param {
+            pat: self.lower_pat(&param.pat),
+            decorators: lower_decorators(self, &param.decorators),
+        }
+    }
+
+    fn lower_type_params(&self, params: Option<&swc::TsTypeParamDecl>) -> Vec<TypeParam> {
+        params
+            .map(|params| {
+                params
+                    .params
+                    .iter()
+                    .map(|p| TypeParam {
+                        name: p.name.sym.to_string(),
+                        constraint: p.constraint.as_ref().map(|c| self.lower_type(c)),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Lowers the target of `extends`/`implements` clauses.
+    fn lower_heritage(
+        &self,
+        expr: &swc::Expr,
+        type_args: Option<&swc::TsTypeParamInstantiation>,
+    ) -> Type {
+        match expr {
+            swc::Expr::Ident(ident) => Type::Named {
+                name: ident.sym.to_string(),
+                args: self.lower_type_args(type_args),
+            },
+            _ => Type::Unknown,
+        }
+    }
+
+    fn lower_class_member(&mut self, member: &swc::ClassMember) -> Option<ClassMember> {
+        Some(match member {
+            swc::ClassMember::ClassProp(prop) => ClassMember::Prop(ClassProp {
+                name: prop.key.as_ident()?.sym.to_string(),
+                ty: self.lower_type_ann(prop.type_ann.as_deref()),
+                value: prop.value.as_ref().map(|v| self.lower_expr(v)),
+                optional: prop.is_optional,
+                is_static: prop.is_static,
+                readonly: prop.readonly,
+                accessibility: prop.accessibility.map(lower_accessibility),
+                is_private_name: false,
+                doc: self.doc(decorated(prop.span, &prop.decorators)),
+                span: prop.span,
+            }),
+            swc::ClassMember::PrivateProp(prop) => ClassMember::Prop(ClassProp {
+                name: self.private_name(&prop.key.name),
+                ty: self.lower_type_ann(prop.type_ann.as_deref()),
+                value: prop.value.as_ref().map(|v| self.lower_expr(v)),
+                optional: prop.is_optional,
+                is_static: prop.is_static,
+                readonly: prop.readonly,
+                accessibility: prop.accessibility.map(lower_accessibility),
+                is_private_name: true,
+                doc: self.doc(decorated(prop.span, &prop.decorators)),
+                span: prop.span,
+            }),
+            swc::ClassMember::Method(method) => ClassMember::Method(ClassMethod {
+                name: method.key.as_ident()?.sym.to_string(),
+                kind: lower_method_kind(method.kind),
+                function: self.lower_function(&method.function),
+                is_static: method.is_static,
+                is_abstract: method.is_abstract,
+                accessibility: method.accessibility.map(lower_accessibility),
+                is_private_name: false,
+                mutates_this: false,
+                doc: self.doc(decorated(method.span, &method.function.decorators)),
+                span: method.span,
+            }),
+            swc::ClassMember::PrivateMethod(method) => ClassMember::Method(ClassMethod {
+                name: self.private_name(&method.key.name),
+                kind: lower_method_kind(method.kind),
+                function: self.lower_function(&method.function),
+                is_static: method.is_static,
+                is_abstract: method.is_abstract,
+                accessibility: method.accessibility.map(lower_accessibility),
+                is_private_name: true,
+                mutates_this: false,
+                doc: self.doc(decorated(method.span, &method.function.decorators)),
+                span: method.span,
+            }),
+            swc::ClassMember::Constructor(ctor) => ClassMember::Constructor(Constructor {
+                params: ctor
+                    .params
+                    .iter()
+                    .filter_map(|param| self.lower_ctor_param(param))
+                    .collect(),
+                body: ctor.body.as_ref().map(|body| self.lower_block(body)),
+                span: ctor.span,
+            }),
+            _ => return None,
+        })
+    }
+
+    fn lower_ctor_param(&mut self, param: &swc::ParamOrTsParamProp) -> Option<CtorParam> {
+        match param {
+            swc::ParamOrTsParamProp::TsParamProp(prop) => match &prop.param {
+                swc::TsParamPropParam::Ident(ident) => Some(CtorParam::Prop {
+                    name: ident.id.sym.to_string(),
+                    ty: self.lower_type_ann(ident.type_ann.as_deref()),
+                    accessibility: prop.accessibility.map(lower_accessibility),
+                    readonly: prop.readonly,
+                }),
+                swc::TsParamPropParam::Assign(_) => None,
+            },
+            swc::ParamOrTsParamProp::Param(param) => {
+                Some(CtorParam::Param(self.lower_param(param)))
+            }
+        }
+    }
+
+    fn lower_interface_member(&mut self, member: &swc::TsTypeElement) -> Option<InterfaceMember> {
+        match member {
+            swc::TsTypeElement::TsPropertySignature(prop) => Some(InterfaceMember::Property {
+                name: prop.key.as_ident()?.sym.to_string(),
+                ty: self.lower_type_ann(prop.type_ann.as_deref()),
+                optional: prop.optional,
+                readonly: prop.readonly,
+                doc: self.doc([prop.span.lo]),
+            }),
+            swc::TsTypeElement::TsMethodSignature(method) => {
+                let name = method.key.as_ident()?.sym.to_string();
+                self.with_type_params(method.type_params.as_deref(), |this| {
+                    Some(InterfaceMember::Method {
+                        name,
+                        params: method
+                            .params
+                            .iter()
+                            .map(|p| this.lower_ts_fn_param(p))
+                            .collect(),
+                        return_type: this.lower_type_ann(method.type_ann.as_deref()),
+                        optional: method.optional,
+                        doc: this.doc([method.span.lo]),
+                    })
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn lower_ts_fn_param(&self, param: &swc::TsFnParam) -> Param {
+        let pat = match param {
+            swc::TsFnParam::Ident(ident) => Pat::Ident(Binding {
+                name: ident.id.sym.to_string(),
+                ty: self.lower_type_ann(ident.type_ann.as_deref()),
+                mutable: false,
+                num: NumRepr::F64,
+            }),
+            _ => Pat::Unsupported,
+        };
+        Param {
+            pat,
+            decorators: Vec::new(),
+        }
+    }
+}
+
+/// Decorators are kept by name; only `@Name` and `@Name(args)` forms are recognized.
+fn lower_decorators(lowerer: &mut Lowerer, decorators: &[swc::Decorator]) -> Vec<Decorator> {
+    decorators
+        .iter()
+        .filter_map(|decorator| match decorator.expr.as_ref() {
+            swc::Expr::Ident(ident) => Some(Decorator {
+                name: ident.sym.to_string(),
+                args: Vec::new(),
+            }),
+            swc::Expr::Call(call) => {
+                let swc::Callee::Expr(callee) = &call.callee else {
+                    return None;
+                };
+                let ident = callee.as_ident()?;
+                Some(Decorator {
+                    name: ident.sym.to_string(),
+                    args: call
+                        .args
+                        .iter()
+                        .map(|arg| lowerer.lower_expr(&arg.expr))
+                        .collect(),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn lower_accessibility(accessibility: swc::Accessibility) -> Accessibility {
+    match accessibility {
+        swc::Accessibility::Public => Accessibility::Public,
+        swc::Accessibility::Protected => Accessibility::Protected,
+        swc::Accessibility::Private => Accessibility::Private,
+    }
+}
+
+fn lower_method_kind(kind: swc::MethodKind) -> MethodKind {
+    match kind {
+        swc::MethodKind::Method => MethodKind::Method,
+        swc::MethodKind::Getter => MethodKind::Getter,
+        swc::MethodKind::Setter => MethodKind::Setter,
+    }
+}
+
+/// The positions a JSDoc block above a node with `decorators` can be at.
+fn decorated(span: Span, decorators: &[swc::Decorator]) -> impl Iterator<Item = BytePos> {
+    std::iter::once(span.lo).chain(decorators.first().map(|d| d.span.lo))
+}
@@ -0,0 +1,312 @@
+use swc_ecma_ast as swc;
+
+use super::{str_value, Lowerer};
+use crate::expr::{
+    Arg, ArrowBody, ArrowExpr, AssignOp, AssignTarget, BinaryOp, Binding, CallExpr, Callee, Expr,
+    ExprKind, Lit, MemberExpr, MemberProp, NumRepr, ObjectPatProp, Pat, Prop, PropKey, UnaryOp,
+    UpdateOp,
+};
+use crate::types::Type;
+
+impl Lowerer {
+    pub(crate) fn lower_expr(&mut self, expr: &swc::Expr) -> Expr {
+        let span = swc_common::Spanned::span(expr);
+        let kind = match expr {
+            swc::Expr::Ident(ident) => ExprKind::Ident(ident.sym.to_string()),
+            swc::Expr::This(_) => ExprKind::This,
+            swc::Expr::Lit(lit) => ExprKind::Lit(lower_lit(lit)),
+            swc::Expr::Tpl(tpl) => ExprKind::Tpl {
+                quasis: tpl.quasis.iter().map(|q| q.raw.to_string()).collect(),
+                exprs: tpl.exprs.iter().map(|e| self.lower_expr(e)).collect(),
+            },
+            swc::Expr::Array(array) => ExprKind::Array(
+                array
+                    .elems
+                    .iter()
+                    .map(|elem| elem.as_ref().map(|e| self.lower_arg(e)))
+                    .collect(),
+            ),
+            swc::Expr::Object(object) => ExprKind::Object(
+                object
+                    .props
+                    .iter()
+                    .map(|prop| self.lower_prop(prop))
+                    .collect(),
+            ),
+            swc::Expr::Unary(unary) => ExprKind::Unary {
+                op: lower_unary_op(unary.op),
+                arg: Box::new(self.lower_expr(&unary.arg)),
+            },
+            swc::Expr::Update(update) => ExprKind::Update {
+                op: match update.op {
+                    swc::UpdateOp::PlusPlus => UpdateOp::Inc,
+                    swc::UpdateOp::MinusMinus => UpdateOp::Dec,
+                },
+                prefix: update.prefix,
+                arg: Box::new(self.lower_expr(&update.arg)),
+            },
+            swc::Expr::Bin(bin) => ExprKind::Bin {
+                op: lower_binary_op(bin.op),
+                left: Box::new(self.lower_expr(&bin.left)),
+                right: Box::new(self.lower_expr(&bin.right)),
+            },
+            swc::Expr::Assign(assign) => ExprKind::Assign {
+                op: lower_assign_op(assign.op),
+                target: self.lower_assign_target(&assign.left),
+                right: Box::new(self.lower_expr(&assign.right)),
+            },
+            swc::Expr::Member(member) => ExprKind::Member(self.lower_member(member)),
+            swc::Expr::SuperProp(super_prop) => ExprKind::Member(MemberExpr {
+                obj: Box::new(Expr::new(ExprKind::Super, super_prop.obj.span)),
+                prop: match &super_prop.prop {
+                    swc::SuperProp::Ident(ident) => MemberProp::Ident(ident.sym.to_string()),
+                    swc::SuperProp::Computed(computed) => {
+                        MemberProp::Computed(Box::new(self.lower_expr(&computed.expr)))
+                    }
+                },
+            }),
+            swc::Expr::Cond(cond) => ExprKind::Cond {
+                test: Box::new(self.lower_expr(&cond.test)),
+                cons: Box::new(self.lower_expr(&cond.cons)),
+                alt: Box::new(self.lower_expr(&cond.alt)),
+            },
+            swc::Expr::Call(call) => ExprKind::Call(CallExpr {
+                callee: match &call.callee {
+                    swc::Callee::Expr(callee) => Callee::Expr(Box::new(self.lower_expr(callee))),
+                    swc::Callee::Super(_) => Callee::Super,
+                    swc::Callee::Import(_) => Callee::Import,
+                },
+                args: call.args.iter().map(|a| self.lower_arg(a)).collect(),
+                type_args: self.lower_type_args(call.type_args.as_deref()),
+            }),
+            swc::Expr::New(new) => ExprKind::New(CallExpr {
+                callee: Callee::Expr(Box::new(self.lower_expr(&new.callee))),
+                args: new
+                    .args
+                    .iter()
+                    .flatten()
+                    .map(|a| self.lower_arg(a))
+                    .collect(),
+                type_args: self.lower_type_args(new.type_args.as_deref()),
+            }),
+            swc::Expr::Seq(seq) => {
+                ExprKind::Seq(seq.exprs.iter().map(|e| self.lower_expr(e)).collect())
+            }
+            swc::Expr::Paren(paren) => ExprKind::Paren(Box::new(self.lower_expr(&paren.expr))),
+            swc::Expr::Arrow(arrow) => ExprKind::Arrow(self.lower_arrow(arrow)),
+            swc::Expr::Await(await_expr) => {
+                ExprKind::Await(Box::new(self.lower_expr(&await_expr.arg)))
+            }
+            swc::Expr::OptChain(opt_chain) => match opt_chain.base.as_ref() {
+                swc::OptChainBase::Member(member) => ExprKind::OptMember(self.lower_member(member)),
+                swc::OptChainBase::Call(call) => ExprKind::OptCall(CallExpr {
+                    callee: Callee::Expr(Box::new(self.lower_expr(&call.callee))),
+                    args: call.args.iter().map(|a| self.lower_arg(a)).collect(),
+                    type_args: self.lower_type_args(call.type_args.as_deref()),
+                }),
+            },
+            other => ExprKind::Unsupported {
+                what: unsupported_expr_name(other).to_string(),
+            },
+        };
+        Expr::new(kind, span)
+    }
+
+    pub(crate) fn lower_pat(&mut self, pat: &swc::Pat) -> Pat {
+        match pat {
+            swc::Pat::Ident(ident) => Pat::Ident(Binding {
+                name: ident.id.sym.to_string(),
+                ty: self.lower_type_ann(ident.type_ann.as_deref()),
+                mutable: false,
+                num: NumRepr::F64,
+            }),
+            swc::Pat::Object(object) => Pat::Object(
+                object
+                    .props
+                    .iter()
+                    .map(|prop| match prop {
+                        swc::ObjectPatProp::KeyValue(kv) => ObjectPatProp::KeyValue {
+                            key: self.lower_prop_name(&kv.key),
+                            value: self.lower_pat(&kv.value),
+                        },
+                        swc::ObjectPatProp::Assign(assign) => ObjectPatProp::Assign {
+                            key: assign.key.sym.to_string(),
+                            default: assign.value.as_ref().map(|v| self.lower_expr(v)),
+                        },
+                        swc::ObjectPatProp::Rest(_) => ObjectPatProp::Rest,
+                    })
+                    .collect(),
+            ),
+            swc::Pat::Array(array) => Pat::Array(
+                array
+                    .elems
+                    .iter()
+                    .map(|elem| elem.as_ref().map(|p| self.lower_pat(p)))
+                    .collect(),
+            ),
+            _ => Pat::Unsupported,
+        }
+    }
+
+    pub(crate) fn lower_type_args(
+        &self,
+        type_args: Option<&swc::TsTypeParamInstantiation>,
+    ) -> Vec<Type> {
+        type_args
+            .map(|args| args.params.iter().map(|p| self.lower_type(p)).collect())
+            .unwrap_or_default()
+    }
+
+    fn lower_arg(&mut self, arg: &swc::ExprOrSpread) -> Arg {
+        Arg {
+            expr: self.lower_expr(&arg.expr),
+            spread: arg.spread.is_some(),
+        }
+    }
+
+    fn lower_prop(&mut self, prop: &swc::PropOrSpread) -> Prop {
+        let swc::PropOrSpread::Prop(prop) = prop else {
+            return Prop::Unsupported;
+        };
+        match prop.as_ref() {
+            swc::Prop::KeyValue(kv) => Prop::KeyValue {
+                key: self.lower_prop_name(&kv.key),
+                value: self.lower_expr(&kv.value),
+            },
+            swc::Prop::Shorthand(ident) => Prop::Shorthand(ident.sym.to_string()),
+            _ => Prop::Unsupported,
+        }
+    }
+
+    fn lower_prop_name(&mut self, name: &swc::PropName) -> PropKey {
+        match name {
+            swc::PropName::Ident(ident) => PropKey::Ident(ident.sym.to_string()),
+            swc::PropName::Str(s) => PropKey::Str(str_value(s)),
+            swc::PropName::Num(n) => PropKey::Num(n.to_string()),
+            swc::PropName::BigInt(n) => PropKey::BigInt(n.value.to_string()),
+            swc::PropName::Computed(computed) => {
+                PropKey::Computed(Box::new(self.lower_expr(&computed.expr)))
+            }
+        }
+    }
+
+    fn lower_member(&mut self, member: &swc::MemberExpr) -> MemberExpr {
+        MemberExpr {
+            obj: Box::new(self.lower_expr(&member.obj)),
+            prop: match &member.prop {
+                swc::MemberProp::Ident(ident) => MemberProp::Ident(ident.sym.to_string()),
+                swc::MemberProp::Computed(computed) => {
+                    MemberProp::Computed(Box::new(self.lower_expr(&computed.expr)))
+                }
+                swc::MemberProp::PrivateName(name) => {
+                    MemberProp::Private(self.private_name(&name.name))
+                }
+            },
+        }
+    }
+
+    fn lower_assign_target(&mut self, target: &swc::AssignTarget) -> AssignTarget {
+        match target {
+            swc::AssignTarget::Simple(swc::SimpleAssignTarget::Ident(ident)) => {
+                AssignTarget::Ident(ident.sym.to_string())
+            }
+            swc::AssignTarget::Simple(swc::SimpleAssignTarget::Member(member)) => {
+                AssignTarget::Member(self.lower_member(member))
+            }
+            _ => AssignTarget::Unsupported,
+        }
+    }
+
+    fn lower_arrow(&mut self, arrow: &swc::ArrowExpr) -> ArrowExpr {
+        self.with_type_params(arrow.type_params.as_deref(), |this| ArrowExpr {
+            params: arrow.params.iter().map(|p| this.lower_pat(p)).collect(),
+            body: match arrow.body.as_ref() {
+                swc::BlockStmtOrExpr::BlockStmt(block) => ArrowBody::Block(this.lower_block(block)),
+                swc::BlockStmtOrExpr::Expr(expr) => {
+                    ArrowBody::Expr(Box::new(this.lower_expr(expr)))
+                }
+            },
+            is_async: arrow.is_async,
+        })
+    }
+}
+
+fn lower_lit(lit: &swc::Lit) -> Lit {
+    match lit {
+        swc::Lit::Str(s) => Lit::Str(str_value(s)),
+        swc::Lit::Num(n) => Lit::Num(n.value),
+        swc::Lit::Bool(b) => Lit::Bool(b.value),
+        swc::Lit::Null(_) => Lit::Null,
+        _ => Lit::Unsupported,
+    }
+}
+
+fn lower_unary_op(op: swc::UnaryOp) -> UnaryOp {
+    match op {
+        swc::UnaryOp::Bang => UnaryOp::Not,
+        swc::UnaryOp::Minus => UnaryOp::Minus,
+        swc::UnaryOp::Plus => UnaryOp::Plus,
+        swc::UnaryOp::TypeOf => UnaryOp::TypeOf,
+        swc::UnaryOp::Tilde => UnaryOp::Tilde,
+        swc::UnaryOp::Void => UnaryOp::Void,
+        swc::UnaryOp::Delete => UnaryOp::Delete,
+    }
+}
+
+fn lower_binary_op(op: swc::BinaryOp) -> BinaryOp {
+    match op {
+        swc::BinaryOp::Add => BinaryOp::Add,
+        swc::BinaryOp::Sub => BinaryOp::Sub,
+        swc::BinaryOp::Mul => BinaryOp::Mul,
+        swc::BinaryOp::Div => BinaryOp::Div,
+        swc::BinaryOp::Mod => BinaryOp::Mod,
+        swc::BinaryOp::Exp => BinaryOp::Exp,
+        swc::BinaryOp::EqEq => BinaryOp::EqEq,
+        swc::BinaryOp::NotEq => BinaryOp::NotEq,
+        swc::BinaryOp::EqEqEq => BinaryOp::EqEqEq,
+        swc::BinaryOp::NotEqEq => BinaryOp::NotEqEq,
+        swc::BinaryOp::Lt => BinaryOp::Lt,
+        swc::BinaryOp::LtEq => BinaryOp::LtEq,
+        swc::BinaryOp::Gt => BinaryOp::Gt,
+        swc::BinaryOp::GtEq => BinaryOp::GtEq,
+        swc::BinaryOp::LogicalAnd => BinaryOp::LogicalAnd,
+        swc::BinaryOp::LogicalOr => BinaryOp::LogicalOr,
+        swc::BinaryOp::NullishCoalescing => BinaryOp::NullishCoalescing,
+        swc::BinaryOp::BitAnd => BinaryOp::BitAnd,
+        swc::BinaryOp::BitOr => BinaryOp::BitOr,
+        swc::BinaryOp::BitXor => BinaryOp::BitXor,
+        swc::BinaryOp::LShift => BinaryOp::LShift,
+        swc::BinaryOp::RShift => BinaryOp::RShift,
+        swc::BinaryOp::ZeroFillRShift => BinaryOp::ZeroFillRShift,
+        swc::BinaryOp::In => BinaryOp::In,
+        swc::BinaryOp::InstanceOf => BinaryOp::InstanceOf,
+    }
+}
+
+fn lower_assign_op(op: swc::AssignOp) -> AssignOp {
+    match op {
+        swc::AssignOp::Assign => AssignOp::Assign,
+        swc::AssignOp::AddAssign => AssignOp::AddAssign,
+        swc::AssignOp::SubAssign => AssignOp::SubAssign,
+        swc::AssignOp::MulAssign => AssignOp::MulAssign,
+        swc::AssignOp::DivAssign => AssignOp::DivAssign,
+        swc::AssignOp::ModAssign => AssignOp::ModAssign,
+        _ => AssignOp::Other,
+    }
+}
+
+fn unsupported_expr_name(expr: &swc::Expr) -> &'static str {
+    match expr {
+        swc::Expr::Fn(_) => "function expressions",
+        swc::Expr::Class(_) => "class expressions",
+        swc::Expr::TaggedTpl(_) => "tagged templates",
+        swc::Expr::Yield(_) => "generators",
+        swc::Expr::MetaProp(_) => "meta properties",
+        swc::Expr::TsAs(_)
+        | swc::Expr::TsTypeAssertion(_)
+        | swc::Expr::TsConstAssertion(_)
+        | swc::Expr::TsSatisfies(_) => "type assertions",
+        swc::Expr::TsNonNull(_) => "non-null assertions",
+        _ => "expression",
+    }
+}
@@ -0,0 +1,291 @@
+//! Lowering from the SWC AST into the Tyrus IR.
+//!
+//! Lowering is total: every SWC node produces *some* IR node. Constructs
+//! Tyrus cannot express are kept as `Unsupported` nodes (carrying their span)
+//! instead of being dropped, so later stages can report them precisely.
+
+mod comments;
+mod decl;
+mod expr;
+mod stmt;
+mod types;
+
+use std::collections::{HashMap, HashSet};
+
+use swc_common::comments::SingleThreadedComments;
+use swc_common::BytePos;
+use swc_ecma_ast as swc;
+
+use crate::decl::{Decl, Doc, Import, ImportSpecifier, Item, Module};
+use crate::stmt::{Stmt, StmtKind};
+use crate::types::Type;
+
+/// Lowers a parsed program into an IR [`Module`].
+pub fn lower_program(program: &swc::Program) -> Module {
+    lower_program_with_comments(program, &SingleThreadedComments::default())
+}
+
+/// Lowers a parsed program, honoring the `// tyrus-...` directives among its
+/// `comments` and keeping the others as documentation and statement comments.
+pub fn lower_program_with_comments(
+    program: &swc::Program,
+    comments: &SingleThreadedComments,
+) -> Module {
+    let mut lowerer = Lowerer {
+        float_directives: directive_positions(comments, FLOAT_DIRECTIVE),
+        docs: comments::docs(comments),
+        stmt_comments: comments::stmt_comments(comments),
+        ..Lowerer::default()
+    };
+    let items = match program {
+        swc::Program::Module(m) => m
+            .body
+            .iter()
+            .flat_map(|item| lowerer.lower_module_item(item))
+            .collect(),
+        swc::Program::Script(s) => s
+            .body
+            .iter()
+            .map(|stmt| lowerer.lower_top_level_stmt(stmt))
+            .collect(),
+    };
+    Module { items }
+}
+
+/// Lowers a single TypeScript type, with no type parameters in scope.
+pub fn lower_type(ty: &swc::TsType) -> Type {
+    Lowerer::default().lower_type(ty)
+}
+
+/// Keeps the `number` bindings of the declaration below it as `f64`.
+const FLOAT_DIRECTIVE: &str = "tyrus-float";
+
+/// Positions of the nodes preceded by a `// <directive>` comment.
+fn directive_positions(comments: &SingleThreadedComments, directive: &str) -> HashSet<BytePos> {
+    let (leading, _) = comments.borrow_all();
+    leading
+        .iter()
+        .filter(|(_, comments)| {
+            comments
+                .iter()
+                .any(|comment| comment.text.trim() == directive)
+        })
+        .map(|(pos, _)| *pos)
+        .collect()
+}
+
+/// Tracks the lexical state lowering needs, i.e. which type parameters are in scope.
+#[derive(Default)]
+pub(crate) struct Lowerer {
+    type_param_scopes: Vec<HashSet<String>>,
+    /// Declarations marked `// tyrus-float`, by start position.
+    float_directives: HashSet<BytePos>,
+    /// JSDoc blocks, by the start position of what they document.
+    docs: HashMap<BytePos, Doc>,
+    /// Other comments, by the start position of the statement below them.
+    stmt_comments: HashMap<BytePos, Vec<String>>,
+    /// ES private names of the enclosing classes, innermost last, by the
+    /// name they are lowered to.
+    private_name_scopes: Vec<HashMap<String, String>>,
+}
+
+impl Lowerer {
+    fn with_type_params<R>(
+        &mut self,
+        params: Option<&swc::TsTypeParamDecl>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let scope = params
+            .map(|p| p.params.iter().map(|tp| tp.name.sym.to_string()).collect())
+            .unwrap_or_default();
+        self.type_param_scopes.push(scope);
+        let result = f(self);
+        self.type_param_scopes.pop();
+        result
+    }
+
+    /// Lowers `class` with its ES private names in scope. A field `#x` would
+    /// share the Rust name of a field `x` of the same class, and a method
+    /// `#x()` that of a method `x()`, so they become `priv_x`.
+    fn with_private_names<R>(&mut self, class: &swc::Class, f: impl FnOnce(&mut Self) -> R) -> R {
+        let name = |key: &swc::PropName| key.as_ident().map(|ident| ident.sym.to_string());
+        let (mut fields, mut methods) = (HashSet::new(), HashSet::new());
+        let mut private = Vec::new();
+        for member in &class.body {
+            match member {
+                swc::ClassMember::ClassProp(prop) => fields.extend(name(&prop.key)),
+                swc::ClassMember::Method(method) => methods.extend(name(&method.key)),
+                swc::ClassMember::Constructor(ctor) => {
+                    for param in &ctor.params {
+                        if let swc::ParamOrTsParamProp::TsParamProp(swc::TsParamProp {
+                            param: swc::TsParamPropParam::Ident(ident),
+                            ..
+                        }) = param
+                        {
+                            fields.insert(ident.id.sym.to_string());
+                        }
+                    }
+                }
+                swc::ClassMember::PrivateProp(prop) => private.push((&prop.key, true)),
+                swc::ClassMember::PrivateMethod(method) => private.push((&method.key, false)),
+                _ => {}
+            }
+        }
+        let scope = private
+            .into_iter()
+            .map(|(key, is_field)| {
+                let public = if is_field { &fields } else { &methods };
+                let name = key.name.to_string();
+                let mut lowered = name.clone();
+                while public.contains(&lowered) {
+                    lowered = format!("priv_{lowered}");
+                }
+                (name, lowered)
+            })
+            .collect();
+        self.private_name_scopes.push(scope);
+        let result = f(self);
+        self.private_name_scopes.pop();
+        result
+    }
+
+    /// The name ES private name `#name` is lowered to.
+    fn private_name(&self, name: &str) -> String {
+        self.private_name_scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    fn is_type_param(&self, name: &str) -> bool {
+        self.type_param_scopes.iter().any(|s| s.contains(name))
+    }
+
+    /// The JSDoc block of the node starting at the first of `positions`
+    /// that has one; decorators and `export` come before the node itself.
+    fn doc(&self, positions: impl IntoIterator<Item = BytePos>) -> Option<Doc> {
+        positions
+            .into_iter()
+            .find_map(|pos| self.docs.get(&pos))
+            .cloned()
+    }
+
+    fn lower_module_item(&mut self, item: &swc::ModuleItem) -> Option<Item> {
+        match item {
+            swc::ModuleItem::ModuleDecl(decl) => match decl {
+                swc::ModuleDecl::ExportDecl(export) => {
+                    let mut item = self.lower_top_level_decl(&export.decl, true);
+                    if let Item::Decl { decl, .. } = &mut item {
+                        if let Some(doc @ None) = decl.doc_mut() {
+                            *doc = self.doc([export.span.lo]);
+                        }
+                    }
+                    Some(item)
+                }
+                swc::ModuleDecl::ExportDefaultDecl(default_decl) => match &default_decl.decl {
+                    // Anonymous default exports have no name to give the Rust item.
+                    swc::DefaultDecl::Class(class_expr) => {
+                        let ident = class_expr.ident.as_ref()?;
+                        Some(Item::Decl {
+                            decl: Decl::Class(self.lower_class(ident, &class_expr.class)),
+                            exported: true,
+                        })
+                    }
+                    swc::DefaultDecl::Fn(fn_expr) => {
+                        let ident = fn_expr.ident.as_ref()?;
+                        Some(Item::Decl {
+                            decl: Decl::Fn(self.lower_fn(ident, &fn_expr.function)),
+                            exported: true,
+                        })
+                    }
+                    swc::DefaultDecl::TsInterfaceDecl(iface) => Some(Item::Decl {
+                        decl: Decl::Interface(self.lower_interface(iface)),
+                        exported: true,
+                    }),
+                },
+                swc::ModuleDecl::Import(import) => Some(Item::Import(lower_import(import))),
+                _ => None,
+            },
+            swc::ModuleItem::Stmt(stmt) => Some(self.lower_top_level_stmt(stmt)),
+        }
+    }
+
+    /// Top-level `const`/`let` stay statements: they become the body of `main`.
+    fn lower_top_level_stmt(&mut self, stmt: &swc::Stmt) -> Item {
+        match stmt {
+            swc::Stmt::Decl(decl) if !matches!(decl, swc::Decl::Var(_)) => {
+                self.lower_top_level_decl(decl, false)
+            }
+            _ => Item::Stmt(self.lower_stmt(stmt)),
+        }
+    }
+
+    fn lower_top_level_decl(&mut self, decl: &swc::Decl, exported: bool) -> Item {
+        match self.lower_decl(decl) {
+            Some(decl) => Item::Decl { decl, exported },
+            None => Item::Stmt(Stmt::new(
+                StmtKind::Unsupported {
+                    what: unsupported_decl_name(decl).to_string(),
+                },
+                swc_common::Spanned::span(decl),
+            )),
+        }
+    }
+
+    fn lower_decl(&mut self, decl: &swc::Decl) -> Option<Decl> {
+        Some(match decl {
+            swc::Decl::Fn(f) => Decl::Fn(self.lower_fn(&f.ident, &f.function)),
+            swc::Decl::Class(c) => Decl::Class(self.lower_class(&c.ident, &c.class)),
+            swc::Decl::TsInterface(i) => Decl::Interface(self.lower_interface(i)),
+            swc::Decl::TsTypeAlias(a) => Decl::TypeAlias(self.lower_type_alias(a)),
+            swc::Decl::TsEnum(e) => Decl::Enum(self.lower_enum(e)),
+            swc::Decl::Var(v) => Decl::Var(self.lower_var_decl(v)),
+            swc::Decl::Using(_) | swc::Decl::TsModule(_) => return None,
+        })
+    }
+}
+
+fn unsupported_decl_name(decl: &swc::Decl) -> &'static str {
+    match decl {
+        swc::Decl::Using(_) => "using declarations",
+        swc::Decl::TsModule(_) => "namespaces",
+        _ => "declaration",
+    }
+}
+
+fn lower_import(import: &swc::ImportDecl) -> Import {
+    let specifiers = import
+        .specifiers
+        .iter()
+        .map(|spec| match spec {
+            swc::ImportSpecifier::Named(named) => ImportSpecifier::Named {
+                local: named.local.sym.to_string(),
+                imported: named.imported.as_ref().map(|imported| match imported {
+                    swc::ModuleExportName::Ident(ident) => ident.sym.to_string(),
+                    swc::ModuleExportName::Str(s) => str_value(s),
+                }),
+            },
+            swc::ImportSpecifier::Default(default) => ImportSpecifier::Default {
+                local: default.local.sym.to_string(),
+            },
+            swc::ImportSpecifier::Namespace(ns) => ImportSpecifier::Namespace {
+                local: ns.local.sym.to_string(),
+            },
+        })
+        .collect();
+
+    Import {
+        src: str_value(&import.src),
+        specifiers,
+        span: import.span,
+    }
+}
+
+pub(crate) fn str_value(s: &swc::Str) -> String {
+    s.value.as_str().unwrap_or("").to_string()
+}
+
+#[cfg(test)]
+mod tests;
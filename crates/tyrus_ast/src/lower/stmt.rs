@@ -0,0 +1,137 @@
+use swc_ecma_ast as swc;
+
+use super::Lowerer;
+use crate::expr::Pat;
+use crate::stmt::{
+    CatchClause, ForHead, ForInit, Stmt, StmtKind, SwitchCase, VarDecl, VarDeclarator, VarKind,
+};
+
+impl Lowerer {
+    pub(crate) fn lower_stmt(&mut self, stmt: &swc::Stmt) -> Stmt {
+        let span = swc_common::Spanned::span(stmt);
+        let kind = match stmt {
+            swc::Stmt::Expr(expr) => StmtKind::Expr(self.lower_expr(&expr.expr)),
+            swc::Stmt::Return(ret) => {
+                StmtKind::Return(ret.arg.as_ref().map(|arg| self.lower_expr(arg)))
+            }
+            swc::Stmt::Decl(swc::Decl::Var(var)) => StmtKind::Var(self.lower_var_decl(var)),
+            swc::Stmt::Decl(decl) => match self.lower_decl(decl) {
+                Some(decl) => StmtKind::Decl(Box::new(decl)),
+                None => StmtKind::Unsupported {
+                    what: super::unsupported_decl_name(decl).to_string(),
+                },
+            },
+            swc::Stmt::Block(block) => StmtKind::Block(self.lower_block(block)),
+            swc::Stmt::If(if_stmt) => StmtKind::If {
+                test: self.lower_expr(&if_stmt.test),
+                cons: Box::new(self.lower_stmt(&if_stmt.cons)),
+                alt: if_stmt
+                    .alt
+                    .as_ref()
+                    .map(|alt| Box::new(self.lower_stmt(alt))),
+            },
+            swc::Stmt::While(while_stmt) => StmtKind::While {
+                test: self.lower_expr(&while_stmt.test),
+                body: Box::new(self.lower_stmt(&while_stmt.body)),
+            },
+            swc::Stmt::DoWhile(do_while) => StmtKind::DoWhile {
+                body: Box::new(self.lower_stmt(&do_while.body)),
+                test: self.lower_expr(&do_while.test),
+            },
+            swc::Stmt::For(for_stmt) => StmtKind::For {
+                init: for_stmt.init.as_ref().map(|init| match init {
+                    swc::VarDeclOrExpr::VarDecl(var) => {
+                        let mut var = self.lower_var_decl(var);
+                        // `// tyrus-float` above the loop applies to its counter
+                        var.keep_float |= self.float_directives.contains(&for_stmt.span.lo);
+                        ForInit::Var(var)
+                    }
+                    swc::VarDeclOrExpr::Expr(expr) => ForInit::Expr(self.lower_expr(expr)),
+                }),
+                test: for_stmt.test.as_ref().map(|e| self.lower_expr(e)),
+                update: for_stmt.update.as_ref().map(|e| self.lower_expr(e)),
+                body: Box::new(self.lower_stmt(&for_stmt.body)),
+            },
+            swc::Stmt::ForOf(for_of) => StmtKind::ForOf {
+                left: self.lower_for_head(&for_of.left),
+                right: self.lower_expr(&for_of.right),
+                body: Box::new(self.lower_stmt(&for_of.body)),
+            },
+            swc::Stmt::ForIn(for_in) => StmtKind::ForIn {
+                left: self.lower_for_head(&for_in.left),
+                right: self.lower_expr(&for_in.right),
+                body: Box::new(self.lower_stmt(&for_in.body)),
+            },
+            swc::Stmt::Try(try_stmt) => StmtKind::Try {
+                block: self.lower_block(&try_stmt.block),
+                handler: try_stmt.handler.as_ref().map(|catch| CatchClause {
+                    param: catch.param.as_ref().map(|p| self.lower_pat(p)),
+                    body: self.lower_block(&catch.body),
+                }),
+                finalizer: try_stmt.finalizer.as_ref().map(|f| self.lower_block(f)),
+            },
+            swc::Stmt::Throw(throw) => StmtKind::Throw(self.lower_expr(&throw.arg)),
+            swc::Stmt::Switch(switch) => StmtKind::Switch {
+                discriminant: self.lower_expr(&switch.discriminant),
+                cases: switch
+                    .cases
+                    .iter()
+                    .map(|case| SwitchCase {
+                        test: case.test.as_ref().map(|t| self.lower_expr(t)),
+                        cons: case.cons.iter().map(|s| self.lower_stmt(s)).collect(),
+                    })
+                    .collect(),
+            },
+            swc::Stmt::Break(_) => StmtKind::Break,
+            swc::Stmt::Continue(_) => StmtKind::Continue,
+            swc::Stmt::Empty(_) => StmtKind::Empty,
+            swc::Stmt::Labeled(_) => StmtKind::Unsupported {
+                what: "labeled statements".to_string(),
+            },
+            swc::Stmt::With(_) => StmtKind::Unsupported {
+                what: "with statements".to_string(),
+            },
+            swc::Stmt::Debugger(_) => StmtKind::Unsupported {
+                what: "debugger statements".to_string(),
+            },
+        };
+        let mut stmt = Stmt::new(kind, span);
+        if let Some(comments) = self.stmt_comments.get(&span.lo) {
+            stmt.comments = comments.clone();
+        }
+        stmt
+    }
+
+    pub(crate) fn lower_block(&mut self, block: &swc::BlockStmt) -> Vec<Stmt> {
+        block.stmts.iter().map(|s| self.lower_stmt(s)).collect()
+    }
+
+    pub(crate) fn lower_var_decl(&mut self, var: &swc::VarDecl) -> VarDecl {
+        VarDecl {
+            kind: match var.kind {
+                swc::VarDeclKind::Const => VarKind::Const,
+                swc::VarDeclKind::Let => VarKind::Let,
+                swc::VarDeclKind::Var => VarKind::Var,
+            },
+            decls: var
+                .decls
+                .iter()
+                .map(|decl| VarDeclarator {
+                    pat: self.lower_pat(&decl.name),
+                    init: decl.init.as_ref().map(|init| self.lower_expr(init)),
+                    span: decl.span,
+                })
+                .collect(),
+            keep_float: self.float_directives.contains(&var.span.lo),
+            span: var.span,
+        }
+    }
+
+    fn lower_for_head(&mut self, head: &swc::ForHead) -> ForHead {
+        match head {
+            swc::ForHead::VarDecl(var) => ForHead::Var(self.lower_var_decl(var)),
+            swc::ForHead::Pat(pat) => ForHead::Pat(self.lower_pat(pat)),
+            swc::ForHead::UsingDecl(_) => ForHead::Pat(Pat::Unsupported),
+        }
+    }
+}
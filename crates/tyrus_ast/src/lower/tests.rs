@@ -0,0 +1,254 @@
+use super::comments::parse_doc;
+use super::lower_program_with_comments;
+use crate::decl::{ClassMember, CtorParam, Decl, Doc, Item, Module};
+use crate::expr::{Callee, ExprKind, MemberProp, Pat};
+use crate::stmt::StmtKind;
+use crate::types::{LitType, Type};
+
+fn lower(src: &str) -> Module {
+    let (program, comments) = tyrus_parser::parse_str(src).expect("test source should parse");
+    lower_program_with_comments(&program, &comments)
+}
+
+fn first_decl(module: &Module) -> &Decl {
+    module
+        .items
+        .iter()
+        .find_map(|item| match item {
+            Item::Decl { decl, .. } => Some(decl),
+            _ => None,
+        })
+        .expect("expected a declaration")
+}
+
+#[test]
+fn test_lower_interface_types() {
+    let module = lower(
+        "export interface User { id: number; tags: Array<string>; email?: string; nick: string | undefined; meta: Record<string, boolean>; }",
+    );
+    let Decl::Interface(iface) = first_decl(&module) else {
+        panic!("expected interface");
+    };
+    let types: Vec<_> = iface
+        .members
+        .iter()
+        .map(|m| match m {
+            crate::decl::InterfaceMember::Property { ty, .. } => ty.clone().unwrap(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            Type::Number,
+            Type::Array(Box::new(Type::String)),
+            Type::String,
+            Type::Optional(Box::new(Type::String)),
+            Type::Record(Box::new(Type::String), Box::new(Type::Bool)),
+        ]
+    );
+}
+
+#[test]
+fn test_lower_type_params_in_scope() {
+    let module = lower("function first<T>(items: T[]): Promise<T> { return items[0]; }");
+    let Decl::Fn(f) = first_decl(&module) else {
+        panic!("expected function");
+    };
+    let Pat::Ident(param) = &f.function.params[0].pat else {
+        panic!("expected ident param");
+    };
+    assert_eq!(
+        param.ty,
+        Some(Type::Array(Box::new(Type::Param("T".into()))))
+    );
+    assert_eq!(
+        f.function.return_type,
+        Some(Type::Promise(Box::new(Type::Param("T".into()))))
+    );
+}
+
+#[test]
+fn test_lower_literal_union_alias() {
+    let module = lower(r#"type Status = "open" | "closed";"#);
+    let Decl::TypeAlias(alias) = first_decl(&module) else {
+        panic!("expected type alias");
+    };
+    assert_eq!(
+        alias.ty,
+        Type::Union(vec![
+            Type::Literal(LitType::Str("open".into())),
+            Type::Literal(LitType::Str("closed".into())),
+        ])
+    );
+}
+
+#[test]
+fn test_lower_class_members() {
+    let module = lower(
+        "@Controller('cats') class CatsController { #secret = 1; constructor(private readonly svc: CatsService, name: string) {} @Get() findAll(): string { return 'all'; } }",
+    );
+    let Decl::Class(class) = first_decl(&module) else {
+        panic!("expected class");
+    };
+    assert_eq!(
+        class.decorator("Controller").unwrap().str_arg(),
+        Some("cats")
+    );
+
+    let ctor = class.constructor().expect("constructor");
+    assert!(matches!(
+        &ctor.params[0],
+        CtorParam::Prop { name, readonly: true, ty: Some(Type::Named { .. }), .. } if name == "svc"
+    ));
+    assert!(matches!(&ctor.params[1], CtorParam::Param(_)));
+
+    assert!(class.members.iter().any(|m| matches!(
+        m,
+        ClassMember::Prop(p) if p.name == "secret" && p.is_private_name
+    )));
+    assert!(class.members.iter().any(|m| matches!(
+        m,
+        ClassMember::Method(method) if method.name == "findAll"
+            && method.function.decorators[0].name == "Get"
+    )));
+}
+
+#[test]
+fn test_lower_colliding_private_names() {
+    let module =
+        lower("class P { #x = 1; x = 2; #y = 3; read(): number { return this.#x + this.#y; } }");
+    let Decl::Class(class) = first_decl(&module) else {
+        panic!("expected class");
+    };
+    let props: Vec<_> = class
+        .members
+        .iter()
+        .filter_map(|m| match m {
+            ClassMember::Prop(p) => Some((p.name.as_str(), p.is_private_name)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(props, vec![("priv_x", true), ("x", false), ("y", true)]);
+
+    let read = class.methods().find(|m| m.name == "read").expect("read");
+    let Some(StmtKind::Return(Some(ret))) = read.function.body.as_ref().map(|b| &b[0].kind) else {
+        panic!("expected return");
+    };
+    let ExprKind::Bin { left, right, .. } = &ret.kind else {
+        panic!("expected binary expression");
+    };
+    for (operand, name) in [(left, "priv_x"), (right, "y")] {
+        assert!(matches!(
+            &operand.kind,
+            ExprKind::Member(member) if member.prop == MemberProp::Private(name.to_string())
+        ));
+    }
+}
+
+#[test]
+fn test_lower_super_calls() {
+    let module = lower(
+        r#"
+        class B extends A {
+            constructor(x: number) { super(x); }
+            run(): string { return super.run(); }
+        }
+        "#,
+    );
+    let Decl::Class(class) = first_decl(&module) else {
+        panic!("expected class");
+    };
+    assert_eq!(class.super_class_name(), Some("A"));
+
+    let body = class.constructor().unwrap().body.as_ref().unwrap();
+    let StmtKind::Expr(expr) = &body[0].kind else {
+        panic!("expected expression statement");
+    };
+    assert!(matches!(&expr.kind, ExprKind::Call(call) if call.callee == Callee::Super));
+
+    let run = class.methods().find(|m| m.name == "run").unwrap();
+    let StmtKind::Return(Some(ret)) = &run.function.body.as_ref().unwrap()[0].kind else {
+        panic!("expected return");
+    };
+    let ExprKind::Call(call) = &ret.kind else {
+        panic!("expected call");
+    };
+    let Callee::Expr(callee) = &call.callee else {
+        panic!("expected method callee");
+    };
+    assert!(matches!(
+        &callee.kind,
+        ExprKind::Member(member)
+            if matches!(member.obj.kind, ExprKind::Super) && member.prop.as_ident() == Some("run")
+    ));
+}
+
+#[test]
+fn test_lower_keeps_unsupported_with_span() {
+    let module = lower("label: for (;;) { break; }\nconst f = function () {};");
+    let Item::Stmt(stmt) = &module.items[0] else {
+        panic!("expected statement");
+    };
+    assert!(matches!(&stmt.kind, StmtKind::Unsupported { what } if what == "labeled statements"));
+    assert!(!stmt.span.is_dummy());
+
+    let Item::Stmt(stmt) = &module.items[1] else {
+        panic!("expected statement");
+    };
+    let StmtKind::Var(var) = &stmt.kind else {
+        panic!("expected var declaration");
+    };
+    let init = var.decls[0].init.as_ref().unwrap();
+    assert!(matches!(&init.kind, ExprKind::Unsupported { what } if what == "function expressions"));
+}
+
+#[test]
+fn test_parse_doc() {
+    let doc = parse_doc(
+        "*\n * Finds a user.\n *\n * @param {string} userId - The id\n *   to look up.\n * @param [limit=10] How many.\n * @returns {User} The user.\n * @deprecated Use `find`.\n * @see find\n ",
+    );
+    assert_eq!(
+        doc,
+        Doc {
+            lines: vec![
+                "Finds a user.".to_string(),
+                String::new(),
+                "@see find".to_string()
+            ],
+            params: vec![
+                ("userId".to_string(), "The id to look up.".to_string()),
+                ("limit".to_string(), "How many.".to_string()),
+            ],
+            returns: Some("The user.".to_string()),
+            deprecated: Some("Use `find`.".to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_lower_comments() {
+    let module = lower(
+        "/** A user. */\nexport interface User {\n  /** Their name. */\n  name: string;\n}\n\n/** Greets. */\nexport function greet(): void {\n  // tyrus-float\n  const a = 1;\n  // Say hi\n  /* twice */\n  console.log(a);\n}\n",
+    );
+    let Decl::Interface(iface) = first_decl(&module) else {
+        panic!("expected interface");
+    };
+    assert_eq!(iface.doc.as_ref().unwrap().lines, vec!["A user."]);
+    let crate::decl::InterfaceMember::Property { doc, .. } = &iface.members[0] else {
+        panic!("expected property");
+    };
+    assert_eq!(doc.as_ref().unwrap().lines, vec!["Their name."]);
+
+    let Some(Item::Decl {
+        decl: Decl::Fn(func),
+        ..
+    }) = module.items.get(1)
+    else {
+        panic!("expected function");
+    };
+    assert_eq!(func.doc.as_ref().unwrap().lines, vec!["Greets."]);
+    let body = func.function.body.as_ref().unwrap();
+    assert!(body[0].comments.is_empty());
+    assert_eq!(body[1].comments, vec!["Say hi", "twice"]);
+}
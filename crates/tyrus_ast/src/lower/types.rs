@@ -0,0 +1,164 @@
+use swc_ecma_ast as swc;
+
+use super::{str_value, Lowerer};
+use crate::types::{LitType, PropSig, Type};
+
+impl Lowerer {
+    pub(crate) fn lower_type_ann(&self, ann: Option<&swc::TsTypeAnn>) -> Option<Type> {
+        ann.map(|ann| self.lower_type(&ann.type_ann))
+    }
+
+    pub(crate) fn lower_type(&self, ty: &swc::TsType) -> Type {
+        match ty {
+            swc::TsType::TsKeywordType(k) => lower_keyword(k.kind),
+            swc::TsType::TsArrayType(array) => {
+                Type::Array(Box::new(self.lower_type(&array.elem_type)))
+            }
+            swc::TsType::TsTypeRef(type_ref) => self.lower_type_ref(type_ref),
+            swc::TsType::TsUnionOrIntersectionType(
+                swc::TsUnionOrIntersectionType::TsUnionType(union),
+            ) => self.lower_union(&union.types),
+            swc::TsType::TsLitType(lit) => match &lit.lit {
+                swc::TsLit::Str(s) => Type::Literal(LitType::Str(str_value(s))),
+                swc::TsLit::Number(n) => Type::Literal(LitType::Num(n.value)),
+                swc::TsLit::Bool(b) => Type::Literal(LitType::Bool(b.value)),
+                swc::TsLit::BigInt(_) | swc::TsLit::Tpl(_) => Type::Unknown,
+            },
+            swc::TsType::TsTypeLit(lit) => Type::Object(
+                lit.members
+                    .iter()
+                    .filter_map(|member| match member {
+                        swc::TsTypeElement::TsPropertySignature(prop) => Some(PropSig {
+                            name: prop_key_name(&prop.key)?,
+                            ty: self
+                                .lower_type_ann(prop.type_ann.as_deref())
+                                .unwrap_or(Type::Unknown),
+                            optional: prop.optional,
+                        }),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            swc::TsType::TsTupleType(tuple) => Type::Tuple(
+                tuple
+                    .elem_types
+                    .iter()
+                    .map(|elem| self.lower_type(&elem.ty))
+                    .collect(),
+            ),
+            swc::TsType::TsFnOrConstructorType(swc::TsFnOrConstructorType::TsFnType(f)) => {
+                Type::Function {
+                    params: f
+                        .params
+                        .iter()
+                        .map(|p| self.lower_fn_param_type(p))
+                        .collect(),
+                    ret: Box::new(self.lower_type(&f.type_ann.type_ann)),
+                }
+            }
+            swc::TsType::TsParenthesizedType(paren) => self.lower_type(&paren.type_ann),
+            swc::TsType::TsOptionalType(opt) => {
+                Type::Optional(Box::new(self.lower_type(&opt.type_ann)))
+            }
+            swc::TsType::TsTypeOperator(op) if op.op == swc::TsTypeOperatorOp::ReadOnly => {
+                self.lower_type(&op.type_ann)
+            }
+            _ => Type::Unknown,
+        }
+    }
+
+    fn lower_type_ref(&self, type_ref: &swc::TsTypeRef) -> Type {
+        let Some(ident) = type_ref.type_name.as_ident() else {
+            // Qualified names (`ns.Type`) have no Rust counterpart yet.
+            return Type::Unknown;
+        };
+        let name = ident.sym.as_str();
+        let args: Vec<Type> = type_ref
+            .type_params
+            .as_ref()
+            .map(|params| params.params.iter().map(|p| self.lower_type(p)).collect())
+            .unwrap_or_default();
+
+        if self.is_type_param(name) {
+            return Type::Param(name.to_string());
+        }
+
+        let mut args = args.into_iter();
+        match name {
+            "Date" => Type::Date,
+            "Array" | "ReadonlyArray" => {
+                Type::Array(Box::new(args.next().unwrap_or(Type::Unknown)))
+            }
+            "Promise" => Type::Promise(Box::new(args.next().unwrap_or(Type::Unknown))),
+            "Record" => {
+                let key = args.next();
+                let value = args.next();
+                match (key, value) {
+                    (Some(key), Some(value)) => Type::Record(Box::new(key), Box::new(value)),
+                    _ => Type::Record(Box::new(Type::String), Box::new(Type::Unknown)),
+                }
+            }
+            _ => Type::Named {
+                name: name.to_string(),
+                args: args.collect(),
+            },
+        }
+    }
+
+    fn lower_union(&self, members: &[Box<swc::TsType>]) -> Type {
+        let mut nullable = false;
+        let mut rest = Vec::new();
+        for member in members {
+            match self.lower_type(member) {
+                Type::Null => nullable = true,
+                ty => rest.push(ty),
+            }
+        }
+
+        let inner = match rest.len() {
+            0 => Type::Unknown,
+            1 => rest.remove(0),
+            _ => Type::Union(rest),
+        };
+
+        if nullable {
+            Type::Optional(Box::new(inner))
+        } else {
+            inner
+        }
+    }
+
+    fn lower_fn_param_type(&self, param: &swc::TsFnParam) -> Type {
+        let ann = match param {
+            swc::TsFnParam::Ident(ident) => ident.type_ann.as_deref(),
+            swc::TsFnParam::Array(array) => array.type_ann.as_deref(),
+            swc::TsFnParam::Rest(rest) => rest.type_ann.as_deref(),
+            swc::TsFnParam::Object(object) => object.type_ann.as_deref(),
+        };
+        self.lower_type_ann(ann).unwrap_or(Type::Unknown)
+    }
+}
+
+fn lower_keyword(kind: swc::TsKeywordTypeKind) -> Type {
+    match kind {
+        swc::TsKeywordTypeKind::TsStringKeyword => Type::String,
+        swc::TsKeywordTypeKind::TsNumberKeyword => Type::Number,
+        swc::TsKeywordTypeKind::TsBooleanKeyword => Type::Bool,
+        swc::TsKeywordTypeKind::TsVoidKeyword => Type::Void,
+        swc::TsKeywordTypeKind::TsUndefinedKeyword | swc::TsKeywordTypeKind::TsNullKeyword => {
+            Type::Null
+        }
+        swc::TsKeywordTypeKind::TsNeverKeyword => Type::Never,
+        swc::TsKeywordTypeKind::TsAnyKeyword => Type::Any,
+        _ => Type::Unknown,
+    }
+}
+
+/// Name of an identifier or string-literal property key.
+pub(crate) fn prop_key_name(key: &swc::Expr) -> Option<String> {
+    match key {
+        swc::Expr::Ident(ident) => Some(ident.sym.to_string()),
+        swc::Expr::Lit(swc::Lit::Str(s)) => Some(str_value(s)),
+        _ => None,
+    }
+}
@@ -0,0 +1,176 @@
+use swc_common::Span;
+
+use crate::decl::Decl;
+use crate::expr::{Expr, Pat};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stmt {
+    pub kind: StmtKind,
+    /// The comments above the statement, line by line.
+    pub comments: Vec<String>,
+    pub span: Span,
+}
+
+// A `for` header holds its expressions inline; boxing them is not worth the noise.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum StmtKind {
+    Expr(Expr),
+    Return(Option<Expr>),
+    Var(VarDecl),
+    Block(Vec<Stmt>),
+    If {
+        test: Expr,
+        cons: Box<Stmt>,
+        alt: Option<Box<Stmt>>,
+    },
+    While {
+        test: Expr,
+        body: Box<Stmt>,
+    },
+    DoWhile {
+        body: Box<Stmt>,
+        test: Expr,
+    },
+    For {
+        init: Option<ForInit>,
+        test: Option<Expr>,
+        update: Option<Expr>,
+        body: Box<Stmt>,
+    },
+    ForOf {
+        left: ForHead,
+        right: Expr,
+        body: Box<Stmt>,
+    },
+    ForIn {
+        left: ForHead,
+        right: Expr,
+        body: Box<Stmt>,
+    },
+    Try {
+        block: Vec<Stmt>,
+        handler: Option<CatchClause>,
+        finalizer: Option<Vec<Stmt>>,
+    },
+    Throw(Expr),
+    Switch {
+        discriminant: Expr,
+        cases: Vec<SwitchCase>,
+    },
+    /// A `match` over a Rust enum synthesized for a TypeScript union. Produced
+    /// by narrowing in the analyzer, never by lowering.
+    Match {
+        scrutinee: Expr,
+        arms: Vec<MatchArm>,
+    },
+    Break,
+    Continue,
+    Empty,
+    /// A nested declaration (function, class, ...) inside a body.
+    Decl(Box<Decl>),
+    /// Anything lowering could not map; `what` names the construct for diagnostics.
+    Unsupported {
+        what: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarKind {
+    Const,
+    Let,
+    Var,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarDecl {
+    pub kind: VarKind,
+    pub decls: Vec<VarDeclarator>,
+    /// Set by a `// tyrus-float` comment: its `number` bindings stay `f64`
+    /// even when they only ever hold integers.
+    pub keep_float: bool,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarDeclarator {
+    pub pat: Pat,
+    pub init: Option<Expr>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ForInit {
+    Var(VarDecl),
+    Expr(Expr),
+}
+
+/// The left-hand side of `for..of` / `for..in`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ForHead {
+    Var(VarDecl),
+    Pat(Pat),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatchClause {
+    pub param: Option<Pat>,
+    pub body: Vec<Stmt>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwitchCase {
+    /// `None` for the `default` case.
+    pub test: Option<Expr>,
+    pub cons: Vec<Stmt>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    /// Alternatives of the arm (`A { .. } | B { .. }`).
+    pub pats: Vec<MatchPat>,
+    pub body: Vec<Stmt>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchPat {
+    /// A struct variant of the tagged enum generated for `union`, selected by
+    /// its tag value. `fields` are the variant fields bound by name in the arm.
+    Variant {
+        union: String,
+        tag: String,
+        fields: Vec<String>,
+    },
+    /// A newtype variant of the untagged enum generated for `union`, binding
+    /// its value as `binding`.
+    Newtype {
+        union: String,
+        variant: String,
+        binding: String,
+    },
+    Wildcard,
+}
+
+impl Stmt {
+    pub fn new(kind: StmtKind, span: Span) -> Self {
+        Self {
+            kind,
+            comments: Vec::new(),
+            span,
+        }
+    }
+
+    pub fn is_block(&self) -> bool {
+        matches!(self.kind, StmtKind::Block(_))
+    }
+}
+
+impl ForHead {
+    /// The single binding introduced by the loop head, e.g. `x` in `for (const x of xs)`.
+    pub fn binding_pat(&self) -> Option<&Pat> {
+        match self {
+            ForHead::Var(var) => var.decls.first().map(|d| &d.pat),
+            ForHead::Pat(pat) => Some(pat),
+        }
+    }
+}
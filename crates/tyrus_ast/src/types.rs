@@ -0,0 +1,114 @@
+/// A resolved TypeScript type.
+///
+/// Lowering collapses the many syntactic forms SWC keeps around (`T[]` vs
+/// `Array<T>`, `T | undefined` vs `T | null`, parenthesized types, ...) into
+/// one canonical shape, so codegen and the analyses only ever have to match
+/// on the meaning of a type, not on how it was spelled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    String,
+    Number,
+    Bool,
+    Void,
+    /// `undefined` or `null` on their own.
+    Null,
+    Never,
+    /// The `any` keyword. Kept distinct from `Unknown` so analyses can tell an
+    /// explicit escape hatch from a type Tyrus simply could not model.
+    Any,
+    /// `unknown`, `object`, and every type form Tyrus does not model yet.
+    Unknown,
+    /// `Date` (serialized as an ISO string).
+    Date,
+    /// `T[]` and `Array<T>`.
+    Array(Box<Type>),
+    /// `Record<K, V>`.
+    Record(Box<Type>, Box<Type>),
+    /// `Promise<T>`.
+    Promise(Box<Type>),
+    /// `T | undefined`, `T | null` and optional members (`x?: T`).
+    Optional(Box<Type>),
+    /// A union without `undefined`/`null` members.
+    Union(Vec<Type>),
+    /// A literal type such as `"circle"` or `42`.
+    Literal(LitType),
+    /// An inline object type: `{ kind: "circle"; r: number }`.
+    Object(Vec<PropSig>),
+    /// `[A, B]`.
+    Tuple(Vec<Type>),
+    /// `(a: A) => R`.
+    Function {
+        params: Vec<Type>,
+        ret: Box<Type>,
+    },
+    /// A type parameter in scope (`T` in `class Box<T>`).
+    Param(String),
+    /// A reference to a user-defined type (interface, class, alias, enum).
+    Named {
+        name: String,
+        args: Vec<Type>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LitType {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+}
+
+/// A property of an inline object type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropSig {
+    pub name: String,
+    pub ty: Type,
+    pub optional: bool,
+}
+
+impl Type {
+    pub fn named(name: impl Into<String>) -> Self {
+        Type::Named {
+            name: name.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Returns the inner type of `Promise<T>`, or the type itself otherwise.
+    pub fn unwrap_promise(&self) -> &Type {
+        match self {
+            Type::Promise(inner) => inner,
+            other => other,
+        }
+    }
+
+    pub fn is_optional(&self) -> bool {
+        matches!(self, Type::Optional(_))
+    }
+
+    pub fn is_void_or_promise_void(&self) -> bool {
+        matches!(self.unwrap_promise(), Type::Void)
+    }
+
+    /// Whether this is `number` or a numeric literal type.
+    pub fn is_number(&self) -> bool {
+        matches!(self, Type::Number | Type::Literal(LitType::Num(_)))
+    }
+
+    /// Whether values of this type are `Copy` in generated code.
+    pub fn is_copy(&self) -> bool {
+        match self {
+            Type::Number | Type::Bool => true,
+            Type::Literal(lit) => !matches!(lit, LitType::Str(_)),
+            Type::Optional(inner) => inner.is_copy(),
+            _ => false,
+        }
+    }
+
+    /// Name of the user-defined type this refers to, if any.
+    pub fn as_named(&self) -> Option<&str> {
+        match self {
+            Type::Named { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,309 @@
+//! Mutable traversal of the IR.
+//!
+//! Implementors override the `visit_*` hooks they care about and call the
+//! matching `walk_*` function to keep descending into children.
+
+use crate::decl::{ClassMember, CtorParam, Decl, Function, Item, Module};
+use crate::expr::{
+    ArrowBody, AssignTarget, Callee, Expr, ExprKind, MemberProp, ObjectPatProp, Pat, Prop, PropKey,
+};
+use crate::stmt::{ForHead, ForInit, Stmt, StmtKind, VarDecl};
+
+pub trait VisitMut {
+    fn visit_module_mut(&mut self, module: &mut Module) {
+        walk_module_mut(self, module);
+    }
+
+    fn visit_decl_mut(&mut self, decl: &mut Decl) {
+        walk_decl_mut(self, decl);
+    }
+
+    fn visit_function_mut(&mut self, function: &mut Function) {
+        walk_function_mut(self, function);
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        walk_stmt_mut(self, stmt);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        walk_expr_mut(self, expr);
+    }
+
+    fn visit_pat_mut(&mut self, pat: &mut Pat) {
+        walk_pat_mut(self, pat);
+    }
+}
+
+pub fn walk_module_mut<V: VisitMut + ?Sized>(v: &mut V, module: &mut Module) {
+    for item in &mut module.items {
+        match item {
+            Item::Import(_) => {}
+            Item::Decl { decl, .. } => v.visit_decl_mut(decl),
+            Item::Stmt(stmt) => v.visit_stmt_mut(stmt),
+        }
+    }
+}
+
+pub fn walk_decl_mut<V: VisitMut + ?Sized>(v: &mut V, decl: &mut Decl) {
+    match decl {
+        Decl::Fn(f) => v.visit_function_mut(&mut f.function),
+        Decl::Class(class) => {
+            for member in &mut class.members {
+                match member {
+                    ClassMember::Prop(prop) => {
+                        if let Some(value) = &mut prop.value {
+                            v.visit_expr_mut(value);
+                        }
+                    }
+                    ClassMember::Method(method) => v.visit_function_mut(&mut method.function),
+                    ClassMember::Constructor(ctor) => {
+                        for param in &mut ctor.params {
+                            if let CtorParam::Param(param) = param {
+                                v.visit_pat_mut(&mut param.pat);
+                            }
+                        }
+                        for stmt in ctor.body.iter_mut().flatten() {
+                            v.visit_stmt_mut(stmt);
+                        }
+                    }
+                }
+            }
+        }
+        Decl::Enum(e) => {
+            for member in &mut e.members {
+                if let Some(init) = &mut member.init {
+                    v.visit_expr_mut(init);
+                }
+            }
+        }
+        Decl::Var(var) => walk_var_decl_mut(v, var),
+        Decl::Interface(_) | Decl::TypeAlias(_) => {}
+    }
+}
+
+pub fn walk_function_mut<V: VisitMut + ?Sized>(v: &mut V, function: &mut Function) {
+    for param in &mut function.params {
+        v.visit_pat_mut(&mut param.pat);
+    }
+    for stmt in function.body.iter_mut().flatten() {
+        v.visit_stmt_mut(stmt);
+    }
+}
+
+fn walk_var_decl_mut<V: VisitMut + ?Sized>(v: &mut V, var: &mut VarDecl) {
+    for decl in &mut var.decls {
+        v.visit_pat_mut(&mut decl.pat);
+        if let Some(init) = &mut decl.init {
+            v.visit_expr_mut(init);
+        }
+    }
+}
+
+fn walk_for_head_mut<V: VisitMut + ?Sized>(v: &mut V, head: &mut ForHead) {
+    match head {
+        ForHead::Var(var) => walk_var_decl_mut(v, var),
+        ForHead::Pat(pat) => v.visit_pat_mut(pat),
+    }
+}
+
+pub fn walk_stmt_mut<V: VisitMut + ?Sized>(v: &mut V, stmt: &mut Stmt) {
+    match &mut stmt.kind {
+        StmtKind::Expr(expr) | StmtKind::Throw(expr) => v.visit_expr_mut(expr),
+        StmtKind::Return(arg) => {
+            if let Some(arg) = arg {
+                v.visit_expr_mut(arg);
+            }
+        }
+        StmtKind::Var(var) => walk_var_decl_mut(v, var),
+        StmtKind::Block(stmts) => {
+            for stmt in stmts {
+                v.visit_stmt_mut(stmt);
+            }
+        }
+        StmtKind::If { test, cons, alt } => {
+            v.visit_expr_mut(test);
+            v.visit_stmt_mut(cons);
+            if let Some(alt) = alt {
+                v.visit_stmt_mut(alt);
+            }
+        }
+        StmtKind::While { test, body } | StmtKind::DoWhile { body, test } => {
+            v.visit_expr_mut(test);
+            v.visit_stmt_mut(body);
+        }
+        StmtKind::For {
+            init,
+            test,
+            update,
+            body,
+        } => {
+            match init {
+                Some(ForInit::Var(var)) => walk_var_decl_mut(v, var),
+                Some(ForInit::Expr(expr)) => v.visit_expr_mut(expr),
+                None => {}
+            }
+            if let Some(test) = test {
+                v.visit_expr_mut(test);
+            }
+            if let Some(update) = update {
+                v.visit_expr_mut(update);
+            }
+            v.visit_stmt_mut(body);
+        }
+        StmtKind::ForOf { left, right, body } | StmtKind::ForIn { left, right, body } => {
+            walk_for_head_mut(v, left);
+            v.visit_expr_mut(right);
+            v.visit_stmt_mut(body);
+        }
+        StmtKind::Try {
+            block,
+            handler,
+            finalizer,
+        } => {
+            for stmt in block {
+                v.visit_stmt_mut(stmt);
+            }
+            if let Some(handler) = handler {
+                if let Some(param) = &mut handler.param {
+                    v.visit_pat_mut(param);
+                }
+                for stmt in &mut handler.body {
+                    v.visit_stmt_mut(stmt);
+                }
+            }
+            for stmt in finalizer.iter_mut().flatten() {
+                v.visit_stmt_mut(stmt);
+            }
+        }
+        StmtKind::Switch {
+            discriminant,
+            cases,
+        } => {
+            v.visit_expr_mut(discriminant);
+            for case in cases {
+                if let Some(test) = &mut case.test {
+                    v.visit_expr_mut(test);
+                }
+                for stmt in &mut case.cons {
+                    v.visit_stmt_mut(stmt);
+                }
+            }
+        }
+        StmtKind::Match { scrutinee, arms } => {
+            v.visit_expr_mut(scrutinee);
+            for arm in arms {
+                for stmt in &mut arm.body {
+                    v.visit_stmt_mut(stmt);
+                }
+            }
+        }
+        StmtKind::Decl(decl) => v.visit_decl_mut(decl),
+        StmtKind::Break | StmtKind::Continue | StmtKind::Empty | StmtKind::Unsupported { .. } => {}
+    }
+}
+
+pub fn walk_expr_mut<V: VisitMut + ?Sized>(v: &mut V, expr: &mut Expr) {
+    match &mut expr.kind {
+        ExprKind::Ident(_)
+        | ExprKind::This
+        | ExprKind::Super
+        | ExprKind::Lit(_)
+        | ExprKind::Unsupported { .. } => {}
+        ExprKind::Tpl { exprs, .. } | ExprKind::Seq(exprs) => {
+            for expr in exprs {
+                v.visit_expr_mut(expr);
+            }
+        }
+        ExprKind::Array(elems) => {
+            for elem in elems.iter_mut().flatten() {
+                v.visit_expr_mut(&mut elem.expr);
+            }
+        }
+        ExprKind::Object(props) => {
+            for prop in props {
+                if let Prop::KeyValue { key, value } = prop {
+                    if let PropKey::Computed(key) = key {
+                        v.visit_expr_mut(key);
+                    }
+                    v.visit_expr_mut(value);
+                }
+            }
+        }
+        ExprKind::Unary { arg, .. } | ExprKind::Update { arg, .. } => v.visit_expr_mut(arg),
+        ExprKind::Paren(inner) | ExprKind::Await(inner) => v.visit_expr_mut(inner),
+        ExprKind::Bin { left, right, .. } => {
+            v.visit_expr_mut(left);
+            v.visit_expr_mut(right);
+        }
+        ExprKind::Assign { target, right, .. } => {
+            if let AssignTarget::Member(member) = target {
+                v.visit_expr_mut(&mut member.obj);
+                if let MemberProp::Computed(prop) = &mut member.prop {
+                    v.visit_expr_mut(prop);
+                }
+            }
+            v.visit_expr_mut(right);
+        }
+        ExprKind::Member(member) | ExprKind::OptMember(member) => {
+            v.visit_expr_mut(&mut member.obj);
+            if let MemberProp::Computed(prop) = &mut member.prop {
+                v.visit_expr_mut(prop);
+            }
+        }
+        ExprKind::Cond { test, cons, alt } => {
+            v.visit_expr_mut(test);
+            v.visit_expr_mut(cons);
+            v.visit_expr_mut(alt);
+        }
+        ExprKind::Call(call) | ExprKind::New(call) | ExprKind::OptCall(call) => {
+            if let Callee::Expr(callee) = &mut call.callee {
+                v.visit_expr_mut(callee);
+            }
+            for arg in &mut call.args {
+                v.visit_expr_mut(&mut arg.expr);
+            }
+        }
+        ExprKind::Arrow(arrow) => {
+            for param in &mut arrow.params {
+                v.visit_pat_mut(param);
+            }
+            match &mut arrow.body {
+                ArrowBody::Block(stmts) => {
+                    for stmt in stmts {
+                        v.visit_stmt_mut(stmt);
+                    }
+                }
+                ArrowBody::Expr(body) => v.visit_expr_mut(body),
+            }
+        }
+    }
+}
+
+pub fn walk_pat_mut<V: VisitMut + ?Sized>(v: &mut V, pat: &mut Pat) {
+    match pat {
+        Pat::Ident(_) | Pat::Unsupported => {}
+        Pat::Object(props) => {
+            for prop in props {
+                match prop {
+                    ObjectPatProp::KeyValue { key, value } => {
+                        if let PropKey::Computed(key) = key {
+                            v.visit_expr_mut(key);
+                        }
+                        v.visit_pat_mut(value);
+                    }
+                    ObjectPatProp::Assign {
+                        default: Some(default),
+                        ..
+                    } => v.visit_expr_mut(default),
+                    ObjectPatProp::Assign { default: None, .. } | ObjectPatProp::Rest => {}
+                }
+            }
+        }
+        Pat::Array(elems) => {
+            for elem in elems.iter_mut().flatten() {
+                v.visit_pat_mut(elem);
+            }
+        }
+    }
+}
@@ -1,7 +1,11 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use miette::Result;
 use std::path::PathBuf;
+use tyrus_analyzer::lints::{Lint, LintLevel};
 use tyrus_common::fs::FilePath;
+use tyrus_diagnostics::report::{self, Record};
+use tyrus_diagnostics::{MessageFormat, TyrusError};
+use tyrus_orchestrator::FixMode;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -12,40 +16,181 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Check the input file for errors
+    /// Check the input file, or every file of a project, for errors
     Check {
-        /// Input file path
+        /// Input file or directory path
         path: PathBuf,
+        /// How to print diagnostics: human, json or sarif
+        #[arg(long, value_name = "FORMAT", default_value = "human")]
+        message_format: MessageFormat,
+        #[command(flatten)]
+        lints: LintArgs,
+        /// Rewrite the sources to resolve the lints that have a mechanical fix
+        #[arg(long)]
+        fix: bool,
+        /// With --fix, print the diff of the rewrite instead of writing it
+        #[arg(long, requires = "fix", conflicts_with = "message_format")]
+        dry_run: bool,
     },
     /// Build the output Rust code
     Build {
         /// Input file or directory path
         path: PathBuf,
-        /// Output directory path (default: ./tyrus_output)
+        /// Output directory path (default: ./tyrus_output), or output file of a
+        /// single-file build (default: stdout)
         #[arg(short, long)]
         output: Option<PathBuf>,
+        /// Emit `todo!()` for constructs Tyrus cannot translate instead of failing
+        #[arg(long)]
+        allow_todo: bool,
+        /// Skip the lints `check` runs instead of failing on them
+        #[arg(long)]
+        no_lint: bool,
+        /// Run `cargo check` on the generated crate and report its errors on the TypeScript
+        #[arg(long)]
+        verify: bool,
+        /// Precede generated items and statements with `// ts: file:line` comments
+        #[arg(long)]
+        provenance: bool,
+        /// Write a source map v3 next to each generated file, as `file.rs.map`
+        #[arg(long)]
+        source_map: bool,
+        /// How to print diagnostics: human, json or sarif
+        #[arg(long, value_name = "FORMAT", default_value = "human")]
+        message_format: MessageFormat,
+        #[command(flatten)]
+        lints: LintArgs,
     },
 }
 
+/// Lint levels over those of `tyrus.toml`; `--deny` wins over `--warn`,
+/// which wins over `--allow`.
+#[derive(Args)]
+struct LintArgs {
+    /// Do not report LINT (e.g. no-any)
+    #[arg(long = "allow", value_name = "LINT")]
+    allow: Vec<Lint>,
+    /// Report LINT as a warning
+    #[arg(long = "warn", value_name = "LINT")]
+    warn: Vec<Lint>,
+    /// Report LINT as an error
+    #[arg(long = "deny", value_name = "LINT")]
+    deny: Vec<Lint>,
+}
+
+impl LintArgs {
+    fn levels(self) -> Vec<(Lint, LintLevel)> {
+        let with = |lints: Vec<Lint>, level| lints.into_iter().map(move |lint| (lint, level));
+        with(self.allow, LintLevel::Allow)
+            .chain(with(self.warn, LintLevel::Warn))
+            .chain(with(self.deny, LintLevel::Deny))
+            .collect()
+    }
+}
+
+impl Commands {
+    fn message_format(&self) -> MessageFormat {
+        match self {
+            Commands::Check { message_format, .. } | Commands::Build { message_format, .. } => {
+                *message_format
+            }
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize miette
     miette::set_panic_hook();
 
     let cli = Cli::parse();
+    let message_format = cli.command.message_format();
+    let is_build = matches!(cli.command, Commands::Build { .. });
+
+    if let Err(error) = run(cli.command) {
+        if message_format == MessageFormat::Human {
+            return Err(error.into());
+        }
+        // `build` and `check` have printed their diagnostics already, unless
+        // the arguments were rejected before building
+        let printed = match error {
+            TyrusError::CheckFailed { .. } => true,
+            TyrusError::OutputRequired { .. } => false,
+            _ => is_build,
+        };
+        if !printed {
+            let records = Record::collect(&error);
+            match message_format {
+                MessageFormat::Json => print!("{}", report::json_lines(&records)),
+                _ => println!("{}", report::sarif(&records)),
+            }
+        }
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
 
-    match cli.command {
-        Commands::Check { path } => {
-            tyrus_orchestrator::check(FilePath::from(path))?;
+fn run(command: Commands) -> std::result::Result<(), TyrusError> {
+    match command {
+        Commands::Check {
+            path,
+            message_format,
+            lints,
+            fix,
+            dry_run,
+        } => {
+            let fix = match (fix, dry_run) {
+                (false, _) => FixMode::Off,
+                (true, false) => FixMode::Apply,
+                (true, true) => FixMode::DryRun,
+            };
+            let options = tyrus_orchestrator::CheckOptions {
+                message_format,
+                lints: lints.levels(),
+                fix,
+            };
+            tyrus_orchestrator::check_with_options(FilePath::from(path), &options)?;
         }
-        Commands::Build { path, output } => {
+        Commands::Build {
+            path,
+            output,
+            allow_todo,
+            no_lint,
+            verify,
+            provenance,
+            source_map,
+            message_format,
+            lints,
+        } => {
+            let options = tyrus_orchestrator::BuildOptions {
+                message_format,
+                allow_todo,
+                no_lint,
+                lints: lints.levels(),
+                verify,
+                provenance,
+                source_map,
+            };
             if path.is_dir() {
                 let output_dir = output.unwrap_or_else(|| PathBuf::from("./tyrus_output"));
-                tyrus_orchestrator::build_project(path, output_dir)?;
-                println!("✅ Project built successfully!");
+                tyrus_orchestrator::build_project_with_options(path, output_dir, &options)?;
+                if message_format == MessageFormat::Human {
+                    println!("✅ Project built successfully!");
+                }
             } else {
-                let output_code = tyrus_orchestrator::build(FilePath::from(path))?;
-                println!("{}", output_code);
+                // JSON and SARIF output must parse as a whole
+                if message_format != MessageFormat::Human && output.is_none() {
+                    return Err(TyrusError::OutputRequired {
+                        format: message_format,
+                    });
+                }
+                let output_code =
+                    tyrus_orchestrator::build_with_options(FilePath::from(path), &options)?;
+                match output {
+                    Some(output_file) => std::fs::write(output_file, output_code)?,
+                    None => println!("{}", output_code),
+                }
             }
         }
     }
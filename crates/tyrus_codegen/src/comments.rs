@@ -0,0 +1,255 @@
+//! Documentation and comments carried over from the TypeScript.
+//!
+//! JSDoc becomes `#[doc]` and `#[deprecated]` attributes, and statement
+//! comments become `__tyrus_comment!("...");` markers, since token streams
+//! cannot hold comments. [`render`] turns both into `///` and `//` comments
+//! once the code is formatted.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use tyrus_ast::Doc;
+
+use crate::convert::context::Context;
+
+const MARKER: &str = "__tyrus_comment";
+
+/// The rustdoc of `doc`: its description, then `# Arguments` and `# Returns`
+/// sections for `@param` and `@returns`, and `#[deprecated]` for
+/// `@deprecated`.
+pub fn doc_attrs(cx: &Context, doc: Option<&Doc>) -> TokenStream {
+    let Some(doc) = doc else {
+        return TokenStream::new();
+    };
+    let mut sections: Vec<Vec<String>> = Vec::new();
+    if !doc.lines.is_empty() {
+        sections.push(doc.lines.clone());
+    }
+    if !doc.params.is_empty() {
+        sections.push(vec!["# Arguments".to_string()]);
+        sections.push(
+            doc.params
+                .iter()
+                .map(|(name, text)| match text.as_str() {
+                    "" => format!("* `{}`", cx.snake_case(name)),
+                    text => format!("* `{}` - {}", cx.snake_case(name), text),
+                })
+                .collect(),
+        );
+    }
+    if let Some(returns) = doc.returns.as_ref().filter(|text| !text.is_empty()) {
+        sections.push(vec!["# Returns".to_string()]);
+        sections.push(vec![returns.clone()]);
+    }
+    let lines = sections.join(&String::new());
+    let docs = lines.iter().map(|line| {
+        let line = if line.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", line)
+        };
+        quote! { #[doc = #line] }
+    });
+    let deprecated = match doc.deprecated.as_deref() {
+        None => quote! {},
+        Some("") => quote! { #[deprecated] },
+        Some(note) => quote! { #[deprecated(note = #note)] },
+    };
+    quote! { #(#docs)* #deprecated }
+}
+
+/// Markers for `lines`, the comments above a statement.
+pub fn markers(lines: &[String]) -> TokenStream {
+    let markers = lines.iter().map(|line| quote! { __tyrus_comment!(#line); });
+    quote! { #(#markers)* }
+}
+
+/// `code` with `#[doc = "..."]` lines as `///` comments and comment markers
+/// as `//` comments, or `/* */` where the marker shares its line.
+pub fn render(code: &str) -> String {
+    let mut rendered = String::with_capacity(code.len());
+    for line in render_markers(code).split_inclusive('\n') {
+        let content = line.trim_end_matches('\n');
+        let trimmed = content.trim_start();
+        let indent = &content[..content.len() - trimmed.len()];
+        match trimmed
+            .strip_prefix("#[doc = \"")
+            .and_then(|rest| rest.strip_suffix("\"]"))
+        {
+            Some(text) => {
+                let newline = &line[content.len()..];
+                rendered.push_str(&format!("{}///{}{}", indent, unescape(text), newline));
+            }
+            None => rendered.push_str(line),
+        }
+    }
+    rendered
+}
+
+/// Renders the markers of `code`. Formatters may break a long marker over
+/// several lines, so markers are found in the whole text rather than line
+/// by line.
+fn render_markers(code: &str) -> String {
+    let mut rendered = String::with_capacity(code.len());
+    let mut rest = code;
+    while let Some(start) = rest.find(MARKER) {
+        let Some((text, after)) = parse_marker(&rest[start..]) else {
+            rendered.push_str(&rest[..start + MARKER.len()]);
+            rest = &rest[start + MARKER.len()..];
+            continue;
+        };
+        let before = &rest[..start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let text = unescape(text);
+        rendered.push_str(before);
+        if before[line_start..].trim().is_empty() && after.trim_start_matches(' ').starts_with('\n')
+        {
+            rendered.push_str(if text.is_empty() { "//" } else { "// " });
+            rendered.push_str(&text);
+            rest = after.trim_start_matches(' ');
+        } else {
+            rendered.push_str(&format!("/* {} */ ", text.replace("*/", "* /")));
+            rest = after.trim_start();
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// The escaped text of the marker `text` starts with, and what follows it.
+fn parse_marker(text: &str) -> Option<(&str, &str)> {
+    let rest = text.strip_prefix(MARKER)?.trim_start();
+    let rest = rest.strip_prefix('!')?.trim_start();
+    let rest = rest.strip_prefix('(')?.trim_start();
+    let rest = rest.strip_prefix('"')?;
+    let end = string_end(rest)?;
+    let after = rest[end + 1..].trim_start().strip_prefix(')')?;
+    let after = after.trim_start().strip_prefix(';')?;
+    Some((&rest[..end], after))
+}
+
+/// The index of the `"` closing the string literal `text` is the inside of.
+fn string_end(text: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(i),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// The value of the inside of a Rust string literal.
+fn unescape(text: &str) -> String {
+    let mut value = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('t') => value.push('\t'),
+            Some('r') => value.push('\r'),
+            Some('0') => value.push('\0'),
+            Some('u') => {
+                let code: String = chars
+                    .by_ref()
+                    .skip_while(|&c| c == '{')
+                    .take_while(|&c| c != '}')
+                    .collect();
+                if let Some(c) = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                    value.push(c);
+                }
+            }
+            Some(c) => value.push(c),
+            None => {}
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Naming;
+
+    #[test]
+    fn test_doc_attrs() {
+        let doc = Doc {
+            lines: vec!["Finds a user.".to_string()],
+            params: vec![("userId".to_string(), "The id.".to_string())],
+            returns: Some("The user.".to_string()),
+            deprecated: Some("use `find`".to_string()),
+        };
+        let cx = Context::default();
+        assert_eq!(
+            attrs(doc_attrs(&cx, Some(&doc))),
+            vec![
+                "doc = \" Finds a user.\"",
+                "doc = \"\"",
+                "doc = \" # Arguments\"",
+                "doc = \"\"",
+                "doc = \" * `user_id` - The id.\"",
+                "doc = \"\"",
+                "doc = \" # Returns\"",
+                "doc = \"\"",
+                "doc = \" The user.\"",
+                "deprecated (note = \"use `find`\")",
+            ]
+        );
+        assert!(doc_attrs(&cx, None).is_empty());
+
+        let preserve = Context {
+            naming: Naming::Preserve,
+            ..Default::default()
+        };
+        assert!(attrs(doc_attrs(&preserve, Some(&doc)))
+            .contains(&"doc = \" * `userId` - The id.\"".to_string()));
+    }
+
+    fn attrs(tokens: TokenStream) -> Vec<String> {
+        tokens
+            .into_iter()
+            .filter_map(|token| match token {
+                proc_macro2::TokenTree::Group(group) => Some(group.stream().to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render() {
+        let code = r#"#[doc = " Says \"hi\"."]
+#[doc = ""]
+pub fn f() {
+    __tyrus_comment!("first");
+    __tyrus_comment!("");
+    g();
+}
+fn h () { __tyrus_comment ! ("inline */ here") ; g () ; }
+fn i() {
+    __tyrus_comment!(
+        "a comment too long for one line"
+    );
+}
+"#;
+        assert_eq!(
+            render(code),
+            r#"/// Says "hi".
+///
+pub fn f() {
+    // first
+    //
+    g();
+}
+fn h () { /* inline * / here */ g () ; }
+fn i() {
+    // a comment too long for one line
+}
+"#
+        );
+    }
+}
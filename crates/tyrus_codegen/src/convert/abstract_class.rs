@@ -0,0 +1,229 @@
+//! `abstract class A` becomes `trait A`: abstract methods are required and
+//! concrete ones are default methods. Fields live in an `AState` struct that
+//! implementors embed as `base` and deref to, which the trait requires.
+//!
+//! A concrete subclass implements the trait by delegating to its own methods.
+
+use std::collections::HashMap;
+
+use quote::{format_ident, quote};
+use tyrus_analyzer::typeck::substitute;
+use tyrus_ast::{ClassDecl, ClassMember, ClassMethod, CtorParam, Pat, Type};
+
+use super::inheritance::{is_overridable, trait_method_sig};
+use super::interface::RustGenerator;
+use super::type_mapper::trait_path;
+use crate::comments;
+
+pub fn state_name(class_name: &str) -> String {
+    format!("{}State", class_name)
+}
+
+/// Whether the abstract class has anything for a state struct to hold.
+fn has_state(class: &ClassDecl) -> bool {
+    class.members.iter().any(|member| match member {
+        ClassMember::Prop(_) => true,
+        ClassMember::Method(method) => method.is_static,
+        ClassMember::Constructor(cons) => cons
+            .params
+            .iter()
+            .any(|param| matches!(param, CtorParam::Prop { .. })),
+    })
+}
+
+/// The `AState` struct of an abstract class: its fields, constructor and
+/// static methods, as a plain class. `None` when there is nothing to hold.
+pub fn state_decl(class: &ClassDecl) -> Option<ClassDecl> {
+    if !class.is_abstract || !has_state(class) {
+        return None;
+    }
+    Some(ClassDecl {
+        name: state_name(&class.name),
+        type_params: class.type_params.clone(),
+        decorators: Vec::new(),
+        super_class: class.super_class.clone(),
+        implements: Vec::new(),
+        is_abstract: false,
+        members: class
+            .members
+            .iter()
+            .filter(|member| !matches!(member, ClassMember::Method(m) if !m.is_static))
+            .cloned()
+            .collect(),
+        span: class.span,
+        doc: None,
+    })
+}
+
+/// `method` with the parent's type parameters replaced per `subst`.
+fn substitute_method(method: &ClassMethod, subst: &HashMap<String, Type>) -> ClassMethod {
+    let mut method = method.clone();
+    for param in &mut method.function.params {
+        if let Pat::Ident(binding) = &mut param.pat {
+            binding.ty = binding.ty.as_ref().map(|ty| substitute(ty, subst));
+        }
+    }
+    method.function.return_type = method
+        .function
+        .return_type
+        .as_ref()
+        .map(|ty| substitute(ty, subst));
+    method
+}
+
+/// Instance methods of an abstract class, which all belong to its trait.
+/// Abstract methods can't be marked `async`, so returning a `Promise` makes
+/// them async in the trait.
+fn trait_methods(class: &ClassDecl) -> impl Iterator<Item = ClassMethod> + '_ {
+    class.methods().filter(|m| !m.is_static).map(|m| {
+        let mut m = m.clone();
+        if m.is_abstract && matches!(m.function.return_type, Some(Type::Promise(_))) {
+            m.function.is_async = true;
+        }
+        m
+    })
+}
+
+impl RustGenerator {
+    /// The superclass of `class` when it is abstract and declared in the project.
+    pub fn abstract_parent(&self, class: &ClassDecl) -> Option<&ClassDecl> {
+        class
+            .super_class_name()
+            .and_then(|name| self.classes.get(name))
+            .filter(|parent| parent.is_abstract)
+    }
+
+    /// What `class` embeds as `base`: an abstract superclass's state struct
+    /// (nothing if it has none), otherwise the superclass itself.
+    pub fn base_type(&self, class: &ClassDecl) -> Option<Type> {
+        match (self.abstract_parent(class), &class.super_class) {
+            (Some(parent), Some(Type::Named { args, .. })) => {
+                state_decl(parent).map(|state| Type::Named {
+                    name: state.name,
+                    args: args.clone(),
+                })
+            }
+            _ => class.super_class.clone(),
+        }
+    }
+
+    /// Emits the state struct and the trait of an abstract class.
+    pub fn process_abstract_class(&mut self, n: &ClassDecl) {
+        let state = state_decl(n);
+        if let Some(state) = &state {
+            self.process_class_decl(state);
+        }
+
+        let trait_name = format_ident!("{}", n.name);
+        let params: Vec<_> = n
+            .type_params
+            .iter()
+            .map(|p| format_ident!("{}", p.name))
+            .collect();
+        let generics = if params.is_empty() {
+            quote! {}
+        } else {
+            quote! { <#(#params),*> }
+        };
+
+        let methods: Vec<_> = trait_methods(n).collect();
+        let is_async = methods.iter().any(|m| m.function.is_async);
+        let mut bounds = Vec::new();
+        if let Some(state) = &state {
+            let state_ident = format_ident!("{}", state.name);
+            bounds.push(quote! { std::ops::Deref<Target = #state_ident #generics> });
+        }
+        // Like interface traits, so shared `Arc<dyn A>` values can be injected
+        bounds.push(quote! { Send });
+        bounds.push(quote! { Sync });
+        let attr = if is_async {
+            quote! { #[axum::async_trait] }
+        } else {
+            quote! {}
+        };
+        let bounds = quote! { : #(#bounds)+* };
+        let items: Vec<_> = methods
+            .iter()
+            .map(|m| self.convert_method(m, true).0)
+            .collect();
+
+        let vis = if self.is_exporting {
+            quote! { pub }
+        } else {
+            quote! {}
+        };
+        let doc = comments::doc_attrs(&self.cx, n.doc.as_ref());
+        let tokens = quote! {
+            #doc
+            #attr
+            #vis trait #trait_name #generics #bounds {
+                #(#items)*
+            }
+        };
+        self.code.push_str(&tokens.to_string());
+        self.code.push('\n');
+    }
+
+    /// Emits `impl A for Class` when `class` concretely extends the abstract
+    /// class `A`. Abstract methods and overridden ones call the class's own.
+    pub fn process_abstract_impl(&mut self, class: &ClassDecl) {
+        let cx = &self.cx;
+        let Some(parent) = self.abstract_parent(class) else {
+            return;
+        };
+        let Some(Type::Named { name, args }) = &class.super_class else {
+            return;
+        };
+        if class.is_abstract || !class.type_params.is_empty() {
+            return;
+        }
+        let subst: HashMap<_, _> = parent
+            .type_params
+            .iter()
+            .map(|p| p.name.clone())
+            .zip(args.iter().cloned())
+            .collect();
+
+        let struct_name = format_ident!("{}", class.name);
+        let methods: Vec<_> = trait_methods(parent)
+            .filter(|m| m.is_abstract || is_overridable(m))
+            .filter(|m| {
+                class
+                    .methods()
+                    .any(|own| own.name == m.name && !own.is_static)
+            })
+            .map(|m| substitute_method(&m, &subst))
+            .collect();
+        let is_async = trait_methods(parent).any(|m| m.function.is_async);
+        let items = methods.iter().map(|m| {
+            let (sig, args) = trait_method_sig(cx, m, false);
+            let method_name = format_ident!("{}", cx.snake_case(&m.name));
+            let call = quote! { #struct_name::#method_name(self, #(#args),*) };
+            let call = if m.function.is_async {
+                quote! { #call.await }
+            } else {
+                call
+            };
+            quote! {
+                #sig {
+                    #call
+                }
+            }
+        });
+
+        let attr = if is_async {
+            quote! { #[axum::async_trait] }
+        } else {
+            quote! {}
+        };
+        let path = trait_path(cx, name, args);
+        let tokens = quote! {
+            #attr
+            impl #path for #struct_name {
+                #(#items)*
+            }
+        };
+        self.code.push_str(&tokens.to_string());
+        self.code.push('\n');
+    }
+}
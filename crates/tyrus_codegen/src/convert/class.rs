@@ -1,24 +1,125 @@
 use quote::{format_ident, quote};
-use swc_ecma_ast::{
-    AssignTarget, ClassDecl, ClassMember, Constructor, Expr, ExprStmt, Lit, Pat, Stmt,
+use tyrus_ast::{
+    Accessibility, Arg, AssignTarget, Callee, ClassDecl, ClassMember, ClassMethod, ClassProp,
+    Constructor, CtorParam, Expr, ExprKind, MethodKind, Pat, StmtKind, Type,
 };
 
-use super::func::{convert_expr_pub, convert_stmt_pub, to_snake_case};
+use super::context::Context;
+use super::func::{
+    convert_expr, convert_stmt, convert_stmt_recursive, convert_value, mutability, stmt_markers,
+};
+use super::inheritance::base_field;
 use super::interface::RustGenerator;
-use super::type_mapper::{is_optional_type, map_ts_type};
+use super::members::{convert_static_prop, StaticItem};
+use super::type_mapper::{map_param_type, map_type_opt, unwrap_promise_type};
+use crate::comments;
+
+/// Heuristic: a reference to a user-defined type (not a primitive) is an injected
+/// dependency and gets wrapped in `Arc`.
+fn is_dependency_type(ty: Option<&Type>) -> bool {
+    match ty {
+        Some(Type::Named { name, .. }) => !matches!(
+            name.as_str(),
+            "String" | "f64" | "bool" | "i32" | "Vec" | "Option"
+        ),
+        _ => false,
+    }
+}
+
+/// Dependencies are shared through `Arc`; trait types already map to
+/// `Arc<dyn Trait>`.
+fn shared(
+    cx: &Context,
+    ty: Option<&Type>,
+    mapped: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if ty.is_some_and(|ty| cx.is_trait_type(ty)) {
+        mapped
+    } else {
+        quote! { std::sync::Arc<#mapped> }
+    }
+}
+
+/// `private` and `#name` members are not `pub`.
+fn member_vis(
+    accessibility: Option<Accessibility>,
+    is_private_name: bool,
+) -> proc_macro2::TokenStream {
+    if is_private_name || accessibility == Some(Accessibility::Private) {
+        quote! {}
+    } else {
+        quote! { pub }
+    }
+}
+
+const HTTP_METHOD_DECORATORS: [&str; 5] = ["Get", "Post", "Put", "Delete", "Patch"];
+
+/// Whether the method is a NestJS route handler (`@Get()`, `@Post()`, ...).
+pub fn is_route_handler(method: &ClassMethod) -> bool {
+    method
+        .function
+        .decorators
+        .iter()
+        .any(|d| HTTP_METHOD_DECORATORS.contains(&d.name.as_str()))
+}
+
+/// Whether `new_di` takes this constructor parameter.
+fn is_di_param(param: &CtorParam) -> bool {
+    match param {
+        CtorParam::Prop { .. } => true,
+        CtorParam::Param(param) => match &param.pat {
+            Pat::Ident(binding) => is_dependency_type(binding.ty.as_ref()),
+            _ => false,
+        },
+    }
+}
+
+/// Parameters of `constructor` for `new` and `new_di`, with the argument
+/// idents that forward them.
+fn constructor_signature(
+    cx: &Context,
+    constructor: &Constructor,
+) -> (
+    Vec<proc_macro2::TokenStream>,
+    Vec<proc_macro2::Ident>,
+    Vec<proc_macro2::TokenStream>,
+    Vec<proc_macro2::Ident>,
+) {
+    let (mut params, mut args, mut di_params, mut di_args) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for param in &constructor.params {
+        let (name, ty) = match param {
+            CtorParam::Prop { name, ty, .. } => (name, ty.as_ref()),
+            CtorParam::Param(param) => match &param.pat {
+                Pat::Ident(binding) => (&binding.name, binding.ty.as_ref()),
+                _ => continue,
+            },
+        };
+        let param_name = format_ident!("{}", cx.snake_case(name));
+        let mut param_type = map_type_opt(cx, ty);
+        if is_dependency_type(ty) {
+            param_type = shared(cx, ty, param_type);
+        }
+        params.push(quote! { #param_name: #param_type });
+        args.push(param_name.clone());
+        if is_di_param(param) {
+            di_params.push(quote! { #param_name: #param_type });
+            di_args.push(param_name);
+        }
+    }
+    (params, args, di_params, di_args)
+}
 
 impl RustGenerator {
     pub fn process_class_decl(&mut self, n: &ClassDecl) {
-        let class_name = n.ident.sym.to_string();
-        let struct_name = format_ident!("{}", class_name);
-
-        // Extract generic params early
-        let mut generic_params = std::collections::HashSet::new();
-        if let Some(type_params) = &n.class.type_params {
-            for param in &type_params.params {
-                generic_params.insert(param.name.sym.to_string());
-            }
+        let cx = &self.cx;
+        if n.is_abstract {
+            self.process_abstract_class(n);
+            return;
         }
+        let base = self.base_type(n);
+        let class_name = n.name.clone();
+        let struct_name = format_ident!("{}", class_name);
 
         // 1. Generate Struct (Properties)
         let mut fields = Vec::new();
@@ -27,18 +128,28 @@ impl RustGenerator {
         let mut class_fields_meta = Vec::new();
 
         let mut dependency_fields = std::collections::HashSet::new();
+        let vis = if self.is_exporting {
+            quote! { pub }
+        } else {
+            quote! {}
+        };
+        let mut consts = Vec::new();
+        let mut lazy_statics = Vec::new();
 
-        for member in &n.class.body {
+        for member in &n.members {
             match member {
-                ClassMember::ClassProp(prop) => {
-                    if let Some((field_tokens, name, is_opt, is_dep)) =
-                        self.convert_prop(prop, &generic_params)
-                    {
-                        fields.push(field_tokens);
-                        class_fields_meta.push((name.clone(), is_opt));
-                        if is_dep {
-                            dependency_fields.insert(name);
-                        }
+                ClassMember::Prop(prop) if prop.is_static => {
+                    match convert_static_prop(cx, &class_name, prop, &vis) {
+                        StaticItem::Const(tokens) => consts.push(tokens),
+                        StaticItem::Static(tokens) => lazy_statics.push(tokens),
+                    }
+                }
+                ClassMember::Prop(prop) => {
+                    let (field_tokens, name, is_opt, is_dep) = self.convert_prop(prop);
+                    fields.push(field_tokens);
+                    class_fields_meta.push((name.clone(), is_opt));
+                    if is_dep {
+                        dependency_fields.insert(name);
                     }
                 }
                 ClassMember::Method(method) => {
@@ -47,106 +158,66 @@ impl RustGenerator {
                 ClassMember::Constructor(cons) => {
                     constructor = Some(cons);
                 }
-                _ => {}
             }
         }
 
         // Collect fields from constructor (private/public params)
         if let Some(cons) = constructor {
             for param in &cons.params {
-                if let swc_ecma_ast::ParamOrTsParamProp::TsParamProp(prop) = param {
-                    if let swc_ecma_ast::TsParamPropParam::Ident(ident) = &prop.param {
-                        let field_name_str = ident.sym.to_string();
-                        let field_name = format_ident!("{}", to_snake_case(&field_name_str));
-
-                        let type_ann = ident.type_ann.as_ref();
-                        let mut field_type = map_ts_type(type_ann);
-
-                        // Heuristic: If it's a TypeRef (not primitive), wrap in Arc
-                        let is_dependency = if let Some(ann) = type_ann {
-                            if let Some(type_ref) = ann.type_ann.as_ts_type_ref() {
-                                if let Some(ident) = type_ref.type_name.as_ident() {
-                                    let name = ident.sym.as_str();
-                                    !matches!(
-                                        name,
-                                        "String"
-                                            | "f64"
-                                            | "bool"
-                                            | "i32"
-                                            | "Vec"
-                                            | "Option"
-                                            | "Array"
-                                    )
-                                } else {
-                                    true
-                                }
-                            } else {
-                                false
-                            }
-                        } else {
-                            false
-                        };
-
-                        if is_dependency {
-                            field_type = quote! { std::sync::Arc<#field_type> };
-                            dependency_fields.insert(field_name_str.clone());
-                        }
+                if let CtorParam::Prop {
+                    name,
+                    ty,
+                    accessibility,
+                    ..
+                } = param
+                {
+                    let field_name = format_ident!("{}", cx.snake_case(name));
+                    let mut field_type = map_type_opt(cx, ty.as_ref());
 
-                        fields.push(quote! { pub #field_name: #field_type });
+                    if is_dependency_type(ty.as_ref()) {
+                        field_type = shared(cx, ty.as_ref(), field_type);
+                        dependency_fields.insert(name.clone());
                     }
+
+                    let vis = member_vis(*accessibility, false);
+                    fields.push(quote! { #vis #field_name: #field_type });
                 }
             }
         }
 
-        let vis = if self.is_exporting {
-            quote! { pub }
-        } else {
-            quote! {}
-        };
+        let has_own_fields = !fields.is_empty();
+        if let Some(base) = &base {
+            fields.insert(0, base_field(cx, base));
+        }
 
-        let (generics_struct_decl, generics_impl_decl, generics_use) = if let Some(type_params) =
-            &n.class.type_params
-        {
-            let params_struct: Vec<_> = type_params
-                .params
+        let has_generics = !n.type_params.is_empty();
+        let (generics_struct_decl, generics_impl_decl, generics_use) = if has_generics {
+            let params_use: Vec<_> = n
+                .type_params
                 .iter()
-                .map(|p| {
-                    let name = p.name.sym.to_string();
-                    format_ident!("{}", name)
-                })
+                .map(|p| format_ident!("{}", p.name))
                 .collect();
 
-            let params_impl: Vec<_> = type_params
-                .params
+            let params_impl: Vec<_> = params_use
                 .iter()
-                .map(|p| {
-                    let name = p.name.sym.to_string();
-                    let ident = format_ident!("{}", name);
+                .map(|ident| {
                     quote! { #ident: serde::de::DeserializeOwned + serde::Serialize + Clone + Default + std::fmt::Debug }
                 })
                 .collect();
 
-            let params_use: Vec<_> = type_params
-                .params
-                .iter()
-                .map(|p| format_ident!("{}", p.name.sym.to_string()))
-                .collect();
-
             // Add PhantomData to usage to avoid unused type parameter error
-            if !params_use.is_empty() {
-                let phantom_type = if params_use.len() == 1 {
-                    quote! { #(#params_use)* }
-                } else {
-                    quote! { (#(#params_use),*) }
-                };
-                fields.push(quote! {
-                    #[serde(skip)]
-                    pub _marker: std::marker::PhantomData<#phantom_type>
-                });
-            }
+            let phantom_type = if params_use.len() == 1 {
+                quote! { #(#params_use)* }
+            } else {
+                quote! { (#(#params_use),*) }
+            };
+            fields.push(quote! {
+                #[serde(skip)]
+                pub _marker: std::marker::PhantomData<#phantom_type>
+            });
 
             (
-                quote! { <#(#params_struct),*> },
+                quote! { <#(#params_use),*> },
                 quote! { <#(#params_impl),*> },
                 quote! { <#(#params_use),*> },
             )
@@ -154,11 +225,23 @@ impl RustGenerator {
             (quote! {}, quote! {}, quote! {})
         };
 
-        let struct_def = quote! {
-            #[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-            #[serde(rename_all = "camelCase")]
-            #vis struct #struct_name #generics_struct_decl {
-                #(#fields),*
+        let doc = comments::doc_attrs(cx, n.doc.as_ref());
+        let struct_def = if self.holds_trait_objects(n) {
+            quote! {
+                #doc
+                #[derive(Clone)]
+                #vis struct #struct_name #generics_struct_decl {
+                    #(#fields),*
+                }
+            }
+        } else {
+            quote! {
+                #doc
+                #[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+                #[serde(rename_all = "camelCase")]
+                #vis struct #struct_name #generics_struct_decl {
+                    #(#fields),*
+                }
             }
         };
 
@@ -166,19 +249,20 @@ impl RustGenerator {
         self.code.push('\n');
 
         // 2. Generate Impl (Methods)
-        let mut impl_items = Vec::new();
+        let mut impl_items = consts;
 
         // Constructor
         if let Some(cons) = constructor {
             let constructor_tokens = self.convert_constructor(
-                &struct_name,
                 cons,
                 &class_fields_meta,
-                n.class.type_params.is_some(),
-                &generic_params,
+                has_generics,
                 &dependency_fields,
+                base.as_ref(),
             );
             impl_items.push(constructor_tokens);
+        } else if let Some(base) = base.as_ref().and_then(Type::as_named) {
+            impl_items.push(self.inherited_constructor(base, has_own_fields || has_generics));
         } else {
             // Default constructor if none exists
             impl_items.push(quote! {
@@ -194,48 +278,27 @@ impl RustGenerator {
         // Methods
         let mut routes: Vec<(String, String, String)> = Vec::new();
         for method in methods {
-            let (method_tokens, route_info) = self.convert_method(method);
+            let (method_tokens, route_info) = self.convert_method(method, false);
             impl_items.push(method_tokens);
             if let Some(info) = route_info {
                 routes.push(info);
             }
         }
+        // Inherited methods that must reach this class's overrides
+        for method in self.copied_methods(n) {
+            let mut copy = method.clone();
+            copy.mutates_this = self.mutates_in(n, &method.name, &mut Default::default());
+            impl_items.push(self.convert_method(&copy, false).0);
+        }
 
         // Generate router() if it's a controller
         // Check for @Controller decorator
-        let mut is_controller = false;
-        let mut controller_path = String::new();
-
-        for decorator in &n.class.decorators {
-            if let Expr::Call(call) = &*decorator.expr {
-                if let swc_ecma_ast::Callee::Expr(expr) = &call.callee {
-                    if let Expr::Ident(ident) = &**expr {
-                        if ident.sym == "Controller" {
-                            is_controller = true;
-                            if let Some(arg) = call.args.first() {
-                                if let Expr::Lit(Lit::Str(s)) = &*arg.expr {
-                                    controller_path =
-                                        s.value.as_str().unwrap_or_default().to_string();
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        if is_controller {
-            // Generate FromRequestParts implementation to allow `self` injection
-            // impl<S> axum::extract::FromRequestParts<S> for CatsController
-            // where S: Send + Sync
-            // {
-            //     type Rejection = std::convert::Infallible;
-            //     async fn from_request_parts(_parts: &mut axum::http::request::Parts, state: &S) -> Result<Self, Self::Rejection> {
-            //         let axum::Extension(controller) = axum::Extension::<std::sync::Arc<Self>>::from_request_parts(_parts, state).await.unwrap_or_default();
-            //         Ok(controller.as_ref().clone())
-            //     }
-            // }
+        if let Some(controller) = n.decorator("Controller") {
+            let controller_path = controller.str_arg().unwrap_or_default().to_string();
 
+            // Generate FromRequestParts implementation to allow `self` injection:
+            // the controller is fully constructed with its dependencies in main.rs and
+            // provided to the router as an `Extension<Arc<Self>>`.
             let from_request_impl = quote! {
                 #[axum::async_trait]
                 impl<S> axum::extract::FromRequestParts<S> for #struct_name
@@ -255,12 +318,6 @@ impl RustGenerator {
             self.code.push('\n');
 
             // Generate router function
-            // pub fn router() -> axum::Router {
-            //     axum::Router::new()
-            //         .route("/path", get(handler))
-            //         .route("/path2", post(handler2))
-            // }
-
             let mut route_calls = Vec::new();
             for (method_name, http_method, path) in &routes {
                 let method_ident = format_ident!("{}", method_name);
@@ -276,7 +333,6 @@ impl RustGenerator {
                 // Combine controller path and method path
                 // Controller: "cats", Method: "/" -> "/cats"
                 // Controller: "cats", Method: "/:id" -> "/cats/:id"
-
                 let full_path = if controller_path.is_empty() {
                     path.clone()
                 } else {
@@ -301,19 +357,8 @@ impl RustGenerator {
                 });
             }
 
-            // Note: We don't add .layer(Extension(Self::default())) here anymore because
-            // we expect the controller to be fully constructed with dependencies in main.rs
-            // and passed as an extension there.
-            // Actually, if we use Self::default(), we bypass DI.
-            // So we should REMOVE Self::default() from here if we want DI.
-            // But for now, let's keep it but assume it will be overridden or unused if we inject properly in main.
-            // Wait, if we add .layer(...) here, it overrides outer layers?
-            // Axum layers are applied outside-in.
-            // If main.rs does .merge(router).layer(Extension(controller)), that Extension is available to the router.
-            // If router() does .layer(Extension(default)), that might shadow the one from main.
-            // So we should REMOVE .layer(Extension(Self::default())) from here!
-            // The controller instance should be provided by the caller (main.rs).
-
+            // No `.layer(Extension(Self::default()))` here: it would shadow the
+            // DI-constructed controller that main.rs provides as an outer layer.
             impl_items.push(quote! {
                 pub fn router() -> axum::Router {
                     axum::Router::new()
@@ -324,7 +369,7 @@ impl RustGenerator {
             // Add to metadata
             self.controllers.push(crate::ControllerMetadata {
                 struct_name: class_name.clone(),
-                route_path: controller_path.clone(),
+                route_path: controller_path,
             });
         }
 
@@ -336,246 +381,233 @@ impl RustGenerator {
 
         self.code.push_str(&impl_block.to_string());
         self.code.push('\n');
+        for lazy_static in lazy_statics {
+            self.code.push_str(&lazy_static.to_string());
+            self.code.push('\n');
+        }
+
+        self.process_inheritance(n, &generics_impl_decl, &generics_use);
+        self.process_implements(n);
+        self.process_abstract_impl(n);
+    }
+
+    /// The constructor `new` of class `name` takes its parameters from: its
+    /// own, or the nearest ancestor's declared in the project.
+    fn nearest_constructor(&self, name: &str) -> Option<&Constructor> {
+        let mut class = self.classes.get(name);
+        for _ in 0..=self.classes.len() {
+            let current = class?;
+            if let Some(cons) = current.constructor() {
+                return Some(cons);
+            }
+            class = current
+                .super_class_name()
+                .and_then(|name| self.classes.get(name));
+        }
+        None
     }
 
-    fn convert_prop(
+    /// `new` and `new_di` for a subclass without a constructor of its own:
+    /// they take the nearest ancestor constructor's parameters and pass them
+    /// on to the superclass.
+    fn inherited_constructor(
         &self,
-        prop: &swc_ecma_ast::ClassProp,
-        generic_params: &std::collections::HashSet<String>,
-    ) -> Option<(proc_macro2::TokenStream, String, bool, bool)> {
-        let field_name_str = if let Some(ident) = prop.key.as_ident() {
-            ident.sym.to_string()
+        base: &str,
+        has_other_fields: bool,
+    ) -> proc_macro2::TokenStream {
+        let base_ident = format_ident!("{}", base);
+        let (params, args, di_params, di_args) = match self.nearest_constructor(base) {
+            Some(cons) => constructor_signature(&self.cx, cons),
+            None => Default::default(),
+        };
+        let rest = if has_other_fields {
+            quote! { ..Default::default() }
         } else {
-            return None;
+            quote! {}
         };
-        let field_name = format_ident!("{}", to_snake_case(&field_name_str));
-        let mut field_type = map_ts_type(prop.type_ann.as_ref());
-
-        // Check dependency
-        let is_dependency = if let Some(ann) = prop.type_ann.as_ref() {
-            if let Some(type_ref) = ann.type_ann.as_ts_type_ref() {
-                if let Some(ident) = type_ref.type_name.as_ident() {
-                    let name = ident.sym.as_str();
-                    if generic_params.contains(name) {
-                        false
-                    } else {
-                        !matches!(
-                            name,
-                            "String" | "f64" | "bool" | "i32" | "Vec" | "Option" | "Array"
-                        )
-                    }
-                } else {
-                    true
+        quote! {
+            pub fn new(#(#params),*) -> Self {
+                Self {
+                    base: #base_ident::new(#(#args),*),
+                    #rest
                 }
-            } else {
-                false
             }
-        } else {
-            false
-        };
+            pub fn new_di(#(#di_params),*) -> Self {
+                Self {
+                    base: #base_ident::new_di(#(#di_args),*),
+                    #rest
+                }
+            }
+        }
+    }
+
+    fn convert_prop(&self, prop: &ClassProp) -> (proc_macro2::TokenStream, String, bool, bool) {
+        let cx = &self.cx;
+        let field_name = format_ident!("{}", cx.snake_case(&prop.name));
+        let mut field_type = map_type_opt(cx, prop.ty.as_ref());
 
+        // Check dependency
+        let is_dependency = is_dependency_type(prop.ty.as_ref());
         if is_dependency {
-            field_type = quote! { std::sync::Arc<#field_type> };
+            field_type = shared(cx, prop.ty.as_ref(), field_type);
         }
 
-        let is_optional_union = is_optional_type(prop.type_ann.as_deref());
-        let is_effectively_optional = prop.is_optional || is_optional_union;
+        let is_optional_union = prop.ty.as_ref().is_some_and(Type::is_optional);
+        let is_effectively_optional = prop.optional || is_optional_union;
 
-        if prop.is_optional {
+        if prop.optional {
             // If it's optional via `?`, we wrap in Option.
-            // If it's optional via union `| undefined`, map_ts_type already wraps it in Option.
-            // But if it was already wrapped in Arc, we wrap Arc in Option? Option<Arc<T>>.
+            // If it's optional via union `| undefined`, map_type already wraps it in Option.
             field_type = quote! { Option<#field_type> };
         }
 
-        Some((
+        let vis = member_vis(prop.accessibility, prop.is_private_name);
+        let doc = comments::doc_attrs(cx, prop.doc.as_ref());
+        (
             quote! {
-                pub #field_name: #field_type
+                #doc
+                #vis #field_name: #field_type
             },
-            field_name_str,
+            prop.name.clone(),
             is_effectively_optional,
             is_dependency,
-        ))
+        )
     }
 
     fn convert_constructor(
         &self,
-        _struct_name: &proc_macro2::Ident,
         constructor: &Constructor,
         class_fields: &[(String, bool)],
         has_generics: bool,
-        generic_params: &std::collections::HashSet<String>,
         dependency_fields: &std::collections::HashSet<String>,
+        super_class: Option<&Type>,
     ) -> proc_macro2::TokenStream {
+        let cx = &self.cx;
         let mut params = Vec::new();
         let mut field_inits = Vec::new();
+        // Parameter properties are stored last, after the body has read them
+        let mut prop_inits = Vec::new();
         let mut initialized_fields = std::collections::HashSet::new();
         let mut dependency_params: std::collections::HashSet<String> =
             std::collections::HashSet::new();
 
         for param in &constructor.params {
             match param {
-                swc_ecma_ast::ParamOrTsParamProp::TsParamProp(prop) => {
-                    if let swc_ecma_ast::TsParamPropParam::Ident(ident) = &prop.param {
-                        let param_name_str = ident.sym.to_string();
-                        let param_name = format_ident!("{}", to_snake_case(&param_name_str));
-
-                        let type_ann = ident.type_ann.as_ref();
-                        let mut param_type = map_ts_type(type_ann);
-
-                        // Heuristic: If it's a TypeRef (not primitive), wrap in Arc
-                        let is_dependency = if let Some(ann) = type_ann {
-                            if let Some(type_ref) = ann.type_ann.as_ts_type_ref() {
-                                if let Some(ident) = type_ref.type_name.as_ident() {
-                                    let name = ident.sym.as_str();
-                                    if generic_params.contains(name) {
-                                        false
-                                    } else {
-                                        !matches!(
-                                            name,
-                                            "String"
-                                                | "f64"
-                                                | "bool"
-                                                | "i32"
-                                                | "Vec"
-                                                | "Option"
-                                                | "Array"
-                                        )
-                                    }
-                                } else {
-                                    true
-                                }
-                            } else {
-                                false
-                            }
-                        } else {
-                            false
-                        };
-
-                        if is_dependency {
-                            param_type = quote! { std::sync::Arc<#param_type> };
-                            dependency_params.insert(param_name_str.clone());
-                        }
-
-                        params.push(quote! { #param_name: #param_type });
-                        field_inits.push(quote! { #param_name: #param_name });
-                        initialized_fields.insert(param_name_str);
+                CtorParam::Prop { name, ty, .. } => {
+                    let param_name = format_ident!("{}", cx.snake_case(name));
+                    let mut param_type = map_type_opt(cx, ty.as_ref());
+
+                    // Heuristic: If it's a TypeRef (not primitive), wrap in Arc
+                    if is_dependency_type(ty.as_ref()) {
+                        param_type = shared(cx, ty.as_ref(), param_type);
+                        dependency_params.insert(name.clone());
                     }
-                }
-                swc_ecma_ast::ParamOrTsParamProp::Param(pat_param) => {
-                    if let Pat::Ident(ident) = &pat_param.pat {
-                        let param_name = format_ident!("{}", to_snake_case(ident.sym.as_ref()));
-                        let mut param_type = map_ts_type(ident.type_ann.as_ref());
-
-                        // Check dependency
-                        let is_dependency = if let Some(ann) = ident.type_ann.as_ref() {
-                            if let Some(type_ref) = ann.type_ann.as_ts_type_ref() {
-                                if let Some(ident) = type_ref.type_name.as_ident() {
-                                    let name = ident.sym.as_str();
-                                    if generic_params.contains(name) {
-                                        false
-                                    } else {
-                                        !matches!(
-                                            name,
-                                            "String"
-                                                | "f64"
-                                                | "bool"
-                                                | "i32"
-                                                | "Vec"
-                                                | "Option"
-                                                | "Array"
-                                        )
-                                    }
-                                } else {
-                                    true
-                                }
-                            } else {
-                                false
-                            }
-                        } else {
-                            false
-                        };
 
-                        if is_dependency {
-                            param_type = quote! { std::sync::Arc<#param_type> };
-                            dependency_params.insert(ident.sym.to_string());
+                    params.push(quote! { #param_name: #param_type });
+                    prop_inits.push(quote! { #param_name: #param_name });
+                    initialized_fields.insert(name.clone());
+                }
+                CtorParam::Param(param) => {
+                    if let Pat::Ident(binding) = &param.pat {
+                        let mutability = mutability(binding);
+                        let param_name = format_ident!("{}", cx.snake_case(&binding.name));
+                        let mut param_type = map_type_opt(cx, binding.ty.as_ref());
+
+                        if is_dependency_type(binding.ty.as_ref()) {
+                            param_type = shared(cx, binding.ty.as_ref(), param_type);
+                            dependency_params.insert(binding.name.clone());
                         }
 
-                        params.push(quote! { #param_name: #param_type });
+                        params.push(quote! { #mutability #param_name: #param_type });
                     }
                 }
             }
         }
 
-        // Try to extract field assignments from constructor body
-        if let Some(body) = &constructor.body {
-            for stmt in &body.stmts {
-                if let Stmt::Expr(ExprStmt { expr, .. }) = stmt {
-                    if let Expr::Assign(assign) = &**expr {
-                        // Check if left side is this.field
-                        if let AssignTarget::Simple(simple) = &assign.left {
-                            if let Some(member) = simple.as_member() {
-                                if member.obj.is_this() {
-                                    if let Some(prop_ident) = member.prop.as_ident() {
-                                        let field_name_str = prop_ident.sym.to_string();
-                                        let field_name =
-                                            format_ident!("{}", to_snake_case(&field_name_str));
-                                        let value = convert_expr_pub(&assign.right);
-
-                                        // If field is optional but assigned value is not Option, wrap it?
-                                        // Usually in Rust constructor we assign the value directly.
-                                        // But if the field is Option<T>, and we assign T, we need Some(T).
-                                        // This is tricky without type info of the expression.
-                                        // For now, assume user assigns correct type or we wrap in Some if it's a literal?
-                                        // Actually, if TS says `this.opt = "val"`, Rust expects `Option<String>`.
-                                        // We might need to wrap in `Some(...)`.
-                                        // Let's check if the field is optional.
-                                        let is_optional = class_fields
-                                            .iter()
-                                            .find(|(n, _)| n == &field_name_str)
-                                            .map(|(_, opt)| *opt)
-                                            .unwrap_or(false);
-
-                                        let value = if is_optional {
-                                            quote! { Some(#value) } // Naive wrapping, might double wrap if already Some
-                                        } else {
-                                            value
-                                        };
-
-                                        let value = if dependency_fields.contains(&field_name_str) {
-                                            // Check if the assigned value is a parameter that is already a dependency
-                                            let is_already_wrapped = if let Expr::Ident(ident) =
-                                                &*assign.right
-                                            {
-                                                dependency_params.contains(&ident.sym.to_string())
-                                            } else {
-                                                false
-                                            };
-
-                                            if is_already_wrapped {
-                                                value
-                                            } else {
-                                                quote! { std::sync::Arc::new(#value) }
-                                            }
-                                        } else {
-                                            value
-                                        };
-
-                                        field_inits.push(quote! { #field_name: #value });
-                                        initialized_fields.insert(field_name_str);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+        // `super(args)` initializes the embedded base class
+        let super_args = constructor
+            .body
+            .iter()
+            .flatten()
+            .find_map(|stmt| match &stmt.kind {
+                StmtKind::Expr(Expr {
+                    kind: ExprKind::Call(call),
+                    ..
+                }) if call.callee == Callee::Super => Some(&call.args),
+                _ => None,
+            });
+        let base_ident = super_class
+            .and_then(Type::as_named)
+            .map(|name| format_ident!("{}", name));
+        if let Some(base_ident) = &base_ident {
+            let args = super_args
+                .into_iter()
+                .flatten()
+                .map(|arg| convert_expr(cx, &arg.expr));
+            field_inits.push(quote! { base: #base_ident::new(#(#args),*) });
+        }
+
+        // Try to extract field assignments (`this.field = value`) from constructor body
+        for stmt in constructor.body.iter().flatten() {
+            let StmtKind::Expr(Expr {
+                kind: ExprKind::Assign { target, right, .. },
+                ..
+            }) = &stmt.kind
+            else {
+                continue;
+            };
+            let AssignTarget::Member(member) = target else {
+                continue;
+            };
+            if !member.obj.is_this() {
+                continue;
             }
+            let Some(field_name_str) = member.prop.as_ident() else {
+                continue;
+            };
+
+            let field_name = format_ident!("{}", cx.snake_case(field_name_str));
+            let value = convert_expr(cx, right);
+
+            // If the field is Option<T> and we assign T, we need Some(T).
+            // Without type info of the expression we wrap naively, which might
+            // double wrap if the value is already an Option.
+            let is_optional = class_fields
+                .iter()
+                .find(|(n, _)| n == field_name_str)
+                .map(|(_, opt)| *opt)
+                .unwrap_or(false);
+
+            let value = if is_optional {
+                quote! { Some(#value) }
+            } else {
+                value
+            };
+
+            let value = if dependency_fields.contains(field_name_str) {
+                // Check if the assigned value is a parameter that is already a dependency
+                let is_already_wrapped = right
+                    .as_ident()
+                    .is_some_and(|name| dependency_params.contains(name));
+
+                if is_already_wrapped {
+                    value
+                } else {
+                    quote! { std::sync::Arc::new(#value) }
+                }
+            } else {
+                value
+            };
+
+            field_inits.push(quote! { #field_name: #value });
+            initialized_fields.insert(field_name_str.to_string());
         }
+        field_inits.extend(prop_inits);
 
         // Fill in missing optional fields with None
         for (name, is_optional) in class_fields {
             if *is_optional && !initialized_fields.contains(name) {
-                let field_name = format_ident!("{}", to_snake_case(name));
+                let field_name = format_ident!("{}", cx.snake_case(name));
                 field_inits.push(quote! { #field_name: None });
             }
         }
@@ -585,193 +617,148 @@ impl RustGenerator {
             field_inits.push(quote! { _marker: std::marker::PhantomData });
         }
 
-        if !field_inits.is_empty() {
-            // Generate new_di (Dependency Injection constructor)
-            // It takes only dependencies and defaults primitives
-            let mut di_params = Vec::new();
-            let mut di_field_inits = Vec::new();
-            let mut di_initialized_fields = std::collections::HashSet::new();
-
-            for param in &constructor.params {
-                match param {
-                    swc_ecma_ast::ParamOrTsParamProp::TsParamProp(prop) => {
-                        if let swc_ecma_ast::TsParamPropParam::Ident(ident) = &prop.param {
-                            let param_name_str = ident.sym.to_string();
-                            let param_name = format_ident!("{}", to_snake_case(&param_name_str));
-                            let type_ann = ident.type_ann.as_ref();
-                            let mut param_type = map_ts_type(type_ann);
-
-                            let _type_str = param_type.to_string();
-                            // Check if it's a dependency (not primitive/std type)
-                            // We check if it starts with Vec, Option, or matches primitives
-                            let is_dependency = if let Some(ann) = type_ann {
-                                if let Some(type_ref) = ann.type_ann.as_ts_type_ref() {
-                                    if let Some(ident) = type_ref.type_name.as_ident() {
-                                        let name = ident.sym.as_str();
-                                        if generic_params.contains(name) {
-                                            false
-                                        } else {
-                                            !matches!(
-                                                name,
-                                                "String"
-                                                    | "f64"
-                                                    | "bool"
-                                                    | "i32"
-                                                    | "Vec"
-                                                    | "Option"
-                                                    | "Array"
-                                            )
-                                        }
-                                    } else {
-                                        // Complex type ref (e.g. qualified name), assume dependency
-                                        true
-                                    }
-                                } else {
-                                    false
-                                }
-                            } else {
-                                false
-                            };
-
-                            if is_dependency {
-                                param_type = quote! { std::sync::Arc<#param_type> };
-                                di_params.push(quote! { #param_name: #param_type });
-                                di_field_inits.push(quote! { #param_name: #param_name });
-                            } else {
-                                di_params.push(quote! { #param_name: #param_type });
-                                di_field_inits.push(quote! { #param_name: #param_name });
-                            }
-                            // Always mark as initialized for TsParamProp as it creates a field
-                            di_initialized_fields.insert(ident.sym.to_string());
-                        }
+        if field_inits.is_empty() {
+            // Nothing is assigned, so every field keeps its default as without
+            // a constructor
+            return quote! {
+                pub fn new(#(#params),*) -> Self {
+                    Self::default()
+                }
+            };
+        }
+
+        // Generate new_di (Dependency Injection constructor)
+        // It takes only dependencies and defaults primitives
+        let mut di_params = Vec::new();
+        let mut di_field_inits = Vec::new();
+        let mut di_initialized_fields = std::collections::HashSet::new();
+        let mut di_idents = std::collections::HashMap::new();
+
+        for param in &constructor.params {
+            match param {
+                CtorParam::Prop { name, ty, .. } => {
+                    let param_name = format_ident!("{}", cx.snake_case(name));
+                    let mut param_type = map_type_opt(cx, ty.as_ref());
+
+                    if is_dependency_type(ty.as_ref()) {
+                        param_type = shared(cx, ty.as_ref(), param_type);
                     }
-                    swc_ecma_ast::ParamOrTsParamProp::Param(pat_param) => {
-                        if let Pat::Ident(ident) = &pat_param.pat {
-                            let param_name_str = ident.sym.to_string();
-                            let param_name = format_ident!("{}", param_name_str);
-                            let param_type = map_ts_type(ident.type_ann.as_ref());
-
-                            // Check dependency using same logic
-                            let is_dependency = if let Some(ann) = ident.type_ann.as_ref() {
-                                if let Some(type_ref) = ann.type_ann.as_ts_type_ref() {
-                                    if let Some(ident) = type_ref.type_name.as_ident() {
-                                        let name = ident.sym.as_str();
-                                        if generic_params.contains(name) {
-                                            false
-                                        } else {
-                                            !matches!(
-                                                name,
-                                                "String"
-                                                    | "f64"
-                                                    | "bool"
-                                                    | "i32"
-                                                    | "Vec"
-                                                    | "Option"
-                                                    | "Array"
-                                            )
-                                        }
-                                    } else {
-                                        true
-                                    }
-                                } else {
-                                    false
-                                }
-                            } else {
-                                false
-                            };
-
-                            if is_dependency {
-                                let arc_type = quote! { std::sync::Arc<#param_type> };
-                                di_params.push(quote! { #param_name: #arc_type });
-
-                                // If this param matches a class field, initialize it
-                                if class_fields.iter().any(|(n, _)| n == &param_name_str) {
-                                    di_field_inits.push(quote! { #param_name: #param_name });
-                                    di_initialized_fields.insert(param_name_str);
-                                }
+                    di_params.push(quote! { #param_name: #param_type });
+                    di_field_inits.push(quote! { #param_name: #param_name });
+                    di_idents.insert(name.as_str(), param_name);
+
+                    // Always mark as initialized for parameter properties as they create a field
+                    di_initialized_fields.insert(name.clone());
+                }
+                CtorParam::Param(param) => {
+                    if let Pat::Ident(binding) = &param.pat {
+                        let param_name = format_ident!("{}", binding.name);
+
+                        if is_dependency_type(binding.ty.as_ref()) {
+                            let param_type = map_type_opt(cx, binding.ty.as_ref());
+                            let param_type = shared(cx, binding.ty.as_ref(), param_type);
+                            di_params.push(quote! { #param_name: #param_type });
+                            di_idents.insert(binding.name.as_str(), param_name.clone());
+
+                            // If this param matches a class field, initialize it
+                            if class_fields.iter().any(|(n, _)| n == &binding.name) {
+                                di_field_inits.push(quote! { #param_name: #param_name });
+                                di_initialized_fields.insert(binding.name.clone());
                             }
-                            // If not dependency, we don't add to params, and we don't add to inits.
-                            // We also do NOT add to di_initialized_fields, so it gets Default::default() later.
                         }
+                        // Non-dependency params are dropped: their fields get Default::default() below.
                     }
                 }
             }
+        }
 
-            if has_generics {
-                di_field_inits.push(quote! { _marker: std::marker::PhantomData });
-            }
+        if has_generics {
+            di_field_inits.push(quote! { _marker: std::marker::PhantomData });
+        }
 
-            for (name, _) in class_fields {
-                if !di_initialized_fields.contains(name) {
-                    let field_name = format_ident!("{}", to_snake_case(name));
-                    di_field_inits.push(quote! { #field_name: Default::default() });
-                }
-            }
+        // The base's `new_di` gets whatever `super(...)` forwards from this one's
+        // parameters; anything else is defaulted
+        if let Some(base_ident) = &base_ident {
+            let super_args = super_args.map(Vec::as_slice).unwrap_or_default();
+            let forwarded = |arg: &Arg| arg.expr.as_ident().and_then(|name| di_idents.get(name));
+            let args: Vec<_> = if self.classes.contains_key(&base_ident.to_string()) {
+                let base_params = self
+                    .nearest_constructor(&base_ident.to_string())
+                    .map(|cons| cons.params.as_slice())
+                    .unwrap_or_default();
+                base_params
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, param)| is_di_param(param))
+                    .map(|(i, _)| match super_args.get(i).and_then(forwarded) {
+                        Some(ident) => quote! { #ident },
+                        None => quote! { Default::default() },
+                    })
+                    .collect()
+            } else {
+                // Declared outside the project: pass on the injected arguments only
+                super_args
+                    .iter()
+                    .filter_map(forwarded)
+                    .map(|ident| quote! { #ident })
+                    .collect()
+            };
+            di_field_inits.push(quote! { base: #base_ident::new_di(#(#args),*) });
+        }
 
-            quote! {
-                pub fn new(#(#params),*) -> Self {
-                    Self {
-                        #(#field_inits),*
-                    }
-                }
+        for (name, _) in class_fields {
+            if !di_initialized_fields.contains(name) {
+                let field_name = format_ident!("{}", cx.snake_case(name));
+                di_field_inits.push(quote! { #field_name: Default::default() });
+            }
+        }
 
-                pub fn new_di(#(#di_params),*) -> Self {
-                    Self {
-                        #(#di_field_inits),*
-                    }
+        quote! {
+            pub fn new(#(#params),*) -> Self {
+                Self {
+                    #(#field_inits),*
                 }
             }
-        } else {
-            // Fallback if we can't parse constructor body
-            quote! {
-                pub fn new(#(#params),*) -> Self {
-                    todo!("Complex constructor not yet supported")
+
+            pub fn new_di(#(#di_params),*) -> Self {
+                Self {
+                    #(#di_field_inits),*
                 }
             }
         }
     }
 
-    fn convert_method(
+    /// Converts a method for an inherent impl, or with `in_trait` for a trait
+    /// declaration: no visibility, object-safe parameters, and no body for
+    /// abstract methods.
+    pub(crate) fn convert_method(
         &self,
-        method: &swc_ecma_ast::ClassMethod,
+        method: &ClassMethod,
+        in_trait: bool,
     ) -> (proc_macro2::TokenStream, Option<(String, String, String)>) {
-        let method_name_str = if let Some(ident) = method.key.as_ident() {
-            ident.sym.to_string()
+        let cx = &self.cx;
+        let function = &method.function;
+        let method_name = if method.kind == MethodKind::Setter {
+            cx.setter_ident(&method.name)
         } else {
-            return (quote! { /* unsupported method key */ }, None);
+            format_ident!("{}", cx.snake_case(&method.name))
         };
-        let method_name = format_ident!("{}", to_snake_case(&method_name_str));
 
         // Check for NestJS decorators (@Get, @Post, etc.)
         let mut http_method = None;
         let mut route_path = String::new();
 
-        for decorator in &method.function.decorators {
-            if let Expr::Call(call) = &*decorator.expr {
-                if let swc_ecma_ast::Callee::Expr(expr) = &call.callee {
-                    if let Expr::Ident(ident) = &**expr {
-                        let name = ident.sym.as_str();
-                        if matches!(name, "Get" | "Post" | "Put" | "Delete" | "Patch") {
-                            http_method = Some(name.to_string());
-                            // Extract route path if present
-                            if let Some(arg) = call.args.first() {
-                                if let Expr::Lit(Lit::Str(s)) = &*arg.expr {
-                                    route_path = s.value.as_str().unwrap_or_default().to_string();
-                                }
-                            }
-                        }
-                    }
+        for decorator in &function.decorators {
+            if HTTP_METHOD_DECORATORS.contains(&decorator.name.as_str()) {
+                http_method = Some(decorator.name.clone());
+                // Extract route path if present
+                if let Some(path) = decorator.str_arg() {
+                    route_path = path.to_string();
                 }
             }
         }
 
         let is_handler = http_method.is_some();
-        println!(
-            "Method: {}, is_handler: {}, decorators: {}",
-            method_name,
-            is_handler,
-            method.function.decorators.len()
-        );
 
         // Build parameters
         let mut params = Vec::new();
@@ -780,50 +767,45 @@ impl RustGenerator {
         if is_handler {
             // For handlers, we consume self (injected via FromRequest)
             params.push(quote! { self });
-        } else {
+        } else if method.kind == MethodKind::Setter || (method.mutates_this && !in_trait) {
+            params.push(quote! { &mut self });
+        } else if !method.is_static {
             // For regular methods, use &self
             params.push(quote! { &self });
         }
 
-        for param in &method.function.params {
-            if let Pat::Ident(ident) = &param.pat {
-                let param_name = format_ident!("{}", to_snake_case(ident.sym.as_ref()));
-                let param_type = map_ts_type(ident.type_ann.as_ref());
+        for param in &function.params {
+            if let Pat::Ident(binding) = &param.pat {
+                let param_name = format_ident!("{}", cx.snake_case(&binding.name));
+                let param_type = if is_handler || in_trait {
+                    map_type_opt(cx, binding.ty.as_ref())
+                } else {
+                    map_param_type(cx, binding.ty.as_ref())
+                };
 
                 // Check for @Body decorator on parameters
-                let mut is_body = false;
-
-                // Correctly check decorators on the Param node
-                for decorator in &param.decorators {
-                    if let Expr::Call(call) = &*decorator.expr {
-                        if let swc_ecma_ast::Callee::Expr(expr) = &call.callee {
-                            if let Expr::Ident(ident) = &**expr {
-                                if ident.sym == "Body" {
-                                    is_body = true;
-                                }
-                            }
-                        }
-                    }
-                }
-
-                if is_body {
-                    params.push(quote! { axum::Json(#param_name): axum::Json<#param_type> });
+                let mutability = mutability(binding);
+                if param.has_decorator("Body") {
+                    params.push(
+                        quote! { axum::Json(#mutability #param_name): axum::Json<#param_type> },
+                    );
                 } else {
-                    params.push(quote! { #param_name: #param_type });
+                    params.push(quote! { #mutability #param_name: #param_type });
                 }
             }
         }
 
-        let mut return_type = if method.function.is_async {
-            let inner =
-                super::type_mapper::unwrap_promise_type(method.function.return_type.as_ref());
+        let mut return_type = if function.is_async {
+            let inner = unwrap_promise_type(cx, function.return_type.as_ref());
             if !is_handler {
                 quote! { Result<#inner, crate::AppError> }
             } else {
                 inner
             }
+        } else if method.kind == MethodKind::Setter {
+            quote! { () }
         } else {
-            map_ts_type(method.function.return_type.as_ref())
+            map_type_opt(cx, function.return_type.as_ref())
         };
 
         // If it's a handler, wrap return type in Json unless it's String
@@ -840,13 +822,12 @@ impl RustGenerator {
 
         // Convert body
         let mut body_stmts = Vec::new();
-        if let Some(body) = &method.function.body {
+        if let Some(body) = &function.body {
             // Define return handler
-            let return_handler = |ret: &swc_ecma_ast::ReturnStmt| -> proc_macro2::TokenStream {
-                if let Some(arg) = &ret.arg {
-                    let expr = convert_expr_pub(arg);
-
+            let return_handler = |arg: Option<&Expr>| -> proc_macro2::TokenStream {
+                if let Some(arg) = arg {
                     if is_handler {
+                        let expr = convert_expr(cx, arg);
                         // Check if we wrapped the return type in Json (inside Result)
                         let ret_str = return_type.to_string();
                         let uses_json = ret_str.contains("axum :: Json");
@@ -856,10 +837,12 @@ impl RustGenerator {
                         } else {
                             quote! { return Ok(#expr.into()); }
                         }
-                    } else if method.function.is_async {
+                    } else if function.is_async {
                         // For async methods, wrap in Ok
+                        let expr = convert_value(cx, arg);
                         quote! { return Ok(#expr); }
                     } else {
+                        let expr = convert_value(cx, arg);
                         quote! { return #expr; }
                     }
                 } else {
@@ -867,27 +850,26 @@ impl RustGenerator {
                 }
             };
 
-            for stmt in &body.stmts {
-                if is_handler || method.function.is_async {
-                    body_stmts.push(super::func::convert_stmt_recursive(stmt, &return_handler));
-                } else {
-                    body_stmts.push(convert_stmt_pub(stmt));
+            cx.with_trait_params(&function.params, || {
+                for stmt in body {
+                    body_stmts.push(stmt_markers(stmt));
+                    if is_handler || function.is_async {
+                        body_stmts.push(convert_stmt_recursive(cx, stmt, &return_handler));
+                    } else {
+                        body_stmts.push(convert_stmt(cx, stmt));
+                    }
                 }
-            }
+            });
         }
 
-        let fn_keyword = if is_handler || method.function.is_async {
+        let fn_keyword = if is_handler || function.is_async {
             quote! { async fn }
         } else {
             quote! { fn }
         };
 
-        let doc_comment = if is_handler {
-            let method_str = if let Some(m) = http_method.as_ref() {
-                m.to_uppercase()
-            } else {
-                "GET".to_string() // Default or unreachable if guarded
-            };
+        let doc_comment = if let Some(m) = http_method.as_ref() {
+            let method_str = m.to_uppercase();
             let route = if route_path.is_empty() {
                 "/".to_string()
             } else {
@@ -900,11 +882,22 @@ impl RustGenerator {
             quote! {}
         };
 
+        let vis = if in_trait {
+            quote! {}
+        } else {
+            member_vis(method.accessibility, method.is_private_name)
+        };
+        let body = if function.body.is_some() {
+            quote! { { #(#body_stmts)* } }
+        } else {
+            quote! { ; }
+        };
+
+        let doc = comments::doc_attrs(cx, method.doc.as_ref());
         let tokens = quote! {
+            #doc
             #doc_comment
-            pub #fn_keyword #method_name(#(#params),*) -> #return_type {
-                #(#body_stmts)*
-            }
+            #vis #fn_keyword #method_name(#(#params),*) -> #return_type #body
         };
 
         let route_info = http_method.map(|method| (method_name.to_string(), method, route_path));
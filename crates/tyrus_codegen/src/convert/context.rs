@@ -0,0 +1,78 @@
+//! What the converters know about the module being generated and its
+//! project, and what they collect while converting it.
+//!
+//! The generator of a module owns its [`Context`] and passes it to the
+//! conversion functions, so generating one module never depends on another.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+
+use tyrus_analyzer::typeck::unions::UntaggedUnion;
+use tyrus_ast::{ClassDecl, InterfaceDecl};
+
+use super::func::Naming;
+use super::members::{class_members, ClassMembers};
+use super::unsupported::Unsupported;
+
+#[derive(Default)]
+pub struct Context {
+    pub naming: Naming,
+    /// Interfaces of the project that declare methods, by name. They are
+    /// emitted as traits, and values of their type are trait objects.
+    pub(crate) trait_interfaces: BTreeMap<String, InterfaceDecl>,
+    /// Trait interfaces a class mutates `this` in the methods of. Their trait
+    /// objects are `Arc<Mutex<dyn Iface>>`, locked to read them.
+    pub(crate) mutated_interfaces: BTreeSet<String>,
+    /// Abstract classes of the project, which are emitted as traits too.
+    pub(crate) abstract_classes: BTreeSet<String>,
+    /// Extended classes of the project, emitted with a trait that parameters
+    /// of their type take.
+    pub(crate) class_traits: BTreeSet<String>,
+    /// Parameters of the functions being converted that take a class trait,
+    /// whose fields are read through it.
+    pub(crate) trait_params: RefCell<BTreeSet<String>>,
+    /// Discriminated unions of the project, emitted as tagged enums, with
+    /// their tag: `Shape` → `kind`.
+    pub(crate) tagged_unions: BTreeMap<String, String>,
+    /// Untagged unions of the project declared through an alias, by alias.
+    pub(crate) untagged_unions: BTreeMap<String, UntaggedUnion>,
+    /// Required fields of the project's classes and interfaces, by name. The
+    /// untagged enums holding them try the variants with more of them first.
+    pub(crate) required_fields: BTreeMap<String, usize>,
+    /// Accessors and static properties of the project's classes, by class.
+    pub(crate) class_members: BTreeMap<String, ClassMembers>,
+    /// Inline unions mapped so far, by enum name. Their enums are emitted
+    /// once, after the module's items or in the project's `unions` module.
+    pub(crate) unions: RefCell<BTreeMap<String, UntaggedUnion>>,
+    /// Constructs emitted as `todo!()` so far, in emission order.
+    pub(crate) reports: RefCell<Vec<Unsupported>>,
+}
+
+impl Context {
+    /// The context of a module that can see the project's `classes` and
+    /// `trait_interfaces`.
+    pub fn new<'a>(
+        naming: Naming,
+        classes: impl IntoIterator<Item = &'a ClassDecl>,
+        trait_interfaces: BTreeMap<String, InterfaceDecl>,
+    ) -> Self {
+        let classes: Vec<_> = classes.into_iter().collect();
+        let mutated_interfaces = trait_interfaces
+            .values()
+            .filter(|iface| iface.is_mutated_by(classes.iter().copied()))
+            .map(|iface| iface.name.clone())
+            .collect();
+        Self {
+            naming,
+            trait_interfaces,
+            mutated_interfaces,
+            abstract_classes: classes
+                .iter()
+                .filter(|class| class.is_abstract)
+                .map(|class| class.name.clone())
+                .collect(),
+            class_members: class_members(classes),
+            ..Default::default()
+        }
+    }
+}
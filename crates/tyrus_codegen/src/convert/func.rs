@@ -1,65 +1,75 @@
+use std::str::FromStr;
+
 use quote::{format_ident, quote};
-use swc_ecma_ast::{
-    AwaitExpr, BinExpr, BinaryOp, CallExpr, Callee, Decl, Expr, ExprOrSpread, FnDecl, Lit,
-    MemberExpr, Pat, Stmt, UpdateExpr, UpdateOp,
+use swc_common::Span;
+use tyrus_analyzer::features::Feature;
+use tyrus_ast::{
+    Arg, ArrowBody, ArrowExpr, AssignOp, AssignTarget, BinaryOp, Binding, CallExpr, Callee, Expr,
+    ExprKind, FnDecl, ForInit, Lit, LitType, MatchArm, MatchPat, MemberExpr, MemberProp, NumRepr,
+    ObjectPatProp, Pat, Prop, PropKey, Stmt, StmtKind, Type, UnaryOp, UpdateOp, Usage,
 };
 
-use super::type_mapper::{map_ts_type, unwrap_promise_type};
+use super::context::Context;
+use super::type_mapper::{map_param_type, map_type, map_type_opt, unwrap_promise_type};
+use crate::{comments, span_map};
 
 impl super::interface::RustGenerator {
     pub fn process_fn_decl(&mut self, n: &FnDecl) {
-        let fn_name = to_snake_case(&n.ident.sym);
+        let cx = &self.cx;
+        let function = &n.function;
+        let fn_name = cx.snake_case(&n.name);
         let fn_ident = format_ident!("{}", fn_name);
 
         // Check if async
-        let is_async = n.function.is_async;
+        let is_async = function.is_async;
 
         // Extract parameters
         let mut params = Vec::new();
-        for param in &n.function.params {
-            if let Pat::Ident(ident_pat) = &param.pat {
-                let param_name = format_ident!("{}", ident_pat.sym.to_string());
-                let param_type = map_ts_type(ident_pat.type_ann.as_ref());
-                params.push(quote! { #param_name: #param_type });
+        for param in &function.params {
+            if let Pat::Ident(binding) = &param.pat {
+                let mutability = mutability(binding);
+                let param_name = format_ident!("{}", binding.name);
+                let param_type = map_param_type(cx, binding.ty.as_ref());
+                params.push(quote! { #mutability #param_name: #param_type });
             }
         }
 
         // Extract return type - unwrap Promise<T> for async functions
         let return_type = if is_async {
-            if n.function.return_type.is_none() {
+            if function.return_type.is_none() {
                 // If no return type, assume void for async functions
                 quote! { Result<(), crate::AppError> }
             } else {
-                let inner = unwrap_promise_type(n.function.return_type.as_ref());
+                let inner = unwrap_promise_type(cx, function.return_type.as_ref());
                 quote! { Result<#inner, crate::AppError> }
             }
-        } else if n.function.return_type.is_none() {
+        } else if function.return_type.is_none() {
             quote! { () }
         } else {
-            map_ts_type(n.function.return_type.as_ref())
+            map_type_opt(cx, function.return_type.as_ref())
         };
 
         // Check if void
-        let is_void = if n.function.return_type.is_none() {
-            true
-        } else {
-            super::type_mapper::is_void_or_promise_void(n.function.return_type.as_deref())
-        };
+        let is_void = function
+            .return_type
+            .as_ref()
+            .is_none_or(Type::is_void_or_promise_void);
 
         // Convert body
         let mut body_stmts = Vec::new();
-        if let Some(block_stmt) = &n.function.body {
-            if is_async {
+        if let Some(body) = &function.body {
+            cx.with_trait_params(&function.params, || if is_async {
                 // Use recursive converter to handle return Ok(...)
-                for stmt in &block_stmt.stmts {
-                    body_stmts.push(convert_stmt_recursive(stmt, &|ret_stmt| {
-                        if let Some(arg) = &ret_stmt.arg {
-                            let expr = convert_expr(arg);
+                for stmt in body {
+                    body_stmts.push(stmt_markers(stmt));
+                    body_stmts.push(convert_stmt_recursive(cx, stmt, &|arg| {
+                        if let Some(arg) = arg {
+                            let expr = convert_value(cx, arg);
 
                             // Heuristic: If returning an object literal, it is converted to json!() (Value).
                             // But the function might return a struct (User).
                             // We need to deserialize Value -> Struct.
-                            if !is_void && matches!(arg.as_ref(), swc_ecma_ast::Expr::Object(_)) {
+                            if !is_void && matches!(arg.kind, ExprKind::Object(_)) {
                                 quote! {
                                     return Ok(serde_json::from_value(#expr).unwrap_or_else(|e| panic!("Failed to convert return value: {}", e)));
                                 }
@@ -71,29 +81,14 @@ impl super::interface::RustGenerator {
                         }
                     }));
                 }
-
-                if is_void {
-                    // Fallback for void functions
-                    // Only append if the last statement isn't a return (though rustc handles unreachable code)
-                    // But strictly, we need Ok(()) if control flow reaches end.
-                    // Since we can't easily analyze control flow, appending Ok(()) is safe for void functions.
-                    // But we must ensure it doesn't cause "unreachable expression" warnings if possible,
-                    // or just accept the warning.
-                    // The previous error was "expected bool, found ()".
-                    // If we only append when is_void is true, we avoid that error.
-                    // We might get "unreachable code" warning if there was an explicit return before, but that's fine (just a warning).
-                    // Ideally we suppress it or check, but let's just append.
-                    // Actually, to avoid "unreachable expression" warning which might be treated as error in some configs:
-                    // We can't easily avoid it without CFG.
-                    // Let's just append it.
-                }
             } else {
-                for stmt in &block_stmt.stmts {
-                    body_stmts.push(convert_stmt_recursive(stmt, &|ret_stmt| {
-                        if let Some(arg) = &ret_stmt.arg {
-                            let expr = convert_expr(arg);
+                for stmt in body {
+                    body_stmts.push(stmt_markers(stmt));
+                    body_stmts.push(convert_stmt_recursive(cx, stmt, &|arg| {
+                        if let Some(arg) = arg {
+                            let expr = convert_value(cx, arg);
                             // Heuristic: same as async, needed for Struct return types
-                            if !is_void && matches!(arg.as_ref(), swc_ecma_ast::Expr::Object(_)) {
+                            if !is_void && matches!(arg.kind, ExprKind::Object(_)) {
                                 quote! {
                                     return serde_json::from_value(#expr).unwrap_or_else(|e| panic!("Failed to convert return value: {}", e));
                                 }
@@ -105,7 +100,7 @@ impl super::interface::RustGenerator {
                         }
                     }));
                 }
-            }
+            });
         }
 
         let vis = if self.is_exporting {
@@ -114,22 +109,25 @@ impl super::interface::RustGenerator {
             quote! {}
         };
 
-        let generics = if let Some(type_params) = &n.function.type_params {
-            let params: Vec<_> = type_params
-                .params
+        let generics = if function.type_params.is_empty() {
+            quote! {}
+        } else {
+            let params: Vec<_> = function
+                .type_params
                 .iter()
                 .map(|p| {
-                    let name = p.name.sym.to_string();
-                    let ident = format_ident!("{}", name);
+                    let ident = format_ident!("{}", p.name);
                     quote! { #ident: serde::de::DeserializeOwned + serde::Serialize + Clone }
                 })
                 .collect();
             quote! { <#(#params),*> }
-        } else {
-            quote! {}
         };
 
+        let doc = comments::doc_attrs(cx, n.doc.as_ref());
         let fn_def = if is_async {
+            // Void async functions may fall off the end of the body, so they need a trailing Ok(()).
+            // This can trigger an "unreachable expression" warning after an explicit return,
+            // which is acceptable until we have control-flow analysis.
             let fallback = if is_void {
                 quote! { Ok(()) }
             } else {
@@ -137,6 +135,7 @@ impl super::interface::RustGenerator {
             };
 
             quote! {
+                #doc
                 #vis async fn #fn_ident #generics (#(#params),*) -> #return_type {
                     #(#body_stmts)*
                     #fallback
@@ -144,6 +143,7 @@ impl super::interface::RustGenerator {
             }
         } else {
             quote! {
+                #doc
                 #vis fn #fn_ident #generics (#(#params),*) -> #return_type {
                     #(#body_stmts)*
                 }
@@ -155,94 +155,161 @@ impl super::interface::RustGenerator {
     }
 }
 
-pub fn convert_stmt_pub(stmt: &Stmt) -> proc_macro2::TokenStream {
-    convert_stmt(stmt)
+/// Wraps a converted statement in braces unless it already is a block.
+fn as_block(stmt: &Stmt, tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if stmt.is_block() {
+        tokens
+    } else {
+        quote! { { #tokens } }
+    }
+}
+
+/// `mut` for bindings that mutability inference found to be mutated.
+pub(crate) fn mutability(binding: &Binding) -> proc_macro2::TokenStream {
+    if binding.mutable {
+        quote! { mut }
+    } else {
+        quote! {}
+    }
 }
 
-pub fn convert_expr_pub(expr: &Expr) -> proc_macro2::TokenStream {
-    convert_expr(expr)
+/// Rust type of a `number` represented as `repr`.
+pub(crate) fn num_type(repr: NumRepr) -> proc_macro2::TokenStream {
+    match repr {
+        NumRepr::F64 => quote! { f64 },
+        NumRepr::I64 => quote! { i64 },
+        NumRepr::Usize => quote! { usize },
+    }
 }
 
-pub fn convert_stmt(stmt: &Stmt) -> proc_macro2::TokenStream {
-    match stmt {
-        Stmt::Return(ret_stmt) => {
-            if let Some(arg) = &ret_stmt.arg {
-                let expr = convert_expr(arg);
+/// `: i64` or `: usize` for variables integer inference made integers, so
+/// their literals are not inferred as `i32`, and the declared type of those
+/// holding trait objects, which `vec![Arc::new(..)]` is only coerced to
+/// when annotated.
+fn annotation(cx: &Context, binding: &Binding) -> proc_macro2::TokenStream {
+    if let Some(ty) = binding
+        .ty
+        .as_ref()
+        .filter(|ty| cx.contains_trait_object(ty))
+    {
+        let ty = map_type(cx, ty);
+        return quote! { : #ty };
+    }
+    match binding.num {
+        NumRepr::F64 => quote! {},
+        repr => {
+            let ty = num_type(repr);
+            quote! { : #ty }
+        }
+    }
+}
+
+/// Whether `stmt` contains a `continue` of the loop it is the body of.
+fn continues(stmt: &Stmt) -> bool {
+    let any = |stmts: &[Stmt]| stmts.iter().any(continues);
+    match &stmt.kind {
+        StmtKind::Continue => true,
+        StmtKind::Block(stmts) => any(stmts),
+        StmtKind::If { cons, alt, .. } => continues(cons) || alt.as_deref().is_some_and(continues),
+        StmtKind::Try {
+            block,
+            handler,
+            finalizer,
+        } => {
+            any(block)
+                || handler.as_ref().is_some_and(|handler| any(&handler.body))
+                || finalizer.as_deref().is_some_and(any)
+        }
+        StmtKind::Switch { cases, .. } => cases.iter().any(|case| any(&case.cons)),
+        StmtKind::Match { arms, .. } => arms.iter().any(|arm| any(&arm.body)),
+        _ => false,
+    }
+}
+
+/// Pattern of the single identifier bound by a loop head, or `fallback`.
+fn loop_binding(
+    cx: &Context,
+    left: &tyrus_ast::ForHead,
+    fallback: &str,
+) -> proc_macro2::TokenStream {
+    match left.binding_pat().and_then(Pat::as_ident) {
+        Some(binding) => {
+            let mutability = mutability(binding);
+            let ident = format_ident!("{}", cx.snake_case(&binding.name));
+            quote! { #mutability #ident }
+        }
+        None => {
+            let ident = format_ident!("{}", fallback);
+            quote! { #ident }
+        }
+    }
+}
+
+pub fn convert_stmt(cx: &Context, stmt: &Stmt) -> proc_macro2::TokenStream {
+    match &stmt.kind {
+        StmtKind::Return(arg) => {
+            if let Some(arg) = arg {
+                let expr = convert_value(cx, arg);
                 quote! { return #expr; }
             } else {
                 quote! { return; }
             }
         }
-        Stmt::Expr(expr_stmt) => {
-            let expr = convert_expr(&expr_stmt.expr);
+        StmtKind::Expr(expr) => {
+            let expr = convert_expr(cx, expr);
             quote! { #expr; }
         }
-        Stmt::Decl(Decl::Var(var_decl)) => {
+        StmtKind::Var(var_decl) => {
             // Handle variable declarations (const/let)
             let mut declarations = Vec::new();
             for decl in &var_decl.decls {
                 // If there is an initializer, convert it
-                let init_expr_opt = decl.init.as_ref().map(|init| convert_expr(init));
+                let init_expr_opt = decl.init.as_ref().map(|expr| convert_value(cx, expr));
 
-                match &decl.name {
-                    Pat::Ident(ident) => {
-                        let var_name = to_snake_case(&ident.id.sym);
-                        let var_ident = format_ident!("{}", var_name);
+                match &decl.pat {
+                    Pat::Ident(binding) => {
+                        let var_ident = format_ident!("{}", cx.snake_case(&binding.name));
 
-                        // If uninitialized
+                        let mutability = mutability(binding);
+                        let annotation = annotation(cx, binding);
                         if let Some(init_expr) = init_expr_opt {
-                            if matches!(var_decl.kind, swc_ecma_ast::VarDeclKind::Const) {
-                                declarations.push(quote! {
-                                    let #var_ident = #init_expr;
-                                });
-                            } else {
-                                declarations.push(quote! {
-                                    let mut #var_ident = #init_expr;
-                                });
-                            }
+                            declarations.push(quote! {
+                                let #mutability #var_ident #annotation = #init_expr;
+                            });
                         } else {
                             declarations.push(quote! {
-                                let mut #var_ident;
+                                let #mutability #var_ident #annotation;
                             });
                         }
                     }
-                    Pat::Object(obj_pat) => {
+                    Pat::Object(props) => {
                         // const { a, b } = obj;
                         if let Some(init_expr) = init_expr_opt {
                             let temp_name = format_ident!("__destruct_val");
                             declarations.push(quote! { let #temp_name = #init_expr; });
 
-                            for prop in &obj_pat.props {
+                            for prop in props {
                                 match prop {
-                                    swc_ecma_ast::ObjectPatProp::KeyValue(kv) => {
-                                        if let Pat::Ident(target) = &*kv.value {
+                                    ObjectPatProp::KeyValue { key, value } => {
+                                        if let Pat::Ident(target) = value {
                                             let target_name =
-                                                format_ident!("{}", to_snake_case(&target.id.sym));
-                                            let source_key = match &kv.key {
-                                                swc_ecma_ast::PropName::Ident(i) => {
-                                                    i.sym.to_string()
-                                                }
-                                                swc_ecma_ast::PropName::Str(s) => {
-                                                    format!("{:?}", s.value)
-                                                        .trim_matches('"')
-                                                        .to_string()
-                                                }
+                                                format_ident!("{}", cx.snake_case(&target.name));
+                                            let source_key = match key {
+                                                PropKey::Ident(name) | PropKey::Str(name) => name,
                                                 _ => continue,
                                             };
                                             let source_ident =
-                                                format_ident!("{}", to_snake_case(&source_key));
+                                                format_ident!("{}", cx.snake_case(source_key));
                                             declarations.push(quote! {
                                                 let #target_name = #temp_name.#source_ident.clone();
                                             });
                                         }
                                     }
-                                    swc_ecma_ast::ObjectPatProp::Assign(assign) => {
-                                        let target_name =
-                                            format_ident!("{}", to_snake_case(&assign.key.sym));
-                                        let source_ident =
-                                            format_ident!("{}", to_snake_case(&assign.key.sym));
-                                        if let Some(default) = &assign.value {
-                                            let default_expr = convert_expr(default);
+                                    ObjectPatProp::Assign { key, default } => {
+                                        let target_name = format_ident!("{}", cx.snake_case(key));
+                                        let source_ident = format_ident!("{}", cx.snake_case(key));
+                                        if let Some(default) = default {
+                                            let default_expr = convert_expr(cx, default);
                                             declarations.push(quote! {
                                                 let #target_name = #temp_name.#source_ident.clone().unwrap_or(#default_expr);
                                             });
@@ -252,21 +319,21 @@ pub fn convert_stmt(stmt: &Stmt) -> proc_macro2::TokenStream {
                                             });
                                         }
                                     }
-                                    _ => {}
+                                    ObjectPatProp::Rest => {}
                                 }
                             }
                         }
                     }
-                    Pat::Array(arr_pat) => {
+                    Pat::Array(elems) => {
                         // const [a, b] = arr;
                         if let Some(init_expr) = init_expr_opt {
                             let temp_name = format_ident!("__destruct_val");
                             declarations.push(quote! { let #temp_name = #init_expr; });
 
-                            for (i, elem) in arr_pat.elems.iter().enumerate() {
-                                if let Some(Pat::Ident(ident)) = elem {
+                            for (i, elem) in elems.iter().enumerate() {
+                                if let Some(Pat::Ident(binding)) = elem {
                                     let target_name =
-                                        format_ident!("{}", to_snake_case(&ident.id.sym));
+                                        format_ident!("{}", cx.snake_case(&binding.name));
                                     declarations.push(quote! {
                                         let #target_name = #temp_name[#i].clone();
                                     });
@@ -274,8 +341,9 @@ pub fn convert_stmt(stmt: &Stmt) -> proc_macro2::TokenStream {
                             }
                         }
                     }
-                    _ => {
-                        declarations.push(quote! { /* unsupported pattern */ });
+                    Pat::Unsupported => {
+                        let todo = cx.todo(Feature::RestAndDefaultPatterns, decl.span);
+                        declarations.push(quote! { #todo; });
                     }
                 }
             }
@@ -283,27 +351,24 @@ pub fn convert_stmt(stmt: &Stmt) -> proc_macro2::TokenStream {
                 #(#declarations)*
             }
         }
-        Stmt::Block(block) => {
-            let stmts: Vec<_> = block.stmts.iter().map(convert_stmt).collect();
+        StmtKind::Block(stmts) => {
+            let stmts: Vec<_> = stmts
+                .iter()
+                .map(|stmt| convert_listed_stmt(cx, stmt))
+                .collect();
             quote! {
                 {
                     #(#stmts)*
                 }
             }
         }
-        Stmt::If(if_stmt) => {
-            let test = convert_expr(&if_stmt.test);
-            let cons = convert_stmt(&if_stmt.cons);
+        StmtKind::If { test, cons, alt } => {
+            let test = convert_expr(cx, test);
+            let cons_block = as_block(cons, convert_stmt(cx, cons));
 
-            let cons_block = if matches!(*if_stmt.cons, Stmt::Block(_)) {
-                quote! { #cons }
-            } else {
-                quote! { { #cons } }
-            };
-
-            let alt = if let Some(alt) = &if_stmt.alt {
-                let alt_stmt = convert_stmt(alt);
-                let alt_block = if matches!(&**alt, Stmt::Block(_) | Stmt::If(_)) {
+            let alt = if let Some(alt) = alt {
+                let alt_stmt = convert_stmt(cx, alt);
+                let alt_block = if matches!(alt.kind, StmtKind::Block(_) | StmtKind::If { .. }) {
                     quote! { #alt_stmt }
                 } else {
                     quote! { { #alt_stmt } }
@@ -317,108 +382,105 @@ pub fn convert_stmt(stmt: &Stmt) -> proc_macro2::TokenStream {
                 if #test #cons_block #alt
             }
         }
-        Stmt::While(while_stmt) => {
-            let test = convert_expr(&while_stmt.test);
-            let body = convert_stmt(&while_stmt.body);
-            let body_block = if matches!(*while_stmt.body, Stmt::Block(_)) {
-                quote! { #body }
-            } else {
-                quote! { { #body } }
-            };
+        StmtKind::While { test, body } => {
+            let test = convert_expr(cx, test);
+            let body_block = as_block(body, convert_stmt(cx, body));
             quote! {
                 while #test #body_block
             }
         }
-        // for (const x of arr) → for x in arr
-        Stmt::ForOf(for_of) => {
-            let body = convert_stmt(&for_of.body);
-            let right = convert_expr(&for_of.right);
-            let body_block = if matches!(*for_of.body, Stmt::Block(_)) {
-                quote! { #body }
-            } else {
-                quote! { { #body } }
+        // for (init; test; update) body → { init; while test { body; update; } }
+        StmtKind::For {
+            init,
+            test,
+            update,
+            body,
+        } => {
+            let init = match init {
+                Some(ForInit::Var(var)) => {
+                    convert_stmt(cx, &Stmt::new(StmtKind::Var(var.clone()), stmt.span))
+                }
+                Some(ForInit::Expr(expr)) => {
+                    let expr = convert_expr(cx, expr);
+                    quote! { #expr; }
+                }
+                None => quote! {},
             };
-
-            // Extract the loop variable name from the declaration
-            let var_ident = match &for_of.left {
-                swc_ecma_ast::ForHead::VarDecl(var_decl) => {
-                    if let Some(decl) = var_decl.decls.first() {
-                        if let Pat::Ident(ident) = &decl.name {
-                            let name = to_snake_case(&ident.id.sym);
-                            format_ident!("{}", name)
-                        } else {
-                            format_ident!("_item")
+            let update = update.as_ref().map(|update| {
+                let update = convert_expr(cx, update);
+                quote! { #update; }
+            });
+            let exit = test.as_ref().map(|test| {
+                let test = convert_expr(cx, test);
+                quote! { if !(#test) { break; } }
+            });
+            let body_tokens = convert_stmt(cx, body);
+            if continues(body) {
+                // `continue` skips the rest of the body but not the update
+                quote! {
+                    {
+                        #init
+                        let mut __first = true;
+                        loop {
+                            if !__first {
+                                #update
+                            }
+                            __first = false;
+                            #exit
+                            #body_tokens
                         }
-                    } else {
-                        format_ident!("_item")
                     }
                 }
-                swc_ecma_ast::ForHead::Pat(pat) => {
-                    if let Pat::Ident(ident) = pat.as_ref() {
-                        let name = to_snake_case(&ident.id.sym);
-                        format_ident!("{}", name)
-                    } else {
-                        format_ident!("_item")
+            } else if let Some(test) = test {
+                let test = convert_expr(cx, test);
+                quote! {
+                    {
+                        #init
+                        while #test {
+                            #body_tokens
+                            #update
+                        }
                     }
                 }
-                _ => format_ident!("_item"),
-            };
+            } else {
+                quote! {
+                    {
+                        #init
+                        loop {
+                            #body_tokens
+                            #update
+                        }
+                    }
+                }
+            }
+        }
+        // for (const x of arr) → for x in arr
+        StmtKind::ForOf { left, right, body } => {
+            let right = convert_expr(cx, right);
+            let body_block = as_block(body, convert_stmt(cx, body));
+            let var_ident = loop_binding(cx, left, "_item");
 
             quote! {
                 for #var_ident in #right #body_block
             }
         }
         // for (const k in obj) → for k in obj.keys()
-        Stmt::ForIn(for_in) => {
-            let body = convert_stmt(&for_in.body);
-            let right = convert_expr(&for_in.right);
-            let body_block = if matches!(*for_in.body, Stmt::Block(_)) {
-                quote! { #body }
-            } else {
-                quote! { { #body } }
-            };
-
-            let var_ident = match &for_in.left {
-                swc_ecma_ast::ForHead::VarDecl(var_decl) => {
-                    if let Some(decl) = var_decl.decls.first() {
-                        if let Pat::Ident(ident) = &decl.name {
-                            let name = to_snake_case(&ident.id.sym);
-                            format_ident!("{}", name)
-                        } else {
-                            format_ident!("_key")
-                        }
-                    } else {
-                        format_ident!("_key")
-                    }
-                }
-                swc_ecma_ast::ForHead::Pat(pat) => {
-                    if let Pat::Ident(ident) = pat.as_ref() {
-                        let name = to_snake_case(&ident.id.sym);
-                        format_ident!("{}", name)
-                    } else {
-                        format_ident!("_key")
-                    }
-                }
-                _ => format_ident!("_key"),
-            };
+        StmtKind::ForIn { left, right, body } => {
+            let right = convert_expr(cx, right);
+            let body_block = as_block(body, convert_stmt(cx, body));
+            let var_ident = loop_binding(cx, left, "_key");
 
             quote! {
                 for #var_ident in #right.keys().cloned() #body_block
             }
         }
         // do { ... } while (x) → loop { ...; if !x { break; } }
-        Stmt::DoWhile(do_while) => {
-            let body = convert_stmt(&do_while.body);
-            let test = convert_expr(&do_while.test);
-            let body_inner = if matches!(*do_while.body, Stmt::Block(_)) {
-                // Extract inner statements from block
-                quote! { #body }
-            } else {
-                quote! { #body }
-            };
+        StmtKind::DoWhile { body, test } => {
+            let body = convert_stmt(cx, body);
+            let test = convert_expr(cx, test);
             quote! {
                 loop {
-                    #body_inner
+                    #body
                     if !(#test) {
                         break;
                     }
@@ -426,20 +488,34 @@ pub fn convert_stmt(stmt: &Stmt) -> proc_macro2::TokenStream {
             }
         }
         // try { ... } catch (e) { ... } → match (|| { ... })() { Ok/Err }
-        Stmt::Try(try_stmt) => {
-            let try_body: Vec<_> = try_stmt.block.stmts.iter().map(convert_stmt).collect();
+        StmtKind::Try {
+            block,
+            handler,
+            finalizer,
+        } => {
+            let try_body: Vec<_> = block
+                .iter()
+                .map(|stmt| convert_listed_stmt(cx, stmt))
+                .collect();
 
-            if let Some(catch) = &try_stmt.handler {
-                let catch_body: Vec<_> = catch.body.stmts.iter().map(convert_stmt).collect();
+            if let Some(catch) = handler {
+                let catch_body: Vec<_> = catch
+                    .body
+                    .iter()
+                    .map(|stmt| convert_listed_stmt(cx, stmt))
+                    .collect();
                 let err_ident = catch
                     .param
                     .as_ref()
-                    .and_then(|p| p.as_ident())
-                    .map(|i| format_ident!("{}", to_snake_case(&i.id.sym)))
+                    .and_then(Pat::as_ident)
+                    .map(|b| format_ident!("{}", cx.snake_case(&b.name)))
                     .unwrap_or_else(|| format_ident!("_err"));
 
-                if let Some(finalizer) = &try_stmt.finalizer {
-                    let finally_body: Vec<_> = finalizer.stmts.iter().map(convert_stmt).collect();
+                if let Some(finalizer) = finalizer {
+                    let finally_body: Vec<_> = finalizer
+                        .iter()
+                        .map(|stmt| convert_listed_stmt(cx, stmt))
+                        .collect();
                     quote! {
                         let __try_result = (|| -> Result<(), Box<dyn std::error::Error>> {
                             #(#try_body)*
@@ -461,8 +537,11 @@ pub fn convert_stmt(stmt: &Stmt) -> proc_macro2::TokenStream {
                         }
                     }
                 }
-            } else if let Some(finalizer) = &try_stmt.finalizer {
-                let finally_body: Vec<_> = finalizer.stmts.iter().map(convert_stmt).collect();
+            } else if let Some(finalizer) = finalizer {
+                let finally_body: Vec<_> = finalizer
+                    .iter()
+                    .map(|stmt| convert_listed_stmt(cx, stmt))
+                    .collect();
                 quote! {
                     {
                         #(#try_body)*
@@ -476,28 +555,31 @@ pub fn convert_stmt(stmt: &Stmt) -> proc_macro2::TokenStream {
             }
         }
         // throw new Error("msg") → return Err("msg".into())
-        Stmt::Throw(throw_stmt) => {
-            let arg = convert_expr(&throw_stmt.arg);
+        StmtKind::Throw(arg) => {
+            let arg = convert_expr(cx, arg);
             quote! {
                 return Err(#arg.into());
             }
         }
         // switch (x) { case a: ...; break; } → match x { a => { ... } }
-        Stmt::Switch(switch_stmt) => {
-            let discriminant = convert_expr(&switch_stmt.discriminant);
+        StmtKind::Switch {
+            discriminant,
+            cases,
+        } => {
+            let discriminant = convert_expr(cx, discriminant);
             let mut arms = Vec::new();
 
-            for case in &switch_stmt.cases {
+            for case in cases {
                 // Filter out break statements from case body
                 let body_stmts: Vec<_> = case
                     .cons
                     .iter()
-                    .filter(|s| !matches!(s, Stmt::Break(_)))
-                    .map(convert_stmt)
+                    .filter(|s| !matches!(s.kind, StmtKind::Break))
+                    .map(|stmt| convert_listed_stmt(cx, stmt))
                     .collect();
 
                 if let Some(test) = &case.test {
-                    let test_expr = convert_expr(test);
+                    let test_expr = convert_expr(cx, test);
                     arms.push(quote! {
                         x if x == #test_expr => {
                             #(#body_stmts)*
@@ -514,7 +596,7 @@ pub fn convert_stmt(stmt: &Stmt) -> proc_macro2::TokenStream {
             }
 
             // If no default arm was found, add a wildcard
-            let has_default = switch_stmt.cases.iter().any(|c| c.test.is_none());
+            let has_default = cases.iter().any(|c| c.test.is_none());
             if !has_default {
                 arms.push(quote! { _ => {} });
             }
@@ -525,104 +607,244 @@ pub fn convert_stmt(stmt: &Stmt) -> proc_macro2::TokenStream {
                 }
             }
         }
-        Stmt::Break(_) => quote! { break; },
-        Stmt::Continue(_) => quote! { continue; },
-        _ => quote! { /* unsupported statement */ },
+        // Narrowed discriminated union → match on the generated enum
+        StmtKind::Match { scrutinee, arms } => {
+            convert_match(cx, scrutinee, arms, &|s| convert_stmt(cx, s))
+        }
+        StmtKind::Break => quote! { break; },
+        StmtKind::Continue => quote! { continue; },
+        StmtKind::Empty => quote! {},
+        StmtKind::Decl(_) => {
+            let todo = cx.todo(Feature::NestedDeclarations, stmt.span);
+            quote! { #todo; }
+        }
+        StmtKind::Unsupported { what } => {
+            let todo = cx.todo(Feature::Syntax(what.clone()), stmt.span);
+            quote! { #todo; }
+        }
+    }
+}
+
+/// `match` over a synthesized union enum; arm bodies are converted with `convert`.
+fn convert_match(
+    cx: &Context,
+    scrutinee: &Expr,
+    arms: &[MatchArm],
+    convert: &dyn Fn(&Stmt) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let tokens = convert_expr(cx, scrutinee);
+    let arms: Vec<_> = arms
+        .iter()
+        .map(|arm| {
+            let pats: Vec<_> = arm
+                .pats
+                .iter()
+                .map(|pat| convert_match_pat(cx, pat))
+                .collect();
+            let body: Vec<_> = arm.body.iter().map(convert).collect();
+            quote! {
+                #(#pats)|* => {
+                    #(#body)*
+                }
+            }
+        })
+        .collect();
+
+    // Without ownership inference the scrutinee may still be used afterwards
+    let scrutinee = if scrutinee.usage == Usage::Unknown {
+        quote! { #tokens.clone() }
+    } else {
+        tokens
+    };
+    quote! {
+        match #scrutinee {
+            #(#arms)*
+        }
+    }
+}
+
+fn convert_match_pat(cx: &Context, pat: &MatchPat) -> proc_macro2::TokenStream {
+    match pat {
+        MatchPat::Variant { union, tag, fields } => {
+            let union = format_ident!("{}", union);
+            let variant = format_ident!("{}", to_pascal_case(tag));
+            let fields = fields.iter().map(|f| format_ident!("{}", cx.snake_case(f)));
+            quote! { #union::#variant { #(#fields,)* .. } }
+        }
+        MatchPat::Newtype {
+            union,
+            variant,
+            binding,
+        } => {
+            let union = format_ident!("{}", union);
+            let variant = format_ident!("{}", variant);
+            let binding = format_ident!("{}", cx.snake_case(binding));
+            quote! { #union::#variant(#binding) }
+        }
+        MatchPat::Wildcard => quote! { _ },
+    }
+}
+
+pub fn convert_expr(cx: &Context, expr: &Expr) -> proc_macro2::TokenStream {
+    let tokens = convert_value(cx, expr);
+    // `x as f64.abs()` and `x as f64 < y` don't parse
+    match expr.num_cast {
+        Some(_) => quote! { (#tokens) },
+        None => tokens,
+    }
+}
+
+/// Converts `expr` where it stands on its own, like a `let` initializer,
+/// return value, argument or index, so a conversion needs no parentheses.
+pub fn convert_value(cx: &Context, expr: &Expr) -> proc_macro2::TokenStream {
+    let mut tokens = convert_expr_kind(cx, expr);
+    if let Some(repr) = expr.num_cast {
+        let ty = num_type(repr);
+        // `as` binds tighter than binary operators
+        tokens = match expr.kind {
+            ExprKind::Ident(_)
+            | ExprKind::Lit(_)
+            | ExprKind::Member(_)
+            | ExprKind::Call(_)
+            | ExprKind::Paren(_) => quote! { #tokens as #ty },
+            _ => quote! { (#tokens) as #ty },
+        };
+    }
+    match &expr.coerce {
+        Some(to) => cx.coerce(tokens, &expr.ty, to),
+        None => tokens,
     }
 }
 
-pub fn convert_expr(expr: &Expr) -> proc_macro2::TokenStream {
-    match expr {
-        Expr::Bin(bin) => convert_bin_expr(bin),
-        Expr::This(_) => quote! { self },
-        Expr::Ident(ident) => {
-            let name = ident.sym.as_str();
+/// Converts `expr`, leaving the conversion to its context's number type to
+/// [`convert_expr`].
+fn convert_expr_kind(cx: &Context, expr: &Expr) -> proc_macro2::TokenStream {
+    match &expr.kind {
+        ExprKind::Bin { op, left, right } => convert_bin_expr(cx, *op, left, right, expr.span),
+        ExprKind::This => quote! { self },
+        ExprKind::Super => quote! { self.base },
+        ExprKind::Ident(name) => {
             // Handle special identifiers
             if name == "undefined" {
                 return quote! { None };
             }
             // If starts with uppercase, assume Class/Type and keep as is
             // If starts with lowercase, convert to snake_case (variable/function)
-            if name.chars().next().is_some_and(|c| c.is_uppercase()) {
-                let ident_token = format_ident!("{}", name);
-                quote! { #ident_token }
+            let ident_token = if name.chars().next().is_some_and(|c| c.is_uppercase()) {
+                format_ident!("{}", name)
+            } else {
+                format_ident!("{}", cx.snake_case(name))
+            };
+            // Consumed here but read again later
+            if expr.usage == Usage::Clone {
+                quote! { #ident_token.clone() }
             } else {
-                let ident_name = to_snake_case(name);
-                let ident_token = format_ident!("{}", ident_name);
                 quote! { #ident_token }
             }
         }
-        Expr::Lit(lit) => {
-            // Handle literals
-            match lit {
-                Lit::Num(num) => {
-                    let value = num.value;
+        ExprKind::Lit(lit) => match lit {
+            Lit::Num(value) => match expr.num {
+                NumRepr::F64 => quote! { #value },
+                _ => {
+                    let value = proc_macro2::Literal::i64_unsuffixed(*value as i64);
                     quote! { #value }
                 }
-                Lit::Str(str_lit) => {
-                    let s = str_lit.value.as_str().unwrap_or("");
-                    quote! { String::from(#s) }
-                }
-                Lit::Bool(b) => {
-                    let value = b.value;
-                    quote! { #value }
-                }
-                _ => quote! { todo!("unsupported literal") },
+            },
+            Lit::Str(s) => quote! { String::from(#s) },
+            Lit::Bool(value) => quote! { #value },
+            _ => cx.todo(Feature::RegexAndBigIntLiterals, expr.span),
+        },
+        ExprKind::Member(member) => convert_member_expr(cx, member, &expr.ty, expr.usage, expr.num),
+        ExprKind::Await(arg) => {
+            let arg = convert_expr(cx, arg);
+            quote! { #arg.await? }
+        }
+        ExprKind::Call(call) => convert_call_expr(cx, call),
+        ExprKind::New(new_expr) => convert_new_expr(cx, new_expr),
+        ExprKind::Tpl { quasis, exprs } => convert_tpl_expr(cx, quasis, exprs),
+        ExprKind::Arrow(arrow) => convert_arrow_expr(cx, arrow),
+        ExprKind::Object(props) => convert_object_lit(cx, props, expr.span),
+        ExprKind::Array(elems) => convert_array_lit(cx, elems),
+        ExprKind::Update { op, arg, .. } => {
+            // obj.x++ assigns like obj.x += 1, which knows about setters and statics
+            if let ExprKind::Member(member) = &arg.kind {
+                let assign_op = match op {
+                    UpdateOp::Inc => AssignOp::AddAssign,
+                    UpdateOp::Dec => AssignOp::SubAssign,
+                };
+                let one = Expr::new(ExprKind::Lit(Lit::Num(1.0)), arg.span);
+                return convert_assign_expr(
+                    cx,
+                    assign_op,
+                    &AssignTarget::Member(member.clone()),
+                    &one,
+                    expr.span,
+                );
             }
+            let one = match arg.num {
+                NumRepr::F64 => quote! { 1.0 },
+                _ => quote! { 1 },
+            };
+            let arg = convert_expr(cx, arg);
+            match op {
+                UpdateOp::Inc => quote! { #arg += #one },
+                UpdateOp::Dec => quote! { #arg -= #one },
+            }
+        }
+        ExprKind::Assign { op, target, right } => {
+            convert_assign_expr(cx, *op, target, right, expr.span)
         }
-        Expr::Member(member) => convert_member_expr(member),
-        Expr::Await(await_expr) => convert_await_expr(await_expr),
-        Expr::Call(call_expr) => convert_call_expr(call_expr),
-        Expr::New(new_expr) => convert_new_expr(new_expr),
-        Expr::Tpl(tpl) => convert_tpl_expr(tpl),
-        Expr::Arrow(arrow) => convert_arrow_expr(arrow),
-        Expr::Object(obj) => convert_object_lit(obj),
-        Expr::Array(arr) => convert_array_lit(arr),
-        Expr::Update(update) => convert_update_expr(update),
-        Expr::Assign(assign) => convert_assign_expr(assign),
-        Expr::Unary(unary) => {
-            let arg = convert_expr(&unary.arg);
-            match unary.op {
-                swc_ecma_ast::UnaryOp::Bang => quote! { !#arg },
-                swc_ecma_ast::UnaryOp::Minus => quote! { -#arg },
-                swc_ecma_ast::UnaryOp::Plus => quote! { +#arg },
-                swc_ecma_ast::UnaryOp::TypeOf => {
+        ExprKind::Unary { op, arg } => {
+            let arg = convert_expr(cx, arg);
+            let feature = Feature::Unary(*op);
+            if !feature.is_supported() {
+                return cx.todo(feature, expr.span);
+            }
+            match op {
+                UnaryOp::Not => quote! { !#arg },
+                UnaryOp::Minus => quote! { -#arg },
+                UnaryOp::Plus => quote! { +#arg },
+                UnaryOp::TypeOf => {
                     quote! { std::any::type_name_of_val(&#arg) }
                 }
-                _ => quote! { todo!("unsupported unary op") },
+                UnaryOp::Tilde | UnaryOp::Void | UnaryOp::Delete => {
+                    unreachable!("unsupported operators are reported above")
+                }
             }
         }
         // Ternary: x ? a : b → if x { a } else { b }
-        Expr::Cond(cond) => {
-            let test = convert_expr(&cond.test);
-            let cons = convert_expr(&cond.cons);
-            let alt = convert_expr(&cond.alt);
+        ExprKind::Cond { test, cons, alt } => {
+            let test = convert_expr(cx, test);
+            let cons = convert_expr(cx, cons);
+            let alt = convert_expr(cx, alt);
             quote! {
                 if #test { #cons } else { #alt }
             }
         }
-        // Parenthesized expression: (expr) → expr
-        Expr::Paren(paren) => convert_expr(&paren.expr),
+        // Parenthesized expression: (expr) → (expr), the grouping may be
+        // load-bearing: (a + b) / d
+        ExprKind::Paren(inner) => {
+            let inner = convert_expr(cx, inner);
+            quote! { (#inner) }
+        }
         // Optional chaining: obj?.prop → obj.as_ref().map(|v| v.prop)
-        Expr::OptChain(opt_chain) => match opt_chain.base.as_ref() {
-            swc_ecma_ast::OptChainBase::Member(member) => {
-                let obj = convert_expr(&member.obj);
-                if let Some(ident) = member.prop.as_ident() {
-                    let prop_name = format_ident!("{}", to_snake_case(&ident.sym));
-                    quote! { #obj.as_ref().map(|v| v.#prop_name.clone()).flatten() }
-                } else {
-                    quote! { #obj }
-                }
-            }
-            swc_ecma_ast::OptChainBase::Call(call) => {
-                let callee = convert_expr(&call.callee);
-                let args: Vec<_> = call.args.iter().map(|a| convert_expr(&a.expr)).collect();
-                quote! { #callee.as_ref().map(|v| v(#(#args),*)) }
+        ExprKind::OptMember(member) => {
+            let obj = convert_expr(cx, &member.obj);
+            if let Some(prop) = member.prop.as_ident() {
+                let prop_name = format_ident!("{}", cx.snake_case(prop));
+                quote! { #obj.as_ref().map(|v| v.#prop_name.clone()).flatten() }
+            } else {
+                quote! { #obj }
             }
-        },
+        }
+        ExprKind::OptCall(call) => {
+            let callee = convert_callee(cx, &call.callee);
+            let args: Vec<_> = call.args.iter().map(|arg| convert_arg(cx, arg)).collect();
+            quote! { #callee.as_ref().map(|v| v(#(#args),*)) }
+        }
         // Sequence expression: (a, b, c) → { a; b; c }
-        Expr::Seq(seq) => {
-            let exprs: Vec<_> = seq.exprs.iter().map(|e| convert_expr(e)).collect();
+        ExprKind::Seq(exprs) => {
+            let exprs: Vec<_> = exprs.iter().map(|expr| convert_expr(cx, expr)).collect();
             if let Some(last) = exprs.last() {
                 let init = &exprs[..exprs.len() - 1];
                 quote! { { #(#init;)* #last } }
@@ -630,295 +852,328 @@ pub fn convert_expr(expr: &Expr) -> proc_macro2::TokenStream {
                 quote! { () }
             }
         }
-        _ => quote! { todo!() },
+        ExprKind::Unsupported { what } => cx.todo(Feature::Syntax(what.clone()), expr.span),
     }
 }
 
-fn convert_assign_expr(assign: &swc_ecma_ast::AssignExpr) -> proc_macro2::TokenStream {
-    let right = convert_expr(&assign.right);
-    let left = match &assign.left {
-        swc_ecma_ast::AssignTarget::Simple(simple) => match simple {
-            swc_ecma_ast::SimpleAssignTarget::Ident(ident) => {
-                let name = format_ident!("{}", to_snake_case(ident.sym.as_ref()));
-                quote! { #name }
+/// `=` or the compound assignment operator for `op`, which the feature
+/// registry supports.
+fn assign_op_tokens(op: AssignOp) -> proc_macro2::TokenStream {
+    match op {
+        AssignOp::Assign => quote! { = },
+        AssignOp::AddAssign => quote! { += },
+        AssignOp::SubAssign => quote! { -= },
+        AssignOp::MulAssign => quote! { *= },
+        AssignOp::DivAssign => quote! { /= },
+        AssignOp::ModAssign => quote! { %= },
+        AssignOp::Other => unreachable!("unsupported assignments are reported first"),
+    }
+}
+
+/// The binary operator a compound assignment applies, `+` for `+=`.
+fn compound_bin_op(op: AssignOp) -> Option<proc_macro2::TokenStream> {
+    Some(match op {
+        AssignOp::AddAssign => quote! { + },
+        AssignOp::SubAssign => quote! { - },
+        AssignOp::MulAssign => quote! { * },
+        AssignOp::DivAssign => quote! { / },
+        AssignOp::ModAssign => quote! { % },
+        AssignOp::Assign | AssignOp::Other => return None,
+    })
+}
+
+/// Converts the assignment `target op right` found at `span`.
+fn convert_assign_expr(
+    cx: &Context,
+    op: AssignOp,
+    target: &AssignTarget,
+    right: &Expr,
+    span: Span,
+) -> proc_macro2::TokenStream {
+    let feature = Feature::Assign(op);
+    if !feature.is_supported() {
+        return cx.todo(feature, span);
+    }
+    let op_tokens = assign_op_tokens(op);
+    let right = convert_value(cx, right);
+    let left = match target {
+        AssignTarget::Ident(name) => {
+            let name = format_ident!("{}", cx.snake_case(name));
+            quote! { #name }
+        }
+        AssignTarget::Member(member) => {
+            // Class.counter = v -> *CLASS_COUNTER.lock().unwrap() = v
+            let module_static = member
+                .obj
+                .as_ident()
+                .zip(member.prop.as_ident())
+                .and_then(|(class, name)| cx.module_static(class, name));
+            if let Some((ident, _)) = module_static {
+                return quote! { *#ident.lock().unwrap() #op_tokens #right };
             }
-            swc_ecma_ast::SimpleAssignTarget::Member(member) => {
-                // Handle LHS member access (no clone)
-                let obj = convert_expr(&member.obj);
-                match &member.prop {
-                    swc_ecma_ast::MemberProp::Ident(ident) => {
-                        let prop = format_ident!("{}", ident.sym.as_ref().to_string());
-                        quote! { #obj.#prop }
-                    }
-                    swc_ecma_ast::MemberProp::Computed(computed) => {
-                        if let swc_ecma_ast::Expr::Lit(swc_ecma_ast::Lit::Num(num)) =
-                            &*computed.expr
-                        {
-                            let idx = num.value as usize;
-                            quote! { #obj[#idx] }
-                        } else {
-                            let prop = convert_expr(&computed.expr);
-                            quote! { #obj[#prop] }
-                        }
-                    }
-                    _ => quote! { todo!("complex member assignment") },
+            // Handle LHS member access (no clone)
+            let obj = convert_expr(cx, &member.obj);
+            match &member.prop {
+                // obj.count = v -> obj.set_count(v), and compound
+                // assignments read through the getter first
+                MemberProp::Ident(name) if cx.is_setter(&member.obj.ty, name) => {
+                    let setter = cx.setter_ident(name);
+                    let getter = format_ident!("{}", cx.snake_case(name));
+                    return match compound_bin_op(op) {
+                        Some(bin) => quote! { #obj.#setter(#obj.#getter() #bin #right) },
+                        None => quote! { #obj.#setter(#right) },
+                    };
+                }
+                MemberProp::Ident(name) => {
+                    let prop = format_ident!("{}", name);
+                    quote! { #obj.#prop }
+                }
+                MemberProp::Computed(computed) => convert_index(cx, obj, computed),
+                MemberProp::Private(name) => {
+                    let field = format_ident!("{}", cx.snake_case(name));
+                    quote! { #obj.#field }
                 }
             }
-            _ => quote! { todo!("unsupported assign target") },
-        },
-        _ => quote! { todo!("unsupported assign target") },
+        }
+        AssignTarget::Unsupported => {
+            return cx.todo(Feature::DestructuringAssignments, span);
+        }
     };
 
-    match assign.op {
-        swc_ecma_ast::AssignOp::Assign => quote! { #left = #right },
-        swc_ecma_ast::AssignOp::AddAssign => quote! { #left += #right },
-        swc_ecma_ast::AssignOp::SubAssign => quote! { #left -= #right },
-        _ => quote! { todo!("unsupported assign op") },
-    }
+    quote! { #left #op_tokens #right }
 }
 
-fn convert_update_expr(update: &UpdateExpr) -> proc_macro2::TokenStream {
-    let arg = convert_expr(&update.arg);
-    match update.op {
-        UpdateOp::PlusPlus => quote! { #arg += 1.0 },
-        UpdateOp::MinusMinus => quote! { #arg -= 1.0 },
+/// `obj[idx]`; numeric literal indices become `usize`.
+fn convert_index(
+    cx: &Context,
+    obj: proc_macro2::TokenStream,
+    index: &Expr,
+) -> proc_macro2::TokenStream {
+    if let ExprKind::Lit(Lit::Num(num)) = index.kind {
+        let idx = num as usize;
+        quote! { #obj[#idx] }
+    } else {
+        let prop = convert_value(cx, index);
+        quote! { #obj[#prop] }
     }
 }
 
-fn convert_object_lit(obj: &swc_ecma_ast::ObjectLit) -> proc_macro2::TokenStream {
-    let mut fields = Vec::new();
-    for prop in &obj.props {
-        if let swc_ecma_ast::PropOrSpread::Prop(prop) = prop {
-            match &**prop {
-                swc_ecma_ast::Prop::KeyValue(prop) => {
-                    let key = match &prop.key {
-                        swc_ecma_ast::PropName::Ident(ident) => {
-                            format!("{:?}", ident.sym).trim_matches('"').to_string()
-                        }
-                        swc_ecma_ast::PropName::Str(s) => {
-                            format!("{:?}", s.value).trim_matches('"').to_string()
-                        }
-                        swc_ecma_ast::PropName::Num(n) => n.to_string(),
-                        swc_ecma_ast::PropName::Computed(_) => "computed_key".to_string(), // TODO
-                        swc_ecma_ast::PropName::BigInt(n) => n.value.to_string(),
-                    };
-
-                    let value = if let Expr::Ident(ident) = prop.value.as_ref() {
-                        if ident.sym == "undefined" {
-                            quote! { serde_json::Value::Null }
-                        } else {
-                            convert_expr(&prop.value)
-                        }
-                    } else {
-                        convert_expr(&prop.value)
-                    };
+fn convert_object_lit(cx: &Context, props: &[Prop], span: Span) -> proc_macro2::TokenStream {
+    // Keys are checked before any value is converted, so a literal Tyrus
+    // can't translate is reported alone, as `tyrus check` reports it
+    let mut entries = Vec::new();
+    for prop in props {
+        let entry = match prop {
+            Prop::KeyValue { key, value } => match key {
+                PropKey::Ident(name)
+                | PropKey::Str(name)
+                | PropKey::Num(name)
+                | PropKey::BigInt(name) => (name, Some(value)),
+                PropKey::Computed(_) => return cx.todo(Feature::ComputedKeys, span),
+            },
+            Prop::Shorthand(key) => (key, None),
+            Prop::Unsupported => return cx.todo(Feature::ObjectLiteralMembers, span),
+        };
+        entries.push(entry);
+    }
 
-                    fields.push(quote! { #key: #value });
-                }
-                swc_ecma_ast::Prop::Shorthand(ident) => {
-                    let key = format!("{:?}", ident.sym).trim_matches('"').to_string();
-                    let value = format_ident!("{}", to_snake_case(&key));
-                    fields.push(quote! { #key: #value });
-                }
-                _ => {}
+    let fields = entries.into_iter().map(|(key, value)| {
+        let value = match value {
+            Some(value) if value.as_ident() == Some("undefined") => {
+                quote! { serde_json::Value::Null }
             }
-        }
-    }
+            Some(value) => convert_expr(cx, value),
+            None => {
+                let value = format_ident!("{}", cx.snake_case(key));
+                quote! { #value }
+            }
+        };
+        quote! { #key: #value }
+    });
     quote! { serde_json::json!({ #(#fields),* }) }
 }
 
-fn convert_array_lit(arr: &swc_ecma_ast::ArrayLit) -> proc_macro2::TokenStream {
-    let elems: Vec<_> = arr
-        .elems
+fn convert_array_lit(cx: &Context, elems: &[Option<Arg>]) -> proc_macro2::TokenStream {
+    let elems: Vec<_> = elems
         .iter()
-        .map(|elem| {
-            if let Some(elem) = elem {
-                convert_expr_or_spread(elem)
-            } else {
-                quote! { serde_json::Value::Null }
-            }
+        .map(|elem| match elem {
+            Some(elem) => convert_value(cx, &elem.expr),
+            None => quote! { serde_json::Value::Null },
         })
         .collect();
     quote! { vec![#(#elems),*] }
 }
 
-fn convert_tpl_expr(tpl: &swc_ecma_ast::Tpl) -> proc_macro2::TokenStream {
+fn convert_tpl_expr(cx: &Context, quasis: &[String], exprs: &[Expr]) -> proc_macro2::TokenStream {
     let mut format_str = String::new();
     let mut args = Vec::new();
 
-    let quasis = &tpl.quasis;
-    let exprs = &tpl.exprs;
-
     for (i, quasi) in quasis.iter().enumerate() {
-        format_str.push_str(&quasi.raw);
-        if i < exprs.len() {
+        format_str.push_str(quasi);
+        if let Some(expr) = exprs.get(i) {
             format_str.push_str("{}");
-            args.push(convert_expr(&exprs[i]));
+            args.push(convert_expr(cx, expr));
         }
     }
 
     quote! { format!(#format_str, #(#args),*) }
 }
 
-fn convert_new_expr(new_expr: &swc_ecma_ast::NewExpr) -> proc_macro2::TokenStream {
+fn convert_new_expr(cx: &Context, new_expr: &CallExpr) -> proc_macro2::TokenStream {
     // Convert new Class(args) -> Class::new(args)
-    let callee = convert_expr(&new_expr.callee);
-    let args = if let Some(args) = &new_expr.args {
-        args.iter().map(convert_expr_or_spread).collect()
-    } else {
-        Vec::new()
+    let callee = match new_expr.callee.as_expr().and_then(Expr::as_ident) {
+        Some("Map") => quote! { std::collections::HashMap },
+        Some("Set") => quote! { std::collections::HashSet },
+        _ => convert_callee(cx, &new_expr.callee),
     };
+    let args: Vec<_> = new_expr
+        .args
+        .iter()
+        .map(|arg| convert_arg(cx, arg))
+        .collect();
 
     quote! { #callee::new(#(#args),*) }
 }
 
-fn convert_member_expr(member: &MemberExpr) -> proc_macro2::TokenStream {
+/// Converts `member`, whose value is of type `ty` (represented as `num` when
+/// it is a number) and used as `usage`.
+fn convert_member_expr(
+    cx: &Context,
+    member: &MemberExpr,
+    ty: &Type,
+    usage: Usage,
+    num: NumRepr,
+) -> proc_macro2::TokenStream {
     // Handle this.prop -> self.prop
     if member.obj.is_this() {
-        if let Some(prop_ident) = member.prop.as_ident() {
-            let prop_name = to_snake_case(prop_ident.sym.as_ref());
-            let field = format_ident!("{}", prop_name);
-            return quote! { self.#field.clone() };
+        match &member.prop {
+            MemberProp::Ident(prop) if cx.is_getter(&member.obj.ty, prop) => {
+                let getter = format_ident!("{}", cx.snake_case(prop));
+                return quote! { self.#getter() };
+            }
+            // Fields can't be moved out of `&self`; only clone what is consumed
+            MemberProp::Ident(prop) | MemberProp::Private(prop) => {
+                let field = format_ident!("{}", cx.snake_case(prop));
+                return match usage {
+                    Usage::Unknown | Usage::Clone => quote! { self.#field.clone() },
+                    _ => quote! { self.#field },
+                };
+            }
+            MemberProp::Computed(_) => {}
         }
     }
     // Handle other.prop or other[prop]
-    let obj = convert_expr(&member.obj);
+    let mut obj = convert_expr(cx, &member.obj);
+    let mut usage = usage;
+    // Fields of a class trait parameter are borrowed through the trait;
+    // its methods are the trait's own
+    if let Some(as_class) = cx
+        .class_of_trait_param(&member.obj)
+        .filter(|_| !matches!(ty, Type::Function { .. }))
+    {
+        obj = quote! { #obj.#as_class() };
+        if usage == Usage::Move && !ty.is_copy() {
+            usage = Usage::Clone;
+        }
+    }
 
     match &member.prop {
-        swc_ecma_ast::MemberProp::Ident(ident) => {
+        MemberProp::Ident(prop_name) if cx.is_tag_read(&member.obj.ty, prop_name) => {
+            let method = cx.tag_method(prop_name);
+            quote! { #obj.#method().to_string() }
+        }
+        MemberProp::Ident(prop_name) => {
             // Check if obj is an identifier starting with uppercase (Enum or Class static)
-            // But we already converted obj to TokenStream. We need to check the original AST.
-            let is_static_access = if let swc_ecma_ast::Expr::Ident(obj_ident) = &*member.obj {
-                obj_ident
-                    .sym
-                    .chars()
-                    .next()
-                    .is_some_and(|c| c.is_uppercase())
-            } else {
-                false
-            };
-
-            if is_static_access {
-                // Static access: Enum.Variant or Class.Method
-                // Keep the property name as-is (don't force snake_case)
-                let prop_name = ident.sym.as_ref();
-                let prop = format_ident!("{}", prop_name);
-                // Also, we need the original object name, not the converted token stream
-                if let swc_ecma_ast::Expr::Ident(obj_ident) = &*member.obj {
-                    let obj_name = obj_ident.sym.as_ref();
-                    let obj_ident = format_ident!("{}", obj_name);
-                    quote! { #obj_ident::#prop }
+            let static_obj = member
+                .obj
+                .as_ident()
+                .filter(|name| name.chars().next().is_some_and(|c| c.is_uppercase()));
+
+            if let Some(obj_name) = static_obj {
+                match cx.module_static(obj_name, prop_name) {
+                    Some((ident, true)) => return quote! { #ident.lock().unwrap().clone() },
+                    Some((ident, false)) => return quote! { #ident.clone() },
+                    None => {}
+                }
+                // Static access: Enum.Variant, Class.CONST or Class.method.
+                // Only static methods are snake_cased, like their definitions
+                let prop = if matches!(ty, Type::Function { .. }) {
+                    format_ident!("{}", cx.snake_case(prop_name))
+                } else {
+                    format_ident!("{}", prop_name)
+                };
+                let obj_ident = format_ident!("{}", obj_name);
+                quote! { #obj_ident::#prop }
+            } else if let Some(stdlib_code) =
+                crate::stdlib::try_handle_member(cx, &member.obj, prop_name, num)
+            {
+                stdlib_code
+            } else if cx.is_getter(&member.obj.ty, prop_name) {
+                let getter = format_ident!("{}", cx.snake_case(prop_name));
+                quote! { #obj.#getter() }
+            } else if cx.is_trait_getter(&member.obj.ty, prop_name) {
+                // Properties of trait interfaces are getters
+                let prop = format_ident!("{}", cx.snake_case(prop_name));
+                if cx.is_mutated_interface(&member.obj.ty) {
+                    locked_call(obj, prop, Vec::new())
                 } else {
-                    // Should be unreachable given is_static_access check
-                    quote! { #obj::#prop }
+                    quote! { #obj.#prop() }
                 }
             } else {
                 // Instance access: obj.prop -> obj.prop (snake_case)
-                let prop_name = to_snake_case(ident.sym.as_ref());
-                let prop = format_ident!("{}", prop_name);
-                quote! { #obj.#prop }
+                let prop = format_ident!("{}", cx.snake_case(prop_name));
+                clone_if(quote! { #obj.#prop }, usage)
             }
         }
-        swc_ecma_ast::MemberProp::Computed(computed) => {
-            // Check if expr is number literal
-            if let swc_ecma_ast::Expr::Lit(swc_ecma_ast::Lit::Num(num)) = &*computed.expr {
-                let idx = num.value as usize;
-                quote! { #obj[#idx] }
-            } else {
-                let prop = convert_expr(&computed.expr);
-                quote! { #obj[#prop] }
-            }
+        MemberProp::Computed(computed) => clone_if(convert_index(cx, obj, computed), usage),
+        MemberProp::Private(name) => {
+            let field = format_ident!("{}", cx.snake_case(name));
+            clone_if(quote! { #obj.#field }, usage)
         }
-        _ => quote! { todo!("complex member access") },
     }
 }
 
-pub fn convert_bin_expr(bin: &BinExpr) -> proc_macro2::TokenStream {
-    let left = convert_expr(&bin.left);
-    let mut right = convert_expr(&bin.right);
-
-    if bin.op == BinaryOp::NullishCoalescing {
-        return quote! { #left.unwrap_or(#right) };
+/// Clones a field or element read as `usage` when it is consumed but can't
+/// be moved out.
+fn clone_if(tokens: proc_macro2::TokenStream, usage: Usage) -> proc_macro2::TokenStream {
+    if usage == Usage::Clone {
+        quote! { #tokens.clone() }
+    } else {
+        tokens
     }
+}
 
-    if bin.op == BinaryOp::Add {
-        // Check if left is string
-        let mut is_left_string = false;
-        let mut left_expr = &*bin.left;
-        while let Expr::Paren(p) = left_expr {
-            left_expr = &p.expr;
-        }
-
-        if let Expr::Call(call) = left_expr {
-            if let Callee::Expr(callee) = &call.callee {
-                if let Expr::Member(member) = &**callee {
-                    if let Some(obj) = member.obj.as_ident() {
-                        if obj.sym == "String" {
-                            if let Some(prop) = member.prop.as_ident() {
-                                if prop.sym == "from" {
-                                    is_left_string = true;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        } else if let Expr::Lit(Lit::Str(_)) = left_expr {
-            is_left_string = true;
-        }
+/// Converts the binary expression `left op right` found at `span`.
+pub fn convert_bin_expr(
+    cx: &Context,
+    op: BinaryOp,
+    left: &Expr,
+    right: &Expr,
+    span: Span,
+) -> proc_macro2::TokenStream {
+    let left_tokens = convert_expr(cx, left);
+    let mut right_tokens = convert_expr(cx, right);
 
-        let mut handled = false;
-
-        // Heuristic: If right side is a string method call, borrow it to allow String + &String
-        if let Expr::Call(call) = &*bin.right {
-            if let Callee::Expr(callee_expr) = &call.callee {
-                if let Expr::Member(member) = &**callee_expr {
-                    if let Some(ident) = member.prop.as_ident() {
-                        let method_name = ident.sym.as_ref();
-                        match method_name {
-                            "toString" | "toUpperCase" | "toLowerCase" | "trim" | "replace"
-                            | "join" | "repeat" | "slice" | "substring" | "substr" => {
-                                right = quote! { &#right };
-                                handled = true;
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-            }
-        }
+    let feature = Feature::Binary(op);
+    if !feature.is_supported() {
+        return cx.todo(feature, span);
+    }
 
-        if !handled {
-            if is_left_string {
-                let mut right_expr = &*bin.right;
-                while let Expr::Paren(p) = right_expr {
-                    right_expr = &p.expr;
-                }
+    if op == BinaryOp::NullishCoalescing {
+        return quote! { #left_tokens.unwrap_or(#right_tokens) };
+    }
 
-                // If right is LitNum, .to_string().
-                if let Expr::Lit(Lit::Num(_)) = right_expr {
-                    right = quote! { &#right.to_string() };
-                }
-                // If right is a call (e.g. round), .to_string().
-                else if let Expr::Call(_) = right_expr {
-                    right = quote! { &#right.to_string() };
-                }
-                // If right is Ident, borrow it.
-                else if let Expr::Ident(_) = right_expr {
-                    right = quote! { &#right };
-                }
-            } else if let Expr::Ident(_) = &*bin.right {
-                // Heuristic: If right side is an identifier, borrow it.
-                right = quote! { &#right };
-            }
-        }
+    // Ownership inference borrows what is appended to a string
+    if op == BinaryOp::Add && right.usage == Usage::Borrow {
+        right_tokens = concat_rhs(right, &right_tokens);
     }
 
-    let op = match bin.op {
+    let op_tokens = match op {
         BinaryOp::Add => quote! { + },
         BinaryOp::Sub => quote! { - },
         BinaryOp::Mul => quote! { * },
         BinaryOp::Div => quote! { / },
+        BinaryOp::Mod => quote! { % },
         BinaryOp::EqEq | BinaryOp::EqEqEq => quote! { == },
         BinaryOp::NotEq | BinaryOp::NotEqEq => quote! { != },
         BinaryOp::Lt => quote! { < },
@@ -927,23 +1182,62 @@ pub fn convert_bin_expr(bin: &BinExpr) -> proc_macro2::TokenStream {
         BinaryOp::GtEq => quote! { >= },
         BinaryOp::LogicalAnd => quote! { && },
         BinaryOp::LogicalOr => quote! { || },
-        _ => quote! { /* unsupported op */ },
+        _ => unreachable!("unsupported operators are reported above"),
     };
 
-    quote! { #left #op #right }
+    quote! { #left_tokens #op_tokens #right_tokens }
 }
 
-pub fn convert_stmt_recursive<F>(stmt: &Stmt, handler: &F) -> proc_macro2::TokenStream
+fn is_string_type(ty: &Type) -> bool {
+    matches!(ty, Type::String | Type::Literal(LitType::Str(_)))
+}
+
+/// Right operand of `String + rhs`, chosen from the inferred type of `rhs`:
+/// strings and untyped variables are borrowed, anything else is formatted
+/// first.
+fn concat_rhs(right: &Expr, tokens: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    match &right.ty {
+        ty if is_string_type(ty) => quote! { &#tokens },
+        Type::Unknown if right.as_ident().is_some() => quote! { &#tokens },
+        _ if matches!(right.unparen().kind, ExprKind::Lit(_) | ExprKind::Call(_)) => {
+            quote! { &#tokens.to_string() }
+        }
+        _ => quote! { &(#tokens).to_string() },
+    }
+}
+
+/// `stmt` as one statement of a list, after its comments and the marker
+/// linking it to its source.
+pub fn convert_listed_stmt(cx: &Context, stmt: &Stmt) -> proc_macro2::TokenStream {
+    let markers = stmt_markers(stmt);
+    let tokens = convert_stmt(cx, stmt);
+    quote! { #markers #tokens }
+}
+
+/// The comments above `stmt` and the marker linking it to its source, for
+/// statements of a list.
+pub(crate) fn stmt_markers(stmt: &Stmt) -> proc_macro2::TokenStream {
+    let comments = comments::markers(&stmt.comments);
+    let span = span_map::marker(stmt.span);
+    quote! { #comments #span }
+}
+
+/// Converts a statement, delegating `return` statements (also nested in blocks and ifs)
+/// to `handler` so callers can wrap returned values, e.g. in `Ok(...)`.
+pub fn convert_stmt_recursive<F>(cx: &Context, stmt: &Stmt, handler: &F) -> proc_macro2::TokenStream
 where
-    F: Fn(&swc_ecma_ast::ReturnStmt) -> proc_macro2::TokenStream,
+    F: Fn(Option<&Expr>) -> proc_macro2::TokenStream,
 {
-    match stmt {
-        Stmt::Return(ret_stmt) => handler(ret_stmt),
-        Stmt::Block(block) => {
-            let stmts: Vec<_> = block
-                .stmts
+    match &stmt.kind {
+        StmtKind::Return(arg) => handler(arg.as_ref()),
+        StmtKind::Block(stmts) => {
+            let stmts: Vec<_> = stmts
                 .iter()
-                .map(|s| convert_stmt_recursive(s, handler))
+                .map(|s| {
+                    let markers = stmt_markers(s);
+                    let tokens = convert_stmt_recursive(cx, s, handler);
+                    quote! { #markers #tokens }
+                })
                 .collect();
             quote! {
                 {
@@ -951,18 +1245,13 @@ where
                 }
             }
         }
-        Stmt::If(if_stmt) => {
-            let test = convert_expr(&if_stmt.test);
-            let cons = convert_stmt_recursive(&if_stmt.cons, handler);
-            let cons_block = if matches!(*if_stmt.cons, Stmt::Block(_)) {
-                quote! { #cons }
-            } else {
-                quote! { { #cons } }
-            };
+        StmtKind::If { test, cons, alt } => {
+            let test = convert_expr(cx, test);
+            let cons_block = as_block(cons, convert_stmt_recursive(cx, cons, handler));
 
-            let alt = if let Some(alt) = &if_stmt.alt {
-                let alt_stmt = convert_stmt_recursive(alt, handler);
-                let alt_block = if matches!(&**alt, Stmt::Block(_) | Stmt::If(_)) {
+            let alt = if let Some(alt) = alt {
+                let alt_stmt = convert_stmt_recursive(cx, alt, handler);
+                let alt_block = if matches!(alt.kind, StmtKind::Block(_) | StmtKind::If { .. }) {
                     quote! { #alt_stmt }
                 } else {
                     quote! { { #alt_stmt } }
@@ -976,134 +1265,137 @@ where
                 if #test #cons_block #alt
             }
         }
+        StmtKind::Match { scrutinee, arms } => convert_match(cx, scrutinee, arms, &|s| {
+            convert_stmt_recursive(cx, s, handler)
+        }),
         // TODO: Add loops if needed. For now, delegate to convert_stmt for others,
         // BUT convert_stmt won't recurse with handler.
-        // So we should implement loops here if we expect returns inside loops.
-        // Assuming simple cases for now.
-        _ => convert_stmt(stmt),
+        _ => convert_stmt(cx, stmt),
     }
 }
 
-fn convert_await_expr(await_expr: &AwaitExpr) -> proc_macro2::TokenStream {
-    let arg = convert_expr(&await_expr.arg);
-    quote! { #arg.await? }
+fn convert_callee(cx: &Context, callee: &Callee) -> proc_macro2::TokenStream {
+    match callee {
+        Callee::Expr(expr) => convert_expr(cx, expr),
+        _ => quote! { unknown_callee },
+    }
 }
 
-fn convert_call_expr(call: &CallExpr) -> proc_macro2::TokenStream {
-    let callee = &call.callee;
+fn convert_call_expr(cx: &Context, call: &CallExpr) -> proc_macro2::TokenStream {
     let args = &call.args;
 
-    // Handle JSON.stringify
-    if let Callee::Expr(expr) = callee {
-        if let Expr::Member(member) = &**expr {
-            if let Expr::Ident(obj) = &*member.obj {
-                if obj.sym == "JSON" {
-                    if let Some(prop) = member.prop.as_ident() {
-                        if prop.sym == "stringify" {
-                            if let Some(arg) = args.first() {
-                                let val = convert_expr_or_spread(arg);
-                                return quote! { serde_json::to_string(&#val).unwrap() };
-                            }
-                        } else if prop.sym == "parse" {
-                            if let Some(arg) = args.first() {
-                                let val = convert_expr_or_spread(arg);
-                                return quote! { serde_json::from_str::<serde_json::Value>(&#val).unwrap() };
-                            }
-                        }
-                    }
-                }
+    match call.callee.as_static_call() {
+        // Handle JSON.stringify
+        Some(("JSON", "stringify")) => {
+            if let Some(arg) = args.first() {
+                let val = convert_arg(cx, arg);
+                return quote! { serde_json::to_string(&#val).unwrap() };
             }
         }
-    }
-
-    // Handle axios.get<T>(...)
-    if let Callee::Expr(expr) = callee {
-        if let Expr::Member(member) = &**expr {
-            if let Expr::Ident(obj) = &*member.obj {
-                if obj.sym == "axios" {
-                    if let Some(prop) = member.prop.as_ident() {
-                        if prop.sym == "get" {
-                            if let Some(arg) = args.first() {
-                                let url = convert_expr_or_spread(arg);
-                                // Handle generics: axios.get<T>(...)
-                                let generic_type = if let Some(type_params) = &call.type_args {
-                                    if let Some(param) = type_params.params.first() {
-                                        let t = super::type_mapper::map_inner_type(param);
-                                        Some(t)
-                                    } else {
-                                        None
-                                    }
-                                } else {
-                                    None
-                                };
-
-                                if let Some(t) = generic_type {
-                                    return quote! {
-                                        reqwest::Client::new().get(#url).send().await?.json::<#t>()
-                                    };
-                                } else {
-                                    return quote! {
-                                        reqwest::Client::new().get(#url).send().await?
-                                    };
-                                }
-                            }
-                        }
-                    }
+        Some(("JSON", "parse")) => {
+            if let Some(arg) = args.first() {
+                let val = convert_arg(cx, arg);
+                return quote! { serde_json::from_str::<serde_json::Value>(&#val).unwrap() };
+            }
+        }
+        // Handle axios.get<T>(...)
+        Some(("axios", "get")) => {
+            if let Some(arg) = args.first() {
+                let url = convert_arg(cx, arg);
+                if let Some(t) = call.type_args.first().map(|ty| map_type(cx, ty)) {
+                    return quote! {
+                        reqwest::Client::new().get(#url).send().await?.json::<#t>()
+                    };
+                } else {
+                    return quote! {
+                        reqwest::Client::new().get(#url).send().await?
+                    };
                 }
             }
         }
+        _ => {}
     }
 
     // Try stdlib handlers first
-    if let Some(stdlib_code) = crate::stdlib::try_handle_stdlib_call(&call.callee, &call.args) {
+    if let Some(stdlib_code) = crate::stdlib::try_handle_stdlib_call(cx, &call.callee, args) {
         return stdlib_code;
     }
 
-    // Check for axios calls (axios.get, axios.post, etc.)
-    if let Callee::Expr(expr) = &call.callee {
-        if let Expr::Member(member) = &**expr {
+    if let Some(member) = call.callee.as_member() {
+        if let Some(method_name) = member.prop.as_ident() {
             // Try stdlib method call
-            if let Some(method_ident) = member.prop.as_ident() {
-                let method_name = method_ident.sym.as_ref();
-                if let Some(stdlib_code) =
-                    crate::stdlib::try_handle_method_call(&member.obj, method_name, &call.args)
-                {
-                    return stdlib_code;
-                }
+            if let Some(stdlib_code) =
+                crate::stdlib::try_handle_method_call(cx, &member.obj, method_name, args)
+            {
+                return stdlib_code;
             }
 
-            // Check if object is "axios"
-            if let Expr::Ident(obj_ident) = &*member.obj {
-                if obj_ident.sym == "axios" {
-                    // Get the HTTP method
-                    if let Some(method_ident) = member.prop.as_ident() {
-                        let method = method_ident.sym.to_string();
-                        return convert_axios_call(&method, &call.args);
-                    }
-                }
+            // Check for axios calls (axios.get, axios.post, etc.)
+            if member.obj.as_ident() == Some("axios") {
+                return convert_axios_call(cx, method_name, args);
             }
         }
-
-        // Check for fetch calls
-        if let Expr::Ident(ident) = &**expr {
-            if ident.sym == "fetch" {
-                return convert_fetch_call(&call.args);
-            }
+        // this.method() -> self.method(), not a call through a cloned field
+        if let (true, MemberProp::Ident(name) | MemberProp::Private(name)) =
+            (member.obj.is_this(), &member.prop)
+        {
+            let method = format_ident!("{}", cx.snake_case(name));
+            let args = args.iter().map(|arg| convert_value(cx, &arg.expr));
+            return quote! { self.#method(#(#args),*) };
+        }
+        if let (true, MemberProp::Ident(name)) =
+            (cx.is_mutated_interface(&member.obj.ty), &member.prop)
+        {
+            let obj = convert_expr(cx, &member.obj);
+            let method = format_ident!("{}", cx.snake_case(name));
+            let args = args
+                .iter()
+                .map(|arg| convert_value(cx, &arg.expr))
+                .collect();
+            return locked_call(obj, method, args);
         }
     }
 
-    // Fallback to generic call conversion
-    let callee = match &call.callee {
-        Callee::Expr(expr) => convert_expr(expr),
-        _ => quote! { unknown_callee },
-    };
+    // Check for fetch calls
+    if call.callee.as_expr().and_then(Expr::as_ident) == Some("fetch") {
+        return convert_fetch_call(cx, args);
+    }
 
-    let args: Vec<_> = call.args.iter().map(convert_expr_or_spread).collect();
+    // Fallback to generic call conversion
+    let callee = convert_callee(cx, &call.callee);
+    let args: Vec<_> = args
+        .iter()
+        .map(|arg| convert_value(cx, &arg.expr))
+        .collect();
 
     quote! { #callee(#(#args),*) }
 }
 
-fn convert_axios_call(method: &str, args: &[ExprOrSpread]) -> proc_macro2::TokenStream {
+/// Calls `method` on `obj`, a locked trait object. The arguments are
+/// evaluated before locking, as they may lock it too, and the guard is
+/// dropped at the end of the `let` rather than of the enclosing statement.
+fn locked_call(
+    obj: proc_macro2::TokenStream,
+    method: proc_macro2::Ident,
+    args: Vec<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    if args.is_empty() {
+        return quote! {{
+            let __value = #obj.lock().unwrap().#method();
+            __value
+        }};
+    }
+    let names: Vec<_> = (0..args.len())
+        .map(|i| format_ident!("__arg{}", i))
+        .collect();
+    quote! {{
+        let (#(#names,)*) = (#(#args,)*);
+        let __value = #obj.lock().unwrap().#method(#(#names),*);
+        __value
+    }}
+}
+
+fn convert_axios_call(cx: &Context, method: &str, args: &[Arg]) -> proc_macro2::TokenStream {
     let method_lower = method.to_lowercase();
     let method_ident = format_ident!("{}", method_lower);
 
@@ -1112,11 +1404,11 @@ fn convert_axios_call(method: &str, args: &[ExprOrSpread]) -> proc_macro2::Token
     }
 
     // First argument is the URL
-    let url = convert_expr_or_spread(&args[0]);
+    let url = convert_arg(cx, &args[0]);
 
     // For POST/PUT, second argument might be data
     if (method_lower == "post" || method_lower == "put") && args.len() > 1 {
-        let data = convert_expr_or_spread(&args[1]);
+        let data = convert_arg(cx, &args[1]);
         quote! {
             reqwest::Client::new()
             .#method_ident(#url)
@@ -1135,38 +1427,36 @@ fn convert_axios_call(method: &str, args: &[ExprOrSpread]) -> proc_macro2::Token
     }
 }
 
-fn convert_fetch_call(args: &[ExprOrSpread]) -> proc_macro2::TokenStream {
+fn convert_fetch_call(cx: &Context, args: &[Arg]) -> proc_macro2::TokenStream {
     if args.is_empty() {
         return quote! { reqwest::get("") };
     }
 
-    let url = convert_expr_or_spread(&args[0]);
+    let url = convert_arg(cx, &args[0]);
     quote! { reqwest::get(#url) }
 }
 
-pub fn convert_arrow_expr(arrow: &swc_ecma_ast::ArrowExpr) -> proc_macro2::TokenStream {
-    convert_arrow_expr_with_hint(arrow, None)
+pub fn convert_arrow_expr(cx: &Context, arrow: &ArrowExpr) -> proc_macro2::TokenStream {
+    convert_arrow_expr_with_hint(cx, arrow, None)
 }
 
 pub fn convert_arrow_expr_with_hint(
-    arrow: &swc_ecma_ast::ArrowExpr,
+    cx: &Context,
+    arrow: &ArrowExpr,
     type_hint: Option<proc_macro2::TokenStream>,
 ) -> proc_macro2::TokenStream {
-    let params = &arrow.params;
-    let body = &arrow.body;
-
-    let param_idents: Vec<_> = params
+    let param_idents: Vec<_> = arrow
+        .params
         .iter()
         .enumerate()
         .map(|(i, p)| {
-            if let Pat::Ident(ident) = p {
-                let name = format_ident!("{}", to_snake_case(&ident.sym));
-                let ts_type = ident
-                    .type_ann
-                    .as_ref()
-                    .map(|ann| super::type_mapper::map_ts_type(Some(ann)));
+            if let Pat::Ident(binding) = p {
+                let mutability = mutability(binding);
+                let ident = format_ident!("{}", cx.snake_case(&binding.name));
+                let name = quote! { #mutability #ident };
 
-                if let Some(t) = ts_type {
+                if let Some(ty) = &binding.ty {
+                    let t = map_type(cx, ty);
                     quote! { #name: #t }
                 } else if let Some(hint) = &type_hint {
                     // Only apply hint to the first parameter for now (common case for map/filter)
@@ -1184,31 +1474,60 @@ pub fn convert_arrow_expr_with_hint(
         })
         .collect();
 
-    let body_code = match &**body {
-        swc_ecma_ast::BlockStmtOrExpr::BlockStmt(block) => {
-            let stmts: Vec<_> = block.stmts.iter().map(convert_stmt).collect();
+    let body_code = match &arrow.body {
+        ArrowBody::Block(stmts) => {
+            let stmts: Vec<_> = stmts
+                .iter()
+                .map(|stmt| convert_listed_stmt(cx, stmt))
+                .collect();
             quote! { { #(#stmts)* } }
         }
-        swc_ecma_ast::BlockStmtOrExpr::Expr(expr) => {
-            let expr_code = convert_expr(expr);
-            quote! { #expr_code }
-        }
-    };
-
-    let is_async = arrow.is_async;
-    let _async_kw = if is_async {
-        quote! { async }
-    } else {
-        quote! {}
+        ArrowBody::Expr(expr) => convert_expr(cx, expr),
     };
 
     quote! { |#(#param_idents),*| #body_code }
 }
 
-pub fn convert_expr_or_spread(arg: &ExprOrSpread) -> proc_macro2::TokenStream {
-    convert_expr(&arg.expr)
+pub fn convert_arg(cx: &Context, arg: &Arg) -> proc_macro2::TokenStream {
+    convert_expr(cx, &arg.expr)
 }
 
+/// How TypeScript identifiers are named in the generated Rust.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Naming {
+    /// Functions, methods, fields and variables in `snake_case`, as Rust
+    /// names them.
+    #[default]
+    SnakeCase,
+    /// The TypeScript names as they are, e.g. to keep them greppable.
+    Preserve,
+}
+
+impl FromStr for Naming {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "snake_case" => Ok(Naming::SnakeCase),
+            "preserve" => Ok(Naming::Preserve),
+            _ => Err(format!(
+                "unknown naming `{s}`, expected snake_case or preserve"
+            )),
+        }
+    }
+}
+
+impl Context {
+    /// `fetchData` -> `fetch_data`, unless the module preserves names.
+    pub fn snake_case(&self, s: &str) -> String {
+        match self.naming {
+            Naming::SnakeCase => to_snake_case(s),
+            Naming::Preserve => s.to_string(),
+        }
+    }
+}
+
+/// `fetchData` -> `fetch_data`.
 pub fn to_snake_case(s: &str) -> String {
     let mut result = String::new();
     for (i, ch) in s.chars().enumerate() {
@@ -1220,6 +1539,15 @@ pub fn to_snake_case(s: &str) -> String {
     result
 }
 
+/// `maxSize` -> `MAX_SIZE`; names without lowercase letters are kept.
+pub fn to_screaming_snake_case(s: &str) -> String {
+    if s.chars().any(char::is_lowercase) {
+        to_snake_case(s).to_uppercase()
+    } else {
+        s.to_string()
+    }
+}
+
 pub fn to_pascal_case(s: &str) -> String {
     let mut result = String::new();
     let mut capitalize_next = true;
@@ -1262,4 +1590,16 @@ mod tests {
     fn test_to_snake_case_empty() {
         assert_eq!(to_snake_case(""), "");
     }
+
+    #[test]
+    fn test_naming_preserve() {
+        let cx = Context {
+            naming: "preserve".parse().unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(cx.snake_case("fetchData"), "fetchData");
+        assert_eq!(to_screaming_snake_case("maxSize"), "MAX_SIZE");
+        assert_eq!(Context::default().snake_case("fetchData"), "fetch_data");
+        assert!("camelCase".parse::<Naming>().is_err());
+    }
 }
@@ -0,0 +1,507 @@
+//! `class B extends A` by composition: `B` embeds an `A` in a `base` field and
+//! derefs to it. A class that is extended within the project also gets an
+//! `ATrait` with the methods it introduces; the class and every subclass
+//! implement it, and subclasses delegate to `base` unless they override.
+//! Parameters typed with an extended class take any `impl ATrait`, whose
+//! `as_a()` reads the fields.
+//!
+//! An inherited method that calls `this.m()` would run the ancestor's `m`
+//! from `base`; when the subclass resolves `m` differently, the subclass
+//! gets its own copy of the method instead, so the call reaches the override.
+//!
+//! Ancestors can be declared in other modules of the project; what their
+//! modules generate next to them is then `use`d by path.
+
+use std::collections::BTreeSet;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use tyrus_ast::visit::{walk_expr_mut, VisitMut};
+use tyrus_ast::{
+    Accessibility, ClassDecl, ClassMethod, Expr, ExprKind, ImportSpecifier, Item, MethodKind,
+    Module, Param, Pat, Type,
+};
+
+use super::abstract_class::state_decl;
+use super::class::is_route_handler;
+use super::context::Context;
+use super::interface::RustGenerator;
+use super::type_mapper::{map_type, map_type_opt, unwrap_promise_type};
+
+/// Guards against `extends` cycles, which TypeScript rejects anyway.
+const MAX_DEPTH: usize = 32;
+
+/// The `base` field embedding the superclass in a subclass struct.
+pub fn base_field(cx: &Context, super_class: &Type) -> TokenStream {
+    let ty = map_type(cx, super_class);
+    quote! {
+        #[serde(flatten)]
+        pub base: #ty
+    }
+}
+
+pub fn trait_ident(class_name: &str) -> proc_macro2::Ident {
+    format_ident!("{}Trait", class_name)
+}
+
+/// The trait method of `class_name`'s trait that borrows the class out of any
+/// implementor, e.g. `as_animal`.
+pub(crate) fn as_class_ident(cx: &Context, class_name: &str) -> proc_macro2::Ident {
+    format_ident!("as_{}", cx.snake_case(class_name))
+}
+
+impl Context {
+    /// Runs `convert` over the body of a function taking `params`, knowing
+    /// which of them take a class trait.
+    pub(crate) fn with_trait_params<T>(&self, params: &[Param], convert: impl FnOnce() -> T) -> T {
+        let mut names = self.trait_params.borrow().clone();
+        names.extend(params.iter().filter_map(|param| {
+            match &param.pat {
+                Pat::Ident(binding)
+                    if binding
+                        .ty
+                        .as_ref()
+                        .and_then(Type::as_named)
+                        .is_some_and(|name| self.class_traits.contains(name)) =>
+                {
+                    Some(binding.name.clone())
+                }
+                _ => None,
+            }
+        }));
+        let outer = self.trait_params.replace(names);
+        let converted = convert();
+        self.trait_params.replace(outer);
+        converted
+    }
+
+    /// The `as_a` method to read fields through when `obj` is a parameter
+    /// taking the trait of class `a`.
+    pub(crate) fn class_of_trait_param(&self, obj: &Expr) -> Option<proc_macro2::Ident> {
+        let name = obj.as_ident()?;
+        let class = obj.ty.as_named()?;
+        (self.trait_params.borrow().contains(name) && self.class_traits.contains(class))
+            .then(|| as_class_ident(self, class))
+    }
+}
+
+/// Instance methods dispatched on the runtime class of `this`: the ones a
+/// subclass can override, and private ones, which may call those.
+fn is_dispatched(method: &ClassMethod) -> bool {
+    method.kind == MethodKind::Method && !method.is_static && !is_route_handler(method)
+}
+
+/// The methods a method calls on `this`, and whether it uses `super`, which
+/// ties it to the class declaring it.
+#[derive(Default)]
+struct ThisCalls {
+    names: BTreeSet<String>,
+    uses_super: bool,
+}
+
+impl ThisCalls {
+    fn of(method: &ClassMethod) -> Self {
+        let mut calls = Self::default();
+        calls.visit_function_mut(&mut method.function.clone());
+        calls
+    }
+}
+
+impl VisitMut for ThisCalls {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match &expr.kind {
+            ExprKind::Super => self.uses_super = true,
+            _ => {
+                if let Some((obj, name, _)) = expr.as_method_call() {
+                    if obj.is_this() {
+                        self.names.insert(name.to_string());
+                    }
+                }
+            }
+        }
+        walk_expr_mut(self, expr);
+    }
+}
+
+/// Instance methods a subclass can override. Private and static methods,
+/// accessors and route handlers stay inherent.
+pub(crate) fn is_overridable(method: &ClassMethod) -> bool {
+    method.kind == MethodKind::Method
+        && !method.is_static
+        && !method.is_private_name
+        && method.accessibility != Some(Accessibility::Private)
+        && !is_route_handler(method)
+}
+
+/// Signature of `method` as declared in a trait, taking `&mut self` when
+/// `mutable`, plus the argument idents to forward to the implementation.
+pub(crate) fn trait_method_sig(
+    cx: &Context,
+    method: &ClassMethod,
+    mutable: bool,
+) -> (TokenStream, Vec<proc_macro2::Ident>) {
+    let function = &method.function;
+    let receiver = if mutable {
+        quote! { &mut self }
+    } else {
+        quote! { &self }
+    };
+    let method_name = format_ident!("{}", cx.snake_case(&method.name));
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+    for param in &function.params {
+        if let Pat::Ident(binding) = &param.pat {
+            let param_name = format_ident!("{}", cx.snake_case(&binding.name));
+            let param_type = map_type_opt(cx, binding.ty.as_ref());
+            params.push(quote! { #param_name: #param_type });
+            args.push(param_name);
+        }
+    }
+    let sig = if function.is_async {
+        let inner = unwrap_promise_type(cx, function.return_type.as_ref());
+        quote! { async fn #method_name(#receiver, #(#params),*) -> Result<#inner, crate::AppError> }
+    } else {
+        let return_type = map_type_opt(cx, function.return_type.as_ref());
+        quote! { fn #method_name(#receiver, #(#params),*) -> #return_type }
+    };
+    (sig, args)
+}
+
+impl RustGenerator {
+    /// `use`s the traits and state structs generated next to the ancestors of
+    /// the module's classes that other modules declare. The impls of a
+    /// subclass name them, while TypeScript imports the superclass at most.
+    /// Imported classes bring their trait along for the parameters they type.
+    pub fn process_foreign_ancestors(&mut self, module: &Module) {
+        let mut uses = BTreeSet::new();
+        let imported = module.items.iter().flat_map(|item| match item {
+            Item::Import(import) => import.specifiers.as_slice(),
+            _ => &[],
+        });
+        for specifier in imported {
+            let ImportSpecifier::Named { local, imported } = specifier else {
+                continue;
+            };
+            let Some(class) = self.classes.get(imported.as_ref().unwrap_or(local)) else {
+                continue;
+            };
+            // Methods of supertraits are only callable with them in scope too
+            for owner in std::iter::once(class).chain(self.trait_ancestors(class)) {
+                if let (Some(path), true) =
+                    (self.class_modules.get(&owner.name), self.has_trait(owner))
+                {
+                    uses.insert(format!("use {}::{};", path, trait_ident(&owner.name)));
+                }
+            }
+        }
+        for class in crate::classes(module) {
+            for ancestor in self.trait_ancestors(class) {
+                if let (Some(path), true) = (
+                    self.class_modules.get(&ancestor.name),
+                    self.has_trait(ancestor),
+                ) {
+                    uses.insert(format!("use {}::{};", path, trait_ident(&ancestor.name)));
+                }
+            }
+            if let Some(parent) = self.abstract_parent(class) {
+                if let (Some(path), Some(state)) =
+                    (self.class_modules.get(&parent.name), state_decl(parent))
+                {
+                    uses.insert(format!("use {}::{};", path, state.name));
+                }
+            }
+        }
+        for use_stmt in uses {
+            self.code.push_str(&use_stmt);
+            self.code.push('\n');
+        }
+    }
+
+    /// The struct of class `name`, by path when another module declares it.
+    fn class_path(&self, name: &str) -> TokenStream {
+        let ident = format_ident!("{}", name);
+        match self.class_modules.get(name) {
+            Some(path) => {
+                let segments = path.split("::").map(|segment| format_ident!("{}", segment));
+                quote! { #(#segments::)* #ident }
+            }
+            None => quote! { #ident },
+        }
+    }
+
+    /// Ancestors of `class` declared in the project, nearest first. The chain
+    /// stops at the first generic or abstract ancestor: generic classes get no
+    /// trait, and abstract ones are traits already.
+    fn trait_ancestors<'a>(&'a self, class: &'a ClassDecl) -> Vec<&'a ClassDecl> {
+        let mut chain = Vec::new();
+        let mut current = class;
+        while let Some(parent) = current
+            .super_class_name()
+            .and_then(|name| self.classes.get(name))
+        {
+            if chain.len() == MAX_DEPTH || !parent.type_params.is_empty() || parent.is_abstract {
+                break;
+            }
+            chain.push(parent);
+            current = parent;
+        }
+        chain
+    }
+
+    /// Methods `class` introduces that subclasses may override. Overrides of
+    /// methods declared by an ancestor belong to the ancestor's trait.
+    fn introduced_methods<'a>(&'a self, class: &'a ClassDecl) -> Vec<&'a ClassMethod> {
+        let ancestors = self.trait_ancestors(class);
+        class
+            .methods()
+            .filter(|m| is_overridable(m))
+            .filter(|m| {
+                !ancestors
+                    .iter()
+                    .any(|a| a.methods().any(|am| am.name == m.name))
+            })
+            .collect()
+    }
+
+    /// Whether the trait method for `method` of `owner` takes `&mut self`:
+    /// when it, or what a class inheriting it runs in its place, mutates `this`.
+    fn mutates(&self, owner: &ClassDecl, method: &ClassMethod) -> bool {
+        method.mutates_this
+            || self.classes.values().any(|class| {
+                self.trait_ancestors(class)
+                    .iter()
+                    .any(|a| a.name == owner.name)
+                    && self.mutates_in(class, &method.name, &mut BTreeSet::new())
+            })
+    }
+
+    /// The method `class` declares as `name`.
+    fn declared<'a>(class: &'a ClassDecl, name: &str) -> Option<&'a ClassMethod> {
+        class.methods().find(|m| m.name == name && is_dispatched(m))
+    }
+
+    /// Whether `class` has a method `name` of its own: declared or copied.
+    fn emits(
+        &self,
+        class: &ClassDecl,
+        name: &str,
+        visiting: &mut BTreeSet<(String, String)>,
+    ) -> bool {
+        Self::declared(class, name).is_some() || self.copied(class, name, visiting).is_some()
+    }
+
+    /// The inherited method `name`, if `class` needs its own copy of it: when
+    /// it calls a method on `this` that `class`, or an ancestor nearer than
+    /// the one `class` inherits `name` from, has of its own. `visiting`
+    /// guards against methods calling each other.
+    fn copied<'a>(
+        &'a self,
+        class: &'a ClassDecl,
+        name: &str,
+        visiting: &mut BTreeSet<(String, String)>,
+    ) -> Option<&'a ClassMethod> {
+        if Self::declared(class, name).is_some()
+            || !visiting.insert((class.name.clone(), name.to_string()))
+        {
+            return None;
+        }
+        let ancestors = self.trait_ancestors(class);
+        let copy = ancestors
+            .iter()
+            .position(|a| self.emits(a, name, visiting))
+            .and_then(|from| {
+                let method = ancestors[from..]
+                    .iter()
+                    .find_map(|a| Self::declared(a, name))?;
+                let calls = ThisCalls::of(method);
+                let nearer = || std::iter::once(class).chain(ancestors[..from].iter().copied());
+                (!calls.uses_super
+                    && calls
+                        .names
+                        .iter()
+                        .any(|called| nearer().any(|c| self.emits(c, called, visiting))))
+                .then_some(method)
+            });
+        visiting.remove(&(class.name.clone(), name.to_string()));
+        copy
+    }
+
+    /// Inherited methods `class` has its own copies of, nearest declarations
+    /// first.
+    pub(crate) fn copied_methods<'a>(&'a self, class: &'a ClassDecl) -> Vec<&'a ClassMethod> {
+        let mut names = BTreeSet::new();
+        self.trait_ancestors(class)
+            .into_iter()
+            .flat_map(|a| a.methods().filter(|m| is_dispatched(m)))
+            .filter(|m| names.insert(m.name.as_str()))
+            .filter_map(|m| self.copied(class, &m.name, &mut BTreeSet::new()))
+            .collect()
+    }
+
+    /// Whether the method `class` runs as `name` mutates `this`, including
+    /// through the methods a copy calls.
+    pub(crate) fn mutates_in(
+        &self,
+        class: &ClassDecl,
+        name: &str,
+        visiting: &mut BTreeSet<(String, String)>,
+    ) -> bool {
+        if let Some(method) = Self::declared(class, name) {
+            return method.mutates_this;
+        }
+        if !visiting.insert((class.name.clone(), name.to_string())) {
+            return false;
+        }
+        let mutates = match self.copied(class, name, &mut BTreeSet::new()) {
+            Some(method) => {
+                method.mutates_this
+                    || ThisCalls::of(method)
+                        .names
+                        .iter()
+                        .any(|called| self.mutates_in(class, called, visiting))
+            }
+            None => self
+                .trait_ancestors(class)
+                .first()
+                .is_some_and(|parent| self.mutates_in(parent, name, visiting)),
+        };
+        visiting.remove(&(class.name.clone(), name.to_string()));
+        mutates
+    }
+
+    fn has_trait(&self, class: &ClassDecl) -> bool {
+        class.type_params.is_empty()
+            && !class.is_abstract
+            && self
+                .classes
+                .values()
+                .any(|c| c.super_class_name() == Some(class.name.as_str()))
+    }
+
+    /// Classes emitted with a trait, whose parameters take any implementor.
+    pub(crate) fn class_traits(&self) -> BTreeSet<String> {
+        self.classes
+            .values()
+            .filter(|class| self.has_trait(class))
+            .map(|class| class.name.clone())
+            .collect()
+    }
+
+    /// Emits what connects `class` to its superclass and subclasses: `Deref`
+    /// to `base`, the class's own trait if it is extended, and impls of that
+    /// trait and of every ancestor's trait.
+    pub fn process_inheritance(
+        &mut self,
+        class: &ClassDecl,
+        generics_impl_decl: &TokenStream,
+        generics_use: &TokenStream,
+    ) {
+        let cx = &self.cx;
+        let struct_name = format_ident!("{}", class.name);
+        let mut items = Vec::new();
+
+        if let Some(base) = self.base_type(class) {
+            let base_ty = map_type(cx, &base);
+            items.push(quote! {
+                impl #generics_impl_decl std::ops::Deref for #struct_name #generics_use {
+                    type Target = #base_ty;
+                    fn deref(&self) -> &Self::Target {
+                        &self.base
+                    }
+                }
+
+                impl #generics_impl_decl std::ops::DerefMut for #struct_name #generics_use {
+                    fn deref_mut(&mut self) -> &mut Self::Target {
+                        &mut self.base
+                    }
+                }
+            });
+        }
+
+        if class.type_params.is_empty() {
+            let ancestors = self.trait_ancestors(class);
+
+            if self.has_trait(class) {
+                let trait_name = trait_ident(&class.name);
+                let supertrait = ancestors.iter().find(|a| self.has_trait(a)).map(|a| {
+                    let ident = trait_ident(&a.name);
+                    quote! { : #ident }
+                });
+                let sigs = self.introduced_methods(class).into_iter().map(|m| {
+                    let (sig, _) = trait_method_sig(cx, m, self.mutates(class, m));
+                    quote! { #sig; }
+                });
+                let as_class = as_class_ident(cx, &class.name);
+                let vis = if self.is_exporting {
+                    quote! { pub }
+                } else {
+                    quote! {}
+                };
+                items.push(quote! {
+                    #vis trait #trait_name #supertrait {
+                        fn #as_class(&self) -> &#struct_name;
+                        #(#sigs)*
+                    }
+                });
+            }
+
+            let parent = ancestors.first().map(|p| format_ident!("{}", p.name));
+            let copied: BTreeSet<_> = self
+                .copied_methods(class)
+                .into_iter()
+                .map(|m| m.name.as_str())
+                .collect();
+            for owner in std::iter::once(class).chain(ancestors.iter().copied()) {
+                if !self.has_trait(owner) {
+                    continue;
+                }
+                let trait_name = trait_ident(&owner.name);
+                let methods = self.introduced_methods(owner).into_iter().map(|m| {
+                    let mutable = self.mutates(owner, m);
+                    let (sig, args) = trait_method_sig(cx, m, mutable);
+                    let method_name = format_ident!("{}", cx.snake_case(&m.name));
+                    let overrides = copied.contains(m.name.as_str())
+                        || class
+                            .methods()
+                            .any(|own| own.name == m.name && is_overridable(own));
+                    let base = if mutable {
+                        quote! { &mut self.base }
+                    } else {
+                        quote! { &self.base }
+                    };
+                    let call = match &parent {
+                        Some(parent) if !overrides => {
+                            quote! { <#parent as #trait_name>::#method_name(#base, #(#args),*) }
+                        }
+                        _ => quote! { #struct_name::#method_name(self, #(#args),*) },
+                    };
+                    let call = if m.function.is_async {
+                        quote! { #call.await }
+                    } else {
+                        call
+                    };
+                    quote! {
+                        #sig {
+                            #call
+                        }
+                    }
+                });
+                let as_class = as_class_ident(cx, &owner.name);
+                let owner_name = self.class_path(&owner.name);
+                items.push(quote! {
+                    impl #trait_name for #struct_name {
+                        fn #as_class(&self) -> &#owner_name {
+                            self
+                        }
+                        #(#methods)*
+                    }
+                });
+            }
+        }
+
+        for item in items {
+            self.code.push_str(&item.to_string());
+            self.code.push('\n');
+        }
+    }
+}
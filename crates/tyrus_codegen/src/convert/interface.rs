@@ -1,10 +1,17 @@
+use std::collections::HashMap;
+
 use quote::{format_ident, quote};
-use swc_ecma_ast::{Lit, TsInterfaceDecl, TsTypeElement};
-use swc_ecma_visit::{Visit, VisitWith};
+use tyrus_ast::{
+    ClassDecl, EnumDecl, ExprKind, InterfaceDecl, InterfaceMember, Lit, LitType, Type,
+    TypeAliasDecl,
+};
+
+use tyrus_analyzer::typeck::unions::{tagged_union, untagged_union, TaggedUnion, UntaggedUnion};
 
-use super::type_mapper::map_ts_type;
+use super::context::Context;
+use super::type_mapper::{map_type, map_type_opt};
 
-use crate::ControllerMetadata;
+use crate::{comments, ControllerMetadata};
 
 #[derive(Default)]
 pub struct RustGenerator {
@@ -13,64 +20,81 @@ pub struct RustGenerator {
     pub is_index: bool,
     pub controllers: Vec<ControllerMetadata>,
     pub main_body: String,
+    /// Classes declared at the top level of the module and of the rest of
+    /// the project, so subclasses can see what they inherit.
+    pub classes: HashMap<String, ClassDecl>,
+    /// The Rust module of each class declared in another module.
+    pub class_modules: HashMap<String, String>,
+    /// What the converters of the module's items know and collect.
+    pub cx: Context,
 }
 
 impl RustGenerator {
-    pub fn new(is_index: bool) -> Self {
+    pub fn new(is_index: bool, cx: Context) -> Self {
         Self {
             code: String::new(),
             is_exporting: false,
             is_index,
             controllers: Vec::new(),
             main_body: String::new(),
+            classes: HashMap::new(),
+            class_modules: HashMap::new(),
+            cx,
         }
     }
-}
 
-impl Visit for RustGenerator {
-    fn visit_ts_interface_decl(&mut self, n: &TsInterfaceDecl) {
-        let interface_name = n.id.sym.to_string();
-        let struct_name = format_ident!("{}", interface_name);
+    pub fn process_interface_decl(&mut self, n: &InterfaceDecl) {
+        let cx = &self.cx;
+        if n.has_methods() {
+            self.process_trait_interface(n);
+            return;
+        }
+        let struct_name = format_ident!("{}", n.name);
 
         let mut fields = Vec::new();
 
-        for member in &n.body.body {
-            if let TsTypeElement::TsPropertySignature(prop) = member {
-                let field_name_str = if let Some(ident) = prop.key.as_ident() {
-                    ident.sym.to_string()
-                } else {
-                    continue; // Skip non-identifier keys for now
-                };
-                let field_name = format_ident!("{}", super::func::to_snake_case(&field_name_str));
+        for member in &n.members {
+            if let InterfaceMember::Property {
+                name,
+                ty,
+                optional,
+                doc,
+                ..
+            } = member
+            {
+                let field_name = format_ident!("{}", cx.snake_case(name));
 
-                let mut field_type = map_ts_type(prop.type_ann.as_ref());
+                let mut field_type = map_type_opt(cx, ty.as_ref());
 
-                if prop.optional {
+                if *optional {
                     field_type = quote! { Option<#field_type> };
                 }
 
+                let doc = comments::doc_attrs(cx, doc.as_ref());
                 fields.push(quote! {
+                    #doc
                     pub #field_name: #field_type
                 });
             }
         }
 
-        let generics = if let Some(type_params) = &n.type_params {
-            let params: Vec<_> = type_params
-                .params
+        let generics = if n.type_params.is_empty() {
+            quote! {}
+        } else {
+            let params: Vec<_> = n
+                .type_params
                 .iter()
                 .map(|p| {
-                    let name = p.name.sym.to_string();
-                    let ident = format_ident!("{}", name);
+                    let ident = format_ident!("{}", p.name);
                     quote! { #ident: Clone }
                 })
                 .collect();
             quote! { <#(#params),*> }
-        } else {
-            quote! {}
         };
 
+        let doc = comments::doc_attrs(cx, n.doc.as_ref());
         let struct_def = quote! {
+            #doc
             #[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
             #[serde(rename_all = "camelCase")]
             pub struct #struct_name #generics {
@@ -82,124 +106,111 @@ impl Visit for RustGenerator {
         self.code.push('\n');
     }
 
-    fn visit_fn_decl(&mut self, n: &swc_ecma_ast::FnDecl) {
-        self.process_fn_decl(n);
-    }
-
-    fn visit_class_decl(&mut self, n: &swc_ecma_ast::ClassDecl) {
-        self.process_class_decl(n);
-    }
+    pub fn process_type_alias_decl(&mut self, n: &TypeAliasDecl) {
+        let cx = &self.cx;
+        let alias_name = format_ident!("{}", n.name);
+        // Every form below starts with the item the alias becomes
+        self.code
+            .push_str(&comments::doc_attrs(cx, n.doc.as_ref()).to_string());
+
+        // Discriminated union: type Shape = { kind: "circle", r: number } | ...
+        if let Some(union) = tagged_union(&n.ty) {
+            self.process_tagged_union(n, &union);
+            return;
+        }
 
-    fn visit_ts_type_alias_decl(&mut self, n: &swc_ecma_ast::TsTypeAliasDecl) {
-        let alias_name_str = n.id.sym.to_string();
-        let alias_name = format_ident!("{}", alias_name_str);
+        // Untagged union: type Id = string | number
+        if let Some(union) = untagged_union(&n.ty) {
+            let vis = if self.is_exporting {
+                quote! { pub }
+            } else {
+                quote! {}
+            };
+            self.process_untagged_union(&n.name, &union, vis);
+            return;
+        }
 
         // Check for String Union: type Status = "open" | "closed"
-        // let mut is_string_union = false; (Unused)
-        if let swc_ecma_ast::TsType::TsUnionOrIntersectionType(
-            swc_ecma_ast::TsUnionOrIntersectionType::TsUnionType(union),
-        ) = &*n.type_ann
-        {
-            // Check if all members are string literals
-            if !union.types.is_empty()
-                && union.types.iter().all(|t| {
-                    matches!(
-                        &**t,
-                        swc_ecma_ast::TsType::TsLitType(lit)
-                            if matches!(lit.lit, swc_ecma_ast::TsLit::Str(_))
-                    )
+        if let Some(values) = string_literal_union(&n.ty) {
+            // Generate Enum
+            let valid_variants: Vec<_> = values
+                .into_iter()
+                .map(|value| {
+                    let variant_ident = format_ident!("{}", super::func::to_pascal_case(&value));
+                    (value, variant_ident)
                 })
-            {
-                // is_string_union = true; (Unused variable removed)
-
-                // Generate Enum
-                let mut valid_variants = Vec::new();
-
-                for t in &union.types {
-                    if let swc_ecma_ast::TsType::TsLitType(lit) = &**t {
-                        if let swc_ecma_ast::TsLit::Str(s) = &lit.lit {
-                            let value = s.value.as_str().unwrap_or("").to_string();
-                            let variant_name = super::func::to_pascal_case(&value);
-                            let variant_ident = format_ident!("{}", variant_name);
-                            valid_variants.push((value, variant_ident));
-                        }
+                .collect();
+
+            let variants: Vec<_> = valid_variants
+                .iter()
+                .enumerate()
+                .map(|(i, (value, variant_ident))| {
+                    let default_attr = if i == 0 {
+                        quote! { #[default] }
+                    } else {
+                        quote! {}
+                    };
+                    quote! {
+                        #default_attr
+                        #[serde(rename = #value)]
+                        #variant_ident
                     }
-                }
+                })
+                .collect();
 
-                let variants: Vec<_> = valid_variants
-                    .iter()
-                    .enumerate()
-                    .map(|(i, (value, variant_ident))| {
-                        let default_attr = if i == 0 {
-                            quote! { #[default] }
-                        } else {
-                            quote! {}
-                        };
-                        quote! {
-                            #default_attr
-                            #[serde(rename = #value)]
-                            #variant_ident
-                        }
-                    })
-                    .collect();
+            let eq_arms_string: Vec<_> = valid_variants
+                .iter()
+                .map(|(value, variant_ident)| {
+                    quote! {
+                        #alias_name::#variant_ident => other == #value
+                    }
+                })
+                .collect();
 
-                let eq_arms_string: Vec<_> = valid_variants
-                    .iter()
-                    .map(|(value, variant_ident)| {
-                        quote! {
-                            #alias_name::#variant_ident => other == #value
-                        }
-                    })
-                    .collect();
+            let eq_arms_str: Vec<_> = valid_variants
+                .iter()
+                .map(|(value, variant_ident)| {
+                    quote! {
+                        #alias_name::#variant_ident => *other == #value
+                    }
+                })
+                .collect();
 
-                let eq_arms_str: Vec<_> = valid_variants
-                    .iter()
-                    .map(|(value, variant_ident)| {
-                        quote! {
-                            #alias_name::#variant_ident => *other == #value
-                        }
-                    })
-                    .collect();
+            let vis = if self.is_exporting {
+                quote! { pub }
+            } else {
+                quote! {}
+            };
 
-                let vis = if self.is_exporting {
-                    quote! { pub }
-                } else {
-                    quote! {}
-                };
-
-                // Add Default to derive
-                let enum_def = quote! {
-                    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
-                    #vis enum #alias_name {
-                        #(#variants),*
-                    }
+            // Add Default to derive
+            let enum_def = quote! {
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+                #vis enum #alias_name {
+                    #(#variants),*
+                }
 
-                    impl PartialEq<String> for #alias_name {
-                        fn eq(&self, other: &String) -> bool {
-                            match self {
-                                #(#eq_arms_string),*
-                            }
+                impl PartialEq<String> for #alias_name {
+                    fn eq(&self, other: &String) -> bool {
+                        match self {
+                            #(#eq_arms_string),*
                         }
                     }
+                }
 
-                    impl PartialEq<&str> for #alias_name {
-                        fn eq(&self, other: &&str) -> bool {
-                             match self {
-                                #(#eq_arms_str),*
-                            }
+                impl PartialEq<&str> for #alias_name {
+                    fn eq(&self, other: &&str) -> bool {
+                         match self {
+                            #(#eq_arms_str),*
                         }
                     }
-                };
-                self.code.push_str(&enum_def.to_string());
-                self.code.push('\n');
-                return;
-            }
+                }
+            };
+            self.code.push_str(&enum_def.to_string());
+            self.code.push('\n');
+            return;
         }
 
-        let alias_type = map_ts_type(Some(&Box::new(swc_ecma_ast::TsTypeAnn {
-            span: swc_common::DUMMY_SP,
-            type_ann: n.type_ann.clone(),
-        })));
+        let alias_type = map_type(cx, &n.ty);
 
         let vis = if self.is_exporting {
             quote! { pub }
@@ -215,47 +226,201 @@ impl Visit for RustGenerator {
         self.code.push('\n');
     }
 
-    fn visit_ts_enum_decl(&mut self, n: &swc_ecma_ast::TsEnumDecl) {
-        let enum_name = format_ident!("{}", n.id.sym.to_string());
+    fn process_tagged_union(&mut self, n: &TypeAliasDecl, union: &TaggedUnion) {
+        let cx = &self.cx;
+        let enum_name = format_ident!("{}", n.name);
+        let tag = &union.tag;
+
+        let variants: Vec<_> = union
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident =
+                    format_ident!("{}", super::func::to_pascal_case(&variant.tag_value));
+                let rename = &variant.tag_value;
+                let fields: Vec<_> = variant
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let snake = cx.snake_case(&field.name);
+                        let field_ident = format_ident!("{}", snake);
+                        let mut field_type = map_type(cx, &field.ty);
+                        if field.optional {
+                            field_type = quote! { Option<#field_type> };
+                        }
+                        let field_name = &field.name;
+                        if snake == field.name {
+                            quote! { #field_ident: #field_type }
+                        } else {
+                            quote! {
+                                #[serde(rename = #field_name)]
+                                #field_ident: #field_type
+                            }
+                        }
+                    })
+                    .collect();
+                quote! {
+                    #[serde(rename = #rename)]
+                    #variant_ident { #(#fields),* }
+                }
+            })
+            .collect();
+
+        let generics = if n.type_params.is_empty() {
+            quote! {}
+        } else {
+            let params: Vec<_> = n
+                .type_params
+                .iter()
+                .map(|p| {
+                    let ident = format_ident!("{}", p.name);
+                    quote! { #ident: Clone }
+                })
+                .collect();
+            quote! { <#(#params),*> }
+        };
+        let generic_args = if n.type_params.is_empty() {
+            quote! {}
+        } else {
+            let params = n.type_params.iter().map(|p| format_ident!("{}", p.name));
+            quote! { <#(#params),*> }
+        };
+
+        let vis = if self.is_exporting {
+            quote! { pub }
+        } else {
+            quote! {}
+        };
+
+        // Reads of the tag outside a narrowing call it
+        let method = cx.tag_method(tag);
+        let tag_arms = union.variants.iter().map(|variant| {
+            let variant_ident =
+                format_ident!("{}", super::func::to_pascal_case(&variant.tag_value));
+            let tag_value = &variant.tag_value;
+            quote! { Self::#variant_ident { .. } => #tag_value }
+        });
+
+        let enum_def = quote! {
+            #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+            #[serde(tag = #tag)]
+            #vis enum #enum_name #generics {
+                #(#variants),*
+            }
+
+            impl #generics #enum_name #generic_args {
+                #vis fn #method(&self) -> &'static str {
+                    match self {
+                        #(#tag_arms),*
+                    }
+                }
+            }
+        };
+
+        self.code.push_str(&enum_def.to_string());
+        self.code.push('\n');
+    }
+
+    /// Emits `#[serde(untagged)]` enums with one newtype variant per member.
+    pub fn process_untagged_union(
+        &mut self,
+        name: &str,
+        union: &UntaggedUnion,
+        vis: proc_macro2::TokenStream,
+    ) {
+        let cx = &self.cx;
+        let enum_name = format_ident!("{}", name);
+        // Serde picks the first variant that deserializes, so a struct must
+        // come before the structs whose fields are a subset of its own
+        let mut ordered: Vec<_> = union.variants.iter().collect();
+        ordered.sort_by_key(|variant| {
+            let fields = variant
+                .ty
+                .as_named()
+                .and_then(|name| cx.required_fields.get(name));
+            std::cmp::Reverse(fields.copied().unwrap_or(0))
+        });
+        let variants: Vec<_> = ordered
+            .into_iter()
+            .map(|variant| {
+                let variant_ident = format_ident!("{}", variant.name);
+                let variant_type = map_type(cx, &variant.ty);
+                quote! { #variant_ident(#variant_type) }
+            })
+            .collect();
+
+        let enum_def = quote! {
+            #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+            #[serde(untagged)]
+            #vis enum #enum_name {
+                #(#variants),*
+            }
+        };
+
+        self.code.push_str(&enum_def.to_string());
+        self.code.push('\n');
+
+        // Strings, numbers and booleans print like JavaScript stringifies them
+        let primitive = union
+            .variants
+            .iter()
+            .all(|variant| matches!(variant.ty, Type::String | Type::Number | Type::Bool));
+        if primitive {
+            let arms = union.variants.iter().map(|variant| {
+                let variant_ident = format_ident!("{}", variant.name);
+                quote! { #enum_name::#variant_ident(value) => std::fmt::Display::fmt(value, f) }
+            });
+            let display = quote! {
+                impl std::fmt::Display for #enum_name {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        match self {
+                            #(#arms),*
+                        }
+                    }
+                }
+            };
+            self.code.push_str(&display.to_string());
+            self.code.push('\n');
+        }
+    }
+
+    pub fn process_enum_decl(&mut self, n: &EnumDecl) {
+        let enum_name = format_ident!("{}", n.name);
+        let doc = comments::doc_attrs(&self.cx, n.doc.as_ref());
 
         // Detect if this is a string enum or numeric enum
         let is_string_enum = n.members.iter().any(|m| {
             m.init
                 .as_ref()
-                .is_some_and(|init| matches!(init.as_ref(), swc_ecma_ast::Expr::Lit(Lit::Str(_))))
+                .is_some_and(|init| matches!(init.kind, ExprKind::Lit(Lit::Str(_))))
         });
 
+        let vis = if self.is_exporting {
+            quote! { pub }
+        } else {
+            quote! {}
+        };
+
         if is_string_enum {
             // String enum → derive Serialize/Deserialize for JSON compat
             let variants: Vec<_> = n
                 .members
                 .iter()
                 .map(|m| {
-                    let variant_name_str = match &m.id {
-                        swc_ecma_ast::TsEnumMemberId::Ident(i) => i.sym.to_string(),
-                        swc_ecma_ast::TsEnumMemberId::Str(s) => {
-                            s.value.as_str().unwrap_or("").to_string()
-                        }
-                    };
-                    let variant_ident = format_ident!("{}", variant_name_str);
+                    let variant_ident = format_ident!("{}", m.name);
+                    let doc = comments::doc_attrs(&self.cx, m.doc.as_ref());
 
                     // Extract the string value for serde rename
-                    let rename = m
-                        .init
-                        .as_ref()
-                        .and_then(|init| {
-                            if let swc_ecma_ast::Expr::Lit(Lit::Str(s)) = init.as_ref() {
-                                Some(s.value.as_str().unwrap_or("").to_string())
-                            } else {
-                                None
-                            }
-                        })
-                        .unwrap_or_else(|| variant_name_str.clone());
+                    let rename = match m.init.as_ref().map(|init| &init.kind) {
+                        Some(ExprKind::Lit(Lit::Str(s))) => s.clone(),
+                        _ => m.name.clone(),
+                    };
 
-                    if rename == variant_name_str {
-                        quote! { #variant_ident }
+                    if rename == m.name {
+                        quote! { #doc #variant_ident }
                     } else {
                         quote! {
+                            #doc
                             #[serde(rename = #rename)]
                             #variant_ident
                         }
@@ -263,13 +428,8 @@ impl Visit for RustGenerator {
                 })
                 .collect();
 
-            let vis = if self.is_exporting {
-                quote! { pub }
-            } else {
-                quote! {}
-            };
-
             let enum_def = quote! {
+                #doc
                 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
                 #vis enum #enum_name {
                     #(#variants),*
@@ -285,35 +445,23 @@ impl Visit for RustGenerator {
                 .members
                 .iter()
                 .map(|m| {
-                    let variant_name_str = match &m.id {
-                        swc_ecma_ast::TsEnumMemberId::Ident(i) => i.sym.to_string(),
-                        swc_ecma_ast::TsEnumMemberId::Str(s) => {
-                            s.value.as_str().unwrap_or("").to_string()
-                        }
-                    };
-                    let variant_ident = format_ident!("{}", variant_name_str);
+                    let variant_ident = format_ident!("{}", m.name);
 
                     // Check if there's an explicit numeric value
-                    if let Some(init) = &m.init {
-                        if let swc_ecma_ast::Expr::Lit(Lit::Num(num)) = init.as_ref() {
-                            current_value = num.value as i64;
-                        }
+                    if let Some(ExprKind::Lit(Lit::Num(num))) = m.init.as_ref().map(|i| &i.kind) {
+                        current_value = *num as i64;
                     }
 
                     let val = current_value as i32;
                     current_value += 1;
 
-                    quote! { #variant_ident = #val }
+                    let doc = comments::doc_attrs(&self.cx, m.doc.as_ref());
+                    quote! { #doc #variant_ident = #val }
                 })
                 .collect();
 
-            let vis = if self.is_exporting {
-                quote! { pub }
-            } else {
-                quote! {}
-            };
-
             let enum_def = quote! {
+                #doc
                 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
                 #[repr(i32)]
                 #vis enum #enum_name {
@@ -325,28 +473,34 @@ impl Visit for RustGenerator {
             self.code.push('\n');
         }
     }
+}
 
-    fn visit_module_item(&mut self, n: &swc_ecma_ast::ModuleItem) {
-        self.process_module_item(n);
+/// Returns the members of a union made only of string literals.
+fn string_literal_union(ty: &Type) -> Option<Vec<String>> {
+    let Type::Union(members) = ty else {
+        return None;
+    };
+    members
+        .iter()
+        .map(|member| match member {
+            Type::Literal(LitType::Str(value)) => Some(value.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+impl Context {
+    /// The method of a tagged enum that returns its `tag`: `kind()`, or
+    /// `r#type()` for a keyword.
+    pub(crate) fn tag_method(&self, tag: &str) -> syn::Ident {
+        let name = self.snake_case(tag);
+        syn::parse_str(&name).unwrap_or_else(|_| format_ident!("r#{}", name))
     }
 
-    fn visit_stmt(&mut self, n: &swc_ecma_ast::Stmt) {
-        // This is called for top-level statements via process_module_item -> visit_with(self)
-        match n {
-            swc_ecma_ast::Stmt::Decl(swc_ecma_ast::Decl::Fn(_))
-            | swc_ecma_ast::Stmt::Decl(swc_ecma_ast::Decl::Class(_))
-            | swc_ecma_ast::Stmt::Decl(swc_ecma_ast::Decl::TsInterface(_))
-            | swc_ecma_ast::Stmt::Decl(swc_ecma_ast::Decl::TsTypeAlias(_))
-            | swc_ecma_ast::Stmt::Decl(swc_ecma_ast::Decl::TsEnum(_)) => {
-                // Top-level declarations: let visitor handle them (writes to self.code)
-                n.visit_children_with(self);
-            }
-            _ => {
-                // Script statements (ExprStmt, VarDecl, If, Loop, etc.): write to self.main_body
-                let stmt_code = super::func::convert_stmt(n);
-                self.main_body.push_str(&stmt_code.to_string());
-                self.main_body.push('\n');
-            }
-        }
+    /// Whether `obj.prop` reads the tag of a tagged enum.
+    pub(crate) fn is_tag_read(&self, obj: &Type, prop: &str) -> bool {
+        obj.as_named()
+            .and_then(|name| self.tagged_unions.get(name))
+            .is_some_and(|tag| tag == prop)
     }
 }
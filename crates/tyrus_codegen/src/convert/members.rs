@@ -0,0 +1,176 @@
+//! Static properties and accessors of the module's classes. Both change how
+//! member expressions on a class are generated: `obj.count` reads through a
+//! getter as `obj.count()`, assigning it calls `obj.set_count(..)`, and a
+//! static property that is not a constant lives in a module-level `LazyLock`
+//! (behind a `Mutex` unless it is `readonly`).
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use tyrus_ast::{ClassDecl, ClassMember, ClassProp, ExprKind, Lit, MethodKind, Type, UnaryOp};
+
+use super::context::Context;
+use super::func::{convert_expr, to_screaming_snake_case};
+use super::type_mapper::map_type_opt;
+
+#[derive(Default)]
+pub(crate) struct ClassMembers {
+    super_class: Option<String>,
+    getters: BTreeSet<String>,
+    setters: BTreeSet<String>,
+    /// Static properties kept in a module-level static, by whether they are
+    /// mutable.
+    statics: BTreeMap<String, bool>,
+}
+
+/// The accessors and static properties of `classes`, by class.
+pub(crate) fn class_members<'a>(
+    classes: impl IntoIterator<Item = &'a ClassDecl>,
+) -> BTreeMap<String, ClassMembers> {
+    classes
+        .into_iter()
+        .map(|class| {
+            let mut members = ClassMembers {
+                super_class: class.super_class_name().map(str::to_string),
+                ..Default::default()
+            };
+            for member in &class.members {
+                match member {
+                    ClassMember::Method(method) if method.kind == MethodKind::Getter => {
+                        members.getters.insert(method.name.clone());
+                    }
+                    ClassMember::Method(method) if method.kind == MethodKind::Setter => {
+                        members.setters.insert(method.name.clone());
+                    }
+                    ClassMember::Prop(prop) if prop.is_static && !is_const(prop) => {
+                        members.statics.insert(prop.name.clone(), !prop.readonly);
+                    }
+                    _ => {}
+                }
+            }
+            (class.name.clone(), members)
+        })
+        .collect()
+}
+
+impl Context {
+    /// Whether class `ty` or one of its ancestors declares accessor `name`.
+    fn has_accessor(&self, ty: &Type, name: &str, setter: bool) -> bool {
+        let classes = &self.class_members;
+        let mut current = ty.as_named();
+        for _ in 0..=classes.len() {
+            let Some(members) = current.and_then(|class| classes.get(class)) else {
+                return false;
+            };
+            let accessors = if setter {
+                &members.setters
+            } else {
+                &members.getters
+            };
+            if accessors.contains(name) {
+                return true;
+            }
+            current = members.super_class.as_deref();
+        }
+        false
+    }
+
+    /// Whether `obj.name` on a value of type `ty` reads through a getter.
+    pub fn is_getter(&self, ty: &Type, name: &str) -> bool {
+        self.has_accessor(ty, name, false)
+    }
+
+    /// Whether assigning `obj.name` on a value of type `ty` goes through a setter.
+    pub fn is_setter(&self, ty: &Type, name: &str) -> bool {
+        self.has_accessor(ty, name, true)
+    }
+
+    pub fn setter_ident(&self, name: &str) -> proc_macro2::Ident {
+        format_ident!("set_{}", self.snake_case(name))
+    }
+
+    /// The module-level static holding `Class.name`, if it is not a constant,
+    /// and whether it is behind a `Mutex`.
+    pub fn module_static(&self, class: &str, name: &str) -> Option<(proc_macro2::Ident, bool)> {
+        let mutable = self
+            .class_members
+            .get(class)
+            .and_then(|members| members.statics.get(name).copied())?;
+        Some((static_ident(class, name), mutable))
+    }
+}
+
+fn static_ident(class: &str, name: &str) -> proc_macro2::Ident {
+    format_ident!(
+        "{}_{}",
+        to_screaming_snake_case(class),
+        to_screaming_snake_case(name)
+    )
+}
+
+/// Readonly number and boolean literals fit an associated `const`; anything
+/// else is built on first use.
+fn is_const(prop: &ClassProp) -> bool {
+    let Some(value) = prop.value.as_ref().filter(|_| prop.readonly) else {
+        return false;
+    };
+    let literal = match &value.kind {
+        ExprKind::Unary {
+            op: UnaryOp::Minus,
+            arg,
+        } => &arg.kind,
+        kind => kind,
+    };
+    matches!(
+        (prop.ty.as_ref(), literal),
+        (None | Some(Type::Number), ExprKind::Lit(Lit::Num(_)))
+            | (None | Some(Type::Bool), ExprKind::Lit(Lit::Bool(_)))
+    )
+}
+
+/// A static property: an associated `const` for the impl block, or a
+/// module-level static with the class's visibility `vis`.
+pub enum StaticItem {
+    Const(TokenStream),
+    Static(TokenStream),
+}
+
+pub fn convert_static_prop(
+    cx: &Context,
+    class: &str,
+    prop: &ClassProp,
+    vis: &TokenStream,
+) -> StaticItem {
+    let name = format_ident!("{}", prop.name);
+    let ty = match &prop.value {
+        Some(value) if prop.ty.is_none() => map_type_opt(cx, Some(&value.ty)),
+        _ => map_type_opt(cx, prop.ty.as_ref()),
+    };
+    let value = match &prop.value {
+        Some(value) => convert_expr(cx, value),
+        None => quote! { Default::default() },
+    };
+    if is_const(prop) {
+        let allow = if prop.name.chars().any(char::is_lowercase) {
+            quote! { #[allow(non_upper_case_globals)] }
+        } else {
+            quote! {}
+        };
+        StaticItem::Const(quote! {
+            #allow
+            pub const #name: #ty = #value;
+        })
+    } else if prop.readonly {
+        let ident = static_ident(class, &prop.name);
+        StaticItem::Static(quote! {
+            #vis static #ident: std::sync::LazyLock<#ty> = std::sync::LazyLock::new(|| #value);
+        })
+    } else {
+        let ident = static_ident(class, &prop.name);
+        StaticItem::Static(quote! {
+            #vis static #ident: std::sync::LazyLock<std::sync::Mutex<#ty>> =
+                std::sync::LazyLock::new(|| std::sync::Mutex::new(#value));
+        })
+    }
+}
@@ -1,5 +1,11 @@
+pub mod abstract_class;
 pub mod class;
+pub mod context;
 pub mod func;
+pub mod inheritance;
 pub mod interface;
+pub mod members;
 pub mod module;
+pub mod traits;
 pub mod type_mapper;
+pub mod unsupported;
@@ -1,66 +1,46 @@
-use swc_ecma_ast::{ModuleDecl, ModuleItem};
-use swc_ecma_visit::VisitWith;
+use tyrus_ast::{Decl, Import, ImportSpecifier, Item};
 
 use super::interface::RustGenerator;
 
 impl RustGenerator {
-    pub fn process_module_item(&mut self, n: &ModuleItem) {
+    pub fn process_item(&mut self, n: &Item) {
+        let cx = &self.cx;
         match n {
-            ModuleItem::ModuleDecl(decl) => match decl {
-                ModuleDecl::ExportDecl(export_decl) => {
-                    self.is_exporting = true;
-                    export_decl.decl.visit_with(self);
-                    self.is_exporting = false;
-                }
-                ModuleDecl::ExportDefaultDecl(default_decl) => {
-                    self.is_exporting = true;
-                    match &default_decl.decl {
-                        swc_ecma_ast::DefaultDecl::Class(class_expr) => {
-                            // Convert ClassExpr to ClassDecl for visitor
-                            // We need to construct a ClassDecl manually or just visit the ClassExpr
-                            // But our visitor expects ClassDecl.
-                            // Actually, ClassExpr has an ident (optional). If it's default export, it might be anonymous.
-                            // If anonymous, we can't easily make it a named struct in Rust without a name.
-                            // For now, assume it has a name or we skip.
-                            if let Some(ident) = &class_expr.ident {
-                                let decl = swc_ecma_ast::ClassDecl {
-                                    ident: ident.clone(),
-                                    declare: false,
-                                    class: class_expr.class.clone(),
-                                };
-                                self.process_class_decl(&decl);
-                            }
-                        }
-                        swc_ecma_ast::DefaultDecl::Fn(fn_expr) => {
-                            if let Some(ident) = &fn_expr.ident {
-                                let decl = swc_ecma_ast::FnDecl {
-                                    ident: ident.clone(),
-                                    declare: false,
-                                    function: fn_expr.function.clone(),
-                                };
-                                self.process_fn_decl(&decl);
-                            }
-                        }
-                        _ => {}
-                    }
-                    self.is_exporting = false;
-                }
-                ModuleDecl::Import(import_decl) => {
-                    self.process_import_decl(import_decl);
-                }
-                _ => {
-                    // Other module declarations
-                }
-            },
-            ModuleItem::Stmt(stmt) => {
-                stmt.visit_with(self);
+            Item::Import(import_decl) => {
+                self.process_import_decl(import_decl);
+            }
+            Item::Decl { decl, exported } => {
+                self.code
+                    .push_str(&crate::span_map::marker(decl.span()).to_string());
+                self.code.push('\n');
+                self.is_exporting = *exported;
+                self.process_decl(decl);
+                self.is_exporting = false;
+            }
+            Item::Stmt(stmt) => {
+                // Script statements (ExprStmt, VarDecl, If, Loop, etc.): write to self.main_body
+                let stmt_code = super::func::convert_listed_stmt(cx, stmt);
+                self.main_body.push_str(&stmt_code.to_string());
+                self.main_body.push('\n');
             }
         }
     }
 
-    fn process_import_decl(&mut self, n: &swc_ecma_ast::ImportDecl) {
-        let src_atom = &n.src.value;
-        let mut src = src_atom.as_str().unwrap_or("").to_string();
+    fn process_decl(&mut self, decl: &Decl) {
+        match decl {
+            Decl::Fn(f) => self.process_fn_decl(f),
+            Decl::Class(c) => self.process_class_decl(c),
+            Decl::Interface(i) => self.process_interface_decl(i),
+            Decl::TypeAlias(a) => self.process_type_alias_decl(a),
+            Decl::Enum(e) => self.process_enum_decl(e),
+            // Exported top-level bindings have no Rust counterpart yet
+            Decl::Var(_) => {}
+        }
+    }
+
+    fn process_import_decl(&mut self, n: &Import) {
+        let cx = &self.cx;
+        let mut src = n.src.clone();
 
         // Ignore @nestjs imports
         if src.starts_with("@nestjs") {
@@ -109,17 +89,8 @@ impl RustGenerator {
 
         for specifier in &n.specifiers {
             match specifier {
-                swc_ecma_ast::ImportSpecifier::Named(named) => {
-                    let imported_name = if let Some(imported) = &named.imported {
-                        match imported {
-                            swc_ecma_ast::ModuleExportName::Ident(ident) => ident.sym.to_string(),
-                            swc_ecma_ast::ModuleExportName::Str(s) => {
-                                s.value.as_str().unwrap_or("").to_string()
-                            }
-                        }
-                    } else {
-                        named.local.sym.to_string()
-                    };
+                ImportSpecifier::Named { local, imported } => {
+                    let imported_name = imported.as_ref().unwrap_or(local).clone();
 
                     // Apply casing logic to imported name
                     // If starts with Uppercase, keep it (Class/Type)
@@ -131,15 +102,15 @@ impl RustGenerator {
                     {
                         imported_name.clone()
                     } else {
-                        super::func::to_snake_case(&imported_name)
+                        cx.snake_case(&imported_name)
                     };
 
-                    let local_name = named.local.sym.to_string();
+                    let local_name = local.clone();
                     let local_rust_name =
                         if local_name.chars().next().is_some_and(|c| c.is_uppercase()) {
                             local_name.clone()
                         } else {
-                            super::func::to_snake_case(&local_name)
+                            cx.snake_case(&local_name)
                         };
 
                     let use_stmt = if imported_rust_name == local_rust_name {
@@ -154,13 +125,13 @@ impl RustGenerator {
                     self.code.push_str(&use_stmt);
                     self.code.push('\n');
                 }
-                swc_ecma_ast::ImportSpecifier::Default(default) => {
-                    let local_name = default.local.sym.to_string();
+                ImportSpecifier::Default { local } => {
+                    let local_name = local.clone();
                     let local_rust_name =
                         if local_name.chars().next().is_some_and(|c| c.is_uppercase()) {
                             local_name.clone()
                         } else {
-                            super::func::to_snake_case(&local_name)
+                            cx.snake_case(&local_name)
                         };
 
                     // Default import usually implies importing the struct/fn with the same name as the module or file
@@ -174,13 +145,13 @@ impl RustGenerator {
                     self.code.push_str(&use_stmt);
                     self.code.push('\n');
                 }
-                swc_ecma_ast::ImportSpecifier::Namespace(ns) => {
-                    let local_name = ns.local.sym.to_string();
+                ImportSpecifier::Namespace { local } => {
+                    let local_name = local.clone();
                     let local_rust_name =
                         if local_name.chars().next().is_some_and(|c| c.is_uppercase()) {
                             local_name.clone()
                         } else {
-                            super::func::to_snake_case(&local_name)
+                            cx.snake_case(&local_name)
                         };
                     let use_stmt = format!("use {} as {};", module_path, local_rust_name);
                     self.code.push_str(&use_stmt);
@@ -0,0 +1,375 @@
+//! Interfaces that declare methods become traits, and `implements` clauses
+//! become impls of them. Values typed with such an interface are trait
+//! objects (`Arc<dyn Iface>`), or `impl Iface` when taken as a parameter.
+//!
+//! Property members of a trait interface become getters, so `store.name`
+//! on a trait object is generated as `store.name()`.
+//!
+//! A trait method takes `&mut self` when a class implementing it mutates
+//! `this` in it. Trait objects of such an interface are shared as
+//! `Arc<Mutex<dyn Iface>>`, parameters included, and locked for each access.
+
+use std::collections::{HashMap, HashSet};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use tyrus_analyzer::features::Feature;
+use tyrus_analyzer::typeck::substitute;
+use tyrus_ast::{ClassDecl, ClassMember, CtorParam, InterfaceDecl, InterfaceMember, Pat, Type};
+
+use super::context::Context;
+use super::interface::RustGenerator;
+use super::type_mapper::{map_type_opt, trait_path, unwrap_promise_type};
+use crate::comments;
+
+/// A trait item derived from an interface member.
+struct TraitItem {
+    sig: TokenStream,
+    name: proc_macro2::Ident,
+    args: Vec<proc_macro2::Ident>,
+    is_async: bool,
+    /// `Some(field)` for getters generated from property members.
+    field: Option<String>,
+    optional: bool,
+    /// The member's rustdoc, for the trait declaration only.
+    doc: TokenStream,
+}
+
+/// The trait items of `iface`, with its type parameters replaced per `subst`.
+/// The `mutating` methods take `&mut self`.
+fn trait_items(
+    cx: &Context,
+    iface: &InterfaceDecl,
+    subst: &HashMap<String, Type>,
+    mutating: &HashSet<String>,
+) -> Vec<TraitItem> {
+    let apply = |ty: Option<&Type>| ty.map(|ty| substitute(ty, subst));
+    iface
+        .members
+        .iter()
+        .map(|member| match member {
+            InterfaceMember::Property {
+                name,
+                ty,
+                optional,
+                doc,
+                ..
+            } => {
+                let getter = format_ident!("{}", cx.snake_case(name));
+                let mut ty = map_type_opt(cx, apply(ty.as_ref()).as_ref());
+                if *optional {
+                    ty = quote! { Option<#ty> };
+                }
+                TraitItem {
+                    sig: quote! { fn #getter(&self) -> #ty },
+                    name: getter,
+                    args: Vec::new(),
+                    is_async: false,
+                    field: Some(name.clone()),
+                    optional: *optional,
+                    doc: comments::doc_attrs(cx, doc.as_ref()),
+                }
+            }
+            InterfaceMember::Method {
+                name,
+                params,
+                return_type,
+                optional,
+                doc,
+            } => {
+                let method_name = format_ident!("{}", cx.snake_case(name));
+                let receiver = if mutating.contains(name) {
+                    quote! { &mut self }
+                } else {
+                    quote! { &self }
+                };
+                let mut params_tokens = Vec::new();
+                let mut args = Vec::new();
+                for param in params {
+                    if let Pat::Ident(binding) = &param.pat {
+                        let param_name = format_ident!("{}", cx.snake_case(&binding.name));
+                        let param_type = map_type_opt(cx, apply(binding.ty.as_ref()).as_ref());
+                        params_tokens.push(quote! { #param_name: #param_type });
+                        args.push(param_name);
+                    }
+                }
+                let return_type = apply(return_type.as_ref());
+                let is_async = matches!(return_type, Some(Type::Promise(_)));
+                let sig = if is_async {
+                    let inner = unwrap_promise_type(cx, return_type.as_ref());
+                    quote! {
+                        async fn #method_name(#receiver, #(#params_tokens),*) -> Result<#inner, crate::AppError>
+                    }
+                } else {
+                    let ret = map_type_opt(cx, return_type.as_ref());
+                    quote! { fn #method_name(#receiver, #(#params_tokens),*) -> #ret }
+                };
+                TraitItem {
+                    sig,
+                    name: method_name,
+                    args,
+                    is_async,
+                    field: None,
+                    optional: *optional,
+                    doc: comments::doc_attrs(cx, doc.as_ref()),
+                }
+            }
+        })
+        .collect()
+}
+
+/// Forwards `item`'s arguments to `callee`, after `receiver` if one is given.
+fn call(item: &TraitItem, callee: TokenStream, receiver: Option<TokenStream>) -> TokenStream {
+    let args = receiver
+        .into_iter()
+        .chain(item.args.iter().map(|a| quote! { #a }));
+    if item.is_async {
+        quote! { #callee(#(#args),*).await }
+    } else {
+        quote! { #callee(#(#args),*) }
+    }
+}
+
+/// `#[axum::async_trait]` keeps traits with async methods object safe.
+fn async_trait_attr(items: &[TraitItem]) -> TokenStream {
+    if items.iter().any(|item| item.is_async) {
+        quote! { #[axum::async_trait] }
+    } else {
+        quote! {}
+    }
+}
+
+impl RustGenerator {
+    /// Emits an interface with methods as a trait, plus a forwarding impl for
+    /// `Arc<T>` so shared trait objects satisfy `impl Iface` parameters, or
+    /// for `Arc<Mutex<T>>` when they are locked.
+    pub fn process_trait_interface(&mut self, n: &InterfaceDecl) {
+        let cx = &self.cx;
+        let trait_name = format_ident!("{}", n.name);
+        let params: Vec<_> = n
+            .type_params
+            .iter()
+            .map(|p| format_ident!("{}", p.name))
+            .collect();
+        let (generics_decl, generics_use, impl_params) = if params.is_empty() {
+            (quote! {}, quote! {}, quote! {})
+        } else {
+            (
+                quote! { <#(#params: Send + Sync),*> },
+                quote! { <#(#params),*> },
+                quote! { #(#params: Send + Sync,)* },
+            )
+        };
+
+        let supertraits = n
+            .extends
+            .iter()
+            .filter(|parent| cx.is_trait_interface(parent))
+            .filter_map(|parent| match parent {
+                Type::Named { name, args } => Some(trait_path(cx, name, args)),
+                _ => None,
+            });
+
+        let items = trait_items(cx, n, &HashMap::new(), &self.mutating_methods(&n.name));
+        let attr = async_trait_attr(&items);
+        let decls = items.iter().map(|item| {
+            let sig = &item.sig;
+            let doc = &item.doc;
+            if !item.optional {
+                return quote! { #doc #sig; };
+            }
+            // Optional members may be left out by implementors
+            let body = if item.is_async {
+                quote! { Ok(Default::default()) }
+            } else {
+                quote! { Default::default() }
+            };
+            quote! { #doc #sig { #body } }
+        });
+        let locked = cx.mutated_interfaces.contains(&n.name);
+        // A lock can't be held across an `.await`
+        let unsupported = (locked && n.has_async_methods())
+            .then(|| cx.todo(Feature::AsyncMethodsOfMutatedInterfaces, n.span));
+        let forwards = items.iter().map(|item| {
+            let sig = &item.sig;
+            let name = &item.name;
+            let body = match &unsupported {
+                Some(todo) if item.is_async => todo.clone(),
+                _ if locked => call(item, quote! { self.lock().unwrap().#name }, None),
+                _ => call(item, quote! { (**self).#name }, None),
+            };
+            quote! { #sig { #body } }
+        });
+        let shared = if locked {
+            quote! { std::sync::Arc<std::sync::Mutex<Impl>> }
+        } else {
+            quote! { std::sync::Arc<Impl> }
+        };
+
+        let doc = comments::doc_attrs(cx, n.doc.as_ref());
+        let tokens = quote! {
+            #doc
+            #attr
+            pub trait #trait_name #generics_decl: #(#supertraits +)* Send + Sync {
+                #(#decls)*
+            }
+
+            #attr
+            impl<#impl_params Impl: #trait_name #generics_use + ?Sized> #trait_name #generics_use for #shared {
+                #(#forwards)*
+            }
+        };
+        self.code.push_str(&tokens.to_string());
+        self.code.push('\n');
+    }
+
+    /// Emits `impl Iface for Class` for every trait interface the class
+    /// implements. Methods delegate to the class's own; getters clone fields.
+    pub fn process_implements(&mut self, class: &ClassDecl) {
+        let cx = &self.cx;
+        if !class.type_params.is_empty() {
+            return;
+        }
+        let struct_name = format_ident!("{}", class.name);
+        for implemented in &class.implements {
+            let Type::Named { name, args } = implemented else {
+                continue;
+            };
+            let Some(iface) = cx.trait_interface(name) else {
+                continue;
+            };
+            let subst = iface
+                .type_params
+                .iter()
+                .map(|p| p.name.clone())
+                .zip(args.iter().cloned())
+                .collect();
+            let items = trait_items(cx, iface, &subst, &self.mutating_methods(name));
+            let attr = async_trait_attr(&items);
+            let path = trait_path(cx, name, args);
+
+            let impls = items.iter().filter_map(|item| {
+                let sig = &item.sig;
+                let name = &item.name;
+                let body = match &item.field {
+                    Some(field) => {
+                        let field_ident = format_ident!("{}", cx.snake_case(field));
+                        let value = quote! { self.#field_ident.clone() };
+                        if item.optional && !field_is_optional(class, field) {
+                            quote! { Some(#value) }
+                        } else {
+                            value
+                        }
+                    }
+                    None if declares_method(cx, class, &item.name) => {
+                        call(item, quote! { #struct_name::#name }, Some(quote! { self }))
+                    }
+                    // Inherited from the superclass
+                    None if class.super_class.is_some() => {
+                        call(item, quote! { self.base.#name }, None)
+                    }
+                    None => return None,
+                };
+                Some(quote! { #sig { #body } })
+            });
+
+            let tokens = quote! {
+                #attr
+                impl #path for #struct_name {
+                    #(#impls)*
+                }
+            };
+            self.code.push_str(&tokens.to_string());
+            self.code.push('\n');
+        }
+    }
+
+    /// Methods of interface `iface` that a class implementing it mutates
+    /// `this` in.
+    fn mutating_methods(&self, iface: &str) -> HashSet<String> {
+        self.classes
+            .values()
+            .filter(|class| {
+                class
+                    .implements
+                    .iter()
+                    .any(|ty| ty.as_named() == Some(iface))
+            })
+            .flat_map(|class| class.methods())
+            .filter(|method| method.mutates_this)
+            .map(|method| method.name.clone())
+            .collect()
+    }
+
+    /// Classes holding trait objects, directly or through a field of another
+    /// class, can only derive `Clone`.
+    pub fn holds_trait_objects(&self, class: &ClassDecl) -> bool {
+        self.class_holds_trait_objects(class, &mut Vec::new())
+    }
+
+    fn class_holds_trait_objects(&self, class: &ClassDecl, seen: &mut Vec<String>) -> bool {
+        if seen.contains(&class.name) {
+            return false;
+        }
+        seen.push(class.name.clone());
+        let own = class.members.iter().any(|member| match member {
+            ClassMember::Prop(prop) => prop
+                .ty
+                .as_ref()
+                .is_some_and(|ty| self.type_holds_trait_objects(ty, seen)),
+            ClassMember::Constructor(cons) => cons.params.iter().any(|param| {
+                matches!(param, CtorParam::Prop { ty: Some(ty), .. } if self.type_holds_trait_objects(ty, seen))
+            }),
+            ClassMember::Method(_) => false,
+        });
+        own || class
+            .super_class_name()
+            .and_then(|base| self.classes.get(base))
+            .is_some_and(|base| self.class_holds_trait_objects(base, seen))
+    }
+
+    fn type_holds_trait_objects(&self, ty: &Type, seen: &mut Vec<String>) -> bool {
+        if self.cx.contains_trait_object(ty) {
+            return true;
+        }
+        match ty {
+            Type::Named { name, args } => {
+                args.iter()
+                    .any(|arg| self.type_holds_trait_objects(arg, seen))
+                    || self
+                        .classes
+                        .get(name)
+                        .is_some_and(|class| self.class_holds_trait_objects(class, seen))
+            }
+            Type::Array(inner) | Type::Promise(inner) | Type::Optional(inner) => {
+                self.type_holds_trait_objects(inner, seen)
+            }
+            Type::Record(key, value) => {
+                self.type_holds_trait_objects(key, seen)
+                    || self.type_holds_trait_objects(value, seen)
+            }
+            Type::Union(members) | Type::Tuple(members) => members
+                .iter()
+                .any(|member| self.type_holds_trait_objects(member, seen)),
+            _ => false,
+        }
+    }
+}
+
+fn declares_method(cx: &Context, class: &ClassDecl, snake_name: &proc_macro2::Ident) -> bool {
+    class
+        .methods()
+        .any(|m| !m.is_static && format_ident!("{}", cx.snake_case(&m.name)) == *snake_name)
+}
+
+fn field_is_optional(class: &ClassDecl, field: &str) -> bool {
+    class.members.iter().any(|member| match member {
+        ClassMember::Prop(prop) => {
+            prop.name == field && (prop.optional || prop.ty.as_ref().is_some_and(Type::is_optional))
+        }
+        ClassMember::Constructor(cons) => cons.params.iter().any(|param| {
+            matches!(param, CtorParam::Prop { name, ty: Some(ty), .. } if name == field && ty.is_optional())
+        }),
+        ClassMember::Method(_) => false,
+    })
+}
@@ -1,258 +1,229 @@
 use proc_macro2::TokenStream;
-use quote::quote;
-use swc_ecma_ast::{TsType, TsTypeAnn};
+use quote::{format_ident, quote};
+use swc_ecma_ast::TsTypeAnn;
+use tyrus_analyzer::typeck::unions::{untagged_union, UntaggedUnion};
+use tyrus_ast::{InterfaceDecl, InterfaceMember, Type};
 
-/// Maps TypeScript types to Rust types
-#[allow(clippy::borrowed_box)]
-pub fn map_ts_type(type_ann: Option<&Box<TsTypeAnn>>) -> TokenStream {
-    if let Some(type_ann) = type_ann {
-        match &*type_ann.type_ann {
-            TsType::TsKeywordType(k) => match k.kind {
-                swc_ecma_ast::TsKeywordTypeKind::TsStringKeyword => quote! { String },
-                swc_ecma_ast::TsKeywordTypeKind::TsNumberKeyword => quote! { f64 },
-                swc_ecma_ast::TsKeywordTypeKind::TsBooleanKeyword => quote! { bool },
-                swc_ecma_ast::TsKeywordTypeKind::TsVoidKeyword => quote! { () },
-                _ => quote! { serde_json::Value },
-            },
-            TsType::TsArrayType(array_type) => {
-                let inner_type = map_inner_type(&array_type.elem_type);
-                quote! { Vec<#inner_type> }
-            }
-            TsType::TsTypeRef(t) => {
-                if let Some(ident) = t.type_name.as_ident() {
-                    let name = ident.sym.as_str();
-                    match name {
-                        "Date" => quote! { String },
-                        "Array" => {
-                            if let Some(type_params) = &t.type_params {
-                                if let Some(first_param) = type_params.params.first() {
-                                    let inner = map_inner_type(first_param);
-                                    quote! { Vec<#inner> }
-                                } else {
-                                    quote! { Vec<serde_json::Value> }
-                                }
-                            } else {
-                                quote! { Vec<serde_json::Value> }
-                            }
-                        }
-                        "Record" => {
-                            if let Some(type_params) = &t.type_params {
-                                if type_params.params.len() >= 2 {
-                                    let key = map_inner_type(&type_params.params[0]);
-                                    let value = map_inner_type(&type_params.params[1]);
-                                    quote! { std::collections::HashMap<#key, #value> }
-                                } else {
-                                    quote! { std::collections::HashMap<String, serde_json::Value> }
-                                }
-                            } else {
-                                quote! { std::collections::HashMap<String, serde_json::Value> }
-                            }
-                        }
-                        _ => {
-                            // User defined type (Struct or Enum)
-                            let type_ident =
-                                proc_macro2::Ident::new(name, proc_macro2::Span::call_site());
+use super::context::Context;
+use super::inheritance::trait_ident;
 
-                            if let Some(type_params) = &t.type_params {
-                                let params: Vec<_> = type_params
-                                    .params
-                                    .iter()
-                                    .map(|p| map_inner_type(p))
-                                    .collect();
-                                quote! { #type_ident<#(#params),*> }
-                            } else {
-                                quote! { #type_ident }
-                            }
-                        }
-                    }
-                } else {
-                    quote! { serde_json::Value }
-                }
-            }
-            TsType::TsUnionOrIntersectionType(union_or_intersection) => {
-                // Check for Optional (T | undefined)
-                if let swc_ecma_ast::TsUnionOrIntersectionType::TsUnionType(union) =
-                    union_or_intersection
-                {
-                    let mut is_optional = false;
-                    let mut inner_type = None;
+impl Context {
+    pub fn trait_interface(&self, name: &str) -> Option<&InterfaceDecl> {
+        self.trait_interfaces.get(name)
+    }
 
-                    for type_opt in &union.types {
-                        match &**type_opt {
-                            TsType::TsKeywordType(k)
-                                if k.kind
-                                    == swc_ecma_ast::TsKeywordTypeKind::TsUndefinedKeyword
-                                    || k.kind == swc_ecma_ast::TsKeywordTypeKind::TsNullKeyword =>
-                            {
-                                is_optional = true;
-                            }
-                            _ => {
-                                if inner_type.is_none() {
-                                    inner_type = Some(map_inner_type(type_opt));
-                                }
-                            }
-                        }
-                    }
+    /// Whether `ty` names an interface emitted as a trait.
+    pub fn is_trait_interface(&self, ty: &Type) -> bool {
+        ty.as_named()
+            .is_some_and(|name| self.trait_interfaces.contains_key(name))
+    }
 
-                    if is_optional {
-                        if let Some(inner) = inner_type {
-                            quote! { Option<#inner> }
-                        } else {
-                            quote! { Option<serde_json::Value> }
-                        }
-                    } else {
-                        // Regular union - fallback to Value for now
-                        quote! { serde_json::Value }
-                    }
-                } else {
-                    quote! { serde_json::Value }
-                }
-            }
-            _ => quote! { serde_json::Value },
+    /// Whether `ty` names a trait interface whose trait objects are locked.
+    pub fn is_mutated_interface(&self, ty: &Type) -> bool {
+        ty.as_named()
+            .is_some_and(|name| self.mutated_interfaces.contains(name))
+    }
+
+    /// Whether `ty` names a trait interface or an abstract class, both of which
+    /// are emitted as traits.
+    pub fn is_trait_type(&self, ty: &Type) -> bool {
+        self.is_trait_interface(ty)
+            || ty
+                .as_named()
+                .is_some_and(|name| self.abstract_classes.contains(name))
+    }
+
+    /// Whether `name` is a property of the trait interface `ty`, read through a getter.
+    pub fn is_trait_getter(&self, ty: &Type, name: &str) -> bool {
+        ty.as_named()
+            .and_then(|iface| self.trait_interface(iface))
+            .is_some_and(|iface| {
+                iface.members.iter().any(
+                    |m| matches!(m, InterfaceMember::Property { name: prop, .. } if prop == name),
+                )
+            })
+    }
+
+    /// The untagged union `ty` is, written inline or through an alias.
+    pub fn untagged_union(&self, ty: &Type) -> Option<UntaggedUnion> {
+        match ty {
+            Type::Named { name, .. } => self.untagged_unions.get(name).cloned(),
+            _ => untagged_union(ty),
         }
-    } else {
-        quote! { serde_json::Value }
     }
-}
 
-/// Unwraps Promise<T> to T for async function return types
-#[allow(clippy::borrowed_box)]
-pub fn unwrap_promise_type(type_ann: Option<&Box<TsTypeAnn>>) -> TokenStream {
-    if let Some(type_ann) = type_ann {
-        if let TsType::TsTypeRef(type_ref) = &*type_ann.type_ann {
-            // Check if this is a Promise type
-            if let Some(ident) = type_ref.type_name.as_ident() {
-                if ident.sym == "Promise" {
-                    // Extract the generic parameter T from Promise<T>
-                    if let Some(type_params) = &type_ref.type_params {
-                        if let Some(first_param) = type_params.params.first() {
-                            // Recursively map the inner type
-                            return map_inner_type(first_param);
-                        }
-                    }
-                }
+    /// Converts `tokens`, a value of type `from`, for a place of type `to`: a
+    /// class instance becomes a trait object of its interface or base class,
+    /// and a member of an untagged union its variant.
+    pub fn coerce(&self, tokens: TokenStream, from: &Type, to: &Type) -> TokenStream {
+        if let Some(union) = self.untagged_union(to) {
+            let Some(variant) = union.variant_of(from) else {
+                return tokens;
+            };
+            let path = map_type(self, to);
+            let variant = format_ident!("{}", variant.name);
+            return quote! { #path::#variant(#tokens) };
+        }
+        if self.is_trait_type(from) {
+            tokens
+        } else if self.is_mutated_interface(to) {
+            quote! { std::sync::Arc::new(std::sync::Mutex::new(#tokens)) }
+        } else if self.is_trait_type(to) {
+            quote! { std::sync::Arc::new(#tokens) }
+        } else {
+            tokens
+        }
+    }
+
+    /// Whether a value of type `ty` holds a trait object somewhere, which rules
+    /// out deriving `Default`, `Debug`, comparisons and serde on its container.
+    pub fn contains_trait_object(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Named { args, .. } => {
+                self.is_trait_type(ty) || args.iter().any(|arg| self.contains_trait_object(arg))
+            }
+            Type::Array(inner) | Type::Promise(inner) | Type::Optional(inner) => {
+                self.contains_trait_object(inner)
             }
+            Type::Record(key, value) => {
+                self.contains_trait_object(key) || self.contains_trait_object(value)
+            }
+            Type::Union(members) | Type::Tuple(members) => members
+                .iter()
+                .any(|member| self.contains_trait_object(member)),
+            _ => false,
         }
     }
-    // If not a Promise or no generic, fall back to regular mapping
-    map_ts_type(type_ann)
 }
 
-pub fn is_optional_type(type_ann: Option<&TsTypeAnn>) -> bool {
-    if let Some(type_ann) = type_ann {
-        if let TsType::TsUnionOrIntersectionType(
-            swc_ecma_ast::TsUnionOrIntersectionType::TsUnionType(union),
-        ) = &*type_ann.type_ann
+/// Path of a trait type with its type arguments, as used in bounds and
+/// `impl` headers.
+pub fn trait_path(cx: &Context, name: &str, args: &[Type]) -> TokenStream {
+    let ident = format_ident!("{}", name);
+    if args.is_empty() {
+        quote! { #ident }
+    } else {
+        let args = args.iter().map(|arg| map_type(cx, arg));
+        quote! { #ident<#(#args),*> }
+    }
+}
+
+/// Maps a function parameter type: like [`map_type_opt`], except that a
+/// trait interface is taken as `impl Trait` rather than as a trait object,
+/// and an extended class as `impl ClassTrait`, so that subclasses are
+/// accepted. Locked trait objects, and the `Arc<dyn Trait>` of an abstract
+/// class its subclasses are wrapped in, are taken as they are.
+pub fn map_param_type(cx: &Context, ty: Option<&Type>) -> TokenStream {
+    match ty {
+        Some(named @ Type::Named { name, args })
+            if cx.is_trait_interface(named) && !cx.is_mutated_interface(named) =>
         {
-            for type_opt in &union.types {
-                if let TsType::TsKeywordType(k) = &**type_opt {
-                    if k.kind == swc_ecma_ast::TsKeywordTypeKind::TsUndefinedKeyword
-                        || k.kind == swc_ecma_ast::TsKeywordTypeKind::TsNullKeyword
-                    {
-                        return true;
-                    }
-                }
-            }
+            let path = trait_path(cx, name, args);
+            quote! { impl #path }
         }
+        Some(Type::Named { name, args }) if args.is_empty() && cx.class_traits.contains(name) => {
+            let ident = trait_ident(name);
+            quote! { impl #ident }
+        }
+        _ => map_type_opt(cx, ty),
     }
-    false
 }
 
-pub fn is_void_or_promise_void(type_ann: Option<&TsTypeAnn>) -> bool {
-    if let Some(type_ann) = type_ann {
-        match &*type_ann.type_ann {
-            TsType::TsKeywordType(k) => k.kind == swc_ecma_ast::TsKeywordTypeKind::TsVoidKeyword,
-            TsType::TsTypeRef(type_ref) => {
-                // Check Promise<void>
-                if let Some(ident) = type_ref.type_name.as_ident() {
-                    if ident.sym == "Promise" {
-                        if let Some(type_params) = &type_ref.type_params {
-                            if let Some(first_param) = type_params.params.first() {
-                                // Check if inner is void
-                                if let TsType::TsKeywordType(k) = &**first_param {
-                                    return k.kind
-                                        == swc_ecma_ast::TsKeywordTypeKind::TsVoidKeyword;
-                                }
-                            }
-                        }
-                    }
-                }
-                false
-            }
-            _ => false,
-        }
-    } else {
-        // If no return type, assume void for functions (in this context)
-        // But map_ts_type maps None to Value.
-        // We need consistency.
-        // For now, let's say None is NOT void if we map it to Value.
-        // But in process_fn_decl we might override this.
-        false
+/// Maps TypeScript types to Rust types
+#[allow(clippy::borrowed_box)]
+pub fn map_ts_type(cx: &Context, type_ann: Option<&Box<TsTypeAnn>>) -> TokenStream {
+    let ty = type_ann.map(|ann| tyrus_ast::lower::lower_type(&ann.type_ann));
+    map_type_opt(cx, ty.as_ref())
+}
+
+/// Maps an optional annotation; a missing annotation becomes `serde_json::Value`.
+pub fn map_type_opt(cx: &Context, ty: Option<&Type>) -> TokenStream {
+    match ty {
+        Some(ty) => map_type(cx, ty),
+        None => quote! { serde_json::Value },
     }
 }
 
-pub fn map_inner_type(ts_type: &swc_ecma_ast::TsType) -> TokenStream {
-    match ts_type {
-        TsType::TsKeywordType(k) => match k.kind {
-            swc_ecma_ast::TsKeywordTypeKind::TsStringKeyword => quote! { String },
-            swc_ecma_ast::TsKeywordTypeKind::TsNumberKeyword => quote! { f64 },
-            swc_ecma_ast::TsKeywordTypeKind::TsBooleanKeyword => quote! { bool },
-            swc_ecma_ast::TsKeywordTypeKind::TsVoidKeyword => quote! { () },
-            _ => quote! { serde_json::Value },
-        },
-        TsType::TsArrayType(array_type) => {
-            let inner_type = map_inner_type(&array_type.elem_type);
-            quote! { Vec<#inner_type> }
+/// Maps an IR type to the Rust type used in generated code.
+pub fn map_type(cx: &Context, ty: &Type) -> TokenStream {
+    match ty {
+        Type::String | Type::Date => quote! { String },
+        Type::Number => quote! { f64 },
+        Type::Bool => quote! { bool },
+        Type::Void => quote! { () },
+        Type::Array(inner) => {
+            let inner = map_type(cx, inner);
+            quote! { Vec<#inner> }
+        }
+        Type::Record(key, value) => {
+            let key = map_type(cx, key);
+            let value = map_type(cx, value);
+            quote! { std::collections::HashMap<#key, #value> }
+        }
+        // Async return types are unwrapped by the caller; elsewhere the value is what matters.
+        Type::Promise(inner) => map_type(cx, inner),
+        Type::Optional(inner) => {
+            let inner = map_type(cx, inner);
+            quote! { Option<#inner> }
         }
-        TsType::TsTypeRef(t) => {
-            if let Some(ident) = t.type_name.as_ident() {
-                let name = ident.sym.as_str();
-                match name {
-                    "Date" => quote! { String },
-                    "Array" => {
-                        if let Some(type_params) = &t.type_params {
-                            if let Some(first_param) = type_params.params.first() {
-                                let inner = map_inner_type(first_param);
-                                quote! { Vec<#inner> }
-                            } else {
-                                quote! { Vec<serde_json::Value> }
-                            }
-                        } else {
-                            quote! { Vec<serde_json::Value> }
-                        }
-                    }
-                    "Record" => {
-                        if let Some(type_params) = &t.type_params {
-                            if type_params.params.len() >= 2 {
-                                let key = map_inner_type(&type_params.params[0]);
-                                let value = map_inner_type(&type_params.params[1]);
-                                quote! { std::collections::HashMap<#key, #value> }
-                            } else {
-                                quote! { std::collections::HashMap<String, serde_json::Value> }
-                            }
-                        } else {
-                            quote! { std::collections::HashMap<String, serde_json::Value> }
-                        }
-                    }
-                    _ => {
-                        let type_ident =
-                            proc_macro2::Ident::new(name, proc_macro2::Span::call_site());
-                        quote! { #type_ident }
-                    }
-                }
+        Type::Param(name) => {
+            let ident = format_ident!("{}", name);
+            quote! { #ident }
+        }
+        Type::Named { name, args } if name == "Map" || name == "Set" => {
+            let collection = if name == "Map" {
+                quote! { std::collections::HashMap }
             } else {
-                quote! { serde_json::Value }
+                quote! { std::collections::HashSet }
+            };
+            let args = args.iter().map(|arg| map_type(cx, arg));
+            quote! { #collection<#(#args),*> }
+        }
+        Type::Named { name, args } if cx.is_mutated_interface(ty) => {
+            let path = trait_path(cx, name, args);
+            quote! { std::sync::Arc<std::sync::Mutex<dyn #path>> }
+        }
+        Type::Named { name, args } if cx.is_trait_type(ty) => {
+            let path = trait_path(cx, name, args);
+            quote! { std::sync::Arc<dyn #path> }
+        }
+        Type::Named { name, args } => {
+            // User defined type (Struct or Enum)
+            let ident = format_ident!("{}", name);
+            if args.is_empty() {
+                quote! { #ident }
+            } else {
+                let args = args.iter().map(|arg| map_type(cx, arg));
+                quote! { #ident<#(#args),*> }
             }
         }
+        // string | number, User | Admin → a generated untagged enum
+        Type::Union(_) => match untagged_union(ty) {
+            Some(union) => {
+                let name = union.synthesized_name();
+                let ident = format_ident!("{}", name);
+                cx.unions.borrow_mut().insert(name, union);
+                quote! { #ident }
+            }
+            None => quote! { serde_json::Value },
+        },
+        // Other unions, literals and inline object types fall back to Value for now
         _ => quote! { serde_json::Value },
     }
 }
 
+/// Unwraps Promise<T> to T for async function return types
+pub fn unwrap_promise_type(cx: &Context, ty: Option<&Type>) -> TokenStream {
+    map_type_opt(cx, ty.map(Type::unwrap_promise))
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use super::*;
     use swc_common::DUMMY_SP;
-    use swc_ecma_ast::{TsKeywordType, TsKeywordTypeKind};
+    use swc_ecma_ast::{TsKeywordType, TsKeywordTypeKind, TsType};
 
     #[test]
     fn test_map_ts_type_string() {
@@ -264,7 +235,7 @@ mod tests {
             span: DUMMY_SP,
             type_ann: Box::new(ts_type),
         });
-        let result = map_ts_type(Some(&type_ann));
+        let result = map_ts_type(&Context::default(), Some(&type_ann));
         assert_eq!(result.to_string(), "String");
     }
 
@@ -278,7 +249,7 @@ mod tests {
             span: DUMMY_SP,
             type_ann: Box::new(ts_type),
         });
-        let result = map_ts_type(Some(&type_ann));
+        let result = map_ts_type(&Context::default(), Some(&type_ann));
         assert_eq!(result.to_string(), "f64");
     }
 
@@ -292,13 +263,42 @@ mod tests {
             span: DUMMY_SP,
             type_ann: Box::new(ts_type),
         });
-        let result = map_ts_type(Some(&type_ann));
+        let result = map_ts_type(&Context::default(), Some(&type_ann));
         assert_eq!(result.to_string(), "bool");
     }
 
     #[test]
     fn test_map_ts_type_none() {
-        let result = map_ts_type(None);
+        let result = map_ts_type(&Context::default(), None);
         assert_eq!(result.to_string(), "serde_json :: Value");
     }
+
+    #[test]
+    fn test_map_trait_interface() {
+        let storage = InterfaceDecl {
+            name: "Storage".to_string(),
+            type_params: Vec::new(),
+            extends: Vec::new(),
+            members: Vec::new(),
+            doc: None,
+            span: DUMMY_SP,
+        };
+        let cx = Context {
+            trait_interfaces: BTreeMap::from([("Storage".to_string(), storage)]),
+            ..Default::default()
+        };
+
+        let ty = Type::named("Storage");
+        assert_eq!(
+            map_type(&cx, &ty).to_string(),
+            "std :: sync :: Arc < dyn Storage >"
+        );
+        assert_eq!(map_param_type(&cx, Some(&ty)).to_string(), "impl Storage");
+        assert!(cx.contains_trait_object(&Type::Array(Box::new(ty))));
+
+        assert_eq!(
+            map_type(&Context::default(), &Type::named("Storage")).to_string(),
+            "Storage"
+        );
+    }
 }
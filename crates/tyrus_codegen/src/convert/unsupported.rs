@@ -0,0 +1,35 @@
+//! Constructs codegen cannot translate yet.
+//!
+//! Conversion functions return tokens rather than results, so they report
+//! the [`Feature`]s the registry marks unsupported to their [`Context`] and
+//! emit a `todo!()` in its place.
+//! [`generate_module`](crate::generate_module) hands the reports of a module
+//! to its caller, which fails the build on them unless told to allow `todo!()`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use swc_common::Span;
+use tyrus_analyzer::features::Feature;
+
+use super::context::Context;
+
+/// A construct that was emitted as `todo!()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unsupported {
+    /// What the construct is, e.g. the `void` operator.
+    pub feature: Feature,
+    /// Where it is in the TypeScript source.
+    pub span: Span,
+}
+
+impl Context {
+    /// Reports `feature` at `span` and returns the `todo!()` emitted for it.
+    pub fn todo(&self, feature: Feature, span: Span) -> TokenStream {
+        debug_assert!(!feature.is_supported(), "{feature} is supported");
+        let message = format!("unsupported by Tyrus: {feature}");
+        self.reports
+            .borrow_mut()
+            .push(Unsupported { feature, span });
+        quote! { todo!(#message) }
+    }
+}
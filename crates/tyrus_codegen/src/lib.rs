@@ -1,9 +1,19 @@
+pub mod comments;
 pub mod convert;
+pub mod span_map;
 pub mod stdlib;
 
+use convert::context::Context;
+pub use convert::func::Naming;
 use convert::interface::RustGenerator;
+pub use convert::unsupported::Unsupported;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use swc_common::comments::SingleThreadedComments;
+
 use swc_ecma_ast::Program;
-use swc_ecma_visit::VisitWith;
+use tyrus_analyzer::typeck::unions::{tagged_union, untagged_union, UntaggedUnion};
+use tyrus_analyzer::typeck::TypeEnv;
+use tyrus_ast::{ClassDecl, Decl, InterfaceDecl, Item, Module};
 
 #[derive(Debug, Clone)]
 pub struct ControllerMetadata {
@@ -12,15 +22,237 @@ pub struct ControllerMetadata {
 }
 
 pub struct GeneratedCode {
+    /// Carries [`span_map`] and [`comments`] markers; once formatted, render
+    /// them with [`comments::render`] and strip them with [`span_map::extract`].
     pub code: String,
     pub controllers: Vec<ControllerMetadata>,
+    /// Constructs emitted as `todo!()` because Tyrus cannot translate them.
+    pub unsupported: Vec<Unsupported>,
+    /// Whether the top-level statements of an index module became its
+    /// `pub fn main()`.
+    pub entry: bool,
+    /// Synthesized unions the module uses from the project's `unions`
+    /// module, which [`generate_unions`] declares.
+    pub unions: BTreeMap<String, UntaggedUnion>,
+}
+
+/// The declarations of a project that a module can build on without
+/// declaring them: classes of other modules, which subclasses inherit from,
+/// interfaces emitted as traits, and the types of everything the modules
+/// export.
+#[derive(Debug, Clone, Default)]
+pub struct Project {
+    classes: HashMap<String, ClassDecl>,
+    /// The Rust module each class is declared in, e.g. `crate::models::user`.
+    modules: HashMap<String, String>,
+    trait_interfaces: BTreeMap<String, InterfaceDecl>,
+    exports: TypeEnv,
+    /// The Rust module of each exported declaration, which the project's
+    /// `unions` module `use`s.
+    declarations: HashMap<String, String>,
+    /// Whether modules were added, whose synthesized unions are then emitted
+    /// once, in the `unions` module, rather than by each module using them.
+    shares_unions: bool,
+}
+
+impl Project {
+    /// Adds the top-level declarations of `module`, which is generated as the
+    /// Rust module at `path`.
+    pub fn add(&mut self, module: &Module, path: &str) {
+        for class in classes(module) {
+            self.classes.insert(class.name.clone(), class.clone());
+            self.modules.insert(class.name.clone(), path.to_string());
+        }
+        self.trait_interfaces.extend(trait_interfaces(module));
+        self.exports.extend(TypeEnv::collect_exports(module));
+        for item in &module.items {
+            let Item::Decl {
+                decl,
+                exported: true,
+            } = item
+            else {
+                continue;
+            };
+            let name = match decl {
+                Decl::Class(class) => &class.name,
+                Decl::Interface(iface) => &iface.name,
+                Decl::TypeAlias(alias) => &alias.name,
+                Decl::Enum(decl) => &decl.name,
+                Decl::Fn(_) | Decl::Var(_) => continue,
+            };
+            self.declarations.insert(name.clone(), path.to_string());
+        }
+        self.shares_unions = true;
+    }
+
+    /// The types of everything the modules export.
+    pub fn exports(&self) -> &TypeEnv {
+        &self.exports
+    }
+
+    /// The classes the modules declare.
+    pub fn classes(&self) -> impl Iterator<Item = &ClassDecl> {
+        self.classes.values()
+    }
+
+    /// Whether the `unions` module can declare `union`: every type it holds
+    /// is exported by a module of the project.
+    fn shares(&self, union: &UntaggedUnion) -> bool {
+        self.shares_unions
+            && union
+                .named_types()
+                .all(|name| self.declarations.contains_key(name))
+    }
+
+    /// Trait interfaces whose methods a class implementing them mutates
+    /// `this` in, whose trait objects are locked.
+    pub fn mutated_interfaces(&self) -> BTreeSet<String> {
+        self.trait_interfaces
+            .values()
+            .filter(|iface| iface.is_mutated_by(self.classes.values()))
+            .map(|iface| iface.name.clone())
+            .collect()
+    }
+}
+
+/// Interfaces of `module` that declare methods, which are emitted as traits.
+fn trait_interfaces(module: &Module) -> impl Iterator<Item = (String, InterfaceDecl)> + '_ {
+    module.items.iter().filter_map(|item| match item {
+        Item::Decl {
+            decl: Decl::Interface(iface),
+            ..
+        } if iface.has_methods() => Some((iface.name.clone(), iface.clone())),
+        _ => None,
+    })
+}
+
+/// Discriminated unions `module` can name, declared in it or exported by
+/// the rest of `project`, with their tag.
+fn tagged_unions(module: &Module, project: &Project) -> BTreeMap<String, String> {
+    let local = TypeEnv::collect(module).aliases;
+    project
+        .exports
+        .aliases
+        .iter()
+        .chain(&local)
+        .filter_map(|(name, (_, ty))| Some((name.clone(), tagged_union(ty)?.tag)))
+        .collect()
 }
 
-pub fn generate(program: &Program, is_index: bool) -> GeneratedCode {
-    let mut generator = RustGenerator::new(is_index);
-    program.visit_with(&mut generator);
+/// Untagged unions `module` can name through an alias, declared in it or
+/// exported by the rest of `project`.
+fn untagged_unions(module: &Module, project: &Project) -> BTreeMap<String, UntaggedUnion> {
+    let local = TypeEnv::collect(module).aliases;
+    project
+        .exports
+        .aliases
+        .iter()
+        .chain(&local)
+        .filter_map(|(name, (_, ty))| Some((name.clone(), untagged_union(ty)?)))
+        .collect()
+}
 
-    if !generator.main_body.is_empty() && is_index {
+/// The required fields of each class and interface `env` declares.
+fn required_fields(env: &TypeEnv) -> BTreeMap<String, usize> {
+    env.shapes
+        .keys()
+        .map(|name| (name.clone(), env.required_fields(name)))
+        .collect()
+}
+
+pub(crate) fn classes(module: &Module) -> impl Iterator<Item = &ClassDecl> {
+    module.items.iter().filter_map(|item| match item {
+        Item::Decl {
+            decl: Decl::Class(class),
+            ..
+        } => Some(class),
+        _ => None,
+    })
+}
+
+/// Lowers `program` and runs the analyzer passes codegen relies on, against
+/// the declarations of the rest of `project`; its `comments` carry
+/// `// tyrus-...` directives.
+pub fn analyze(program: &Program, comments: &SingleThreadedComments, project: &Project) -> Module {
+    let mut module = tyrus_ast::lower_program_with_comments(program, comments);
+    tyrus_analyzer::typeck::check_module_with(&mut module, &project.exports);
+    let imported: Vec<_> = project.classes.values().collect();
+    tyrus_analyzer::ownership::infer_ownership_with(&mut module, &imported);
+    tyrus_analyzer::mutability::infer_mutability(&mut module);
+    tyrus_analyzer::integers::infer_integers(&mut module);
+    module
+}
+
+/// Generates Rust code for `program`, a module on its own, naming its
+/// identifiers per `naming`.
+pub fn generate(
+    program: &Program,
+    comments: &SingleThreadedComments,
+    is_index: bool,
+    naming: Naming,
+) -> GeneratedCode {
+    let project = Project::default();
+    generate_module(
+        &analyze(program, comments, &project),
+        is_index,
+        naming,
+        &project,
+    )
+}
+
+/// Generates Rust code from an already lowered (and ideally type-checked)
+/// module of `project`, naming its identifiers per `naming`.
+pub fn generate_module(
+    module: &Module,
+    is_index: bool,
+    naming: Naming,
+    project: &Project,
+) -> GeneratedCode {
+    let mut known = project.classes.clone();
+    let mut class_modules = project.modules.clone();
+    for class in classes(module) {
+        class_modules.remove(&class.name);
+        known.insert(class.name.clone(), class.clone());
+    }
+    // Abstract classes keep their fields in a generated state struct
+    let states: Vec<_> = known
+        .values()
+        .filter_map(convert::abstract_class::state_decl)
+        .collect();
+    known.extend(states.into_iter().map(|state| (state.name.clone(), state)));
+    let mut interfaces = project.trait_interfaces.clone();
+    interfaces.extend(trait_interfaces(module));
+    let cx = Context::new(naming, known.values(), interfaces);
+    let mut generator = RustGenerator::new(is_index, cx);
+    generator.classes = known;
+    generator.class_modules = class_modules;
+    generator.cx.class_traits = generator.class_traits();
+    generator.cx.tagged_unions = tagged_unions(module, project);
+    generator.cx.untagged_unions = untagged_unions(module, project);
+    let mut env = project.exports.clone();
+    env.extend(TypeEnv::collect(module));
+    generator.cx.required_fields = required_fields(&env);
+    generator.process_foreign_ancestors(module);
+    for item in &module.items {
+        generator.process_item(item);
+    }
+    let (unions, local): (BTreeMap<_, _>, BTreeMap<_, _>) = generator
+        .cx
+        .unions
+        .take()
+        .into_iter()
+        .partition(|(_, union)| project.shares(union));
+    for (name, union) in local {
+        generator.process_untagged_union(&name, &union, quote::quote! { pub });
+    }
+    let uses: String = unions
+        .keys()
+        .map(|name| format!("use crate::unions::{};\n", name))
+        .collect();
+    generator.code.insert_str(0, &uses);
+
+    let entry = !generator.main_body.is_empty() && is_index;
+    if entry {
         generator.code.push_str("\npub fn main() {\n");
         generator.code.push_str(&generator.main_body);
         generator.code.push_str("}\n");
@@ -29,5 +261,40 @@ pub fn generate(program: &Program, is_index: bool) -> GeneratedCode {
     GeneratedCode {
         code: generator.code,
         controllers: generator.controllers,
+        unsupported: generator.cx.reports.take(),
+        entry,
+        unions,
+    }
+}
+
+/// Generates the `unions` module of `project`, declaring the synthesized
+/// `unions` its modules share.
+pub fn generate_unions(
+    unions: &BTreeMap<String, UntaggedUnion>,
+    naming: Naming,
+    project: &Project,
+) -> String {
+    let cx = Context::new(
+        naming,
+        project.classes.values(),
+        project.trait_interfaces.clone(),
+    );
+    let mut generator = RustGenerator::new(false, cx);
+    generator.cx.required_fields = required_fields(&project.exports);
+    let uses: BTreeSet<_> = unions
+        .values()
+        .flat_map(UntaggedUnion::named_types)
+        .filter_map(|name| {
+            Some(format!(
+                "use {}::{};\n",
+                project.declarations.get(name)?,
+                name
+            ))
+        })
+        .collect();
+    generator.code.extend(uses);
+    for (name, union) in unions {
+        generator.process_untagged_union(name, union, quote::quote! { pub });
     }
+    generator.code
 }
@@ -0,0 +1,346 @@
+//! Links the generated Rust back to the TypeScript it came from.
+//!
+//! Codegen emits a `__tyrus_span!(lo, hi);` marker before each item and
+//! statement. Markers survive formatting, and [`extract`] then removes them,
+//! recording where each one stood in the final code. The resulting
+//! [`SpanMap`] renders as `// ts: file:line` comments ([`SpanMap::annotate`])
+//! or as a source map v3 ([`SpanMap::to_source_map`]).
+
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use serde_json::json;
+use swc_common::{BytePos, Span};
+
+const MARKER: &str = "__tyrus_span";
+
+/// The marker for what follows it coming from `span`; empty for spans that
+/// point nowhere.
+pub fn marker(span: Span) -> TokenStream {
+    if span.is_dummy() {
+        return TokenStream::new();
+    }
+    let lo = Literal::u32_unsuffixed(span.lo.0);
+    let hi = Literal::u32_unsuffixed(span.hi.0);
+    quote! { __tyrus_span!(#lo, #hi); }
+}
+
+/// `tokens` preceded by the marker for `span`.
+pub fn marked(span: Span, tokens: TokenStream) -> TokenStream {
+    let marker = marker(span);
+    quote! { #marker #tokens }
+}
+
+/// Byte offsets into the generated code, each mapped to the source span of
+/// the item or statement starting there.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpanMap {
+    /// Sorted by `offset`.
+    pub entries: Vec<Mapping>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mapping {
+    pub offset: usize,
+    pub span: Span,
+}
+
+impl SpanMap {
+    /// The span of the innermost item or statement starting at or before
+    /// `offset`.
+    pub fn lookup(&self, offset: usize) -> Option<Span> {
+        let index = self.entries.partition_point(|m| m.offset <= offset);
+        index.checked_sub(1).map(|i| self.entries[i].span)
+    }
+
+    /// The mappings of `other`, whose code was appended at `base`.
+    pub fn extend(&mut self, base: usize, other: SpanMap) {
+        self.entries
+            .extend(other.entries.into_iter().map(|m| Mapping {
+                offset: base + m.offset,
+                ..m
+            }));
+    }
+
+    /// `code` with a `// ts: name:line` comment above each line that starts
+    /// an item or statement from a new line of `source`, named `name`, and
+    /// the map of the annotated code.
+    pub fn annotate(&self, code: &str, name: &str, source: &str) -> (String, SpanMap) {
+        let source_lines = Lines::new(source);
+        let mut annotated = String::with_capacity(code.len());
+        let mut map = SpanMap::default();
+        let mut copied = 0;
+        let mut last_line = None;
+        for mapping in &self.entries {
+            let line_start = code[..mapping.offset].rfind('\n').map_or(0, |i| i + 1);
+            let indent = &code[line_start..mapping.offset];
+            let line = source_lines.position(source_offset(mapping.span)).0 + 1;
+            if line_start >= copied && indent.trim().is_empty() && last_line != Some(line) {
+                annotated.push_str(&code[copied..line_start]);
+                annotated.push_str(&format!("{}// ts: {}:{}\n", indent, name, line));
+                copied = line_start;
+                last_line = Some(line);
+            }
+            map.entries.push(Mapping {
+                offset: annotated.len() + mapping.offset - copied,
+                span: mapping.span,
+            });
+        }
+        annotated.push_str(&code[copied..]);
+        (annotated, map)
+    }
+
+    /// A source map v3 from `code`, the Rust file `file`, to `source_text`,
+    /// the TypeScript at `source`.
+    pub fn to_source_map(&self, code: &str, file: &str, source: &str, source_text: &str) -> String {
+        let generated = Lines::new(code);
+        let original = Lines::new(source_text);
+        let mut mappings = String::new();
+        let mut line = 0;
+        // Every field but the generated column is relative to the previous segment
+        let mut previous = (0, 0, 0);
+        let mut first_on_line = true;
+        for mapping in &self.entries {
+            let (gen_line, gen_col) = generated.position(mapping.offset);
+            let (src_line, src_col) = original.position(source_offset(mapping.span));
+            while line < gen_line {
+                mappings.push(';');
+                line += 1;
+                previous.0 = 0;
+                first_on_line = true;
+            }
+            if !first_on_line {
+                mappings.push(',');
+            }
+            first_on_line = false;
+            vlq(&mut mappings, gen_col as i64 - previous.0 as i64);
+            vlq(&mut mappings, 0);
+            vlq(&mut mappings, src_line as i64 - previous.1 as i64);
+            vlq(&mut mappings, src_col as i64 - previous.2 as i64);
+            previous = (gen_col, src_line, src_col);
+        }
+        json!({
+            "version": 3,
+            "file": file,
+            "sources": [source],
+            "sourcesContent": [source_text],
+            "names": [],
+            "mappings": mappings,
+        })
+        .to_string()
+    }
+}
+
+/// The 0-based offset of `span` in its source file.
+pub fn source_offset(span: Span) -> usize {
+    span.lo.0 as usize - 1
+}
+
+/// The line starts of a text, to turn offsets into positions.
+struct Lines<'a> {
+    text: &'a str,
+    starts: Vec<usize>,
+}
+
+impl<'a> Lines<'a> {
+    fn new(text: &'a str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Lines { text, starts }
+    }
+
+    /// The 0-based line and UTF-16 column of `offset`, as source maps count
+    /// them.
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.text.len());
+        let line = self.starts.partition_point(|&start| start <= offset) - 1;
+        let column = self.text[self.starts[line]..offset].encode_utf16().count();
+        (line, column)
+    }
+}
+
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Appends `value` as a base64 VLQ.
+fn vlq(out: &mut String, value: i64) {
+    let mut rest = if value < 0 {
+        (-value << 1) | 1
+    } else {
+        value << 1
+    };
+    loop {
+        let mut digit = rest & 31;
+        rest >>= 5;
+        if rest > 0 {
+            digit |= 32;
+        }
+        out.push(BASE64[digit as usize] as char);
+        if rest == 0 {
+            break;
+        }
+    }
+}
+
+/// `code` without its markers, and where they stood.
+pub fn extract(code: &str) -> (String, SpanMap) {
+    let mut cleaned = String::with_capacity(code.len());
+    let mut map = SpanMap::default();
+    let mut rest = code;
+    while let Some(start) = rest.find(MARKER) {
+        let Some((span, len)) = parse_marker(&rest[start..]) else {
+            cleaned.push_str(&rest[..start + MARKER.len()]);
+            rest = &rest[start + MARKER.len()..];
+            continue;
+        };
+        let before = &rest[..start];
+        let after = &rest[start + len..];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        if before[line_start..].trim().is_empty() && after.trim_start_matches(' ').starts_with('\n')
+        {
+            // Alone on its line: drop the line
+            cleaned.push_str(&before[..line_start]);
+            let newline = after.find('\n').unwrap_or(0);
+            rest = &after[newline + 1..];
+            let indent = rest.len() - rest.trim_start_matches(' ').len();
+            map.entries.push(Mapping {
+                offset: cleaned.len() + indent,
+                span,
+            });
+        } else {
+            cleaned.push_str(before);
+            rest = after.trim_start();
+            map.entries.push(Mapping {
+                offset: cleaned.len(),
+                span,
+            });
+        }
+    }
+    cleaned.push_str(rest);
+    (cleaned, map)
+}
+
+/// The span of the marker `text` starts with, and its length through the `;`.
+fn parse_marker(text: &str) -> Option<(Span, usize)> {
+    let end = text.find(';')?;
+    let args = text[MARKER.len()..end]
+        .trim()
+        .strip_prefix('!')?
+        .trim()
+        .strip_prefix('(')?
+        .strip_suffix(')')?;
+    let (lo, hi) = args.split_once(',')?;
+    let lo = lo.trim().parse().ok()?;
+    let hi = hi.trim().parse().ok()?;
+    Some((Span::new(BytePos(lo), BytePos(hi)), end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(lo: u32, hi: u32) -> Span {
+        Span::new(BytePos(lo), BytePos(hi))
+    }
+
+    #[test]
+    fn test_extract_formatted() {
+        let code = "fn main() {\n    __tyrus_span!(1, 10);\n    let x = 1;\n    __tyrus_span!(11, 20);\n    f(x);\n}\n";
+        let (cleaned, map) = extract(code);
+        assert_eq!(cleaned, "fn main() {\n    let x = 1;\n    f(x);\n}\n");
+        assert_eq!(
+            map.entries,
+            vec![
+                Mapping {
+                    offset: 16,
+                    span: span(1, 10)
+                },
+                Mapping {
+                    offset: 31,
+                    span: span(11, 20)
+                },
+            ]
+        );
+        assert_eq!(map.lookup(3), None);
+        assert_eq!(map.lookup(20), Some(span(1, 10)));
+        assert_eq!(map.lookup(31), Some(span(11, 20)));
+    }
+
+    #[test]
+    fn test_extract_unformatted() {
+        let stmt = marked(span(1, 10), quote! { let x = 1; });
+        let code = quote! { fn main() { #stmt } }.to_string();
+        let (cleaned, map) = extract(&code);
+        assert_eq!(cleaned, "fn main () { let x = 1 ; }");
+        assert_eq!(map.lookup(cleaned.find("let").unwrap()), Some(span(1, 10)));
+    }
+
+    #[test]
+    fn test_annotate() {
+        let source = "const a = 1;\nfunction f() {\n  return a;\n}\n";
+        let code = "let a = 1.0;\npub fn f() -> f64 {\n    return a;\n}\n";
+        let map = SpanMap {
+            entries: vec![
+                Mapping {
+                    offset: 0,
+                    span: span(1, 13),
+                },
+                Mapping {
+                    offset: 13,
+                    span: span(14, 43),
+                },
+                Mapping {
+                    offset: 37,
+                    span: span(31, 40),
+                },
+            ],
+        };
+        let (annotated, annotated_map) = map.annotate(code, "src/a.ts", source);
+        assert_eq!(
+            annotated,
+            "// ts: src/a.ts:1\nlet a = 1.0;\n// ts: src/a.ts:2\npub fn f() -> f64 {\n    // ts: src/a.ts:3\n    return a;\n}\n"
+        );
+        for mapping in &annotated_map.entries {
+            assert!(!annotated[mapping.offset..].starts_with("//"));
+        }
+        assert_eq!(&annotated[annotated_map.entries[2].offset..][..6], "return");
+    }
+
+    #[test]
+    fn test_source_map() {
+        let source = "const a = 1;\nfunction f() {\n  return a;\n}\n";
+        let code = "let a = 1.0;\npub fn f() -> f64 {\n    return a;\n}\n";
+        let map = SpanMap {
+            entries: vec![
+                Mapping {
+                    offset: 13,
+                    span: span(14, 43),
+                },
+                Mapping {
+                    offset: 37,
+                    span: span(31, 40),
+                },
+            ],
+        };
+        let source_map: serde_json::Value =
+            serde_json::from_str(&map.to_source_map(code, "a.rs", "a.ts", source)).unwrap();
+        assert_eq!(source_map["version"], 3);
+        assert_eq!(source_map["sources"], json!(["a.ts"]));
+        // Line 2 column 0 to line 2 column 0; line 3 column 4 to line 3 column 2
+        assert_eq!(source_map["mappings"], ";AACA;IACE");
+    }
+
+    #[test]
+    fn test_vlq() {
+        let mut out = String::new();
+        for value in [0, 1, -1, 15, 16, -17, 1000] {
+            vlq(&mut out, value);
+            out.push(',');
+        }
+        assert_eq!(out, "A,C,D,e,gB,jB,w+B,");
+    }
+
+    #[test]
+    fn test_dummy_span() {
+        assert!(marker(Span::default()).is_empty());
+    }
+}
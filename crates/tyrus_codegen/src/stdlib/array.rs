@@ -1,57 +1,86 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use swc_ecma_ast::*;
+use tyrus_ast::{Arg, Expr, ExprKind, NumRepr, Type, Usage};
 
-use super::super::convert::func::{convert_expr, convert_expr_or_spread};
+use super::super::convert::context::Context;
+use super::super::convert::func::{convert_arg, convert_expr};
 
 /// Handle array method calls
-pub fn handle_method(obj: &Expr, method: &str, args: &[ExprOrSpread]) -> Option<TokenStream> {
-    let obj_tokens = convert_expr(obj);
+pub fn handle_method(cx: &Context, obj: &Expr, method: &str, args: &[Arg]) -> Option<TokenStream> {
+    let obj_tokens = convert_expr(cx, obj);
 
     match method {
         "push" => {
             if args.len() == 1 {
-                let arg = convert_expr_or_spread(&args[0]);
+                let arg = convert_arg(cx, &args[0]);
                 Some(quote! { #obj_tokens.push(#arg) })
             } else {
                 None
             }
         }
-        "map" => {
+        "map" | "filter" => {
+            let iter = lazy(cx, obj, method, args)?;
+            Some(quote! { #iter.collect::<Vec<_>>() })
+        }
+        "join" => {
             if args.len() == 1 {
-                let callback = convert_expr_or_spread(&args[0]);
-                let param_count = if let Expr::Arrow(arrow) = &*args[0].expr {
-                    arrow.params.len()
+                let separator = convert_arg(cx, &args[0]);
+                Some(quote! { #obj_tokens.join(&#separator) })
+            } else {
+                None
+            }
+        }
+        "includes" => {
+            if args.len() == 1 {
+                let value = convert_arg(cx, &args[0]);
+                Some(quote! { #obj_tokens.contains(&#value) })
+            } else {
+                None
+            }
+        }
+        "indexOf" => {
+            if args.len() == 1 {
+                let value = convert_arg(cx, &args[0]);
+                Some(quote! {
+                    #obj_tokens.iter().position(|x| *x == #value).map(|i| i as f64).unwrap_or(-1.0)
+                })
+            } else {
+                None
+            }
+        }
+        "some" | "every" => {
+            if args.len() == 1 {
+                let callback = convert_arg(cx, &args[0]);
+                let method = if method == "some" {
+                    quote! { any }
                 } else {
-                    1
+                    quote! { all }
                 };
-
-                if param_count > 1 {
-                    Some(quote! {
-                        #obj_tokens.iter().cloned().enumerate().map(|(idx, val)| (#callback)(val, idx as f64)).collect::<Vec<_>>()
-                    })
-                } else {
-                    Some(quote! { #obj_tokens.iter().cloned().map(#callback).collect::<Vec<_>>() })
-                }
+                Some(quote! { #obj_tokens.iter().#method(|x| (#callback)(x.clone())) })
             } else {
                 None
             }
         }
-        "filter" => {
+        "find" => {
             if args.len() == 1 {
-                let callback = convert_expr_or_spread(&args[0]);
-                // Use filter on borrowed iter, then collect cloned values
-                Some(
-                    quote! { #obj_tokens.iter().filter(|x| (#callback)((*x).clone())).cloned().collect::<Vec<_>>() },
-                )
+                let callback = convert_arg(cx, &args[0]);
+                Some(quote! { #obj_tokens.iter().find(|x| (#callback)((*x).clone())).cloned() })
             } else {
                 None
             }
         }
-        "join" => {
+        "forEach" => {
             if args.len() == 1 {
-                let separator = convert_expr_or_spread(&args[0]);
-                Some(quote! { #obj_tokens.join(&#separator) })
+                let callback = convert_arg(cx, &args[0]);
+                let values = values(cx, obj);
+                Some(quote! { #values.for_each(#callback) })
+            } else {
+                None
+            }
+        }
+        "pop" => {
+            if args.is_empty() {
+                Some(quote! { #obj_tokens.pop() })
             } else {
                 None
             }
@@ -59,3 +88,78 @@ pub fn handle_method(obj: &Expr, method: &str, args: &[ExprOrSpread]) -> Option<
         _ => None,
     }
 }
+
+/// The elements of `obj`, owned.
+fn values(cx: &Context, obj: &Expr) -> TokenStream {
+    if let Some(iter) = chained(cx, obj) {
+        return iter;
+    }
+    let obj_tokens = convert_expr(cx, obj);
+    // Elements of an array that is not used again are moved, not cloned
+    if obj.usage == Usage::Move {
+        quote! { #obj_tokens.into_iter() }
+    } else {
+        quote! { #obj_tokens.iter().cloned() }
+    }
+}
+
+/// The iterator of a `map` or `filter` call that `obj` is, which the next
+/// call chains onto rather than collecting it into a temporary.
+fn chained(cx: &Context, obj: &Expr) -> Option<TokenStream> {
+    let ExprKind::Call(call) = &obj.kind else {
+        return None;
+    };
+    let member = call.callee.as_member()?;
+    if !matches!(member.obj.ty, Type::Array(_) | Type::Unknown | Type::Any) {
+        return None;
+    }
+    lazy(cx, &member.obj, member.prop.as_ident()?, &call.args)
+}
+
+/// `obj.map(..)` or `obj.filter(..)` as an iterator, before it is collected.
+fn lazy(cx: &Context, obj: &Expr, method: &str, args: &[Arg]) -> Option<TokenStream> {
+    let [arg] = args else {
+        return None;
+    };
+    let callback = convert_arg(cx, arg);
+    match method {
+        "map" => {
+            let values = values(cx, obj);
+            let param_count = if let ExprKind::Arrow(arrow) = &arg.expr.kind {
+                arrow.params.len()
+            } else {
+                1
+            };
+
+            if param_count > 1 {
+                Some(quote! {
+                    #values.enumerate().map(|(idx, val)| (#callback)(val, idx as f64))
+                })
+            } else {
+                Some(quote! { #values.map(#callback) })
+            }
+        }
+        "filter" => match chained(cx, obj) {
+            Some(values) => Some(quote! { #values.filter(|x| (#callback)(x.clone())) }),
+            None if obj.usage == Usage::Move => {
+                let obj_tokens = convert_expr(cx, obj);
+                Some(quote! { #obj_tokens.into_iter().filter(|x| (#callback)(x.clone())) })
+            }
+            // Borrowed elements are only cloned once they pass
+            None => {
+                let obj_tokens = convert_expr(cx, obj);
+                Some(quote! { #obj_tokens.iter().filter(|x| (#callback)((*x).clone())).cloned() })
+            }
+        },
+        _ => None,
+    }
+}
+
+/// `arr.length` (also used for `map.size` and `set.size`)
+pub fn length(cx: &Context, obj: &Expr, num: NumRepr) -> TokenStream {
+    let obj_tokens = convert_expr(cx, obj);
+    match num {
+        NumRepr::Usize => quote! { #obj_tokens.len() },
+        _ => quote! { (#obj_tokens.len() as f64) },
+    }
+}
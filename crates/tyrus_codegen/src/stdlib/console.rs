@@ -1,19 +1,20 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use swc_ecma_ast::*;
+use tyrus_ast::Arg;
 
-use super::super::convert::func::convert_expr_or_spread;
+use super::super::convert::context::Context;
+use super::super::convert::func::convert_arg;
 
 /// Handle console.* calls
-pub fn handle(method: &str, args: &[ExprOrSpread]) -> Option<TokenStream> {
+pub fn handle(cx: &Context, method: &str, args: &[Arg]) -> Option<TokenStream> {
     match method {
         "log" => {
-            let args_tokens: Vec<_> = args.iter().map(convert_expr_or_spread).collect();
+            let args_tokens: Vec<_> = args.iter().map(|arg| convert_arg(cx, arg)).collect();
             let fmt_str = "{} ".repeat(args.len()).trim_end().to_string();
             Some(quote! { println!(#fmt_str, #(#args_tokens),*) })
         }
         "error" => {
-            let args_tokens: Vec<_> = args.iter().map(convert_expr_or_spread).collect();
+            let args_tokens: Vec<_> = args.iter().map(|arg| convert_arg(cx, arg)).collect();
             let fmt_str = "{} ".repeat(args.len()).trim_end().to_string();
             Some(quote! { eprintln!(#fmt_str, #(#args_tokens),*) })
         }
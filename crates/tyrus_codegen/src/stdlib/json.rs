@@ -1,15 +1,16 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use swc_ecma_ast::*;
+use tyrus_ast::Arg;
 
-use super::super::convert::func::convert_expr_or_spread;
+use super::super::convert::context::Context;
+use super::super::convert::func::convert_arg;
 
 /// Handle JSON.* calls
-pub fn handle(method: &str, args: &[ExprOrSpread]) -> Option<TokenStream> {
+pub fn handle(cx: &Context, method: &str, args: &[Arg]) -> Option<TokenStream> {
     match method {
         "stringify" => {
             if args.len() == 1 {
-                let obj = convert_expr_or_spread(&args[0]);
+                let obj = convert_arg(cx, &args[0]);
                 Some(quote! { serde_json::to_string(&#obj).unwrap() })
             } else {
                 None
@@ -17,7 +18,7 @@ pub fn handle(method: &str, args: &[ExprOrSpread]) -> Option<TokenStream> {
         }
         "parse" => {
             if args.len() == 1 {
-                let json_str = convert_expr_or_spread(&args[0]);
+                let json_str = convert_arg(cx, &args[0]);
                 Some(quote! { serde_json::from_str(#json_str).unwrap() })
             } else {
                 None
@@ -0,0 +1,35 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use tyrus_ast::{Arg, Expr};
+
+use super::super::convert::context::Context;
+use super::super::convert::func::{convert_arg, convert_expr};
+
+/// Handle Map method calls (`Map` is emitted as `HashMap`)
+pub fn handle_method(cx: &Context, obj: &Expr, method: &str, args: &[Arg]) -> Option<TokenStream> {
+    let obj_tokens = convert_expr(cx, obj);
+
+    match (method, args) {
+        ("get", [key]) => {
+            let key = convert_arg(cx, key);
+            Some(quote! { #obj_tokens.get(&#key).cloned() })
+        }
+        ("set", [key, value]) => {
+            let key = convert_arg(cx, key);
+            let value = convert_arg(cx, value);
+            Some(quote! { #obj_tokens.insert(#key, #value) })
+        }
+        ("has", [key]) => {
+            let key = convert_arg(cx, key);
+            Some(quote! { #obj_tokens.contains_key(&#key) })
+        }
+        ("delete", [key]) => {
+            let key = convert_arg(cx, key);
+            Some(quote! { #obj_tokens.remove(&#key).is_some() })
+        }
+        ("clear", []) => Some(quote! { #obj_tokens.clear() }),
+        ("keys", []) => Some(quote! { #obj_tokens.keys().cloned().collect::<Vec<_>>() }),
+        ("values", []) => Some(quote! { #obj_tokens.values().cloned().collect::<Vec<_>>() }),
+        _ => None,
+    }
+}
@@ -1,31 +1,32 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use swc_ecma_ast::*;
+use tyrus_ast::Arg;
 
-use super::super::convert::func::convert_expr_or_spread;
+use super::super::convert::context::Context;
+use super::super::convert::func::convert_arg;
 
 /// Handle Math.* calls
-pub fn handle(method: &str, args: &[ExprOrSpread]) -> Option<TokenStream> {
+pub fn handle(cx: &Context, method: &str, args: &[Arg]) -> Option<TokenStream> {
     match method {
         "max" => {
-            if args.len() == 1 && args[0].spread.is_some() {
+            if args.len() == 1 && args[0].spread {
                 // Math.max(...arr)
-                let arg = convert_expr_or_spread(&args[0]);
+                let arg = convert_arg(cx, &args[0]);
                 Some(quote! {
                     #arg.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b))
                 })
             } else if args.len() == 2 {
-                if args[0].spread.is_some() {
+                if args[0].spread {
                     // Math.max(...arr, val)
-                    let arr = convert_expr_or_spread(&args[0]);
-                    let val = convert_expr_or_spread(&args[1]);
+                    let arr = convert_arg(cx, &args[0]);
+                    let val = convert_arg(cx, &args[1]);
                     Some(quote! {
                         #arr.iter().fold(#val, |a, &b| a.max(b))
                     })
                 } else {
                     // Math.max(a, b)
-                    let a = convert_expr_or_spread(&args[0]);
-                    let b = convert_expr_or_spread(&args[1]);
+                    let a = convert_arg(cx, &args[0]);
+                    let b = convert_arg(cx, &args[1]);
                     Some(quote! { #a.max(#b) })
                 }
             } else {
@@ -33,15 +34,15 @@ pub fn handle(method: &str, args: &[ExprOrSpread]) -> Option<TokenStream> {
             }
         }
         "min" => {
-            if args.len() == 1 && args[0].spread.is_some() {
+            if args.len() == 1 && args[0].spread {
                 // Math.min(...arr) -> arr.iter().fold(f64::INFINITY, |a, &b| a.min(b))
-                let arg = convert_expr_or_spread(&args[0]);
+                let arg = convert_arg(cx, &args[0]);
                 Some(quote! {
                     #arg.iter().fold(f64::INFINITY, |a, &b| a.min(b))
                 })
             } else if args.len() == 2 {
-                let a = convert_expr_or_spread(&args[0]);
-                let b = convert_expr_or_spread(&args[1]);
+                let a = convert_arg(cx, &args[0]);
+                let b = convert_arg(cx, &args[1]);
                 Some(quote! { #a.min(#b) })
             } else {
                 None
@@ -49,7 +50,7 @@ pub fn handle(method: &str, args: &[ExprOrSpread]) -> Option<TokenStream> {
         }
         "round" => {
             if args.len() == 1 {
-                let x = convert_expr_or_spread(&args[0]);
+                let x = convert_arg(cx, &args[0]);
                 Some(quote! { (#x).round() })
             } else {
                 None
@@ -57,7 +58,7 @@ pub fn handle(method: &str, args: &[ExprOrSpread]) -> Option<TokenStream> {
         }
         "floor" => {
             if args.len() == 1 {
-                let x = convert_expr_or_spread(&args[0]);
+                let x = convert_arg(cx, &args[0]);
                 Some(quote! { (#x).floor() })
             } else {
                 None
@@ -65,7 +66,7 @@ pub fn handle(method: &str, args: &[ExprOrSpread]) -> Option<TokenStream> {
         }
         "ceil" => {
             if args.len() == 1 {
-                let x = convert_expr_or_spread(&args[0]);
+                let x = convert_arg(cx, &args[0]);
                 Some(quote! { (#x).ceil() })
             } else {
                 None
@@ -73,7 +74,7 @@ pub fn handle(method: &str, args: &[ExprOrSpread]) -> Option<TokenStream> {
         }
         "abs" => {
             if args.len() == 1 {
-                let x = convert_expr_or_spread(&args[0]);
+                let x = convert_arg(cx, &args[0]);
                 Some(quote! { (#x).abs() })
             } else {
                 None
@@ -1,56 +1,89 @@
 use proc_macro2::TokenStream;
-use swc_ecma_ast::*;
+use tyrus_ast::{Arg, Callee, Expr, NumRepr, Type};
+
+use crate::convert::context::Context;
 
 pub mod array;
 pub mod console;
 pub mod json;
+pub mod map;
 pub mod math;
+pub mod number;
+pub mod set;
 pub mod string;
 
 /// Main dispatcher for stdlib method calls
-pub fn try_handle_stdlib_call(callee: &Callee, args: &[ExprOrSpread]) -> Option<TokenStream> {
-    // Try to handle as stdlib call
-    if let Callee::Expr(expr) = callee {
-        if let Expr::Member(member) = &**expr {
-            if let Expr::Ident(obj) = &*member.obj {
-                let obj_name = obj.sym.as_ref();
-
-                if obj_name == "Math" {
-                    if let Some(method_ident) = member.prop.as_ident() {
-                        return math::handle(&method_ident.sym, args);
-                    }
-                } else if obj_name == "JSON" {
-                    if let Some(method_ident) = member.prop.as_ident() {
-                        return json::handle(&method_ident.sym, args);
-                    }
-                } else if obj_name == "console" {
-                    if let Some(method_ident) = member.prop.as_ident() {
-                        return console::handle(&method_ident.sym, args);
-                    }
-                }
-            }
-        }
+pub fn try_handle_stdlib_call(cx: &Context, callee: &Callee, args: &[Arg]) -> Option<TokenStream> {
+    // Global functions: parseInt(s) -> Number.parseInt(s)
+    if let Some(name) = callee.as_expr().and_then(Expr::as_ident) {
+        return number::handle(cx, name, args);
+    }
+    let (obj_name, method) = callee.as_static_call()?;
+    match obj_name {
+        "Number" => number::handle(cx, method, args),
+        "Math" => math::handle(cx, method, args),
+        "JSON" => json::handle(cx, method, args),
+        "console" => console::handle(cx, method, args),
+        _ => None,
     }
-
-    None
 }
 
 /// Try to handle method call on an expression (e.g., str.includes())
+///
+/// The shim is picked from the receiver's inferred type. Receivers of
+/// user-defined types are never intercepted, so a class's own `map` or
+/// `filter` is called as written.
 pub fn try_handle_method_call(
+    cx: &Context,
     obj: &Expr,
     method: &str,
-    args: &[ExprOrSpread],
+    args: &[Arg],
+) -> Option<TokenStream> {
+    let receiver = match &obj.ty {
+        Type::Optional(inner) => inner,
+        ty => ty,
+    };
+
+    match receiver {
+        Type::Array(_) => array::handle_method(cx, obj, method, args),
+        Type::String => string::handle_method(cx, obj, method, args),
+        Type::Number | Type::Bool | Type::Date if method == "toString" => {
+            string::handle_method(cx, obj, method, args)
+        }
+        Type::Named { name, .. } if name == "Map" => map::handle_method(cx, obj, method, args),
+        Type::Named { name, .. } if name == "Set" => set::handle_method(cx, obj, method, args),
+        Type::Unknown | Type::Any => handle_by_name(cx, obj, method, args),
+        _ => None,
+    }
+}
+
+/// Property reads on stdlib types, e.g. `arr.length` or `map.size`, whose
+/// value is represented as `num`.
+pub fn try_handle_member(
+    cx: &Context,
+    obj: &Expr,
+    prop: &str,
+    num: NumRepr,
 ) -> Option<TokenStream> {
-    // For now, we'll handle this based on method name
-    // In the future, we could use type inference
+    match (&obj.ty, prop) {
+        (Type::Array(_), "length") => Some(array::length(cx, obj, num)),
+        (Type::String, "length") => Some(string::length(cx, obj, num)),
+        (Type::Named { name, .. }, "size") if name == "Map" || name == "Set" => {
+            Some(array::length(cx, obj, num))
+        }
+        _ => None,
+    }
+}
 
+/// Fallback for receivers whose type could not be inferred: guess from the method name.
+fn handle_by_name(cx: &Context, obj: &Expr, method: &str, args: &[Arg]) -> Option<TokenStream> {
     match method {
         // String methods
         "includes" | "replace" | "split" | "toUpperCase" | "toLowerCase" | "trim" | "toString" => {
-            string::handle_method(obj, method, args)
+            string::handle_method(cx, obj, method, args)
         }
         // Array methods
-        "push" | "map" | "filter" | "join" => array::handle_method(obj, method, args),
+        "push" | "map" | "filter" | "join" => array::handle_method(cx, obj, method, args),
         _ => None,
     }
 }
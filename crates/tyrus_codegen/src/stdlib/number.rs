@@ -0,0 +1,23 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use tyrus_ast::Arg;
+
+use super::super::convert::context::Context;
+use super::super::convert::func::convert_arg;
+
+/// Handle Number.* calls, and the global functions of the same name
+pub fn handle(cx: &Context, method: &str, args: &[Arg]) -> Option<TokenStream> {
+    match method {
+        // Number.parseInt(s) -> s.trim().parse::<f64>().map(f64::trunc)
+        // The radix argument is ignored, and trailing garbage gives NaN
+        "parseInt" => {
+            let s = convert_arg(cx, args.first()?);
+            Some(quote! { #s.trim().parse::<f64>().map(f64::trunc).unwrap_or(f64::NAN) })
+        }
+        "parseFloat" => {
+            let s = convert_arg(cx, args.first()?);
+            Some(quote! { #s.trim().parse::<f64>().unwrap_or(f64::NAN) })
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,29 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use tyrus_ast::{Arg, Expr};
+
+use super::super::convert::context::Context;
+use super::super::convert::func::{convert_arg, convert_expr};
+
+/// Handle Set method calls (`Set` is emitted as `HashSet`)
+pub fn handle_method(cx: &Context, obj: &Expr, method: &str, args: &[Arg]) -> Option<TokenStream> {
+    let obj_tokens = convert_expr(cx, obj);
+
+    match (method, args) {
+        ("add", [value]) => {
+            let value = convert_arg(cx, value);
+            Some(quote! { #obj_tokens.insert(#value) })
+        }
+        ("has", [value]) => {
+            let value = convert_arg(cx, value);
+            Some(quote! { #obj_tokens.contains(&#value) })
+        }
+        ("delete", [value]) => {
+            let value = convert_arg(cx, value);
+            Some(quote! { #obj_tokens.remove(&#value) })
+        }
+        ("clear", []) => Some(quote! { #obj_tokens.clear() }),
+        ("values" | "keys", []) => Some(quote! { #obj_tokens.iter().cloned().collect::<Vec<_>>() }),
+        _ => None,
+    }
+}
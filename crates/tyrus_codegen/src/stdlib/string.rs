@@ -1,17 +1,18 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use swc_ecma_ast::*;
+use tyrus_ast::{Arg, Expr, NumRepr};
 
-use super::super::convert::func::{convert_expr, convert_expr_or_spread};
+use super::super::convert::context::Context;
+use super::super::convert::func::{convert_arg, convert_expr};
 
 /// Handle string method calls
-pub fn handle_method(obj: &Expr, method: &str, args: &[ExprOrSpread]) -> Option<TokenStream> {
-    let obj_tokens = convert_expr(obj);
+pub fn handle_method(cx: &Context, obj: &Expr, method: &str, args: &[Arg]) -> Option<TokenStream> {
+    let obj_tokens = convert_expr(cx, obj);
 
     match method {
         "includes" => {
             if args.len() == 1 {
-                let arg = convert_expr_or_spread(&args[0]);
+                let arg = convert_arg(cx, &args[0]);
                 Some(quote! { #obj_tokens.contains(&#arg) })
             } else {
                 None
@@ -19,8 +20,8 @@ pub fn handle_method(obj: &Expr, method: &str, args: &[ExprOrSpread]) -> Option<
         }
         "replace" => {
             if args.len() == 2 {
-                let pattern = convert_expr_or_spread(&args[0]);
-                let replacement = convert_expr_or_spread(&args[1]);
+                let pattern = convert_arg(cx, &args[0]);
+                let replacement = convert_arg(cx, &args[1]);
                 Some(quote! { #obj_tokens.replace(&#pattern, &#replacement) })
             } else {
                 None
@@ -28,7 +29,7 @@ pub fn handle_method(obj: &Expr, method: &str, args: &[ExprOrSpread]) -> Option<
         }
         "split" => {
             if args.len() == 1 {
-                let delimiter = convert_expr_or_spread(&args[0]);
+                let delimiter = convert_arg(cx, &args[0]);
                 Some(quote! { #obj_tokens.split(&#delimiter).collect::<Vec<_>>() })
             } else {
                 None
@@ -55,6 +56,22 @@ pub fn handle_method(obj: &Expr, method: &str, args: &[ExprOrSpread]) -> Option<
                 None
             }
         }
+        "startsWith" => {
+            if args.len() == 1 {
+                let prefix = convert_arg(cx, &args[0]);
+                Some(quote! { #obj_tokens.starts_with(&#prefix) })
+            } else {
+                None
+            }
+        }
+        "endsWith" => {
+            if args.len() == 1 {
+                let suffix = convert_arg(cx, &args[0]);
+                Some(quote! { #obj_tokens.ends_with(&#suffix) })
+            } else {
+                None
+            }
+        }
         "toString" => {
             if args.is_empty() {
                 Some(quote! { #obj_tokens.to_string() })
@@ -65,3 +82,12 @@ pub fn handle_method(obj: &Expr, method: &str, args: &[ExprOrSpread]) -> Option<
         _ => None,
     }
 }
+
+/// `str.length`, counted in characters rather than bytes
+pub fn length(cx: &Context, obj: &Expr, num: NumRepr) -> TokenStream {
+    let obj_tokens = convert_expr(cx, obj);
+    match num {
+        NumRepr::Usize => quote! { #obj_tokens.chars().count() },
+        _ => quote! { (#obj_tokens.chars().count() as f64) },
+    }
+}
@@ -1,7 +0,0 @@
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Config {
-    pub input_path: String,
-    pub output_path: String,
-}
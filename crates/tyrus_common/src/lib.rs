@@ -1,3 +1,2 @@
-pub mod config;
 pub mod fs;
 pub mod util;
@@ -2,6 +2,10 @@
 use miette::{Diagnostic, NamedSource, SourceSpan};
 use thiserror::Error;
 
+pub mod report;
+
+pub use report::MessageFormat;
+
 #[derive(Error, Diagnostic, Debug)]
 pub enum TyrusError {
     #[error("IO Error: {0}")]
@@ -45,7 +49,134 @@ pub enum TyrusError {
         span: SourceSpan,
     },
 
-    #[error("Unsupported Feature: {feature} is not yet supported in Tyrus.")]
+    #[error("Lint Error: Loose equality coerces its operands. Rust compares them as they are.")]
+    #[diagnostic(code(tyrus::lint::no_loose_equality))]
+    UseOfLooseEquality {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("replace with '===' or '!=='")]
+        span: SourceSpan,
+    },
+
+    #[error("Lint Error: The 'arguments' object has no equivalent in Rust.")]
+    #[diagnostic(
+        code(tyrus::lint::no_arguments),
+        help("name the parameters, or collect them with a rest parameter (`...args: T[]`)")
+    )]
+    UseOfArguments {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("'arguments' used here")]
+        span: SourceSpan,
+    },
+
+    #[error("Lint Error: 'with' statements resolve names at runtime.")]
+    #[diagnostic(
+        code(tyrus::lint::no_with),
+        help("refer to the properties of the object explicitly")
+    )]
+    UseOfWith {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("remove 'with'")]
+        span: SourceSpan,
+    },
+
+    #[error("Lint Error: 'delete' removes a field, which Rust structs cannot do.")]
+    #[diagnostic(
+        code(tyrus::lint::no_delete),
+        help("make the property optional and set it to `undefined`, or use a `Map`")
+    )]
+    UseOfDelete {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("property deleted here")]
+        span: SourceSpan,
+    },
+
+    #[error("Lint Error: Mutating a prototype changes a type at runtime.")]
+    #[diagnostic(
+        code(tyrus::lint::no_prototype_mutation),
+        help("declare the method on the class, or write a function that takes the instance")
+    )]
+    PrototypeMutation {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("prototype mutated here")]
+        span: SourceSpan,
+    },
+
+    #[error("Lint Error: '{ty}' is a struct in Rust and has no computed keys.")]
+    #[diagnostic(
+        code(tyrus::lint::no_dynamic_access),
+        help("access the fields by name, or keep keyed data in a `Record` or `Map`")
+    )]
+    DynamicPropertyAccess {
+        ty: String,
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("the key is not a literal")]
+        span: SourceSpan,
+    },
+
+    #[error("Lint Error: The 'Function' constructor compiles code at runtime.")]
+    #[diagnostic(
+        code(tyrus::lint::no_function_constructor),
+        help("write an arrow function instead")
+    )]
+    FunctionConstructor {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("'Function' constructed here")]
+        span: SourceSpan,
+    },
+
+    #[error("Lint Error: 'this' inside a 'function' depends on how the function is called.")]
+    #[diagnostic(
+        code(tyrus::lint::no_function_this),
+        help("use an arrow function, which keeps the 'this' around it, or a class method")
+    )]
+    ThisInFunction {
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("'this' used here")]
+        span: SourceSpan,
+    },
+
+    #[error("Lint Error: Loose equality between a {left} and a {right} converts one of them.")]
+    #[diagnostic(
+        code(tyrus::lint::no_mixed_equality),
+        help("convert one side explicitly, e.g. `String(n) === s` or `Number(s) === n`")
+    )]
+    MixedEquality {
+        left: &'static str,
+        right: &'static str,
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("compares a {left} with a {right}")]
+        span: SourceSpan,
+    },
+
+    #[error("Check Failed: {errors} error(s) in {files} file(s)")]
+    #[diagnostic(code(tyrus::check_failed))]
+    CheckFailed { errors: usize, files: usize },
+
+    #[error("Lint Errors: {} Oxidizable violation(s) found", errors.len())]
+    #[diagnostic(
+        code(tyrus::lint_errors),
+        help("fix them, or pass --no-lint to build anyway")
+    )]
+    LintErrors {
+        #[related]
+        errors: Vec<TyrusError>,
+    },
+
+    /// A lint whose level is `warn`: reported without failing.
+    #[error(transparent)]
+    #[diagnostic(forward(0), severity(Warning))]
+    LintWarning(Box<TyrusError>),
+
+    #[error("Unsupported Feature: Tyrus cannot translate {feature} yet.")]
     #[diagnostic(code(tyrus::unsupported))]
     UnsupportedFeature {
         feature: String,
@@ -55,6 +186,52 @@ pub enum TyrusError {
         span: SourceSpan,
     },
 
+    #[error("Unsupported Features: {} construct(s) would be emitted as `todo!()`", errors.len())]
+    #[diagnostic(
+        code(tyrus::unsupported_features),
+        help("pass --allow-todo to emit `todo!()` for them and build anyway")
+    )]
+    UnsupportedFeatures {
+        #[related]
+        errors: Vec<TyrusError>,
+    },
+
+    #[error("Rust Error: {message}")]
+    #[diagnostic(code(tyrus::rustc_error))]
+    RustcError {
+        message: String,
+        #[source_code]
+        src: NamedSource<String>,
+        #[label("rustc rejects the Rust generated from this")]
+        span: SourceSpan,
+    },
+
+    #[error("Verify Failed: rustc found {} error(s) in the generated crate", errors.len())]
+    #[diagnostic(
+        code(tyrus::verify_failed),
+        help("the crate is kept in the output directory; run `cargo check` there for rustc's own report")
+    )]
+    VerifyFailed {
+        #[related]
+        errors: Vec<TyrusError>,
+    },
+
+    #[error("Verify Error: {0}")]
+    #[diagnostic(code(tyrus::verify_error))]
+    VerifyError(String),
+
+    #[error("`--{option}` needs a project: pass a directory to build")]
+    #[diagnostic(code(tyrus::project_only))]
+    ProjectOnly { option: &'static str },
+
+    #[error("`--message-format {format}` keeps stdout for diagnostics: pass `--output` to write the generated code to a file")]
+    #[diagnostic(code(tyrus::output_required))]
+    OutputRequired { format: MessageFormat },
+
+    #[error("Config Error: {path}: {message}")]
+    #[diagnostic(code(tyrus::config_error))]
+    ConfigError { path: String, message: String },
+
     #[error("Formatting Error: {0}")]
     #[diagnostic(code(tyrus::fmt_error))]
     FormattingError(String),
@@ -0,0 +1,232 @@
+//! Machine-readable diagnostics for `--message-format json|sarif`, so code
+//! scanning dashboards and editors can consume them directly.
+
+use std::fmt;
+use std::str::FromStr;
+
+use miette::{Diagnostic, Severity, SourceCode, SourceSpan};
+use serde_json::{json, Value};
+
+/// How `check` and `build` print their diagnostics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// miette's graphical reports.
+    #[default]
+    Human,
+    /// One JSON object per diagnostic and line.
+    Json,
+    /// A single SARIF 2.1.0 log.
+    Sarif,
+}
+
+impl FromStr for MessageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            "sarif" => Ok(MessageFormat::Sarif),
+            _ => Err(format!(
+                "unknown message format `{s}`, expected human, json or sarif"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for MessageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MessageFormat::Human => "human",
+            MessageFormat::Json => "json",
+            MessageFormat::Sarif => "sarif",
+        })
+    }
+}
+
+/// A position in a source file; both numbers start at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The source range a diagnostic points at. `end` is exclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub file: String,
+    pub start: Position,
+    pub end: Position,
+}
+
+/// One diagnostic, flattened to the fields machine formats carry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// The miette code, e.g. `tyrus::lint::no_any`.
+    pub code: String,
+    /// `error`, `warning` or `advice`.
+    pub severity: &'static str,
+    /// Where the primary label points, when the diagnostic has one.
+    pub location: Option<Location>,
+    pub message: String,
+    pub help: Option<String>,
+}
+
+impl Record {
+    /// The records of `diagnostic`: one per related diagnostic when it only
+    /// groups them (like `UnsupportedFeatures`), which inherit its help, and
+    /// its own otherwise.
+    pub fn collect(diagnostic: &dyn Diagnostic) -> Vec<Record> {
+        let mut related: Vec<_> = diagnostic
+            .related()
+            .map(|related| related.flat_map(Record::collect).collect())
+            .unwrap_or_default();
+        if related.is_empty() {
+            return vec![Record::new(diagnostic)];
+        }
+        if let Some(help) = diagnostic.help() {
+            for record in related.iter_mut().filter(|r| r.help.is_none()) {
+                record.help = Some(help.to_string());
+            }
+        }
+        related
+    }
+
+    fn new(diagnostic: &dyn Diagnostic) -> Record {
+        let severity = match diagnostic.severity() {
+            Some(Severity::Warning) => "warning",
+            Some(Severity::Advice) => "advice",
+            Some(Severity::Error) | None => "error",
+        };
+        let location = diagnostic.source_code().and_then(|source| {
+            let label = diagnostic.labels()?.next()?;
+            locate(source, *label.inner())
+        });
+        Record {
+            code: diagnostic
+                .code()
+                .map_or_else(|| "tyrus::unknown".to_string(), |code| code.to_string()),
+            severity,
+            location,
+            message: diagnostic.to_string(),
+            help: diagnostic.help().map(|help| help.to_string()),
+        }
+    }
+
+    /// The object `--message-format json` prints for this record.
+    pub fn to_json(&self) -> Value {
+        let position = |p: Position| json!({ "line": p.line, "column": p.column });
+        json!({
+            "code": self.code,
+            "severity": self.severity,
+            "file": self.location.as_ref().map(|l| &l.file),
+            "range": self.location.as_ref().map(|l| json!({
+                "start": position(l.start),
+                "end": position(l.end),
+            })),
+            "message": self.message,
+            "help": self.help,
+        })
+    }
+}
+
+/// Resolves `span` to a file name and line/column range in `source`.
+fn locate(source: &dyn SourceCode, span: SourceSpan) -> Option<Location> {
+    let position = |offset: usize| {
+        let contents = source
+            .read_span(&SourceSpan::new(offset.into(), 0), 0, 0)
+            .ok()?;
+        let position = Position {
+            line: contents.line() + 1,
+            column: contents.column() + 1,
+        };
+        Some((contents.name().map(str::to_string), position))
+    };
+    let (file, start) = position(span.offset())?;
+    let (_, end) = position(span.offset() + span.len())?;
+    Some(Location {
+        file: file?,
+        start,
+        end,
+    })
+}
+
+/// `records` as JSON lines, one object per diagnostic.
+pub fn json_lines(records: &[Record]) -> String {
+    records
+        .iter()
+        .map(|record| record.to_json().to_string() + "\n")
+        .collect()
+}
+
+/// `records` as a SARIF 2.1.0 log with a single run. Each code becomes a rule,
+/// which carries the help text.
+pub fn sarif(records: &[Record]) -> String {
+    let mut rules: Vec<&Record> = Vec::new();
+    let mut results = Vec::new();
+    for record in records {
+        let index = match rules.iter().position(|rule| rule.code == record.code) {
+            Some(index) => index,
+            None => {
+                rules.push(record);
+                rules.len() - 1
+            }
+        };
+        let level = match record.severity {
+            "error" => "error",
+            "warning" => "warning",
+            _ => "note",
+        };
+        let mut result = json!({
+            "ruleId": record.code,
+            "ruleIndex": index,
+            "level": level,
+            "message": { "text": record.message },
+        });
+        if let Some(location) = &record.location {
+            result["locations"] = json!([{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": location.file },
+                    "region": {
+                        "startLine": location.start.line,
+                        "startColumn": location.start.column,
+                        "endLine": location.end.line,
+                        "endColumn": location.end.column,
+                    },
+                },
+            }]);
+        }
+        results.push(result);
+    }
+
+    let rules: Vec<Value> = rules
+        .iter()
+        .map(|rule| {
+            let help = records
+                .iter()
+                .filter(|record| record.code == rule.code)
+                .find_map(|record| record.help.as_ref());
+            let mut value = json!({ "id": rule.code });
+            if let Some(help) = help {
+                value["help"] = json!({ "text": help });
+            }
+            value
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "tyrus",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_string_pretty(&log).expect("a SARIF log serializes")
+}
@@ -0,0 +1,299 @@
+//! `tyrus check`: lints a file, or every file of a project the way
+//! [`build_project`](crate::build_project) walks it, and sums up what it found.
+//! With `--fix` it also rewrites the sources to resolve the lints that have a
+//! mechanical fix, or prints the diff of doing so with `--dry-run`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use miette::{Diagnostic, Severity};
+use tyrus_analyzer::lints::{fix, Fix, Lint, LintLevel, LintLevels};
+use tyrus_common::fs::FilePath;
+use tyrus_diagnostics::report::{self, Record};
+use tyrus_diagnostics::{MessageFormat, TyrusError};
+use walkdir::WalkDir;
+
+use crate::config::{Config, Sources};
+
+/// The diagnostics of one checked file.
+#[derive(Debug)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub diagnostics: Vec<TyrusError>,
+    /// The fixes applied to the file, or that would be with `--dry-run`.
+    pub fixes: Vec<Fix>,
+}
+
+impl FileReport {
+    pub fn error_count(&self) -> usize {
+        self.diagnostics.iter().filter(|d| !is_warning(d)).count()
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.diagnostics.iter().filter(|d| is_warning(d)).count()
+    }
+}
+
+/// What `tyrus check` found, file by file in path order.
+#[derive(Debug, Default)]
+pub struct CheckReport {
+    pub files: Vec<FileReport>,
+}
+
+impl CheckReport {
+    pub fn error_count(&self) -> usize {
+        self.files.iter().map(FileReport::error_count).sum()
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.files.iter().map(FileReport::warning_count).sum()
+    }
+
+    pub fn fix_count(&self) -> usize {
+        self.files.iter().map(|f| f.fixes.len()).sum()
+    }
+
+    /// Totals, then the counts of every file with diagnostics and of every
+    /// lint code.
+    pub fn summary(&self) -> String {
+        let (errors, warnings) = (self.error_count(), self.warning_count());
+        if errors + warnings == 0 {
+            return format!("✅ Checked {} file(s): no problems found", self.files.len());
+        }
+
+        let mut summary = format!(
+            "Checked {} file(s): {errors} error(s), {warnings} warning(s)\n",
+            self.files.len()
+        );
+        summary.push_str("\nBy file:\n");
+        for file in self.files.iter().filter(|f| !f.diagnostics.is_empty()) {
+            let _ = writeln!(
+                summary,
+                "  {}: {} error(s), {} warning(s)",
+                file.path.display(),
+                file.error_count(),
+                file.warning_count()
+            );
+        }
+
+        let mut codes = BTreeMap::new();
+        for diagnostic in self.files.iter().flat_map(|f| &f.diagnostics) {
+            let code = diagnostic
+                .code()
+                .map_or_else(|| "tyrus::unknown".to_string(), |code| code.to_string());
+            *codes.entry(code).or_insert(0) += 1;
+        }
+        summary.push_str("\nBy lint code:\n");
+        for (code, count) in codes {
+            let _ = writeln!(summary, "  {code}: {count}");
+        }
+        summary
+    }
+}
+
+fn is_warning(diagnostic: &TyrusError) -> bool {
+    matches!(
+        diagnostic.severity(),
+        Some(Severity::Warning | Severity::Advice)
+    )
+}
+
+/// What `tyrus check` does with the fixes of the lints it reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FixMode {
+    /// Only report the lints.
+    #[default]
+    Off,
+    /// Rewrite the sources, then report what is left (`--fix`).
+    Apply,
+    /// Print the diff of the rewrite and leave the sources alone
+    /// (`--fix --dry-run`).
+    DryRun,
+}
+
+/// Options of `tyrus check`.
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    pub message_format: MessageFormat,
+    /// Lint levels set on the command line, applied in order over those of
+    /// `tyrus.toml`.
+    pub lints: Vec<(Lint, LintLevel)>,
+    pub fix: FixMode,
+}
+
+pub fn check(path: FilePath) -> Result<(), TyrusError> {
+    check_with_options(path, &CheckOptions::default())
+}
+
+/// Checks the file or project at `path`, prints every diagnostic in the
+/// format of `options` (with a summary when it is for humans), and fails
+/// when any of them is an error.
+pub fn check_with_options(path: FilePath, options: &CheckOptions) -> Result<(), TyrusError> {
+    let config = Config::discover(path.as_ref())?;
+    let levels = config.lint_levels(&options.lints)?;
+    let report = check_report(path.as_ref(), &levels, &config.sources()?, options.fix)?;
+    let mut summary = report.summary();
+    let fixes = report.fix_count();
+    if fixes > 0 {
+        let files = report.files.iter().filter(|f| !f.fixes.is_empty()).count();
+        let verb = match options.fix {
+            FixMode::DryRun => "Would fix",
+            _ => "Fixed",
+        };
+        summary = format!("🔧 {verb} {fixes} problem(s) in {files} file(s)\n{summary}");
+    }
+    if options.fix == FixMode::DryRun && options.message_format == MessageFormat::Human {
+        for file in report.files.iter().filter(|f| !f.fixes.is_empty()) {
+            print!("{}", diff(file)?);
+        }
+    }
+    let errors = report.error_count();
+    let failed_files = report.files.iter().filter(|f| f.error_count() > 0).count();
+
+    let diagnostics = report.files.into_iter().flat_map(|f| f.diagnostics);
+    match options.message_format {
+        MessageFormat::Human => {
+            for diagnostic in diagnostics {
+                println!("{:?}", miette::Report::new(diagnostic));
+            }
+            println!("{summary}");
+        }
+        MessageFormat::Json => {
+            let records: Vec<_> = diagnostics.flat_map(|d| Record::collect(&d)).collect();
+            print!("{}", report::json_lines(&records));
+        }
+        MessageFormat::Sarif => {
+            let records: Vec<_> = diagnostics.flat_map(|d| Record::collect(&d)).collect();
+            println!("{}", report::sarif(&records));
+        }
+    }
+
+    if errors > 0 {
+        return Err(TyrusError::CheckFailed {
+            errors,
+            files: failed_files,
+        });
+    }
+    Ok(())
+}
+
+/// The unified diff of applying the fixes of `file`.
+fn diff(file: &FileReport) -> Result<String, TyrusError> {
+    let source = fs::read_to_string(&file.path).map_err(TyrusError::IoError)?;
+    let fixed = fix::apply(&source, &file.fixes);
+    let name = file.path.display().to_string();
+    Ok(similar::TextDiff::from_lines(&source, &fixed)
+        .unified_diff()
+        .header(&name, &name)
+        .to_string())
+}
+
+/// Lints the file at `path`, or every `.ts` file of `sources` under it, at
+/// `levels`, fixing them as `fix` says. A file that does not parse is
+/// reported with its parse error and does not stop the others.
+pub fn check_report(
+    path: &Path,
+    levels: &LintLevels,
+    sources: &Sources,
+    fix: FixMode,
+) -> Result<CheckReport, TyrusError> {
+    let mut report = CheckReport::default();
+    if path.is_file() {
+        let project = tyrus_codegen::Project::default();
+        report.files.push(check_file(path, levels, fix, &project)?);
+        return Ok(report);
+    }
+    let mut files = Vec::new();
+    for entry in WalkDir::new(path).sort_by_file_name() {
+        let entry = entry.map_err(|e| TyrusError::IoError(e.into()))?;
+        let path = entry.path();
+        if path.is_file()
+            && path.extension().and_then(|s| s.to_str()) == Some("ts")
+            && sources.contains(path)
+        {
+            files.push(path.to_path_buf());
+        }
+    }
+    let project = declared_project(&files);
+    for path in &files {
+        report.files.push(check_file(path, levels, fix, &project)?);
+    }
+    Ok(report)
+}
+
+/// The declarations of the modules at `files`, each analyzed on its own,
+/// which builds analyze every module against.
+fn declared_project(files: &[PathBuf]) -> tyrus_codegen::Project {
+    let mut project = tyrus_codegen::Project::default();
+    for path in files {
+        // A file that does not parse is reported when it is checked
+        let Ok((program, comments)) = tyrus_parser::parse_with_comments(path) else {
+            continue;
+        };
+        let module =
+            tyrus_codegen::analyze(&program, &comments, &tyrus_codegen::Project::default());
+        // Checking generates no Rust, so the modules need no Rust path
+        project.add(&module, "");
+    }
+    project
+}
+
+fn check_file(
+    path: &Path,
+    levels: &LintLevels,
+    fix: FixMode,
+    project: &tyrus_codegen::Project,
+) -> Result<FileReport, TyrusError> {
+    let (diagnostics, fixes) = analyze_file(path, levels, project)?;
+    if fix == FixMode::Off || fixes.is_empty() {
+        return Ok(FileReport {
+            path: path.to_path_buf(),
+            diagnostics,
+            fixes: Vec::new(),
+        });
+    }
+    if fix == FixMode::DryRun {
+        return Ok(FileReport {
+            path: path.to_path_buf(),
+            diagnostics,
+            fixes,
+        });
+    }
+
+    let source = fs::read_to_string(path).map_err(TyrusError::IoError)?;
+    fs::write(path, fix::apply(&source, &fixes)).map_err(TyrusError::IoError)?;
+    // Report what the fixes left, against the rewritten source
+    let (diagnostics, _) = analyze_file(path, levels, project)?;
+    Ok(FileReport {
+        path: path.to_path_buf(),
+        diagnostics,
+        fixes,
+    })
+}
+
+fn analyze_file(
+    path: &Path,
+    levels: &LintLevels,
+    project: &tyrus_codegen::Project,
+) -> Result<(Vec<TyrusError>, Vec<Fix>), TyrusError> {
+    match tyrus_parser::parse_with_comments(path) {
+        Ok((program, comments)) => {
+            let source_code = fs::read_to_string(path).map_err(TyrusError::IoError)?;
+            let file_name = path.to_string_lossy().to_string();
+            let classes: Vec<_> = project.classes().collect();
+            Ok(tyrus_analyzer::Analyzer::analyze_with_fixes(
+                &program,
+                &comments,
+                source_code,
+                file_name,
+                levels,
+                project.exports(),
+                &classes,
+            ))
+        }
+        Err(error @ TyrusError::IoError(_)) => Err(error),
+        Err(error) => Ok((vec![error], Vec::new())),
+    }
+}
@@ -0,0 +1,319 @@
+//! `tyrus.toml`, the configuration of a project. Every table is optional:
+//!
+//! ```toml
+//! [package]
+//! name = "payments"       # the generated crate, `tyrus_app` by default
+//! edition = "2021"
+//!
+//! [build]
+//! target = "server"       # "server", "bin" or "lib"
+//! include = ["src/**/*.ts"]
+//! exclude = ["**/*.spec.ts"]
+//!
+//! [server]
+//! address = "0.0.0.0:3000"
+//!
+//! [dependencies]          # over the crate's defaults; `false` drops one
+//! reqwest = { version = "0.12", features = ["json"] }
+//! rand = false
+//!
+//! [naming]
+//! identifiers = "snake_case"  # or "preserve"
+//!
+//! [lints]
+//! no-any = "warn"
+//! no-var = "allow"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+use tyrus_analyzer::lints::{Lint, LintLevel, LintLevels};
+use tyrus_codegen::Naming;
+use tyrus_diagnostics::TyrusError;
+
+pub const CONFIG_FILE: &str = "tyrus.toml";
+
+const EDITIONS: [&str; 4] = ["2015", "2018", "2021", "2024"];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Where the config was loaded from.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+    #[serde(default)]
+    pub package: Package,
+    #[serde(default)]
+    pub build: Build,
+    #[serde(default)]
+    pub server: Server,
+    /// Dependencies of the generated crate by name, replacing the default
+    /// of the same name; `false` removes a default.
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default)]
+    pub naming: NamingConfig,
+    /// Lint levels by lint name, e.g. `no-any = "warn"`.
+    #[serde(default)]
+    pub lints: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct Package {
+    pub name: String,
+    pub edition: String,
+}
+
+impl Default for Package {
+    fn default() -> Self {
+        Package {
+            name: "tyrus_app".to_string(),
+            edition: "2021".to_string(),
+        }
+    }
+}
+
+impl Package {
+    /// The name the crate's library is used by, e.g. in `main.rs`.
+    pub fn lib_name(&self) -> String {
+        self.name.replace('-', "_")
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct Build {
+    pub target: Target,
+    /// Globs of the `.ts` files to build, relative to the directory of
+    /// `tyrus.toml`; all of them when empty.
+    pub include: Vec<String>,
+    /// Globs of the `.ts` files to leave out, even when included.
+    pub exclude: Vec<String>,
+}
+
+/// What the generated crate is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    /// A library plus a `server` binary serving the controllers with axum.
+    #[default]
+    Server,
+    /// A library plus a binary running the top-level statements of the root
+    /// `index.ts`.
+    Bin,
+    /// A library only.
+    Lib,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct Server {
+    /// The `host:port` the server listens on.
+    pub address: String,
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Server {
+            address: "0.0.0.0:3000".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct NamingConfig {
+    /// `snake_case` or `preserve`.
+    pub identifiers: Option<String>,
+}
+
+/// The `.ts` files of a project, per the `include` and `exclude` globs.
+#[derive(Debug, Clone)]
+pub struct Sources {
+    root: Option<PathBuf>,
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl Sources {
+    /// Whether the `.ts` file at `path` belongs to the project. Files outside
+    /// the directory of `tyrus.toml` always do.
+    pub fn contains(&self, path: &Path) -> bool {
+        let Some(relative) = self
+            .root
+            .as_deref()
+            .and_then(|root| path.strip_prefix(root).ok())
+        else {
+            return true;
+        };
+        self.include
+            .as_ref()
+            .is_none_or(|include| include.is_match(relative))
+            && !self.exclude.is_match(relative)
+    }
+}
+
+impl Config {
+    /// The `tyrus.toml` of the project `input` belongs to: the first one in
+    /// `input` (or its directory, for a file) and its ancestors. Without one,
+    /// the defaults.
+    pub fn discover(input: &Path) -> Result<Config, TyrusError> {
+        match find(input) {
+            Some(path) => Config::load(&path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Config, TyrusError> {
+        let text = fs::read_to_string(path).map_err(TyrusError::IoError)?;
+        let mut config: Config = toml::from_str(&text).map_err(|e| TyrusError::ConfigError {
+            path: path.display().to_string(),
+            message: e.message().to_string(),
+        })?;
+        config.path = Some(path.to_path_buf());
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// An error in this config.
+    pub(crate) fn invalid(&self, message: String) -> TyrusError {
+        TyrusError::ConfigError {
+            path: self
+                .path
+                .as_deref()
+                .unwrap_or(Path::new(CONFIG_FILE))
+                .display()
+                .to_string(),
+            message,
+        }
+    }
+
+    fn validate(&self) -> Result<(), TyrusError> {
+        let name = &self.package.name;
+        let valid_name = name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            && name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_');
+        if !valid_name {
+            return Err(self.invalid(format!(
+                "invalid crate name `{name}`, expected letters, digits, `_` and `-`, starting with a letter or `_`"
+            )));
+        }
+        let edition = &self.package.edition;
+        if !EDITIONS.contains(&edition.as_str()) {
+            return Err(self.invalid(format!(
+                "unknown edition `{edition}`, expected one of {}",
+                EDITIONS.join(", ")
+            )));
+        }
+        let address = &self.server.address;
+        if !is_valid_address(address) {
+            return Err(self.invalid(format!(
+                "invalid server address `{address}`, expected host:port"
+            )));
+        }
+        for (name, value) in &self.dependencies {
+            if !matches!(
+                value,
+                toml::Value::String(_) | toml::Value::Table(_) | toml::Value::Boolean(false)
+            ) {
+                return Err(self.invalid(format!(
+                    "invalid dependency `{name}`, expected a version, a table or false"
+                )));
+            }
+        }
+        self.naming()?;
+        self.sources()?;
+        Ok(())
+    }
+
+    /// The levels of the `[lints]` table, then of `overrides` in order.
+    pub fn lint_levels(&self, overrides: &[(Lint, LintLevel)]) -> Result<LintLevels, TyrusError> {
+        let mut levels = LintLevels::default();
+        for (name, level) in &self.lints {
+            levels.set(
+                name.parse().map_err(|e| self.invalid(e))?,
+                level.parse().map_err(|e| self.invalid(e))?,
+            );
+        }
+        for (lint, level) in overrides {
+            levels.set(*lint, *level);
+        }
+        Ok(levels)
+    }
+
+    pub fn naming(&self) -> Result<Naming, TyrusError> {
+        match &self.naming.identifiers {
+            Some(naming) => naming.parse().map_err(|e| self.invalid(e)),
+            None => Ok(Naming::default()),
+        }
+    }
+
+    pub fn sources(&self) -> Result<Sources, TyrusError> {
+        let globs = |patterns: &[String]| {
+            let mut set = GlobSetBuilder::new();
+            for pattern in patterns {
+                let glob = GlobBuilder::new(pattern)
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|e| self.invalid(format!("invalid glob `{pattern}`: {}", e.kind())))?;
+                set.add(glob);
+            }
+            set.build()
+                .map_err(|e| self.invalid(format!("invalid globs: {e}")))
+        };
+        Ok(Sources {
+            root: self
+                .path
+                .as_deref()
+                .and_then(Path::parent)
+                .map(Path::to_path_buf),
+            include: match self.build.include.as_slice() {
+                [] => None,
+                include => Some(globs(include)?),
+            },
+            exclude: globs(&self.build.exclude)?,
+        })
+    }
+}
+
+fn find(input: &Path) -> Option<PathBuf> {
+    let start = if input.is_dir() {
+        input
+    } else {
+        input.parent()?
+    };
+    start
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Whether `address` is `host:port`, the host being an IP address (IPv6 in
+/// brackets) or a DNS name.
+fn is_valid_address(address: &str) -> bool {
+    if address.parse::<std::net::SocketAddr>().is_ok() {
+        return true;
+    }
+    let Some((host, port)) = address.rsplit_once(':') else {
+        return false;
+    };
+    let labels: Vec<_> = host.split('.').collect();
+    port.parse::<u16>().is_ok()
+        && host.len() <= 253
+        && labels.iter().all(|label| {
+            (1..=63).contains(&label.len())
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+        // Numeric hosts are IPv4 addresses, which parse above when valid
+        && !labels
+            .last()
+            .is_some_and(|label| label.chars().all(|c| c.is_ascii_digit()))
+}
@@ -1,44 +1,103 @@
+use miette::{NamedSource, SourceSpan};
 use std::fs;
 use std::path::{Path, PathBuf};
-use tyrus_diagnostics::TyrusError;
+use tyrus_diagnostics::report::{self, Record};
+use tyrus_diagnostics::{MessageFormat, TyrusError};
 
 use walkdir::WalkDir;
 
 use tyrus_common::fs::FilePath;
 
-pub fn check(path: FilePath) -> Result<(), TyrusError> {
-    let program = tyrus_parser::parse(path.as_ref())?;
+use swc_common::comments::SingleThreadedComments;
+use tyrus_analyzer::lints::{Lint, LintLevel, LintLevels};
 
-    // Read source code for error reporting
-    let source_code = std::fs::read_to_string(path.as_ref()).map_err(TyrusError::IoError)?;
-    let file_name = path.as_ref().to_string_lossy().to_string();
+use crate::config::{Config, Target};
+use crate::source_map::SourceMaps;
 
-    let errors = tyrus_analyzer::Analyzer::analyze(&program, source_code, file_name);
+pub mod check;
+pub mod config;
+mod source_map;
+mod verify;
 
-    if !errors.is_empty() {
-        for error in errors {
-            println!("{:?}", miette::Report::new(error));
-        }
-        return Ok(()); // Or return Err if we want to stop execution
-    }
-
-    let count = match program {
-        swc_ecma_ast::Program::Module(m) => m.body.len(),
-        swc_ecma_ast::Program::Script(s) => s.body.len(),
-    };
-    println!("✅ AST parsed successfully with {} statements", count);
-    Ok(())
-}
+pub use check::{
+    check, check_report, check_with_options, CheckOptions, CheckReport, FileReport, FixMode,
+};
 
 pub fn pipeline() -> Result<(), TyrusError> {
     // Stub implementation
     Ok(())
 }
 
+/// Options of `tyrus build`.
+#[derive(Debug, Clone, Default)]
+pub struct BuildOptions {
+    /// How to print diagnostics. Lint warnings are printed in any format;
+    /// with a machine format the error that fails the build joins them.
+    pub message_format: MessageFormat,
+    /// Emit `todo!()` for constructs Tyrus cannot translate instead of
+    /// failing the build on them.
+    pub allow_todo: bool,
+    /// Skip the lint pass `tyrus check` runs, which otherwise fails the
+    /// build on denied lints (`any`, `var` and `eval` by default).
+    pub no_lint: bool,
+    /// Lint levels set on the command line, applied in order over those of
+    /// `tyrus.toml`.
+    pub lints: Vec<(Lint, LintLevel)>,
+    /// Run `cargo check` on the generated crate and report rustc's errors on
+    /// the TypeScript they come from. Project builds only.
+    pub verify: bool,
+    /// Precede generated items and statements with `// ts: file:line`
+    /// comments.
+    pub provenance: bool,
+    /// Write a source map v3 next to each generated file, as `file.rs.map`.
+    /// Project builds only.
+    pub source_map: bool,
+}
+
 pub fn build(path: FilePath) -> Result<String, TyrusError> {
-    let program = tyrus_parser::parse(path.as_ref())?;
+    build_with_options(path, &BuildOptions::default())
+}
+
+pub fn build_with_options(path: FilePath, options: &BuildOptions) -> Result<String, TyrusError> {
+    let mut warnings = Vec::new();
+    let result = build_file(path, options, &mut warnings);
+    print_diagnostics(warnings, result, options)
+}
+
+fn build_file(
+    path: FilePath,
+    options: &BuildOptions,
+    warnings: &mut Vec<TyrusError>,
+) -> Result<String, TyrusError> {
+    if options.verify {
+        return Err(TyrusError::ProjectOnly { option: "verify" });
+    }
+    if options.source_map {
+        return Err(TyrusError::ProjectOnly {
+            option: "source-map",
+        });
+    }
+    let (program, comments) = tyrus_parser::parse_with_comments(path.as_ref())?;
+    let config = Config::discover(path.as_ref())?;
+    let levels = config.lint_levels(&options.lints)?;
+    let naming = config.naming()?;
+    let errors = lint_errors(
+        path.as_ref(),
+        &program,
+        &comments,
+        &levels,
+        options,
+        warnings,
+    )?;
+    if !errors.is_empty() {
+        return Err(TyrusError::LintErrors { errors });
+    }
     // Default to false for single file build
-    let generated_code = tyrus_codegen::generate(&program, false);
+    let generated_code = tyrus_codegen::generate(&program, &comments, false, naming);
+    let errors = unsupported_errors(path.as_ref(), &generated_code.unsupported, options)?;
+    if !errors.is_empty() {
+        return Err(TyrusError::UnsupportedFeatures { errors });
+    }
     let mut code = generated_code.code;
 
     // Conditionally inject AppError boilerplate:
@@ -53,24 +112,72 @@ pub fn build(path: FilePath) -> Result<String, TyrusError> {
         code.push_str(get_app_error_code());
     }
 
-    format_code(code)
+    let (code, span_map) =
+        tyrus_codegen::span_map::extract(&tyrus_codegen::comments::render(&format_code(code)?));
+    if options.provenance {
+        let source = fs::read_to_string(path.as_ref()).map_err(TyrusError::IoError)?;
+        let name = path.as_ref().to_string_lossy();
+        return Ok(span_map.annotate(&code, &name, &source).0);
+    }
+    Ok(code)
 }
 
 pub fn build_project(input_dir: PathBuf, output_dir: PathBuf) -> Result<(), TyrusError> {
+    build_project_with_options(input_dir, output_dir, &BuildOptions::default())
+}
+
+pub fn build_project_with_options(
+    input_dir: PathBuf,
+    output_dir: PathBuf,
+    options: &BuildOptions,
+) -> Result<(), TyrusError> {
+    let mut warnings = Vec::new();
+    let result = build_crate(input_dir, output_dir, options, &mut warnings);
+    print_diagnostics(warnings, result, options)
+}
+
+fn build_crate(
+    input_dir: PathBuf,
+    output_dir: PathBuf,
+    options: &BuildOptions,
+    warnings: &mut Vec<TyrusError>,
+) -> Result<(), TyrusError> {
     let mut controllers: Vec<String> = Vec::new(); // Just names of controllers
     let mut class_module_map: std::collections::HashMap<String, String> =
         std::collections::HashMap::new();
     let mut generic_classes: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut abstract_classes: std::collections::HashSet<String> = std::collections::HashSet::new();
     let mut programs = Vec::new();
+    let mut comments = Vec::new();
     let mut file_paths = Vec::new();
-
-    // 1. Walk, Parse, and Collect Info
-    for entry in WalkDir::new(&input_dir) {
+    // The Rust module each file becomes, e.g. `crate::models::user`
+    let mut crate_paths = Vec::new();
+    let mut violations = Vec::new();
+    let mut source_maps = SourceMaps::default();
+    let config = Config::discover(&input_dir)?;
+    let levels = config.lint_levels(&options.lints)?;
+    let sources = config.sources()?;
+    let naming = config.naming()?;
+    let lib_name = config.package.lib_name();
+
+    // 1. Walk, Parse, Lint, and Collect Info
+    for entry in WalkDir::new(&input_dir).sort_by_file_name() {
         let entry = entry.map_err(|e| TyrusError::IoError(e.into()))?;
         let path = entry.path();
 
-        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("ts") {
-            let program = tyrus_parser::parse(path)?;
+        if path.is_file()
+            && path.extension().and_then(|s| s.to_str()) == Some("ts")
+            && sources.contains(path)
+        {
+            let (program, file_comments) = tyrus_parser::parse_with_comments(path)?;
+            violations.extend(lint_errors(
+                path,
+                &program,
+                &file_comments,
+                &levels,
+                options,
+                warnings,
+            )?);
 
             // Calculate module path
             let relative_path = path.strip_prefix(&input_dir).unwrap_or(path);
@@ -86,8 +193,6 @@ pub fn build_project(input_dir: PathBuf, output_dir: PathBuf) -> Result<(), Tyru
             let sanitized_stem = file_stem.replace(['.', '-'], "_");
 
             let mut module_parts = Vec::new();
-            module_parts.push("tyrus_app".to_string()); // Use lib crate name
-
             if let Some(parent) = relative_path.parent() {
                 for part in parent.components() {
                     if let std::path::Component::Normal(s) = part {
@@ -98,6 +203,13 @@ pub fn build_project(input_dir: PathBuf, output_dir: PathBuf) -> Result<(), Tyru
                     }
                 }
             }
+            // index.ts becomes the mod.rs of its directory
+            let crate_path = std::iter::once("crate".to_string())
+                .chain(module_parts.iter().cloned())
+                .chain((file_stem != "index").then(|| sanitized_stem.clone()))
+                .collect::<Vec<_>>()
+                .join("::");
+            module_parts.insert(0, lib_name.clone());
             module_parts.push(sanitized_stem);
             let module_path = module_parts.join("::");
 
@@ -113,6 +225,9 @@ pub fn build_project(input_dir: PathBuf, output_dir: PathBuf) -> Result<(), Tyru
                             let class_name = class_decl.ident.sym.to_string();
                             class_module_map.insert(class_name.clone(), module_path.clone());
 
+                            if class_decl.class.is_abstract {
+                                abstract_classes.insert(class_name.clone());
+                            }
                             if let Some(type_params) = &class_decl.class.type_params {
                                 if !type_params.params.is_empty() {
                                     generic_classes.insert(class_name);
@@ -124,34 +239,108 @@ pub fn build_project(input_dir: PathBuf, output_dir: PathBuf) -> Result<(), Tyru
             }
 
             programs.push(program);
+            comments.push(file_comments);
             file_paths.push(path.to_path_buf());
+            crate_paths.push(crate_path);
         }
     }
 
+    // Nothing is written while any file breaks the rules
+    if !violations.is_empty() {
+        return Err(TyrusError::LintErrors { errors: violations });
+    }
+
     // 2. Analyze (Build Dependency Graph)
     let graph = tyrus_analyzer::graph::build_graph(&programs);
     let init_order = graph
         .get_initialization_order()
         .map_err(TyrusError::FormattingError)?; // Using FormattingError as generic error for now
 
-    // 3. Transpile
-    for (i, program) in programs.iter().enumerate() {
-        let path = &file_paths[i];
+    // 3. Transpile, each module seeing the declarations of the others: typed
+    // against what a first pass over each module on its own inferred, then
+    // generated against the result
+    let analyze = |project: &tyrus_codegen::Project| {
+        let mut analyzed = tyrus_codegen::Project::default();
+        let modules: Vec<_> = programs
+            .iter()
+            .zip(&comments)
+            .zip(&crate_paths)
+            .map(|((program, comments), crate_path)| {
+                let module = tyrus_codegen::analyze(program, comments, project);
+                analyzed.add(&module, crate_path);
+                module
+            })
+            .collect();
+        (modules, analyzed)
+    };
+    let (_, declared) = analyze(&tyrus_codegen::Project::default());
+    let (modules, project) = analyze(&declared);
+    let generated: Vec<_> = modules
+        .iter()
+        .zip(&file_paths)
+        .map(|(module, path)| {
+            // Check if it's index.ts
+            let is_index = path.file_stem().and_then(|s| s.to_str()) == Some("index");
+            tyrus_codegen::generate_module(module, is_index, naming, &project)
+        })
+        .collect();
+
+    // Synthesized unions are declared once, in their own module
+    let unions: std::collections::BTreeMap<_, _> = generated
+        .iter()
+        .flat_map(|generated| generated.unions.clone())
+        .collect();
+
+    let mut unsupported = Vec::new();
+    for (path, generated) in file_paths.iter().zip(&generated) {
+        unsupported.extend(unsupported_errors(path, &generated.unsupported, options)?);
+    }
+    // Nothing is written while any file has constructs emitted as `todo!()`
+    if !unsupported.is_empty() {
+        return Err(TyrusError::UnsupportedFeatures {
+            errors: unsupported,
+        });
+    }
+
+    // Where each file goes under the generated crate's src/
+    let relative = |path: &Path| {
         let relative_path = path.strip_prefix(&input_dir).unwrap_or(path);
-        let relative_path = relative_path.strip_prefix("src").unwrap_or(relative_path);
-        let output_path = output_dir.join("src").join(relative_path);
+        relative_path
+            .strip_prefix("src")
+            .unwrap_or(relative_path)
+            .to_path_buf()
+    };
+    // The root index.ts runs as the binary of a `bin` target
+    let entry = file_paths
+        .iter()
+        .zip(&generated)
+        .any(|(path, generated)| generated.entry && relative(path) == Path::new("index.ts"));
+    if config.build.target == Target::Bin && !entry {
+        return Err(config.invalid(
+            "target `bin` runs the top-level statements of the root index.ts, and there are none"
+                .to_string(),
+        ));
+    }
+
+    for (path, generated) in file_paths.iter().zip(generated) {
+        let output_path = output_dir.join("src").join(relative(path));
 
         // Calculate module path for this file
         let file_stem = path.file_stem().unwrap_or_default().to_string_lossy();
         let sanitized_stem = file_stem.replace(['.', '-'], "_");
 
-        // Check if it's index.ts
-        let is_index = path.file_stem().and_then(|s| s.to_str()) == Some("index");
-
-        let generated = tyrus_codegen::generate(program, is_index);
-        let formatted_code = format_code(generated.code)?;
+        let (mut formatted_code, mut span_map) = tyrus_codegen::span_map::extract(
+            &tyrus_codegen::comments::render(&format_code(generated.code)?),
+        );
+        if options.provenance {
+            let source = fs::read_to_string(path).map_err(TyrusError::IoError)?;
+            let name = path.strip_prefix(&input_dir).unwrap_or(path);
+            let name = name.to_string_lossy().replace('\\', "/");
+            (formatted_code, span_map) = span_map.annotate(&formatted_code, &name, &source);
+        }
 
         let output_file = output_path.with_file_name(format!("{}.rs", sanitized_stem));
+        source_maps.insert(output_file.clone(), path.clone(), span_map);
 
         if let Some(parent) = output_file.parent() {
             fs::create_dir_all(parent).map_err(TyrusError::IoError)?;
@@ -166,11 +355,11 @@ pub fn build_project(input_dir: PathBuf, output_dir: PathBuf) -> Result<(), Tyru
     }
 
     // 4. Generate mod.rs
-    for entry in WalkDir::new(&output_dir) {
+    for entry in WalkDir::new(&output_dir).sort_by_file_name() {
         let entry = entry.map_err(|e| TyrusError::IoError(e.into()))?;
         let path = entry.path();
         if path.is_dir() {
-            generate_mod_rs(path)?;
+            generate_mod_rs(path, &mut source_maps)?;
         }
     }
 
@@ -178,7 +367,8 @@ pub fn build_project(input_dir: PathBuf, output_dir: PathBuf) -> Result<(), Tyru
     let src_mod = output_dir.join("src").join("mod.rs");
     let src_lib = output_dir.join("src").join("lib.rs");
     if src_mod.exists() {
-        fs::rename(src_mod, src_lib.clone()).map_err(TyrusError::IoError)?;
+        fs::rename(&src_mod, &src_lib).map_err(TyrusError::IoError)?;
+        source_maps.moved(&src_mod, &src_lib, 0);
     }
 
     // Generate error.rs
@@ -189,16 +379,36 @@ pub fn build_project(input_dir: PathBuf, output_dir: PathBuf) -> Result<(), Tyru
     // Append mod error; pub use error::AppError; to lib.rs
     let mut lib_content = fs::read_to_string(&src_lib).map_err(TyrusError::IoError)?;
     lib_content.push_str("\npub mod error;\npub use error::AppError;\n");
+
+    if !unions.is_empty() {
+        let unions_rs = output_dir.join("src").join("unions.rs");
+        let unions_content = tyrus_codegen::generate_unions(&unions, naming, &project);
+        fs::write(unions_rs, format_code(unions_content)?).map_err(TyrusError::IoError)?;
+        lib_content.push_str("pub mod unions;\n");
+    }
+    if naming == tyrus_codegen::Naming::Preserve {
+        // Inner attributes come first, moving the mapped code down
+        let allow = "#![allow(non_snake_case)]\n\n";
+        lib_content.insert_str(0, allow);
+        source_maps.moved(&src_lib, &src_lib, allow.len());
+    }
     fs::write(&src_lib, lib_content).map_err(TyrusError::IoError)?;
 
     // 5. Generate main.rs
-    let main_content = generate_main_rs(
-        &init_order,
-        &class_module_map,
-        &controllers,
-        &graph,
-        &generic_classes,
-    )?;
+    let main_content = match config.build.target {
+        Target::Server => Some(generate_main_rs(
+            &init_order,
+            &class_module_map,
+            &controllers,
+            &graph,
+            // Abstract classes become traits, so their subclasses are created instead
+            &generic_classes.union(&abstract_classes).cloned().collect(),
+            &project.mutated_interfaces(),
+            &config.server.address,
+        )?),
+        Target::Bin => Some(format!("fn main() {{\n    {}::main();\n}}\n", lib_name)),
+        Target::Lib => None,
+    };
 
     // Ensure src directory exists
     let src_dir = output_dir.join("src");
@@ -206,21 +416,110 @@ pub fn build_project(input_dir: PathBuf, output_dir: PathBuf) -> Result<(), Tyru
         fs::create_dir_all(&src_dir).map_err(TyrusError::IoError)?;
     }
 
-    let main_rs = src_dir.join("main.rs");
-    fs::write(main_rs, main_content).map_err(TyrusError::IoError)?;
+    if let Some(main_content) = main_content {
+        let main_rs = src_dir.join("main.rs");
+        fs::write(main_rs, main_content).map_err(TyrusError::IoError)?;
+    }
 
     // 6. Generate Cargo.toml
-    generate_cargo_toml(&output_dir)?;
+    generate_cargo_toml(&output_dir, &config)?;
+
+    if options.source_map {
+        source_maps.write()?;
+    }
+
+    if options.verify {
+        verify::verify(&output_dir, &source_maps)?;
+    }
 
     Ok(())
 }
 
+/// Denied violations of the Oxidizable rules in the file at `path`, unless
+/// `options` skip the lint pass. Warnings go to `warnings` and do not fail
+/// the build.
+fn lint_errors(
+    path: &Path,
+    program: &swc_ecma_ast::Program,
+    comments: &SingleThreadedComments,
+    levels: &LintLevels,
+    options: &BuildOptions,
+    warnings: &mut Vec<TyrusError>,
+) -> Result<Vec<TyrusError>, TyrusError> {
+    if options.no_lint {
+        return Ok(Vec::new());
+    }
+    let source = fs::read_to_string(path).map_err(TyrusError::IoError)?;
+    let file_name = path.to_string_lossy().to_string();
+    let (found, errors) =
+        tyrus_analyzer::Analyzer::lint(program, comments, source, file_name, levels)
+            .into_iter()
+            .partition::<Vec<_>, _>(|error| matches!(error, TyrusError::LintWarning(_)));
+    warnings.extend(found);
+    Ok(errors)
+}
+
+/// Prints the lint `warnings` of a build in the format of `options`: to
+/// stderr for humans, or with the error of a failed `result` in one JSON or
+/// SARIF output, which leaves nothing for the CLI to print.
+fn print_diagnostics<T>(
+    warnings: Vec<TyrusError>,
+    result: Result<T, TyrusError>,
+    options: &BuildOptions,
+) -> Result<T, TyrusError> {
+    if options.message_format == MessageFormat::Human {
+        for warning in warnings {
+            eprintln!("{:?}", miette::Report::new(warning));
+        }
+        return result;
+    }
+    let records: Vec<_> = warnings
+        .iter()
+        .chain(result.as_ref().err())
+        .flat_map(|diagnostic| Record::collect(diagnostic))
+        .collect();
+    match options.message_format {
+        MessageFormat::Json => print!("{}", report::json_lines(&records)),
+        _ => println!("{}", report::sarif(&records)),
+    }
+    result
+}
+
+/// Diagnostics for the constructs of `path` that were emitted as `todo!()`,
+/// unless `options` allow them.
+fn unsupported_errors(
+    path: &Path,
+    reports: &[tyrus_codegen::Unsupported],
+    options: &BuildOptions,
+) -> Result<Vec<TyrusError>, TyrusError> {
+    if options.allow_todo || reports.is_empty() {
+        return Ok(Vec::new());
+    }
+    let source = fs::read_to_string(path).map_err(TyrusError::IoError)?;
+    let file_name = path.to_string_lossy().to_string();
+    Ok(reports
+        .iter()
+        .map(|report| {
+            // Each file is parsed into its own source map, starting at 1
+            let start = (report.span.lo.0 as usize).saturating_sub(1);
+            let end = (report.span.hi.0 as usize).saturating_sub(1);
+            TyrusError::UnsupportedFeature {
+                feature: report.feature.to_string(),
+                src: NamedSource::new(file_name.clone(), source.clone()),
+                span: SourceSpan::new(start.into(), end.saturating_sub(start)),
+            }
+        })
+        .collect())
+}
+
 fn generate_main_rs(
     init_order: &[String],
     class_module_map: &std::collections::HashMap<String, String>,
     controllers: &[String],
     graph: &tyrus_analyzer::graph::DependencyGraph,
-    generic_classes: &std::collections::HashSet<String>,
+    unconstructed: &std::collections::HashSet<String>,
+    mutated_interfaces: &std::collections::BTreeSet<String>,
+    address: &str,
 ) -> Result<String, TyrusError> {
     let mut main_content = String::new();
     main_content.push_str("#![allow(unused)]\n\n");
@@ -232,11 +531,11 @@ fn generate_main_rs(
     main_content.push_str("#[tokio::main]\n");
     main_content.push_str("async fn main() {\n");
 
-    // Instantiate components in order
-    let mut instantiated_vars = std::collections::HashMap::new();
+    // Instantiate components in order, which the extensions keep
+    let mut instantiated_vars = Vec::new();
 
     for class_name in init_order {
-        if generic_classes.contains(class_name) {
+        if unconstructed.contains(class_name) {
             continue;
         }
         if let Some(module_path) = class_module_map.get(class_name) {
@@ -246,21 +545,30 @@ fn generate_main_rs(
             let deps = graph.get_dependencies(class_name).unwrap_or_default();
             let mut args = Vec::new();
             for dep in deps {
-                let dep_var = tyrus_common::util::to_snake_case(&dep);
+                // An interface is provided by the class implementing it
+                let dep = graph.provider(&dep).unwrap_or(&dep);
+                let dep_var = tyrus_common::util::to_snake_case(dep);
                 args.push(format!("{}.clone()", dep_var));
             }
 
             // Check if it has new_di
             // For now assume yes if it has dependencies, or just call new_di
-            main_content.push_str(&format!(
-                "    let {} = Arc::new({}::{}::new_di({}));\n",
-                var_name,
+            let mut instance = format!(
+                "{}::{}::new_di({})",
                 module_path,
                 class_name,
                 args.join(", ")
-            ));
+            );
+            // Trait objects of an interface whose methods mutate are locked
+            if mutated_interfaces
+                .iter()
+                .any(|iface| graph.provider(iface) == Some(class_name.as_str()))
+            {
+                instance = format!("std::sync::Mutex::new({})", instance);
+            }
+            main_content.push_str(&format!("    let {} = Arc::new({});\n", var_name, instance));
 
-            instantiated_vars.insert(class_name.clone(), var_name);
+            instantiated_vars.push(var_name);
         }
     }
 
@@ -278,7 +586,7 @@ fn generate_main_rs(
     }
 
     // Add extensions
-    for var_name in instantiated_vars.values() {
+    for var_name in &instantiated_vars {
         main_content.push_str(&format!(
             "\n        .layer(Extension({}.clone()))",
             var_name
@@ -286,59 +594,120 @@ fn generate_main_rs(
     }
 
     main_content.push_str(";\n\n");
-    main_content
-        .push_str("    let listener = TcpListener::bind(\"0.0.0.0:3000\").await.unwrap();\n");
-    main_content.push_str("    println!(\"Server running on http://0.0.0.0:3000\");\n");
+    main_content.push_str(&format!(
+        "    let listener = TcpListener::bind({:?}).await.unwrap();\n",
+        address
+    ));
+    main_content.push_str(&format!(
+        "    println!(\"Server running on http://{{}}\", {:?});\n",
+        address
+    ));
     main_content.push_str("    axum::serve(listener, app).await.unwrap();\n");
     main_content.push_str("}\n");
 
     Ok(main_content)
 }
 
-fn generate_cargo_toml(output_dir: &Path) -> Result<(), TyrusError> {
-    let cargo_toml_content = r#"[package]
-name = "tyrus_app"
-version = "0.1.0"
-edition = "2021"
-
-[workspace]
-
-[dependencies]
-tokio = { version = "1.0", features = ["full"] }
-axum = "0.7"
-serde = { version = "1.0", features = ["derive", "rc"] }
-serde_json = "1.0"
-reqwest = { version = "0.11", features = ["json"] }
-tower = { version = "0.4" }
-tower-http = { version = "0.5", features = ["trace"] }
-rand = "0.8"
-
-[[bin]]
-name = "server"
-path = "src/main.rs"
-
-[lib]
-name = "tyrus_app"
-path = "src/lib.rs"
-"#;
+/// The dependencies of the generated crate, before `[dependencies]`
+/// overrides.
+const DEFAULT_DEPENDENCIES: [(&str, &str); 8] = [
+    ("tokio", r#"{ version = "1.0", features = ["full"] }"#),
+    ("axum", r#""0.7""#),
+    (
+        "serde",
+        r#"{ version = "1.0", features = ["derive", "rc"] }"#,
+    ),
+    ("serde_json", r#""1.0""#),
+    ("reqwest", r#"{ version = "0.11", features = ["json"] }"#),
+    ("tower", r#"{ version = "0.4" }"#),
+    ("tower-http", r#"{ version = "0.5", features = ["trace"] }"#),
+    ("rand", r#""0.8""#),
+];
+
+fn generate_cargo_toml(output_dir: &Path, config: &Config) -> Result<(), TyrusError> {
+    let package = &config.package;
+    let mut content = format!(
+        "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"{}\"\n\n[workspace]\n\n[dependencies]\n",
+        package.name, package.edition
+    );
+    let defaults = DEFAULT_DEPENDENCIES.iter().map(|(name, spec)| {
+        (
+            *name,
+            config
+                .dependencies
+                .get(*name)
+                .map(dependency)
+                .unwrap_or_else(|| spec.to_string()),
+        )
+    });
+    let extra = config
+        .dependencies
+        .iter()
+        .filter(|(name, _)| {
+            !DEFAULT_DEPENDENCIES
+                .iter()
+                .any(|(default, _)| default == name)
+        })
+        .map(|(name, spec)| (name.as_str(), dependency(spec)));
+    for (name, spec) in defaults.chain(extra) {
+        // `false` removes the dependency
+        if spec != "false" {
+            content.push_str(&format!("{} = {}\n", name, spec));
+        }
+    }
+
+    match config.build.target {
+        Target::Server => {
+            content.push_str("\n[[bin]]\nname = \"server\"\npath = \"src/main.rs\"\n")
+        }
+        Target::Bin => content.push_str(&format!(
+            "\n[[bin]]\nname = \"{}\"\npath = \"src/main.rs\"\n",
+            package.name
+        )),
+        Target::Lib => {}
+    }
+    content.push_str(&format!(
+        "\n[lib]\nname = \"{}\"\npath = \"src/lib.rs\"\n",
+        package.lib_name()
+    ));
 
     let cargo_toml_path = output_dir.join("Cargo.toml");
-    fs::write(cargo_toml_path, cargo_toml_content).map_err(TyrusError::IoError)?;
+    fs::write(cargo_toml_path, content).map_err(TyrusError::IoError)?;
     Ok(())
 }
 
-fn generate_mod_rs(dir: &Path) -> Result<(), TyrusError> {
+/// A `[dependencies]` value of `tyrus.toml`, as an inline TOML value.
+fn dependency(spec: &toml::Value) -> String {
+    match spec {
+        toml::Value::Table(table) => {
+            let entries: Vec<_> = table
+                .iter()
+                .map(|(key, value)| format!("{} = {}", key, dependency(value)))
+                .collect();
+            format!("{{ {} }}", entries.join(", "))
+        }
+        toml::Value::Array(values) => {
+            let values: Vec<_> = values.iter().map(dependency).collect();
+            format!("[{}]", values.join(", "))
+        }
+        value => value.to_string(),
+    }
+}
+
+fn generate_mod_rs(dir: &Path, source_maps: &mut SourceMaps) -> Result<(), TyrusError> {
     let mut mod_content = String::new();
     let mut has_children = false;
     let mut index_content = String::new();
 
-    // Read directory entries
-    let entries = fs::read_dir(dir).map_err(TyrusError::IoError)?;
-
-    for entry in entries {
-        let entry = entry.map_err(TyrusError::IoError)?;
-        let path = entry.path();
+    // Read directory entries, in the same order on every file system
+    let mut paths = fs::read_dir(dir)
+        .map_err(TyrusError::IoError)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(TyrusError::IoError)?;
+    paths.sort();
 
+    for path in paths {
         // Skip mod.rs, lib.rs, and main.rs
         if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
             if name == "mod.rs" || name == "lib.rs" || name == "main.rs" {
@@ -377,13 +746,14 @@ fn generate_mod_rs(dir: &Path) -> Result<(), TyrusError> {
     // If we found index.rs, delete it and append its content
     let index_path = dir.join("index.rs");
     if index_path.exists() {
-        fs::remove_file(index_path).map_err(TyrusError::IoError)?;
+        fs::remove_file(&index_path).map_err(TyrusError::IoError)?;
     }
 
     // Append index content
     if !index_content.is_empty() {
         mod_content.push('\n');
         mod_content.push_str("// Content from index.ts\n");
+        source_maps.moved(&index_path, &dir.join("mod.rs"), mod_content.len());
         mod_content.push_str(&index_content);
         has_children = true;
     }
@@ -398,39 +768,11 @@ fn generate_mod_rs(dir: &Path) -> Result<(), TyrusError> {
     Ok(())
 }
 
+/// Pretty-prints generated code in-process: parses it with `syn` and prints
+/// the tree with `prettyplease`, async code included.
 fn format_code(code: String) -> Result<String, TyrusError> {
-    // Skip formatting for code containing async (edition compatibility)
-    if code.contains("async fn") {
-        return Ok(format!(
-            "// Note: async/await code - formatting skipped for edition compatibility\n{}",
-            code
-        ));
-    }
-
-    use std::io::Write;
-    use std::process::{Command, Stdio};
-
-    let mut child = Command::new("rustfmt")
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()
-        .map_err(TyrusError::IoError)?;
-
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin
-            .write_all(code.as_bytes())
-            .map_err(TyrusError::IoError)?;
-    }
-
-    let output = child.wait_with_output().map_err(TyrusError::IoError)?;
-
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
-    } else {
-        Err(TyrusError::FormattingError(
-            String::from_utf8_lossy(&output.stderr).to_string(),
-        ))
-    }
+    let file = syn::parse_file(&code).map_err(|e| TyrusError::FormattingError(e.to_string()))?;
+    Ok(prettyplease::unparse(&file))
 }
 
 fn get_app_error_code() -> &'static str {
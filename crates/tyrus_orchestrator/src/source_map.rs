@@ -0,0 +1,70 @@
+//! The maps from generated files back to the TypeScript they came from, for
+//! `build --verify` and `build --source-map`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use swc_common::Span;
+use tyrus_codegen::span_map::SpanMap;
+use tyrus_diagnostics::TyrusError;
+
+/// For each generated file, the `.ts` file it came from and the map back
+/// into it.
+#[derive(Debug, Default)]
+pub(crate) struct SourceMaps {
+    files: HashMap<PathBuf, (PathBuf, SpanMap)>,
+}
+
+impl SourceMaps {
+    pub(crate) fn insert(&mut self, output: PathBuf, source: PathBuf, map: SpanMap) {
+        self.files.insert(output, (source, map));
+    }
+
+    /// Records that the code of `from` now starts at `base` in `to`.
+    pub(crate) fn moved(&mut self, from: &Path, to: &Path, base: usize) {
+        if let Some((source, map)) = self.files.remove(from) {
+            let mut moved = SpanMap::default();
+            moved.extend(base, map);
+            self.files.insert(to.to_path_buf(), (source, moved));
+        }
+    }
+
+    /// The `.ts` file and span that `offset` into `output` came from.
+    pub(crate) fn lookup(&self, output: &Path, offset: usize) -> Option<(&Path, Span)> {
+        let (source, map) = self.files.get(output)?;
+        Some((source, map.lookup(offset)?))
+    }
+
+    /// Writes a source map v3 next to each generated file, as `file.rs.map`.
+    pub(crate) fn write(&self) -> Result<(), TyrusError> {
+        for (output, (source, map)) in &self.files {
+            let code = fs::read_to_string(output).map_err(TyrusError::IoError)?;
+            let source_text = fs::read_to_string(source).map_err(TyrusError::IoError)?;
+            let file = output.file_name().unwrap_or_default().to_string_lossy();
+            let dir = output.parent().unwrap_or(Path::new("."));
+            let source_name = relative(dir, source)?;
+            let json = map.to_source_map(&code, &file, &source_name, &source_text);
+            fs::write(output.with_extension("rs.map"), json).map_err(TyrusError::IoError)?;
+        }
+        Ok(())
+    }
+}
+
+/// `path` relative to the directory `from`, with `/` separators as source
+/// maps expect.
+fn relative(from: &Path, path: &Path) -> Result<String, TyrusError> {
+    let from = fs::canonicalize(from).map_err(TyrusError::IoError)?;
+    let path = fs::canonicalize(path).map_err(TyrusError::IoError)?;
+    let from: Vec<Component> = from.components().collect();
+    let path: Vec<Component> = path.components().collect();
+    let common = from.iter().zip(&path).take_while(|(a, b)| a == b).count();
+    let parts: Vec<String> = std::iter::repeat_n("..".to_string(), from.len() - common)
+        .chain(
+            path[common..]
+                .iter()
+                .map(|part| part.as_os_str().to_string_lossy().to_string()),
+        )
+        .collect();
+    Ok(parts.join("/"))
+}
@@ -0,0 +1,126 @@
+//! `tyrus build --verify`: type-checks the generated crate with `cargo check`
+//! and reports rustc's errors on the TypeScript they were generated from.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use miette::{NamedSource, SourceSpan};
+use serde::Deserialize;
+use swc_common::Span;
+use tyrus_diagnostics::TyrusError;
+
+use crate::source_map::SourceMaps;
+
+/// A line of `cargo check --message-format=json`.
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<CompilerMessage>,
+}
+
+#[derive(Deserialize)]
+struct CompilerMessage {
+    message: String,
+    level: String,
+    code: Option<ErrorCode>,
+    spans: Vec<CompilerSpan>,
+}
+
+#[derive(Deserialize)]
+struct ErrorCode {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct CompilerSpan {
+    file_name: String,
+    byte_start: usize,
+    byte_end: usize,
+    is_primary: bool,
+    label: Option<String>,
+}
+
+/// Runs `cargo check` on the crate in `output_dir` and maps its errors
+/// through `maps`.
+pub(crate) fn verify(output_dir: &Path, maps: &SourceMaps) -> Result<(), TyrusError> {
+    let output = Command::new("cargo")
+        .args(["check", "--quiet", "--message-format=json"])
+        .current_dir(output_dir)
+        .output()
+        .map_err(TyrusError::IoError)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut errors = Vec::new();
+    for line in stdout.lines() {
+        let Ok(CargoMessage {
+            reason,
+            message: Some(message),
+        }) = serde_json::from_str(line)
+        else {
+            continue;
+        };
+        if reason == "compiler-message" && message.level == "error" {
+            errors.push(diagnostic(output_dir, maps, message)?);
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(TyrusError::VerifyFailed { errors });
+    }
+    if !output.status.success() {
+        // Cargo failed before rustc could report anything, e.g. on a
+        // dependency it could not fetch
+        return Err(TyrusError::VerifyError(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// `message` on the TypeScript its primary span was generated from, or on
+/// the generated Rust when no marker precedes it.
+fn diagnostic(
+    output_dir: &Path,
+    maps: &SourceMaps,
+    message: CompilerMessage,
+) -> Result<TyrusError, TyrusError> {
+    let mut text = message.message;
+    if let Some(code) = message.code {
+        text = format!("{} [{}]", text, code.code);
+    }
+    let Some(primary) = message.spans.into_iter().find(|span| span.is_primary) else {
+        return Ok(TyrusError::VerifyError(text));
+    };
+    if let Some(label) = &primary.label {
+        text = format!("{}: {}", text, label);
+    }
+
+    let rust_file = output_dir.join(&primary.file_name);
+    let (file, source, span) = match maps.lookup(&rust_file, primary.byte_start) {
+        Some((ts_file, span)) => {
+            let source = fs::read_to_string(ts_file).map_err(TyrusError::IoError)?;
+            let span = first_line(&source, span);
+            (ts_file.to_path_buf(), source, span)
+        }
+        None => {
+            let source = fs::read_to_string(&rust_file).map_err(TyrusError::IoError)?;
+            let span = SourceSpan::from(primary.byte_start..primary.byte_end);
+            (rust_file, source, span)
+        }
+    };
+    Ok(TyrusError::RustcError {
+        message: text,
+        src: NamedSource::new(file.to_string_lossy(), source),
+        span,
+    })
+}
+
+/// The first line of `span` in `source`: items and statements can span many
+/// lines, while their first one says which it is.
+fn first_line(source: &str, span: Span) -> SourceSpan {
+    let start = (span.lo.0 as usize - 1).min(source.len());
+    let end = (span.hi.0 as usize - 1).clamp(start, source.len());
+    let end = source[start..end].find('\n').map_or(end, |i| start + i);
+    SourceSpan::from(start..end)
+}
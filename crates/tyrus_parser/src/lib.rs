@@ -3,28 +3,57 @@ use std::path::Path;
 use tyrus_diagnostics::TyrusError;
 
 use swc_common::{
+    comments::SingleThreadedComments,
     errors::{ColorConfig, Handler},
     sync::Lrc,
-    SourceMap, Spanned,
+    FileName, SourceFile, SourceMap, Spanned,
 };
 use swc_ecma_ast::Program;
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsSyntax};
 
 pub fn parse(path: &Path) -> Result<Program, TyrusError> {
-    let cm: Lrc<SourceMap> = Default::default();
-    let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
+    parse_with_comments(path).map(|(program, _)| program)
+}
 
+/// Parses `path`, also collecting its comments, which carry `// tyrus-...`
+/// directives.
+pub fn parse_with_comments(path: &Path) -> Result<(Program, SingleThreadedComments), TyrusError> {
+    let cm: Lrc<SourceMap> = Default::default();
     let fm = cm.load_file(path).map_err(TyrusError::IoError)?;
+    parse_file(
+        cm,
+        &fm,
+        path.to_string_lossy().ends_with(".tsx"),
+        &path.to_string_lossy(),
+    )
+}
+
+/// Parses TypeScript source text, as [`parse_with_comments`] does a file.
+/// Its spans start at byte position 1.
+pub fn parse_str(src: &str) -> Result<(Program, SingleThreadedComments), TyrusError> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon.into(), src.to_string());
+    parse_file(cm, &fm, false, "<anon>")
+}
+
+fn parse_file(
+    cm: Lrc<SourceMap>,
+    fm: &SourceFile,
+    tsx: bool,
+    name: &str,
+) -> Result<(Program, SingleThreadedComments), TyrusError> {
+    let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm));
+    let comments = SingleThreadedComments::default();
 
     let lexer = Lexer::new(
         Syntax::Typescript(TsSyntax {
-            tsx: path.to_string_lossy().ends_with(".tsx"),
+            tsx,
             decorators: true,
             ..Default::default()
         }),
         Default::default(),
-        StringInput::from(&*fm),
-        None,
+        StringInput::from(fm),
+        Some(&comments),
     );
 
     let mut parser = Parser::new_from(lexer);
@@ -34,7 +63,7 @@ pub fn parse(path: &Path) -> Result<Program, TyrusError> {
     }
 
     match parser.parse_program() {
-        Ok(program) => Ok(program),
+        Ok(program) => Ok((program, comments)),
         Err(e) => {
             // Convert SWC error to TyrusError
             let span = e.span();
@@ -47,7 +76,7 @@ pub fn parse(path: &Path) -> Result<Program, TyrusError> {
 
             Err(TyrusError::ParserError {
                 message,
-                src: NamedSource::new(path.to_string_lossy(), fm.src.to_string()),
+                src: NamedSource::new(name, fm.src.to_string()),
                 span: SourceSpan::new(start.into(), len),
             })
         }
@@ -13,6 +13,12 @@ use tempfile::TempDir;
 /// # Panics
 /// Panics if `cargo check` fails, printing the full `rustc` error output.
 pub fn assert_rust_compiles(code: &str) {
+    assert_rust_compiles_denying(code, &[]);
+}
+
+/// Like [`assert_rust_compiles`], but also fails on any warning from `lints`
+/// (e.g. `unused_mut`).
+pub fn assert_rust_compiles_denying(code: &str, lints: &[&str]) {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let project_path = temp_dir.path();
 
@@ -31,7 +37,7 @@ name = "tyrus_app"
 path = "src/lib.rs"
 
 [dependencies]
-serde = { version = "1.0", features = ["derive"] }
+serde = { version = "1.0", features = ["derive", "rc"] }
 serde_json = "1.0"
 tokio = { version = "1.0", features = ["full"] }
 axum = "0.7"
@@ -43,10 +49,11 @@ tower-http = { version = "0.5", features = ["trace"] }
     fs::write(project_path.join("Cargo.toml"), cargo_toml).expect("Failed to write Cargo.toml");
 
     // Wrap code with common allows to suppress dead_code warnings
-    let wrapped_code = format!(
-        "#![allow(dead_code, unused_variables, unused_imports)]\n{}",
-        code
-    );
+    let mut wrapped_code = String::from("#![allow(dead_code, unused_variables, unused_imports)]\n");
+    if !lints.is_empty() {
+        wrapped_code.push_str(&format!("#![deny({})]\n", lints.join(", ")));
+    }
+    wrapped_code.push_str(code);
 
     fs::write(src_dir.join("lib.rs"), &wrapped_code).expect("Failed to write lib.rs");
 
@@ -0,0 +1,66 @@
+export abstract class Shape {
+  constructor(public name: string) {}
+
+  abstract area(): number;
+
+  describe(): string {
+    return this.name + " with area " + this.area();
+  }
+}
+
+export class Circle extends Shape {
+  constructor(public radius: number) {
+    super("circle");
+  }
+
+  area(): number {
+    return 3.14 * this.radius * this.radius;
+  }
+}
+
+export class Square extends Shape {
+  side: number = 0;
+
+  area(): number {
+    return this.side * this.side;
+  }
+
+  describe(): string {
+    return "square of side " + this.side;
+  }
+}
+
+export abstract class Job {
+  abstract run(input: string): Promise<string>;
+
+  async runTwice(input: string): Promise<string> {
+    const first = await this.run(input);
+    return await this.run(first);
+  }
+}
+
+export class EchoJob extends Job {
+  async run(input: string): Promise<string> {
+    return input;
+  }
+}
+
+export function report(shape: Shape): string {
+  return shape.describe();
+}
+
+export function totalArea(shapes: Shape[]): number {
+  let sum = 0;
+  for (const shape of shapes) {
+    sum += shape.area();
+  }
+  return sum;
+}
+
+export function unitArea(): number {
+  return totalArea([new Circle(1), new Square("square")]);
+}
+
+export function reportUnit(): string {
+  return report(new Square("square"));
+}
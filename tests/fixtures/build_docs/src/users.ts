@@ -0,0 +1,44 @@
+/** A registered user. */
+export interface User {
+  /** The name they sign in with. */
+  name: string;
+  age: number;
+}
+
+/**
+ * How users are listed.
+ */
+export enum Order {
+  /** Oldest first. */
+  Age = "age",
+  Name = "name",
+}
+
+/** Keeps users in memory. */
+export class UserStore {
+  /** Everyone added so far. */
+  users: User[] = [];
+
+  /**
+   * Adds a user.
+   * @param user - The user to add.
+   */
+  add(user: User): void {
+    // Newest last
+    this.users.push(user);
+  }
+}
+
+/**
+ * Describes a user.
+ *
+ * @param user The user to describe.
+ * @param prefix What to start with.
+ * @returns The description.
+ * @deprecated Use `describe` instead.
+ */
+export function describeUser(user: User, prefix: string): string {
+  // Join the parts
+  const text = prefix + user.name; // with no separator
+  return text;
+}
@@ -0,0 +1,3 @@
+export function loadConfig(raw: string): any {
+  return JSON.parse(raw);
+}
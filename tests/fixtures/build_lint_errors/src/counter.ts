@@ -0,0 +1,7 @@
+export function countUp(limit: number): number {
+  var total = 0;
+  while (total < limit) {
+    total = total + 1;
+  }
+  return total;
+}
@@ -0,0 +1,8 @@
+export function flags(a: number, b: number): number {
+  const mask = a & b;
+  return mask;
+}
+
+export function label(text: string): string {
+  return void text;
+}
@@ -0,0 +1,3 @@
+export function flags(a: number, b: number): number {
+  return a & b;
+}
@@ -0,0 +1,9 @@
+export interface User {
+  name: string;
+  age: number;
+}
+
+export function ageOf(user: User): number {
+  const age = user.name;
+  return age;
+}
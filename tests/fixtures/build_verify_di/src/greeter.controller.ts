@@ -0,0 +1,12 @@
+import { Controller, Get } from '@nestjs/common';
+import { GreeterService } from './greeter.service';
+
+@Controller('greet')
+export class GreeterController {
+  constructor(private greeter: GreeterService) {}
+
+  @Get()
+  hello() {
+    return this.greeter.greet("world");
+  }
+}
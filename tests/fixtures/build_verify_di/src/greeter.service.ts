@@ -0,0 +1,10 @@
+import { Punctuation } from './punctuation';
+import { Store } from './store';
+
+export class GreeterService {
+  constructor(private store: Store, private punctuation: Punctuation) {}
+
+  greet(name: string): string {
+    return "Hello, " + this.store.get(name) + this.punctuation.mark();
+  }
+}
@@ -0,0 +1,9 @@
+export abstract class Punctuation {
+  abstract mark(): string;
+}
+
+export class Exclamation extends Punctuation {
+  mark(): string {
+    return "!";
+  }
+}
@@ -0,0 +1,12 @@
+export interface Store {
+  get(key: string): string;
+}
+
+export class MemoryStore implements Store {
+  reads: number = 0;
+
+  get(key: string): string {
+    this.reads += 1;
+    return "value of " + key;
+  }
+}
@@ -0,0 +1,14 @@
+export class Account {
+  name: string = "ada";
+}
+
+export function owner(who: Account | string): string {
+  if (who instanceof Account) {
+    return who.name;
+  }
+  return "nobody";
+}
+
+export function describe(value: string | number): string {
+  return "value " + value;
+}
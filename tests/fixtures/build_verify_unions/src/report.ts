@@ -0,0 +1,9 @@
+import { Account, describe, owner } from './account';
+
+export function total(value: string | number): string {
+  return "total " + value;
+}
+
+export function report(): string {
+  return describe(1) + total("two") + owner(new Account()) + owner("bob");
+}
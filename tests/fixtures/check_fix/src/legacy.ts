@@ -0,0 +1,20 @@
+export function total(values: number[]): number {
+  var sum = 0;
+  for (var i = 0; i < values.length; i++) {
+    sum += values[i];
+  }
+  return sum;
+}
+
+export function isEmpty(values: number[]): boolean {
+  var count = values.length;
+  return count == 0;
+}
+
+export function parsePayload(raw: string): any {
+  return JSON.parse(raw);
+}
+
+export function run(code: string): void {
+  eval(code);
+}
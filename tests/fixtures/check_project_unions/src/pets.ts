@@ -0,0 +1,9 @@
+export class Cat {
+  name: string = "tom";
+}
+
+export class Dog {
+  name: string = "rex";
+}
+
+export type Pet = Cat | Dog;
@@ -0,0 +1,8 @@
+import { Cat, Pet } from './pets';
+
+export function greet(p: Pet): string {
+  if (p instanceof Cat) {
+    return "meow, " + p.name;
+  }
+  return "woof, " + p.name;
+}
@@ -0,0 +1,28 @@
+interface User {
+  name: string;
+  age: number;
+}
+
+export function count(): number {
+  return arguments.length;
+}
+
+export function field(user: User, key: string): string {
+  return user[key];
+}
+
+export function matches(age: number, input: string): boolean {
+  return age == input;
+}
+
+export function compile(body: string): void {
+  const run = new Function("user", body);
+}
+
+export class Greeter {
+  greeting = "hello";
+
+  greet(): string {
+    return this.greeting;
+  }
+}
@@ -0,0 +1,29 @@
+export function tally(scores: number[], mode: string): number {
+    let total = 0;
+    for (let i = 0; i < scores.length; i++) {
+        total += scores[i];
+    }
+    for (const score of scores) {
+        if (score < 0) {
+            continue;
+        }
+        total += score;
+    }
+    let rounds = 0;
+    do {
+        rounds++;
+    } while (rounds < 3);
+    switch (mode) {
+        case "double":
+            total = total * 2;
+            break;
+        default:
+            total = total + rounds;
+    }
+    try {
+        total = total / scores.length;
+    } catch (e) {
+        total = 0;
+    }
+    return total;
+}
@@ -0,0 +1,50 @@
+export class Database {
+  constructor(public url: string) {}
+}
+
+export class BaseRepository {
+  constructor(protected db: Database) {}
+
+  describe(): string {
+    return "repository";
+  }
+
+  count(): number {
+    return 0;
+  }
+
+  title(): string {
+    return "[" + this.describe() + "]";
+  }
+}
+
+export class UserRepository extends BaseRepository {
+  label: string;
+
+  constructor(db: Database) {
+    super(db);
+    this.label = "users";
+  }
+
+  describe(): string {
+    return this.label + " " + super.describe();
+  }
+
+  findName(id: number): string {
+    return "user " + id;
+  }
+}
+
+export class AdminRepository extends UserRepository {
+  count(): number {
+    return 1;
+  }
+}
+
+export function summary(repo: AdminRepository): string {
+  return repo.describe() + " " + repo.count();
+}
+
+export function label(repo: BaseRepository): string {
+  return repo.title() + " " + repo.count();
+}
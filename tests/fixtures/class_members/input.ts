@@ -0,0 +1,40 @@
+export class Counter {
+  static readonly MAX: number = 10;
+  static readonly PREFIX: string = "counter";
+  static readonly DEFAULT_TAGS: string[] = ["a", "b"];
+  static created: number = 0;
+  #count: number = 0;
+  private label: string = "c";
+
+  static create(label: string): Counter {
+    Counter.created++;
+    return new Counter();
+  }
+
+  static totalCreated(): number {
+    return Counter.created;
+  }
+
+  get count(): number {
+    return this.#count;
+  }
+
+  set count(value: number) {
+    this.#count = value;
+  }
+
+  #remaining(): number {
+    return Counter.MAX - this.#count;
+  }
+
+  describe(): string {
+    return Counter.PREFIX + " " + this.label + ": " + this.#remaining();
+  }
+}
+
+export function run(): number {
+  let counter = Counter.create("main");
+  counter.count = 3;
+  counter.count += 2;
+  return counter.count + Counter.totalCreated() + Counter.DEFAULT_TAGS.length;
+}
@@ -0,0 +1,6 @@
+import { UserRepository } from "./repositories/user.repository";
+
+export function summary(repo: UserRepository): string {
+  repo.add("ada");
+  return repo.describe() + ": " + repo.count();
+}
@@ -0,0 +1,11 @@
+import { UserRepository } from "./user.repository";
+
+export class AdminRepository extends UserRepository {
+  constructor() {
+    super("admins", []);
+  }
+
+  count(): number {
+    return 0;
+  }
+}
@@ -0,0 +1,15 @@
+export class BaseRepository {
+  constructor(public name: string, protected items: string[]) {}
+
+  add(item: string): void {
+    this.items.push(item);
+  }
+
+  count(): number {
+    return this.items.length;
+  }
+
+  describe(): string {
+    return this.name;
+  }
+}
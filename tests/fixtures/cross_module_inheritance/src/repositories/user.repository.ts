@@ -0,0 +1,7 @@
+import { BaseRepository } from "./base.repository";
+
+export class UserRepository extends BaseRepository {
+  describe(): string {
+    return "users";
+  }
+}
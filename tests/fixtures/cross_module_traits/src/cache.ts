@@ -0,0 +1,10 @@
+import { Storage } from "./storage";
+import { read } from "./memory";
+
+export class CacheService {
+  constructor(private storage: Storage) {}
+
+  lookup(key: string): string {
+    return this.storage.name + " has " + read(this.storage, key);
+  }
+}
@@ -0,0 +1,13 @@
+import { Storage } from "./storage";
+
+export class MemoryStorage implements Storage {
+  constructor(public name: string) {}
+
+  get(key: string): string {
+    return this.name + ":" + key;
+  }
+}
+
+export function read(s: Storage, key: string): string {
+  return s.get(key);
+}
@@ -0,0 +1,4 @@
+export interface Storage {
+  name: string;
+  get(key: string): string;
+}
@@ -0,0 +1,64 @@
+export type Shape =
+  | { kind: "circle"; radius: number }
+  | { kind: "square"; side: number }
+  | { kind: "rect"; width: number; height: number; label?: string };
+
+export function area(s: Shape): number {
+  switch (s.kind) {
+    case "circle":
+      return 3.14 * s.radius * s.radius;
+    case "square":
+      return s.side * s.side;
+    case "rect":
+      return s.width * s.height;
+  }
+}
+
+export function describe(s: Shape): string {
+  if (s.kind === "circle") {
+    return "circle of radius " + s.radius;
+  } else if (s.kind === "square" || s.kind === "rect") {
+    return "polygon";
+  }
+  return "unknown";
+}
+
+export function isRound(s: Shape): boolean {
+  let round = false;
+  switch (s.kind) {
+    case "circle":
+      round = true;
+      break;
+    default:
+      round = false;
+  }
+  return round;
+}
+
+export function radiusOf(s: Shape): number {
+  if (s.kind !== "circle") {
+    return 0;
+  }
+  return s.radius;
+}
+
+export function sideOf(s: Shape): number {
+  if (s.kind !== "circle" && s.kind !== "rect") return s.side;
+  return 0;
+}
+
+export function boxArea(s: Shape): number {
+  if (s.kind !== "rect") {
+    return 0;
+  } else {
+    return s.width * s.height;
+  }
+}
+
+export function kindOf(s: Shape): string {
+  return s.kind;
+}
+
+export function sameKind(a: Shape, b: Shape): boolean {
+  return a.kind === b.kind;
+}
@@ -11,3 +11,23 @@ function multiply(x: number, y: number): number {
 function calculateTotal2(a: number, b: number, c: number): number {
     return add(a, b) + c;
 }
+
+function describeSize(width: number, height: number): string {
+    return "size " + width + "x" + height;
+}
+
+function average(a: number, b: number): number {
+    return (a + b) / 2;
+}
+
+function doubling(times: number): number {
+    let big = 1;
+    for (let i = 0; i < times; i++) {
+        big = big * 2;
+    }
+    return big;
+}
+
+function doubledEvens(values: number[]): number[] {
+    return values.filter((v) => v % 2 === 0).map((v) => v * 2);
+}
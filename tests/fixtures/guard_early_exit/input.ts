@@ -0,0 +1,27 @@
+export type Id = string | number;
+
+export function idLength(id: Id): number {
+  if (typeof id === "number") {
+    return id;
+  }
+  return id.length;
+}
+
+export function describeId(id: Id): string {
+  if (typeof id !== "string") {
+    return "n" + id;
+  }
+  return id.toUpperCase();
+}
+
+export class Account {
+  constructor(public name: string) {}
+}
+
+export function owner(who: Account | string): string {
+  if (who instanceof Account) {
+    return who.name;
+  }
+  const handle = "@" + who;
+  return handle;
+}
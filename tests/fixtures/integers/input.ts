@@ -0,0 +1,49 @@
+export function total(prices: number[]): number {
+  let sum = 0;
+  for (let i = 0; i < prices.length; i++) {
+    sum += prices[i];
+  }
+  return sum;
+}
+
+export function last(names: string[]): string {
+  return names[names.length - 1];
+}
+
+export function countEven(limit: number): number {
+  let count = 0;
+  for (let n = 0; n < limit; n++) {
+    if (n % 2 !== 0) {
+      continue;
+    }
+    count++;
+  }
+  return count;
+}
+
+export function middle(values: number[]): number {
+  const mid = Math.floor(values.length / 2);
+  return values[mid];
+}
+
+export function parseCount(text: string): number {
+  const count = parseInt(text);
+  return count * 2;
+}
+
+export function average(values: number[]): number {
+  let sum = 0;
+  let count = 0;
+  for (const value of values) {
+    sum += value;
+    count++;
+  }
+  return sum / count;
+}
+
+export function steps(): number {
+  // tyrus-float
+  let step = 0;
+  step += 1;
+  return step;
+}
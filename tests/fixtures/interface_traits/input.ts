@@ -0,0 +1,70 @@
+export interface Storage {
+  name: string;
+  read(key: string): string;
+  exists(key: string): boolean;
+  write(key: string, value: string): void;
+}
+
+export interface RemoteStore {
+  load(id: number): Promise<string>;
+}
+
+export class MemoryStorage implements Storage {
+  private keys: string[];
+
+  constructor(public name: string) {
+    this.keys = [];
+  }
+
+  read(key: string): string {
+    return "value of " + key;
+  }
+
+  exists(key: string): boolean {
+    return key.length > 0;
+  }
+
+  write(key: string, value: string): void {
+    this.keys.push(key + "=" + value);
+  }
+}
+
+export class HttpStore implements RemoteStore {
+  async load(id: number): Promise<string> {
+    return "remote " + id;
+  }
+}
+
+export class CacheService {
+  constructor(private storage: Storage, private remote: RemoteStore) {}
+
+  lookup(key: string): string {
+    if (this.storage.exists("default")) {
+      return this.storage.name + ": " + this.storage.read(key);
+    }
+    return describe(this.storage);
+  }
+
+  remember(key: string, value: string): void {
+    this.storage.write(key, value);
+  }
+
+  async fetch(id: number): Promise<string> {
+    const value = await this.remote.load(id);
+    return value;
+  }
+}
+
+export function describe(storage: Storage): string {
+  return storage.name + " storage";
+}
+
+export function createCache(): CacheService {
+  return new CacheService(new MemoryStorage("memory"), new HttpStore());
+}
+
+export function storages(): Storage[] {
+  const all: Storage[] = [new MemoryStorage("a")];
+  all.push(new MemoryStorage("b"));
+  return all;
+}
@@ -0,0 +1,16 @@
+export function parsePayload(raw: string): any {
+  return JSON.parse(raw);
+}
+
+export function total(values: number[]): number {
+  // tyrus-ignore-next-line no-var
+  var sum = 0;
+  for (const value of values) {
+    sum += value;
+  }
+  return sum;
+}
+
+export function run(code: string): void {
+  eval(code);
+}
@@ -0,0 +1,44 @@
+export function sum(values: number[]): number {
+  let total = 0;
+  for (const value of values) {
+    total += value;
+  }
+  return total;
+}
+
+export function collect(count: number): string[] {
+  const names: string[] = [];
+  let i = 0;
+  while (i < count) {
+    names.push(`item${i}`);
+    i++;
+  }
+  return names;
+}
+
+export function label(flag: boolean): string {
+  let text: string;
+  if (flag) {
+    text = "on";
+  } else {
+    text = "off";
+  }
+  return text;
+}
+
+export function clamp(value: number, max: number): number {
+  if (value > max) {
+    value = max;
+  }
+  return value;
+}
+
+export function counter(): number {
+  let count = 0;
+  const bump = () => {
+    count += 1;
+  };
+  bump();
+  bump();
+  return count;
+}
@@ -0,0 +1,40 @@
+export class Inventory {
+  items: string[];
+  tags: string[];
+
+  constructor(tags: string[]) {
+    this.items = [];
+    this.tags = tags;
+  }
+
+  add(item: string): void {
+    this.items.push(item);
+  }
+
+  addAll(items: string[]): void {
+    for (const item of items) {
+      this.add(item);
+    }
+  }
+
+  labels(prefix: string): string[] {
+    return this.items.map((item) => prefix + item);
+  }
+
+  count(): number {
+    return this.items.length;
+  }
+}
+
+export function summarize(names: string[]): string {
+  const original = names;
+  const upper = names.map((name) => name.toUpperCase());
+  console.log(original.length);
+  return upper.join(", ");
+}
+
+export function pair(name: string): string[] {
+  const first = name;
+  const second = name;
+  return [first, second];
+}
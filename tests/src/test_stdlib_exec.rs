@@ -102,6 +102,79 @@ fn main() {{
         println!("✅ Console stdlib mapping verified!");
     }
 
+    #[test]
+    fn test_stdlib_collections_execution() {
+        let ts_code = r#"
+            function testCollections(): number {
+                const nums: number[] = [1.0, 2.0, 3.0];
+                let seen = new Set<string>();
+                seen.add("b");
+                let counts = new Map<string, number>();
+                counts.set("a", 1.0);
+                let total = 0.0;
+                if (nums.includes(2.0) && seen.has("b") && counts.has("a")) {
+                    total = nums.length + seen.size + counts.size;
+                }
+                return total;
+            }
+        "#;
+
+        let temp_dir = std::env::temp_dir();
+        let ts_file = temp_dir.join("stdlib_collections_test.ts");
+        std::fs::write(&ts_file, ts_code).unwrap();
+
+        let rust_code = tyrus_orchestrator::build(FilePath::from(ts_file))
+            .expect("Failed to generate Rust code");
+
+        println!("Generated Rust code:\n{}", rust_code);
+
+        let program = format!(
+            r#"
+{}
+
+fn main() {{
+    let result = test_collections();
+    println!("testCollections() = {{}}", result);
+    assert_eq!(result, 5.0, "Expected 3.0 + 1.0 + 1.0 = 5.0");
+    println!("✅ Collections stdlib test passed!");
+}}
+"#,
+            rust_code
+        );
+
+        execute_rust_program(&program, "Collections stdlib");
+    }
+
+    #[test]
+    fn test_user_class_methods_are_not_hijacked() {
+        let ts_code = r#"
+            class Query {
+                map(f: (x: number) => number): Query { return this; }
+                filter(f: (x: number) => boolean): Query { return this; }
+            }
+            function run(q: Query): Query {
+                return q.map(x => x + 1.0).filter(x => x > 1.0);
+            }
+        "#;
+
+        let temp_dir = std::env::temp_dir();
+        let ts_file = temp_dir.join("stdlib_user_class_test.ts");
+        std::fs::write(&ts_file, ts_code).unwrap();
+
+        let rust_code = tyrus_orchestrator::build(FilePath::from(ts_file))
+            .expect("Failed to generate Rust code");
+
+        println!("Generated Rust code:\n{}", rust_code);
+
+        // The user's own methods must be called, not the Array shims
+        assert!(
+            !rust_code.contains(".iter()"),
+            "User class methods should not be mapped to iterators"
+        );
+        assert!(rust_code.contains(".map("), "Should call Query::map");
+        assert!(rust_code.contains(".filter("), "Should call Query::filter");
+    }
+
     fn execute_rust_program(program: &str, test_name: &str) {
         let temp_dir = TempDir::new().unwrap();
         let src_file = temp_dir.path().join("main.rs");
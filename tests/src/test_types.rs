@@ -146,4 +146,84 @@ axum = "0.7"
 
         assert!(stdout.contains("✅ Advanced Types Test Passed!"));
     }
+
+    #[test]
+    fn test_untagged_union_deserializes_the_most_specific_variant() {
+        let ts_code = r#"
+            export class User {
+                name: string;
+                constructor(name: string) {
+                    this.name = name;
+                }
+            }
+
+            export class Admin {
+                name: string;
+                level: number;
+                constructor(name: string, level: number) {
+                    this.name = name;
+                    this.level = level;
+                }
+            }
+
+            export type Person = User | Admin;
+        "#;
+
+        let temp_dir = TempDir::new().unwrap();
+        let ts_file = temp_dir.path().join("people.ts");
+        fs::write(&ts_file, ts_code).unwrap();
+
+        let rust_code = tyrus_orchestrator::build(FilePath::from(ts_file)).unwrap();
+
+        let program = format!(
+            r##"
+            {}
+
+            fn main() {{
+                let admin: Person = serde_json::from_str(r#"{{"name":"Ada","level":3}}"#).unwrap();
+                assert!(matches!(admin, Person::Admin(_)), "{{:?}}", admin);
+                let json = serde_json::to_string(&admin).unwrap();
+                assert_eq!(serde_json::from_str::<Person>(&json).unwrap(), admin);
+                assert!(json.contains("\"level\":3"), "{{}}", json);
+
+                let user: Person = serde_json::from_str(r#"{{"name":"Bob"}}"#).unwrap();
+                assert!(matches!(user, Person::User(_)), "{{:?}}", user);
+
+                println!("✅ Untagged Union Test Passed!");
+            }}
+            "##,
+            rust_code
+        );
+
+        let cargo_toml = r#"
+[package]
+name = "test_untagged_union"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+serde = { version = "1.0", features = ["derive", "rc"] }
+serde_json = "1.0"
+"#;
+        fs::write(temp_dir.path().join("Cargo.toml"), cargo_toml).unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(src_dir.join("main.rs"), program).unwrap();
+
+        let run = Command::new("cargo")
+            .arg("run")
+            .arg("-q")
+            .env_remove("CARGO_TARGET_DIR")
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to run cargo");
+        let stdout = String::from_utf8_lossy(&run.stdout);
+        assert!(
+            run.status.success(),
+            "Run failed:\n{}\nGenerated Code:\n{}",
+            String::from_utf8_lossy(&run.stderr),
+            rust_code
+        );
+        assert!(stdout.contains("✅ Untagged Union Test Passed!"));
+    }
 }